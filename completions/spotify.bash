@@ -10,7 +10,20 @@ _spotify_cli_completion() {
     prev="${COMP_WORDS[COMP_CWORD-1]}"
   fi
 
-  local global_flags="--json -v --no-trunc --width"
+  local global_flags="--json --json-envelope --fields --defer-offline --market --no-explicit --insecure --dry-run --yes --max-width --column-width --no-trunc --timeout"
+
+  # Dynamic values come from the local cache via the hidden `__complete` command.
+  _spotify_cli_values() {
+    local IFS=$'\n'
+    COMPREPLY=( $(compgen -W "$(spotify-cli __complete "$1" 2>/dev/null)" -- "$cur") )
+  }
+
+  case "$prev" in
+    --playlist|--save-to)
+      _spotify_cli_values playlist
+      return
+      ;;
+  esac
 
   if [[ "$cur" == -* ]]; then
     COMPREPLY=( $(compgen -W "$global_flags" -- "$cur") )
@@ -18,130 +31,58 @@ _spotify_cli_completion() {
   fi
 
   if [[ $cword -eq 1 ]]; then
-    COMPREPLY=( $(compgen -W "auth player track search album artist playlist device help system" -- "$cur") )
+    COMPREPLY=( $(compgen -W "album alias audiobook auth block completions config device episode follow help hook info library log man markets nowplaying open pending pin player playlist queue recentlyplayed releases search session show sync track user" -- "$cur") )
     return
   fi
 
   local cmd1="${words[1]}"
+  local sub
   case "$cmd1" in
-    auth)
-      if [[ $cword -eq 2 ]]; then
-        COMPREPLY=( $(compgen -W "login status scopes logout" -- "$cur") )
-        return
-      fi
-      if [[ "$cur" == -* ]]; then
-        COMPREPLY=( $(compgen -W "--client-id" -- "$cur") )
-        return
-      fi
-      ;;
-    player)
-      if [[ $cword -eq 2 ]]; then
-        COMPREPLY=( $(compgen -W "play pause next prev status" -- "$cur") )
-        return
-      fi
-      ;;
-    track)
-      if [[ $cword -eq 2 ]]; then
-        COMPREPLY=( $(compgen -W "like unlike info" -- "$cur") )
-        return
-      fi
-      ;;
-    search)
-      if [[ "$cur" == -* ]]; then
-        COMPREPLY=( $(compgen -W "--type --fuzzy --user --limit --pick --last --play" -- "$cur") )
-        return
-      fi
-      ;;
-    album|artist)
-      if [[ $cword -eq 2 ]]; then
-        COMPREPLY=( $(compgen -W "info play" -- "$cur") )
-        return
-      fi
-      if [[ "$cur" == -* ]]; then
-        COMPREPLY=( $(compgen -W "--fuzzy --user --pick --last --search" -- "$cur") )
-        return
-      fi
-      ;;
-    playlist)
-      if [[ $cword -eq 2 ]]; then
-        COMPREPLY=( $(compgen -W "list info play add follow unfollow pin" -- "$cur") )
-        return
-      fi
-      if [[ "$prev" == "--sort" ]]; then
-        COMPREPLY=( $(compgen -W "name owner public collaborative" -- "$cur") )
-        return
-      fi
-      case "${words[2]}" in
-        list)
-          if [[ "$cur" == -* ]]; then
-            COMPREPLY=( $(compgen -W "--collaborative --owned --public --private --sort" -- "$cur") )
-            return
-          fi
-          ;;
-        info|play|add|follow|unfollow)
-          if [[ "$cur" == -* ]]; then
-            COMPREPLY=( $(compgen -W "--fuzzy --user --pick --last" -- "$cur") )
-            return
-          fi
-          local IFS=$'\n'
-          COMPREPLY=( $(compgen -W "$(spotify-cli complete playlist 2>/dev/null)" -- "$cur") )
-          return
-          ;;
-        pin)
-          if [[ $cword -eq 3 ]]; then
-            COMPREPLY=( $(compgen -W "add remove list play" -- "$cur") )
-            return
-          fi
-          case "${words[3]}" in
-            remove|play)
-              local IFS=$'\n'
-              COMPREPLY=( $(compgen -W "$(spotify-cli complete pin 2>/dev/null)" -- "$cur") )
-              return
-              ;;
-          esac
-          ;;
-      esac
-      ;;
-    device)
-      if [[ $cword -eq 2 ]]; then
-        COMPREPLY=( $(compgen -W "list set" -- "$cur") )
-        return
-      fi
-      if [[ "${words[2]}" == "list" && "$cur" == -* ]]; then
-        COMPREPLY=( $(compgen -W "--live" -- "$cur") )
-        return
-      fi
-      if [[ "${words[2]}" == "set" ]]; then
-        local IFS=$'\n'
-        COMPREPLY=( $(compgen -W "$(spotify-cli complete device 2>/dev/null)" -- "$cur") )
-        return
-      fi
+    album) sub="list play" ;;
+    alias) sub="list add remove" ;;
+    audiobook) sub="list save" ;;
+    auth) sub="login check status scopes logout" ;;
+    block) sub="add remove list check" ;;
+    config) sub="list get set unset path edit" ;;
+    device) sub="list set volume" ;;
+    episode) sub="get resume list save" ;;
+    follow) sub="list artist prune" ;;
+    hook) sub="list add remove" ;;
+    library) sub="list mirror save prune" ;;
+    log) sub="list undo" ;;
+    markets) sub="check" ;;
+    pending) sub="list flush clear" ;;
+    pin) sub="add remove rename dedupe-aliases list tag tags" ;;
+    player) sub="play pause toggle next prev seek restart radio preview status contexts back-to shuffle repeat mode volume max-volume" ;;
+    playlist) sub="list find addto create rename edit delete cuesheet play tracks stats audit user move" ;;
+    queue) sub="list add play clear save load" ;;
+    releases) sub="check" ;;
+    session) sub="start stop show" ;;
+    show) sub="latest episodes list play save" ;;
+    track) sub="locate" ;;
+    user) sub="top stats" ;;
+    completions) sub="bash zsh fish powershell elvish" ;;
+  esac
+
+  if [[ $cword -eq 2 && "$cmd1" == open ]]; then
+    _spotify_cli_values pin
+    return
+  fi
+
+  if [[ $cword -eq 2 && -n "$sub" ]]; then
+    COMPREPLY=( $(compgen -W "$sub" -- "$cur") )
+    return
+  fi
+
+  case "$cmd1:${words[2]}" in
+    playlist:addto|playlist:rename|playlist:edit|playlist:delete|playlist:cuesheet|playlist:play|playlist:stats|playlist:audit|playlist:move)
+      [[ $cword -eq 3 ]] && _spotify_cli_values playlist
       ;;
-    completions)
-      if [[ $cword -eq 2 ]]; then
-        COMPREPLY=( $(compgen -W "bash zsh fish" -- "$cur") )
-        return
-      fi
+    pin:remove|pin:rename)
+      [[ $cword -eq 3 ]] && _spotify_cli_values pin
       ;;
-    system)
-      if [[ $cword -eq 2 ]]; then
-        COMPREPLY=( $(compgen -W "sync cache completions" -- "$cur") )
-        return
-      fi
-      case "${words[2]}" in
-        cache)
-          if [[ $cword -eq 3 ]]; then
-            COMPREPLY=( $(compgen -W "status country user" -- "$cur") )
-            return
-          fi
-          ;;
-        completions)
-          if [[ $cword -eq 3 ]]; then
-            COMPREPLY=( $(compgen -W "bash zsh fish" -- "$cur") )
-            return
-          fi
-          ;;
-      esac
+    device:set|device:volume)
+      [[ $cword -eq 3 ]] && _spotify_cli_values device
       ;;
   esac
 }
@@ -1,54 +1,54 @@
-complete -c spotify-cli -n "not __fish_seen_subcommand_from auth player track search album artist playlist device help system" -a "auth player track search album artist playlist device help system"
-complete -c spotify-cli -s v -l verbose -d "Verbose output"
+set -l objects album alias audiobook auth block completions config device episode follow help hook info library log man markets nowplaying open pending pin player playlist queue recentlyplayed releases search session show sync track user
+
+complete -c spotify-cli -n "not __fish_seen_subcommand_from $objects" -a "$objects"
 complete -c spotify-cli -l json -d "Output JSON"
-complete -c spotify-cli -l no-trunc -d "Disable table truncation"
-complete -c spotify-cli -l width -d "Max table column width"
+complete -c spotify-cli -l json-envelope -d "Wrap JSON in a versioned envelope"
+complete -c spotify-cli -n "not __fish_seen_subcommand_from $objects" -l json-schema -d "Print the JSON Schema of every payload"
+complete -c spotify-cli -l fields -d "Project JSON output to dotted paths"
+complete -c spotify-cli -l defer-offline -d "Journal mutations when Spotify is unreachable"
+complete -c spotify-cli -l market -d "Market (ISO country code)"
+complete -c spotify-cli -l no-explicit -d "Hide explicit tracks"
+complete -c spotify-cli -l insecure -d "Skip TLS certificate verification"
+complete -c spotify-cli -l dry-run -d "Show changes without making them"
+complete -c spotify-cli -s y -l yes -d "Answer yes to confirmation prompts"
+complete -c spotify-cli -l max-width -r -d "Truncate table cells wider than N columns"
+complete -c spotify-cli -l column-width -r -d "Truncation width for a table column"
+complete -c spotify-cli -l no-trunc -d "Never truncate table cells"
+complete -c spotify-cli -l timeout -r -d "Stop the command after SECS seconds"
 
-complete -c spotify-cli -n "__fish_seen_subcommand_from auth" -a "login status scopes logout"
+complete -c spotify-cli -n "__fish_seen_subcommand_from album" -a "list play"
+complete -c spotify-cli -n "__fish_seen_subcommand_from auth" -a "login check status scopes logout"
 complete -c spotify-cli -n "__fish_seen_subcommand_from auth" -l client-id -d "Spotify client id"
+complete -c spotify-cli -n "__fish_seen_subcommand_from config" -a "list get set unset path edit"
+complete -c spotify-cli -n "__fish_seen_subcommand_from device" -a "list set volume"
+complete -c spotify-cli -n "__fish_seen_subcommand_from episode" -a "get resume list save"
+complete -c spotify-cli -n "__fish_seen_subcommand_from library" -a "list mirror save prune"
+complete -c spotify-cli -n "__fish_seen_subcommand_from log" -a "list undo"
+complete -c spotify-cli -n "__fish_seen_subcommand_from markets" -a "check"
+complete -c spotify-cli -n "__fish_seen_subcommand_from alias" -a "list add remove"
+complete -c spotify-cli -n "__fish_seen_subcommand_from audiobook" -a "list save"
+complete -c spotify-cli -n "__fish_seen_subcommand_from follow" -a "list artist prune"
+complete -c spotify-cli -n "__fish_seen_subcommand_from block" -a "add remove list check"
+complete -c spotify-cli -n "__fish_seen_subcommand_from hook" -a "list add remove"
+complete -c spotify-cli -n "__fish_seen_subcommand_from pending" -a "list flush clear"
+complete -c spotify-cli -n "__fish_seen_subcommand_from pin" -a "add remove rename dedupe-aliases list tag tags"
+complete -c spotify-cli -n "__fish_seen_subcommand_from player" -a "play pause toggle next prev seek restart radio preview status contexts back-to shuffle repeat mode volume max-volume"
+complete -c spotify-cli -n "__fish_seen_subcommand_from playlist" -a "list find addto create rename edit delete cuesheet play tracks stats audit user move"
+complete -c spotify-cli -n "__fish_seen_subcommand_from queue" -a "list add play clear save load"
+complete -c spotify-cli -n "__fish_seen_subcommand_from releases" -a "check"
+complete -c spotify-cli -n "__fish_seen_subcommand_from session" -a "start stop show"
+complete -c spotify-cli -n "__fish_seen_subcommand_from show" -a "latest episodes list play save"
+complete -c spotify-cli -n "__fish_seen_subcommand_from track" -a "locate"
+complete -c spotify-cli -n "__fish_seen_subcommand_from user" -a "top stats"
+complete -c spotify-cli -n "__fish_seen_subcommand_from completions" -a "bash zsh fish powershell elvish"
+complete -c spotify-cli -n "__fish_seen_subcommand_from man" -l output-dir -r -d "Directory for the man pages"
 
-complete -c spotify-cli -n "__fish_seen_subcommand_from player" -a "play pause next prev status"
-complete -c spotify-cli -n "__fish_seen_subcommand_from track" -a "like unlike info"
-complete -c spotify-cli -n "__fish_seen_subcommand_from album" -a "info play"
-complete -c spotify-cli -n "__fish_seen_subcommand_from album" -l fuzzy -d "Fuzzy match"
-complete -c spotify-cli -n "__fish_seen_subcommand_from album" -l user -d "Use market from token"
-complete -c spotify-cli -n "__fish_seen_subcommand_from album" -l pick -d "Pick a specific result"
-complete -c spotify-cli -n "__fish_seen_subcommand_from album" -l last -d "Use cached search"
-complete -c spotify-cli -n "__fish_seen_subcommand_from album" -l search -d "Search query"
-complete -c spotify-cli -n "__fish_seen_subcommand_from artist" -a "info play"
-complete -c spotify-cli -n "__fish_seen_subcommand_from artist" -l fuzzy -d "Fuzzy match"
-complete -c spotify-cli -n "__fish_seen_subcommand_from artist" -l user -d "Use market from token"
-complete -c spotify-cli -n "__fish_seen_subcommand_from artist" -l pick -d "Pick a specific result"
-complete -c spotify-cli -n "__fish_seen_subcommand_from artist" -l last -d "Use cached search"
-complete -c spotify-cli -n "__fish_seen_subcommand_from artist" -l search -d "Search query"
-
-complete -c spotify-cli -n "__fish_seen_subcommand_from search" -l type -d "Search type"
-complete -c spotify-cli -n "__fish_seen_subcommand_from search" -l fuzzy -d "Fuzzy match"
-complete -c spotify-cli -n "__fish_seen_subcommand_from search" -l user -d "Use market from token"
-complete -c spotify-cli -n "__fish_seen_subcommand_from search" -l limit -d "Limit results"
-complete -c spotify-cli -n "__fish_seen_subcommand_from search" -l pick -d "Pick a specific result"
-complete -c spotify-cli -n "__fish_seen_subcommand_from search" -l last -d "Use cached search"
-complete -c spotify-cli -n "__fish_seen_subcommand_from search" -l play -d "Play first result"
-
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist" -a "list info play add follow unfollow pin"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from list" -l collaborative -d "Only collaborative playlists"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from list" -l owned -d "Only owned playlists"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from list" -l public -d "Only public playlists"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from list" -l private -d "Only private playlists"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from list" -l sort -d "Sort playlists"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from list" -l sort -a "name owner public collaborative"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from info play add follow unfollow" -l fuzzy -d "Fuzzy match"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from info play add follow unfollow" -l user -d "Use market from token"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from info play add follow unfollow" -l pick -d "Pick a specific result"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from info play add follow unfollow" -l last -d "Use cached search"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from info play add follow unfollow" -a "(spotify-cli complete playlist 2>/dev/null)"
-
-complete -c spotify-cli -n "__fish_seen_subcommand_from device" -a "list set"
-complete -c spotify-cli -n "__fish_seen_subcommand_from device; and __fish_seen_subcommand_from list" -l live -d "Query Spotify directly"
-complete -c spotify-cli -n "__fish_seen_subcommand_from device; and __fish_seen_subcommand_from set" -a "(spotify-cli complete device 2>/dev/null)"
-
-complete -c spotify-cli -n "__fish_seen_subcommand_from cache" -a "status country user"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from pin" -a "add remove list play"
-complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from pin; and __fish_seen_subcommand_from remove play" -a "(spotify-cli complete pin 2>/dev/null)"
-complete -c spotify-cli -n "__fish_seen_subcommand_from completions" -a "bash zsh fish"
-complete -c spotify-cli -n "__fish_seen_subcommand_from system" -a "sync cache completions"
+# Dynamic values come from the local cache via the hidden `__complete` command.
+complete -c spotify-cli -n "__fish_seen_subcommand_from playlist; and __fish_seen_subcommand_from addto rename delete cuesheet play stats audit move" -f -a "(spotify-cli __complete playlist 2>/dev/null)"
+complete -c spotify-cli -n "__fish_seen_subcommand_from library; and __fish_seen_subcommand_from mirror" -l playlist -f -a "(spotify-cli __complete playlist 2>/dev/null)"
+complete -c spotify-cli -n "__fish_seen_subcommand_from user; and __fish_seen_subcommand_from top" -l save-to -f -a "(spotify-cli __complete playlist 2>/dev/null)"
+complete -c spotify-cli -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from remove rename" -f -a "(spotify-cli __complete pin 2>/dev/null)"
+complete -c spotify-cli -n "__fish_seen_subcommand_from open" -f -a "(spotify-cli __complete pin 2>/dev/null)"
+complete -c spotify-cli -n "__fish_seen_subcommand_from open" -l app -d "Open in the desktop app"
+complete -c spotify-cli -n "__fish_seen_subcommand_from open" -l web -d "Open in the web player"
+complete -c spotify-cli -n "__fish_seen_subcommand_from device; and __fish_seen_subcommand_from set volume" -f -a "(spotify-cli __complete device 2>/dev/null)"
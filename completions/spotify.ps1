@@ -0,0 +1,62 @@
+# PowerShell completion for spotify-cli. Dot-source this file from $PROFILE.
+Register-ArgumentCompleter -Native -CommandName spotify-cli -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $globalFlags = @('--json', '--json-envelope', '--fields', '--defer-offline', '--market', '--no-explicit', '--insecure', '--dry-run', '--yes', '--max-width', '--column-width', '--no-trunc', '--timeout')
+    $objects = @('album', 'alias', 'audiobook', 'auth', 'block', 'completions', 'config', 'device', 'episode', 'follow', 'help', 'hook', 'info', 'library', 'log', 'man', 'markets', 'nowplaying', 'open', 'pending', 'pin', 'player', 'playlist', 'queue', 'recentlyplayed', 'releases', 'search', 'session', 'show', 'sync', 'track', 'user')
+    $subcommands = @{
+        'album'       = @('list', 'play')
+        'alias'       = @('list', 'add', 'remove')
+        'audiobook'   = @('list', 'save')
+        'auth'        = @('login', 'check', 'status', 'scopes', 'logout')
+        'block'       = @('add', 'remove', 'list', 'check')
+        'config'      = @('list', 'get', 'set', 'unset', 'path', 'edit')
+        'device'      = @('list', 'set', 'volume')
+        'episode'     = @('get', 'resume', 'list', 'save')
+        'follow'      = @('list', 'artist', 'prune')
+        'hook'        = @('list', 'add', 'remove')
+        'library'     = @('list', 'mirror', 'save', 'prune')
+        'log'         = @('list', 'undo')
+        'markets'     = @('check')
+        'pending'     = @('list', 'flush', 'clear')
+        'pin'         = @('add', 'remove', 'rename', 'dedupe-aliases', 'list', 'tag', 'tags')
+        'player'      = @('play', 'pause', 'toggle', 'next', 'prev', 'seek', 'restart', 'radio', 'preview', 'status', 'contexts', 'back-to', 'shuffle', 'repeat', 'mode', 'volume', 'max-volume')
+        'playlist'    = @('list', 'find', 'addto', 'create', 'rename', 'edit', 'delete', 'cuesheet', 'play', 'tracks', 'stats', 'audit', 'user', 'move')
+        'queue'       = @('list', 'add', 'play', 'clear', 'save', 'load')
+        'releases'    = @('check')
+        'session'     = @('start', 'stop', 'show')
+        'show'        = @('latest', 'episodes', 'list', 'play', 'save')
+        'track'       = @('locate')
+        'user'        = @('top', 'stats')
+        'completions' = @('bash', 'zsh', 'fish', 'powershell', 'elvish')
+    }
+
+    # Words typed so far, excluding flags and the word being completed.
+    $words = @($commandAst.CommandElements | Select-Object -Skip 1 | ForEach-Object { $_.ToString() } | Where-Object { $_ -notlike '-*' -and $_ -ne $wordToComplete })
+
+    # Dynamic values come from the local cache via the hidden `__complete` command.
+    $values = {
+        param($kind)
+        spotify-cli __complete $kind 2>$null
+    }
+
+    if ($wordToComplete -like '-*') {
+        $candidates = $globalFlags
+    } elseif ($words.Count -eq 0) {
+        $candidates = $objects
+    } elseif ($words.Count -eq 1 -and $words[0] -eq 'open') {
+        $candidates = & $values 'pin'
+    } elseif ($words.Count -eq 1) {
+        $candidates = $subcommands[$words[0]]
+    } elseif ($words.Count -eq 2) {
+        switch ("$($words[0]):$($words[1])") {
+            { $_ -in 'playlist:addto', 'playlist:rename', 'playlist:edit', 'playlist:delete', 'playlist:cuesheet', 'playlist:play', 'playlist:stats', 'playlist:audit', 'playlist:move' } { $candidates = & $values 'playlist' }
+            { $_ -in 'pin:remove', 'pin:rename' } { $candidates = & $values 'pin' }
+            { $_ -in 'device:set', 'device:volume' } { $candidates = & $values 'device' }
+        }
+    }
+
+    $candidates | Where-Object { $_ -like "$wordToComplete*" } | ForEach-Object {
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }
+}
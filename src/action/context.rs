@@ -0,0 +1,105 @@
+//! Display names for the playback context (the playlist, album, or show playing from),
+//! and the history of recent contexts behind `player back-to`.
+//!
+//! Names are a decoration: when a lookup fails the context is shown by URI only.
+
+use crate::AppContext;
+use crate::domain::player::{PlaybackContext, PlayerStatus, RecentContext};
+use crate::error::Result;
+use crate::spotify::playback::PlaybackClient;
+use crate::time::unix_time;
+
+/// Context kinds with a `GET /{kind}s/{id}` endpoint that carries a name.
+const NAMED_KINDS: [&str; 5] = ["playlist", "album", "artist", "show", "audiobook"];
+
+/// Fill in the context name from the cache, fetching and caching it on a miss.
+pub fn resolve(ctx: &AppContext, context: &mut PlaybackContext) {
+    if context.name.is_none() {
+        context.name = name(ctx, &context.kind, &context.uri);
+    }
+}
+
+/// Fill in missing names of recent contexts.
+pub fn resolve_recent(ctx: &AppContext, recent: &mut [RecentContext]) {
+    for item in recent.iter_mut().filter(|item| item.name.is_none()) {
+        item.name = name(ctx, &item.kind, &item.uri);
+    }
+}
+
+/// Only playlists and albums can be restarted at a given track.
+pub fn resumes_at_track(kind: &str) -> bool {
+    matches!(kind, "playlist" | "album")
+}
+
+/// Remember the context `status` plays from and how far in it is. Recording is a side
+/// effect, so a failed write only warns.
+pub fn remember(ctx: &AppContext, status: &PlayerStatus) {
+    let Some(context) = status.context.as_ref() else {
+        return;
+    };
+    let track = status.track.as_ref();
+    let recent = RecentContext {
+        uri: context.uri.clone(),
+        kind: context.kind.clone(),
+        name: context.name.clone(),
+        track_uri: track.map(|track| format!("spotify:track:{}", track.id)),
+        track_name: track.map(|track| track.name.clone()),
+        position_ms: status.progress_ms.unwrap_or(0),
+        seen_at: unix_time(),
+    };
+    if let Err(err) = ctx.cache.recent_context_store().record(recent) {
+        eprintln!("warning: unable to update recent contexts: {err}");
+    }
+}
+
+/// Remember the current context before playback moves away from it.
+pub fn remember_current(ctx: &AppContext) {
+    // Nothing to remember when the status is unavailable; the play reports its own errors.
+    if let Ok(status) = ctx
+        .spotify()
+        .and_then(|spotify| spotify.playback().status())
+    {
+        remember(ctx, &status);
+    }
+}
+
+/// Play a recent context again, from its track and position where Spotify allows it.
+pub fn resume(playback: &PlaybackClient, recent: &RecentContext) -> Result<()> {
+    match recent.track_uri.as_deref() {
+        Some(track_uri) if resumes_at_track(&recent.kind) => {
+            playback.play_context_at(&recent.uri, track_uri, recent.position_ms)
+        }
+        _ => playback.play_context(&recent.uri),
+    }
+}
+
+fn name(ctx: &AppContext, kind: &str, uri: &str) -> Option<String> {
+    if kind == "collection" {
+        return Some("Liked Songs".to_string());
+    }
+    if !NAMED_KINDS.contains(&kind) {
+        return None;
+    }
+
+    let store = ctx.cache.context_store();
+    let now = unix_time();
+    if let Ok(Some(name)) = store.get(uri, now) {
+        return Some(name);
+    }
+    let id = uri.rsplit(':').next().unwrap_or_default();
+    match ctx
+        .spotify()
+        .and_then(|spotify| spotify.playback().context_name(kind, id))
+    {
+        Ok(name) => {
+            if let Err(err) = store.put(uri, &name, now) {
+                eprintln!("warning: could not cache context name: {err}");
+            }
+            Some(name)
+        }
+        Err(err) => {
+            eprintln!("warning: could not resolve playback context: {err}");
+            None
+        }
+    }
+}
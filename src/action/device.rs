@@ -0,0 +1,148 @@
+//! Device lookup by name through the local device cache.
+//!
+//! Device ids change whenever a client reconnects, so a cached id is trusted until
+//! Spotify rejects it; then the list is fetched again and the name re-resolved once.
+
+use anyhow::bail;
+
+use crate::AppContext;
+use crate::cache::devices::CacheSnapshot;
+use crate::domain::device::Device;
+use crate::error::{ErrorKind, Result, classify};
+use crate::time::unix_time;
+
+/// Fetch the live device list and store it as the cache snapshot.
+pub fn refresh(ctx: &AppContext) -> Result<Vec<Device>> {
+    let devices = ctx.spotify()?.devices().list()?;
+    ctx.cache.device_cache().save(&CacheSnapshot {
+        updated_at: unix_time(),
+        items: devices.clone(),
+    })?;
+    Ok(devices)
+}
+
+/// Cached devices, fetched live when nothing is cached yet.
+pub fn cached(ctx: &AppContext) -> Result<Vec<Device>> {
+    match ctx.cache.device_cache().load()? {
+        Some(snapshot) => Ok(snapshot.items),
+        None => refresh(ctx),
+    }
+}
+
+/// Transfer playback to the device matching `name`.
+///
+/// A name missing from the cache, or a cached id Spotify no longer knows, triggers
+/// one refresh of the device list before giving up.
+pub fn activate(ctx: &AppContext, name: &str) -> Result<Device> {
+    let device = match find_one(&cached(ctx)?, name) {
+        Ok(device) => device,
+        Err(err) if classify(&err) == ErrorKind::NotFound => find_one(&refresh(ctx)?, name)?,
+        Err(err) => return Err(err),
+    };
+    match ctx.spotify()?.devices().set_active(&device.id) {
+        Ok(()) => Ok(device),
+        Err(err) if classify(&err) == ErrorKind::NotFound => {
+            let device = find_one(&refresh(ctx)?, name)?;
+            ctx.spotify()?.devices().set_active(&device.id)?;
+            Ok(device)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Set the active device's volume; returns false when it was skipped.
+///
+/// Devices that report `supports_volume: false` reject volume changes with a bare 403,
+/// so a failure is checked against the device list: such devices fail with
+/// [`ErrorKind::Unsupported`], or are skipped with `ignore_unsupported`.
+pub fn set_volume(ctx: &AppContext, level: u32, ignore_unsupported: bool) -> Result<bool> {
+    let spotify = ctx.spotify()?;
+    let err = match spotify.playback().set_volume(level) {
+        Ok(()) => return Ok(true),
+        Err(err) => err,
+    };
+    match spotify.devices().active() {
+        Ok(Some(device)) if device.supports_volume == Some(false) => {
+            if ignore_unsupported {
+                return Ok(false);
+            }
+            bail!(ErrorKind::Unsupported.error(format!(
+                "{} does not support volume control; pass --ignore-unsupported to skip it",
+                device.name
+            )))
+        }
+        _ => Err(err),
+    }
+}
+
+/// The single device whose name contains `query`, case-insensitively.
+pub fn find_one(devices: &[Device], query: &str) -> Result<Device> {
+    let matches = find_devices(devices, query);
+    match matches.as_slice() {
+        [device] => Ok((*device).clone()),
+        [] => bail!(ErrorKind::NotFound.error(format!("no device matches '{query}'"))),
+        _ => {
+            let names: Vec<String> = matches.iter().map(|device| device.name.clone()).collect();
+            bail!(
+                ErrorKind::Validation
+                    .error(format!("multiple devices match: {}", names.join(", ")))
+            )
+        }
+    }
+}
+
+pub fn find_devices<'a>(devices: &'a [Device], query: &str) -> Vec<&'a Device> {
+    let query = query.to_lowercase();
+    devices
+        .iter()
+        .filter(|device| device.name.to_lowercase().contains(&query))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_devices, find_one};
+    use crate::domain::device::Device;
+    use crate::error::{ErrorKind, classify};
+
+    fn devices() -> Vec<Device> {
+        vec![
+            Device {
+                id: "1".to_string(),
+                name: "Office Speaker".to_string(),
+                volume_percent: Some(50),
+                supports_volume: None,
+            },
+            Device {
+                id: "2".to_string(),
+                name: "Phone".to_string(),
+                volume_percent: None,
+                supports_volume: None,
+            },
+            Device {
+                id: "3".to_string(),
+                name: "Kitchen Speaker".to_string(),
+                volume_percent: None,
+                supports_volume: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn find_devices_matches_case_insensitive() {
+        let devices = devices();
+        let matches = find_devices(&devices, "office");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, "1");
+    }
+
+    #[test]
+    fn find_one_reports_missing_and_ambiguous_names() {
+        let devices = devices();
+        assert_eq!(find_one(&devices, "kitchen").unwrap().id, "3");
+        let missing = find_one(&devices, "garage").unwrap_err();
+        assert_eq!(classify(&missing), ErrorKind::NotFound);
+        let ambiguous = find_one(&devices, "speaker").unwrap_err();
+        assert_ne!(classify(&ambiguous), ErrorKind::NotFound);
+    }
+}
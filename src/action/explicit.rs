@@ -0,0 +1,52 @@
+//! Client-side explicit-content filtering for `--no-explicit` and the `no_explicit` setting.
+
+use crate::AppContext;
+use crate::domain::search::SearchItem;
+
+/// Drop explicit tracks when filtering is on; items with unknown explicitness are kept.
+pub fn filter(ctx: &AppContext, items: &mut Vec<SearchItem>) {
+    if ctx.no_explicit {
+        retain_clean(items);
+    }
+}
+
+fn retain_clean(items: &mut Vec<SearchItem>) {
+    items.retain(|item| item.explicit != Some(true));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::retain_clean;
+    use crate::domain::search::{SearchItem, SearchType};
+
+    fn item(id: &str, explicit: Option<bool>) -> SearchItem {
+        SearchItem {
+            id: id.to_string(),
+            name: id.to_string(),
+            uri: format!("spotify:track:{id}"),
+            kind: SearchType::Track,
+            artists: Vec::new(),
+            album: None,
+            duration_ms: None,
+            owner: None,
+            score: None,
+            playable: None,
+            linked_from: None,
+            saved: None,
+            explicit,
+            popularity: None,
+        }
+    }
+
+    #[test]
+    fn retain_clean_drops_only_explicit_tracks() {
+        let mut items = vec![
+            item("a", Some(true)),
+            item("b", Some(false)),
+            item("c", None),
+        ];
+        retain_clean(&mut items);
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+    }
+}
@@ -0,0 +1,149 @@
+//! Volume fades around `player play` and `player pause`.
+//!
+//! The device volume is stepped through the Web API, so fades are coarse: at most
+//! `MAX_STEPS` requests spread over the duration. The starting level is restored
+//! when the fade finishes or fails part-way.
+
+use std::time::Duration;
+
+use crate::error::Result;
+use crate::spotify::cancel;
+use crate::spotify::playback::PlaybackClient;
+
+/// Upper bound on volume requests per fade.
+const MAX_STEPS: u32 = 20;
+/// Shortest gap between volume requests.
+const MIN_STEP: Duration = Duration::from_millis(250);
+
+/// Fade the volume to zero, pause, then put the volume back for the next play.
+pub fn pause(playback: &PlaybackClient, duration: Duration) -> Result<()> {
+    let Some(original) = current_volume(playback)? else {
+        return playback.pause();
+    };
+    let guard = Restore::new(playback, original);
+    ramp(playback, original, 0, duration)?;
+    playback.pause()?;
+    guard.finish()
+}
+
+/// Start playback silently and fade up to the volume the device had before,
+/// stopping at `cap` when one is given.
+pub fn play(playback: &PlaybackClient, duration: Duration, cap: Option<u32>) -> Result<()> {
+    let Some(previous) = current_volume(playback)? else {
+        return playback.play();
+    };
+    let target = capped(previous, cap);
+    let guard = Restore::new(playback, target);
+    playback.set_volume(0)?;
+    playback.play()?;
+    ramp(playback, 0, target, duration)?;
+    guard.disarm();
+    Ok(())
+}
+
+fn current_volume(playback: &PlaybackClient) -> Result<Option<u32>> {
+    let Some(device) = playback.status()?.device else {
+        eprintln!("warning: device does not report its volume; skipping the fade");
+        return Ok(None);
+    };
+    if device.supports_volume == Some(false) {
+        eprintln!("warning: device does not support volume control; skipping the fade");
+        return Ok(None);
+    }
+    if device.volume_percent.is_none() {
+        eprintln!("warning: device does not report its volume; skipping the fade");
+    }
+    Ok(device.volume_percent)
+}
+
+fn ramp(playback: &PlaybackClient, from: u32, to: u32, duration: Duration) -> Result<()> {
+    let steps = step_count(duration);
+    let interval = duration / steps;
+    for level in levels(from, to, steps) {
+        if !cancel::sleep(interval) {
+            // The restore guard puts the starting volume back.
+            return cancel::check();
+        }
+        playback.set_volume(level)?;
+    }
+    Ok(())
+}
+
+fn capped(level: u32, cap: Option<u32>) -> u32 {
+    cap.map_or(level, |cap| level.min(cap))
+}
+
+fn step_count(duration: Duration) -> u32 {
+    let steps = duration.as_millis() / MIN_STEP.as_millis();
+    (steps as u32).clamp(1, MAX_STEPS)
+}
+
+/// Evenly spaced levels after `from`, ending exactly at `to`.
+fn levels(from: u32, to: u32, steps: u32) -> Vec<u32> {
+    let (from, to, steps) = (i64::from(from), i64::from(to), i64::from(steps.max(1)));
+    (1..=steps)
+        .map(|step| (from + (to - from) * step / steps) as u32)
+        .collect()
+}
+
+/// Puts the starting volume back if the fade is abandoned by an error.
+struct Restore<'a> {
+    playback: &'a PlaybackClient,
+    level: u32,
+    armed: bool,
+}
+
+impl<'a> Restore<'a> {
+    fn new(playback: &'a PlaybackClient, level: u32) -> Self {
+        Self {
+            playback,
+            level,
+            armed: true,
+        }
+    }
+
+    /// Restore the level now, reporting any failure.
+    fn finish(mut self) -> Result<()> {
+        self.armed = false;
+        self.playback.set_volume(self.level)
+    }
+
+    fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            let _ = self.playback.set_volume(self.level);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_STEPS, capped, levels, step_count};
+    use std::time::Duration;
+
+    #[test]
+    fn levels_step_evenly_and_end_on_target() {
+        assert_eq!(levels(80, 0, 4), vec![60, 40, 20, 0]);
+        assert_eq!(levels(0, 50, 5), vec![10, 20, 30, 40, 50]);
+        assert_eq!(levels(30, 30, 2), vec![30, 30]);
+    }
+
+    #[test]
+    fn capped_never_exceeds_the_cap() {
+        assert_eq!(capped(80, Some(60)), 60);
+        assert_eq!(capped(40, Some(60)), 40);
+        assert_eq!(capped(80, None), 80);
+    }
+
+    #[test]
+    fn step_count_is_bounded() {
+        assert_eq!(step_count(Duration::from_millis(100)), 1);
+        assert_eq!(step_count(Duration::from_secs(1)), 4);
+        assert_eq!(step_count(Duration::from_secs(60)), MAX_STEPS);
+    }
+}
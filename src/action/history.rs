@@ -0,0 +1,92 @@
+//! Local listening history, built up from Spotify's recently played list.
+//!
+//! Spotify only returns the last 50 plays, so every fetch is recorded; recording is a
+//! side effect, and a failed write only warns. With `auto_like_plays` set, each recording
+//! also saves tracks that reached that many plays.
+
+use crate::AppContext;
+use crate::action::{oplog, saved};
+use crate::cache::history::ListeningHistory;
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::session::PlayedTrack;
+use crate::error::Result;
+
+/// Spotify returns at most 50 plays per request.
+const RECENT_LIMIT: u32 = 50;
+
+/// Add fetched plays to the local history.
+pub fn record(ctx: &AppContext, plays: &[PlayedTrack]) {
+    if plays.is_empty() {
+        return;
+    }
+    match ctx.cache.history_store().record(plays) {
+        Ok(history) => auto_like(ctx, &history),
+        Err(err) => eprintln!("warning: unable to update listening history: {err}"),
+    }
+}
+
+/// Record the latest plays and return the whole local history.
+pub fn refresh(ctx: &AppContext) -> Result<ListeningHistory> {
+    let page = ctx.spotify()?.search().played_before(None, RECENT_LIMIT)?;
+    let history = ctx.cache.history_store().record(&page.plays)?;
+    auto_like(ctx, &history);
+    Ok(history)
+}
+
+/// Save tracks played at least `auto_like_plays` times; under `--dry-run` they are only planned.
+///
+/// Each track is handled once, so unliking an auto-liked track sticks.
+fn auto_like(ctx: &AppContext, history: &ListeningHistory) {
+    if let Err(err) = try_auto_like(ctx, history) {
+        eprintln!("warning: auto-like failed: {err}");
+    }
+}
+
+fn try_auto_like(ctx: &AppContext, history: &ListeningHistory) -> Result<()> {
+    let Some(threshold) = ctx.auth.settings()?.auto_like_plays else {
+        return Ok(());
+    };
+    let candidates = history.auto_like_candidates(threshold);
+    if candidates.is_empty() {
+        return Ok(());
+    }
+    let mut handled = Vec::new();
+    let mut unsaved = Vec::new();
+    // Tracks whose saved state is unknown are retried on the next recording.
+    let flags = saved::check(ctx, &candidates);
+    for (id, saved) in candidates.into_iter().zip(flags) {
+        match saved {
+            Some(true) => handled.push(id),
+            Some(false) => unsaved.push(id),
+            None => {}
+        }
+    }
+    if !unsaved.is_empty() {
+        let count = unsaved.len();
+        let operation = LoggedOperation::LibrarySave {
+            ids: unsaved.clone(),
+        };
+        oplog::perform(ctx, operation)?;
+        if !ctx.dry_run {
+            eprintln!("Auto-liked {count} tracks played at least {threshold} times");
+        }
+        handled.extend(unsaved);
+    }
+    if ctx.dry_run || handled.is_empty() {
+        return Ok(());
+    }
+    ctx.cache.history_store().mark_auto_liked(&handled)
+}
+
+/// Warn when the local history starts after `cutoff` (`YYYY-MM-DD`), since plays before it
+/// are unknown and everything looks unplayed.
+pub fn warn_if_shorter(history: &ListeningHistory, cutoff: &str) {
+    match history.since.as_deref() {
+        Some(since) if since <= cutoff => {}
+        Some(since) => eprintln!(
+            "warning: local listening history only goes back to {}; earlier plays are not counted",
+            since.get(..10).unwrap_or(since)
+        ),
+        None => eprintln!("warning: no local listening history yet; nothing counts as played"),
+    }
+}
@@ -0,0 +1,147 @@
+//! User scripts run on playback and playlist events.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::bail;
+use serde_json::{Value, json};
+
+use crate::AppContext;
+use crate::action::context;
+use crate::error::{ErrorKind, Result};
+
+pub const PRE_PLAY: &str = "pre_play";
+pub const TRACK_CHANGE: &str = "on_track_change";
+pub const POST_PLAYLIST_ADD: &str = "post_playlist_add";
+
+/// Events a hook can be attached to.
+pub const EVENTS: [&str; 3] = [PRE_PLAY, TRACK_CHANGE, POST_PLAYLIST_ADD];
+
+/// Run before starting playback; a failing hook cancels the play.
+///
+/// Every play passes through here, so it also records the context being left.
+pub fn pre_play(ctx: &AppContext, uri: Option<&str>) -> Result<()> {
+    context::remember_current(ctx);
+    let commands = configured(ctx, PRE_PLAY);
+    if commands.is_empty() {
+        return Ok(());
+    }
+    let payload = json!({ "event": PRE_PLAY, "uri": uri });
+    for command in commands {
+        if let Err(err) = run(&command, PRE_PLAY, &payload) {
+            bail!(ErrorKind::General.error(format!("{PRE_PLAY} hook cancelled playback: {err}")));
+        }
+    }
+    Ok(())
+}
+
+/// Run after a command changed the current track.
+pub fn track_changed(ctx: &AppContext) {
+    let commands = configured(ctx, TRACK_CHANGE);
+    if commands.is_empty() {
+        return;
+    }
+    let track = ctx
+        .spotify()
+        .and_then(|spotify| spotify.playback().status())
+        .ok()
+        .and_then(|status| status.track);
+    let track = track.map(|track| {
+        json!({
+            "id": track.id,
+            "name": track.name,
+            "artists": track.artists,
+            "album": track.album,
+            "duration_ms": track.duration_ms,
+        })
+    });
+    let payload = json!({ "event": TRACK_CHANGE, "track": track });
+    run_all(&commands, TRACK_CHANGE, &payload);
+}
+
+/// Run after tracks were added to a playlist.
+pub fn playlist_added(
+    ctx: &AppContext,
+    playlist_id: &str,
+    uris: &[String],
+    snapshot_id: Option<&str>,
+) {
+    let commands = configured(ctx, POST_PLAYLIST_ADD);
+    if commands.is_empty() {
+        return;
+    }
+    let payload = json!({
+        "event": POST_PLAYLIST_ADD,
+        "playlist_id": playlist_id,
+        "uris": uris,
+        "snapshot_id": snapshot_id,
+    });
+    run_all(&commands, POST_PLAYLIST_ADD, &payload);
+}
+
+fn configured(ctx: &AppContext, event: &str) -> Vec<String> {
+    let Ok(metadata) = ctx.cache.metadata_store().load() else {
+        return Vec::new();
+    };
+    metadata
+        .settings
+        .hooks
+        .into_iter()
+        .filter(|hook| hook.event == event)
+        .map(|hook| hook.command)
+        .collect()
+}
+
+fn run_all(commands: &[String], event: &str, payload: &Value) {
+    for command in commands {
+        if let Err(err) = run(command, event, payload) {
+            eprintln!("{event} hook failed: {err}");
+        }
+    }
+}
+
+/// Run `command` through the shell with the JSON payload on stdin.
+fn run(command: &str, event: &str, payload: &Value) -> Result<()> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .env("SPOTIFY_CLI_HOOK", event)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::inherit())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores stdin may exit before reading it.
+        let _ = stdin.write_all(payload.to_string().as_bytes());
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        bail!(ErrorKind::General.error(format!("`{command}` exited with {status}")));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::run;
+    use serde_json::json;
+
+    #[test]
+    fn run_passes_payload_on_stdin_and_reports_failure() {
+        let payload = json!({ "event": "pre_play", "uri": "spotify:track:1" });
+        assert!(run("grep -q spotify:track:1", "pre_play", &payload).is_ok());
+        assert!(run("grep -q spotify:album:1", "pre_play", &payload).is_err());
+        assert!(
+            run(
+                "test \"$SPOTIFY_CLI_HOOK\" = pre_play",
+                "pre_play",
+                &payload
+            )
+            .is_ok()
+        );
+    }
+}
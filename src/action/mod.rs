@@ -1,3 +1,14 @@
 //! Application-level actions that coordinate cache, auth, and Spotify IO.
 
+pub mod context;
+pub mod device;
+pub mod explicit;
+pub mod fade;
+pub mod history;
+pub mod hooks;
+pub mod oplog;
+pub mod pending;
 pub mod playlist;
+pub mod preview;
+pub mod saved;
+pub mod target;
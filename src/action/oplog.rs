@@ -0,0 +1,165 @@
+//! Mutations recorded in the operations log.
+
+use anyhow::bail;
+
+use crate::AppContext;
+use crate::action::hooks;
+use crate::domain::oplog::{LoggedOperation, OpLogEntry, SavedKind};
+use crate::domain::playlist::PlaylistDetail;
+use crate::error::{ErrorKind, Result};
+use crate::time::unix_time;
+
+/// Stand-in id for a playlist that `--dry-run` did not create.
+pub(crate) const DRY_RUN_PLAYLIST_ID: &str = "(new)";
+
+/// Apply a mutation and append it to the operations log.
+///
+/// Returns the playlist snapshot id when Spotify reports one.
+pub fn perform(ctx: &AppContext, operation: LoggedOperation) -> Result<Option<String>> {
+    perform_undoing(ctx, operation, None)
+}
+
+pub fn perform_undoing(
+    ctx: &AppContext,
+    operation: LoggedOperation,
+    undoes: Option<u64>,
+) -> Result<Option<String>> {
+    if ctx.dry_run {
+        ctx.planned.borrow_mut().push(operation);
+        return Ok(None);
+    }
+    let snapshot_id = apply(ctx, &operation)?;
+    if let LoggedOperation::PlaylistAdd { playlist_id, uris } = &operation {
+        hooks::playlist_added(ctx, playlist_id, uris, snapshot_id.as_deref());
+    }
+    record(ctx, operation, snapshot_id.clone(), undoes);
+    Ok(snapshot_id)
+}
+
+/// Create a playlist and log it; under `--dry-run` a placeholder is returned.
+pub fn create_playlist(
+    ctx: &AppContext,
+    name: &str,
+    public: Option<bool>,
+) -> Result<PlaylistDetail> {
+    if ctx.dry_run {
+        let playlist = PlaylistDetail {
+            id: DRY_RUN_PLAYLIST_ID.to_string(),
+            name: name.to_string(),
+            uri: String::new(),
+            owner: None,
+            tracks_total: Some(0),
+            collaborative: false,
+            public,
+        };
+        ctx.planned
+            .borrow_mut()
+            .push(LoggedOperation::PlaylistCreate {
+                playlist_id: playlist.id.clone(),
+                name: playlist.name.clone(),
+            });
+        return Ok(playlist);
+    }
+    let playlist = ctx.spotify()?.playlists().create(name, public)?;
+    let operation = LoggedOperation::PlaylistCreate {
+        playlist_id: playlist.id.clone(),
+        name: playlist.name.clone(),
+    };
+    record(ctx, operation, None, None);
+    Ok(playlist)
+}
+
+/// Append an already-applied mutation to the operations log.
+pub fn record(
+    ctx: &AppContext,
+    operation: LoggedOperation,
+    snapshot_id: Option<String>,
+    undoes: Option<u64>,
+) {
+    let entry = OpLogEntry {
+        id: 0,
+        at: unix_time(),
+        operation,
+        snapshot_id,
+        undoes,
+    };
+    // The change already happened; a log write failure must not report it as failed.
+    if let Err(err) = ctx.cache.oplog_store().append(entry) {
+        eprintln!("unable to write operations log: {err}");
+    }
+}
+
+fn apply(ctx: &AppContext, operation: &LoggedOperation) -> Result<Option<String>> {
+    let spotify = ctx.spotify()?;
+    match operation {
+        LoggedOperation::PlaylistAdd { playlist_id, uris } => {
+            spotify.playlists().add_tracks(playlist_id, uris)
+        }
+        LoggedOperation::PlaylistRemove { playlist_id, uris } => {
+            spotify.playlists().remove_tracks(playlist_id, uris)
+        }
+        // The new id is only known afterwards, so creation is logged via `record`.
+        LoggedOperation::PlaylistCreate { .. } => {
+            bail!(ErrorKind::Unsupported.error("playlist create cannot be replayed"))
+        }
+        LoggedOperation::PlaylistRename {
+            playlist_id, to, ..
+        } => spotify.playlists().rename(playlist_id, to).map(|_| None),
+        LoggedOperation::PlaylistEdit {
+            playlist_id, to, ..
+        } => spotify
+            .playlists()
+            .set_visibility(playlist_id, to.public, to.collaborative)
+            .map(|_| None),
+        LoggedOperation::PlaylistMove {
+            playlist_id,
+            range_start,
+            insert_before,
+        } => spotify
+            .playlists()
+            .reorder(playlist_id, *range_start, *insert_before),
+        LoggedOperation::PlaylistFollow { playlist_id } => {
+            spotify.playlists().follow(playlist_id).map(|_| None)
+        }
+        LoggedOperation::PlaylistUnfollow { playlist_id } => {
+            spotify.playlists().unfollow(playlist_id).map(|_| None)
+        }
+        LoggedOperation::LibrarySave { ids } => {
+            for id in ids {
+                spotify.track().like(id)?;
+            }
+            Ok(None)
+        }
+        LoggedOperation::LibraryRemove { ids } => {
+            for id in ids {
+                spotify.track().unlike(id)?;
+            }
+            Ok(None)
+        }
+        LoggedOperation::ItemSave { kind, ids } => match kind {
+            SavedKind::Episode => spotify.episodes().save(ids),
+            SavedKind::Show => spotify.shows().save(ids),
+            SavedKind::Audiobook => spotify.audiobooks().save(ids),
+        }
+        .map(|_| None),
+        LoggedOperation::ItemRemove { kind, ids } => match kind {
+            SavedKind::Episode => spotify.episodes().remove(ids),
+            SavedKind::Show => spotify.shows().remove(ids),
+            SavedKind::Audiobook => spotify.audiobooks().remove(ids),
+        }
+        .map(|_| None),
+        LoggedOperation::ArtistFollow { ids } => spotify.artists().follow(ids).map(|_| None),
+        LoggedOperation::ArtistUnfollow { ids } => spotify.artists().unfollow(ids).map(|_| None),
+        LoggedOperation::QueueAdd { uri } => spotify.playback().add_to_queue(uri).map(|_| None),
+        LoggedOperation::PlayerSkip { count } => {
+            let playback = spotify.playback();
+            for _ in 0..*count {
+                playback.next()?;
+            }
+            Ok(None)
+        }
+        LoggedOperation::PlayerPlay { context_uri } => {
+            spotify.playback().play_context(context_uri).map(|_| None)
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Deferred mutations for flaky connections.
+
+use crate::AppContext;
+use crate::action::oplog;
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::pending::{PendingEntry, PendingOperation};
+use crate::error::{ErrorKind, Result, classify};
+use crate::spotify::error::ApiError;
+use crate::time::unix_time;
+
+/// Outcome of running a mutation that may be deferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applied {
+    Now,
+    Deferred,
+}
+
+/// Apply a mutation, journaling it instead when offline and `--defer-offline` is set.
+pub fn run_or_defer(ctx: &AppContext, operation: PendingOperation) -> Result<Applied> {
+    match apply(ctx, &operation) {
+        Ok(()) => Ok(Applied::Now),
+        Err(err) if ctx.defer_offline && is_offline_error(&err) => {
+            ctx.cache.pending_store().push(PendingEntry {
+                operation,
+                queued_at: unix_time(),
+            })?;
+            Ok(Applied::Deferred)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+pub fn apply(ctx: &AppContext, operation: &PendingOperation) -> Result<()> {
+    match operation {
+        PendingOperation::SaveTrack { id } => {
+            let operation = LoggedOperation::LibrarySave {
+                ids: vec![id.clone()],
+            };
+            oplog::perform(ctx, operation).map(|_| ())
+        }
+        PendingOperation::QueueTrack { uri } => {
+            let operation = LoggedOperation::QueueAdd { uri: uri.clone() };
+            oplog::perform(ctx, operation).map(|_| ())
+        }
+        PendingOperation::FollowArtist { id } => {
+            let operation = LoggedOperation::ArtistFollow {
+                ids: vec![id.clone()],
+            };
+            oplog::perform(ctx, operation).map(|_| ())
+        }
+    }
+}
+
+/// Counts from replaying the pending journal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Flushed {
+    pub applied: usize,
+    /// Entries Spotify rejected outright; they are reported once and removed.
+    pub dropped: usize,
+    pub remaining: usize,
+}
+
+/// Replay journaled operations in order, keeping any that still fail.
+///
+/// The journal stays locked while replaying so concurrent runs neither replay an entry twice
+/// nor lose entries pushed meanwhile.
+pub fn flush(ctx: &AppContext) -> Result<Flushed> {
+    let store = ctx.cache.pending_store();
+    let _lock = store.lock()?;
+    let mut journal = store.load()?;
+    if journal.items.is_empty() {
+        return Ok(Flushed::default());
+    }
+
+    let mut remaining = Vec::new();
+    let mut flushed = Flushed::default();
+    let mut offline = false;
+    for entry in journal.items.drain(..) {
+        if offline {
+            remaining.push(entry);
+            continue;
+        }
+        match apply(ctx, &entry.operation) {
+            Ok(()) => flushed.applied += 1,
+            Err(err) if is_rejected_error(&err) => {
+                eprintln!(
+                    "warning: dropped pending {} {}: {err}",
+                    entry.operation.label(),
+                    entry.operation.target()
+                );
+                flushed.dropped += 1;
+            }
+            Err(err) => {
+                offline = is_offline_error(&err);
+                remaining.push(entry);
+            }
+        }
+    }
+    flushed.remaining = remaining.len();
+    journal.items = remaining;
+    store.save(&journal)?;
+    Ok(flushed)
+}
+
+pub fn is_offline_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|err| err.is_connect() || err.is_timeout())
+    })
+}
+
+/// Errors a retry cannot fix: a bad id, a missing item, or a forbidden request.
+fn is_rejected_error(err: &anyhow::Error) -> bool {
+    let forbidden = err.chain().any(|cause| {
+        cause
+            .downcast_ref::<ApiError>()
+            .is_some_and(|err| err.status == reqwest::StatusCode::FORBIDDEN)
+    });
+    forbidden || matches!(classify(err), ErrorKind::Validation | ErrorKind::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_offline_error, is_rejected_error};
+    use crate::spotify::error::format_api_error;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn is_offline_error_ignores_api_errors() {
+        let err = anyhow::anyhow!("spotify request failed: 500");
+        assert!(!is_offline_error(&err));
+    }
+
+    #[test]
+    fn is_rejected_error_keeps_retryable_failures() {
+        let rejected = |status| {
+            is_rejected_error(&anyhow::anyhow!(format_api_error(
+                "spotify save failed",
+                status,
+                "{}"
+            )))
+        };
+        assert!(rejected(StatusCode::NOT_FOUND));
+        assert!(rejected(StatusCode::BAD_REQUEST));
+        assert!(rejected(StatusCode::FORBIDDEN));
+        assert!(!rejected(StatusCode::UNAUTHORIZED));
+        assert!(!rejected(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!rejected(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+}
@@ -3,14 +3,18 @@
 use anyhow::bail;
 
 use crate::AppContext;
+use crate::action::target;
 use crate::domain::playlist::Playlist;
 use crate::domain::search::SearchType;
-use crate::error::Result;
+use crate::error::{ErrorKind, Result};
 
 #[derive(Debug, Clone)]
 pub struct PlaylistSelection {
     pub id: String,
     pub name: String,
+    /// Set when the playlist is writable only because it is collaborative; holds the owner's
+    /// name, or "another user" when Spotify does not report one.
+    pub foreign_owner: Option<String>,
 }
 
 pub fn resolve_for_write(
@@ -23,46 +27,83 @@ pub fn resolve_for_write(
     let user_name = ctx.auth.ensure_user_name()?;
     if last {
         let item = resolve_search(ctx, None, true, user, pick)?;
-        return Ok(PlaylistSelection {
-            id: item.id,
-            name: item.name,
-        });
+        return writable_detail(ctx, &item.id, user_name.as_deref());
     }
 
     let Some(query) = query else {
-        bail!("missing playlist query; use --last to reuse cached search results");
+        bail!(
+            ErrorKind::Validation
+                .error("missing playlist query; use --last to reuse cached search results")
+        );
     };
 
+    if target::is_now(query) {
+        let id = target::now_id(ctx, SearchType::Playlist)?;
+        return writable_detail(ctx, &id, user_name.as_deref());
+    }
+
     if let Some(playlist) = resolve_from_cache(ctx, query, pick, user_name.as_deref())? {
         if !is_writable(&playlist, user_name.as_deref()) {
-            bail!("playlist is read-only; choose an owned or collaborative playlist");
+            bail!(
+                ErrorKind::Validation
+                    .error("playlist is read-only; choose an owned or collaborative playlist")
+            );
         }
         return Ok(PlaylistSelection {
+            foreign_owner: foreign_owner(playlist.owner.as_deref(), user_name.as_deref()),
             id: playlist.id,
             name: playlist.name,
         });
     }
 
     let item = resolve_search(ctx, Some(query), false, user, pick)?;
-    if let Some(user_name) = user_name.as_deref() {
-        let detail = ctx.spotify()?.playlists().get(&item.id)?;
-        let writable = detail
-            .owner
-            .as_ref()
-            .map(|owner| owner.eq_ignore_ascii_case(user_name))
-            .unwrap_or(false)
-            || detail.collaborative;
-        if !writable {
-            bail!("playlist is read-only; choose an owned or collaborative playlist");
-        }
+    if user_name.is_some() {
+        return writable_detail(ctx, &item.id, user_name.as_deref());
     }
 
     Ok(PlaylistSelection {
         id: item.id,
         name: item.name,
+        foreign_owner: None,
+    })
+}
+
+/// Fetch a playlist and reject it unless the user owns it or it is collaborative.
+fn writable_detail(
+    ctx: &AppContext,
+    id: &str,
+    user_name: Option<&str>,
+) -> Result<PlaylistSelection> {
+    let detail = ctx.spotify()?.playlists().get(id)?;
+    let owned = user_name.is_some_and(|user_name| {
+        detail
+            .owner
+            .as_ref()
+            .is_some_and(|owner| owner.eq_ignore_ascii_case(user_name))
+    });
+    if !owned && !detail.collaborative {
+        bail!(
+            ErrorKind::Validation
+                .error("playlist is read-only; choose an owned or collaborative playlist")
+        );
+    }
+    Ok(PlaylistSelection {
+        foreign_owner: foreign_owner(detail.owner.as_deref(), user_name),
+        id: detail.id,
+        name: detail.name,
     })
 }
 
+/// The owner to warn about, or `None` when the user owns the playlist or is unknown.
+fn foreign_owner(owner: Option<&str>, user_name: Option<&str>) -> Option<String> {
+    let user_name = user_name?;
+    match owner {
+        Some(owner) if owner.eq_ignore_ascii_case(user_name) => None,
+        Some(owner) => Some(owner.to_string()),
+        None => Some("another user".to_string()),
+    }
+}
+
 pub fn resolve_from_cache(
     ctx: &AppContext,
     query: &str,
@@ -126,7 +167,7 @@ fn resolve_search(
         validate_pick(pick, results.items.len())?;
     }
     let Some(item) = pick_result(&results.items, pick).cloned() else {
-        bail!("no playlist results");
+        bail!(ErrorKind::NotFound.error("no playlist results"));
     };
     Ok(item)
 }
@@ -143,7 +184,10 @@ fn resolve_results(
     }
 
     let Some(query) = query else {
-        bail!("missing playlist query; use --last to reuse cached search results");
+        bail!(
+            ErrorKind::Validation
+                .error("missing playlist query; use --last to reuse cached search results")
+        );
     };
 
     let query = build_query(query);
@@ -158,13 +202,13 @@ fn load_cached(
 ) -> Result<crate::domain::search::SearchResults> {
     let cached = ctx.cache.search_store().load()?;
     let Some(cached) = cached else {
-        bail!("no cached search; run `spotify-cli search <query>`");
+        bail!(ErrorKind::NotFound.error("no cached search; run `spotify-cli search <query>`"));
     };
     if cached.results.kind != expected {
-        bail!(
+        bail!(ErrorKind::Validation.error(format!(
             "cached search is {}; run `spotify-cli search playlist <query>`",
             search_type_label(cached.results.kind)
-        );
+        )));
     }
     Ok(cached.results)
 }
@@ -206,10 +250,10 @@ fn pick_result(
 
 fn validate_pick(pick: usize, len: usize) -> Result<()> {
     if pick == 0 {
-        bail!("pick must be 1 or greater");
+        bail!(ErrorKind::Validation.error("pick must be 1 or greater"));
     }
     if pick > len {
-        bail!("pick out of range; got {pick}, max {len}");
+        bail!(ErrorKind::Validation.error(format!("pick out of range; got {pick}, max {len}")));
     }
     Ok(())
 }
@@ -288,7 +332,7 @@ fn is_writable(playlist: &Playlist, user_name: Option<&str>) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{match_from_items, resolve_for_write};
+    use super::{foreign_owner, match_from_items, resolve_for_write};
     use crate::AppContext;
     use crate::cache::Cache;
     use crate::cache::metadata::MetadataStore;
@@ -305,6 +349,7 @@ mod tests {
                 owner: Some("Other".to_string()),
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
             Playlist {
                 id: "2".to_string(),
@@ -312,6 +357,7 @@ mod tests {
                 owner: Some("Me".to_string()),
                 collaborative: false,
                 public: Some(false),
+                description: None,
             },
         ];
         let found = match_from_items(items, "Radar", None, Some("Me"))
@@ -320,6 +366,20 @@ mod tests {
         assert_eq!(found.id, "2");
     }
 
+    #[test]
+    fn foreign_owner_ignores_own_and_unknown_users() {
+        assert_eq!(foreign_owner(Some("me"), Some("Me")), None);
+        assert_eq!(
+            foreign_owner(Some("Friend"), Some("Me")).as_deref(),
+            Some("Friend")
+        );
+        assert_eq!(
+            foreign_owner(None, Some("Me")).as_deref(),
+            Some("another user")
+        );
+        assert_eq!(foreign_owner(Some("Friend"), None), None);
+    }
+
     #[test]
     fn resolve_for_write_requires_query_without_last() {
         let cache = Cache::new().unwrap();
@@ -329,6 +389,11 @@ mod tests {
             auth,
             output: Output::new(false, None, None, false),
             verbose: false,
+            defer_offline: false,
+            no_explicit: false,
+            dry_run: false,
+            assume_yes: false,
+            planned: std::cell::RefCell::new(Vec::new()),
             spotify: std::sync::OnceLock::new(),
         };
         let result = resolve_for_write(&ctx, None, false, false, None);
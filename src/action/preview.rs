@@ -0,0 +1,232 @@
+//! Diff-style previews of planned playlist changes for `--dry-run`.
+//!
+//! The preview decorates the plan: when a playlist cannot be read, its lines are
+//! shown by URI and only duplicates within the plan itself are flagged.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::AppContext;
+use crate::action::oplog::DRY_RUN_PLAYLIST_ID;
+use crate::domain::oplog::{DiffChange, DiffLine, LoggedOperation, PlaylistDiff};
+use crate::domain::search::SearchType;
+
+/// One diff per playlist the plan adds to or removes from, in plan order.
+pub fn playlist_diffs(ctx: &AppContext, operations: &[LoggedOperation]) -> Vec<PlaylistDiff> {
+    touched(operations)
+        .into_iter()
+        .map(|(playlist_id, created_name)| {
+            let current = if playlist_id == DRY_RUN_PLAYLIST_ID {
+                None
+            } else {
+                current(ctx, &playlist_id)
+            };
+            let (name, labels) = match current {
+                Some((name, labels)) => (Some(name), labels),
+                None => (created_name, HashMap::new()),
+            };
+            let existing: Vec<String> = labels.keys().cloned().collect();
+            let mut lines = diff_lines(operations, &playlist_id, &existing);
+            label_lines(ctx, &mut lines, labels);
+            PlaylistDiff {
+                playlist_id,
+                name,
+                lines,
+            }
+        })
+        .collect()
+}
+
+/// Playlists with track changes, with the name of any created by the plan.
+fn touched(operations: &[LoggedOperation]) -> Vec<(String, Option<String>)> {
+    let mut playlists: Vec<(String, Option<String>)> = Vec::new();
+    for operation in operations {
+        if let LoggedOperation::PlaylistAdd { playlist_id, .. }
+        | LoggedOperation::PlaylistRemove { playlist_id, .. } = operation
+            && !playlists.iter().any(|(id, _)| id == playlist_id)
+        {
+            let created = operations.iter().find_map(|operation| match operation {
+                LoggedOperation::PlaylistCreate {
+                    playlist_id: created,
+                    name,
+                } if created == playlist_id => Some(name.clone()),
+                _ => None,
+            });
+            playlists.push((playlist_id.clone(), created));
+        }
+    }
+    playlists
+}
+
+/// Playlist name and a label for each track URI already in it.
+fn current(ctx: &AppContext, playlist_id: &str) -> Option<(String, HashMap<String, String>)> {
+    let fetched = ctx.spotify().and_then(|spotify| {
+        let playlists = spotify.playlists();
+        Ok((
+            playlists.get(playlist_id)?,
+            playlists.items_all(playlist_id)?,
+        ))
+    });
+    match fetched {
+        Ok((playlist, items)) => {
+            let labels = items
+                .into_iter()
+                .map(|item| {
+                    let track = item.track;
+                    let label = track_label(&track.name, &track.artists);
+                    (format!("spotify:track:{}", track.id), label)
+                })
+                .collect();
+            Some((playlist.name, labels))
+        }
+        Err(err) => {
+            eprintln!("warning: could not read playlist {playlist_id} for the preview: {err}");
+            None
+        }
+    }
+}
+
+/// Added and removed lines for one playlist, flagging repeated adds.
+fn diff_lines(
+    operations: &[LoggedOperation],
+    playlist_id: &str,
+    existing: &[String],
+) -> Vec<DiffLine> {
+    let mut present: HashSet<&str> = existing.iter().map(String::as_str).collect();
+    let mut lines = Vec::new();
+    for operation in operations {
+        match operation {
+            LoggedOperation::PlaylistAdd {
+                playlist_id: id,
+                uris,
+            } if id == playlist_id => {
+                for uri in uris {
+                    let duplicate = !present.insert(uri.as_str());
+                    lines.push(line(DiffChange::Added, uri, duplicate));
+                }
+            }
+            LoggedOperation::PlaylistRemove {
+                playlist_id: id,
+                uris,
+            } if id == playlist_id => {
+                for uri in uris {
+                    present.remove(uri.as_str());
+                    lines.push(line(DiffChange::Removed, uri, false));
+                }
+            }
+            _ => {}
+        }
+    }
+    lines
+}
+
+fn line(change: DiffChange, uri: &str, duplicate: bool) -> DiffLine {
+    DiffLine {
+        change,
+        uri: uri.to_string(),
+        label: None,
+        duplicate,
+    }
+}
+
+/// Fill labels from the playlist, looking up tracks that are not in it yet.
+fn label_lines(ctx: &AppContext, lines: &mut [DiffLine], mut labels: HashMap<String, String>) {
+    let missing: Vec<String> = lines
+        .iter()
+        .filter(|line| !labels.contains_key(&line.uri))
+        .filter_map(|line| line.uri.strip_prefix("spotify:track:"))
+        .map(str::to_string)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    if !missing.is_empty() {
+        let found = ctx
+            .spotify()
+            .and_then(|spotify| spotify.search().lookup(SearchType::Track, &missing));
+        match found {
+            Ok(items) => {
+                for item in items.into_iter().flatten() {
+                    labels.insert(item.uri.clone(), track_label(&item.name, &item.artists));
+                }
+            }
+            Err(err) => eprintln!("warning: could not look up tracks for the preview: {err}"),
+        }
+    }
+    for line in lines {
+        line.label = labels.get(&line.uri).cloned();
+    }
+}
+
+fn track_label(name: &str, artists: &[String]) -> String {
+    if artists.is_empty() {
+        return name.to_string();
+    }
+    format!("{} - {}", name, artists.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_lines, touched};
+    use crate::domain::oplog::{DiffChange, LoggedOperation};
+
+    fn uris(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| format!("spotify:track:{id}")).collect()
+    }
+
+    #[test]
+    fn diff_lines_flag_adds_already_present_or_repeated() {
+        let operations = vec![
+            LoggedOperation::PlaylistRemove {
+                playlist_id: "p".to_string(),
+                uris: uris(&["b"]),
+            },
+            LoggedOperation::PlaylistAdd {
+                playlist_id: "p".to_string(),
+                uris: uris(&["a", "b", "c", "c"]),
+            },
+            LoggedOperation::PlaylistAdd {
+                playlist_id: "other".to_string(),
+                uris: uris(&["a"]),
+            },
+        ];
+        let lines = diff_lines(&operations, "p", &uris(&["a", "b"]));
+        let summary: Vec<(DiffChange, &str, bool)> = lines
+            .iter()
+            .map(|line| (line.change, line.uri.as_str(), line.duplicate))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (DiffChange::Removed, "spotify:track:b", false),
+                (DiffChange::Added, "spotify:track:a", true),
+                (DiffChange::Added, "spotify:track:b", false),
+                (DiffChange::Added, "spotify:track:c", false),
+                (DiffChange::Added, "spotify:track:c", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn touched_lists_playlists_once_with_created_names() {
+        let operations = vec![
+            LoggedOperation::PlaylistCreate {
+                playlist_id: "(new)".to_string(),
+                name: "Mix".to_string(),
+            },
+            LoggedOperation::PlaylistAdd {
+                playlist_id: "(new)".to_string(),
+                uris: uris(&["a"]),
+            },
+            LoggedOperation::PlaylistAdd {
+                playlist_id: "(new)".to_string(),
+                uris: uris(&["b"]),
+            },
+            LoggedOperation::QueueAdd {
+                uri: "spotify:track:q".to_string(),
+            },
+        ];
+        assert_eq!(
+            touched(&operations),
+            vec![("(new)".to_string(), Some("Mix".to_string()))]
+        );
+    }
+}
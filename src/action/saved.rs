@@ -0,0 +1,65 @@
+//! Saved-state (library) markers for track listings.
+//!
+//! Checks are a decoration: when they fail the listing is still shown, just without markers.
+
+use crate::AppContext;
+use crate::domain::album::Album;
+use crate::domain::search::{SearchItem, SearchType};
+
+/// Mark track results with whether they are in the library.
+pub fn mark_items(ctx: &AppContext, items: &mut [SearchItem]) {
+    let positions: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.kind == SearchType::Track)
+        .map(|(index, _)| index)
+        .collect();
+    let ids: Vec<String> = positions.iter().map(|&i| items[i].id.clone()).collect();
+    for (position, saved) in positions.into_iter().zip(check(ctx, &ids)) {
+        items[position].saved = saved;
+    }
+}
+
+/// Mark album tracks with whether they are in the library.
+pub fn mark_album(ctx: &AppContext, album: &mut Album) {
+    let ids: Vec<String> = album
+        .tracks
+        .iter()
+        .map(|track| uri_id(&track.uri).to_string())
+        .collect();
+    for (track, saved) in album.tracks.iter_mut().zip(check(ctx, &ids)) {
+        track.saved = saved;
+    }
+}
+
+/// Saved state for each id, in order; `None` everywhere when the check fails.
+pub fn check(ctx: &AppContext, ids: &[String]) -> Vec<Option<bool>> {
+    if ids.is_empty() {
+        return Vec::new();
+    }
+    match ctx
+        .spotify()
+        .and_then(|spotify| spotify.track().contains(ids))
+    {
+        Ok(flags) => flags.into_iter().map(Some).collect(),
+        Err(err) => {
+            eprintln!("warning: could not check saved tracks: {err}");
+            vec![None; ids.len()]
+        }
+    }
+}
+
+fn uri_id(uri: &str) -> &str {
+    uri.rsplit(':').next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::uri_id;
+
+    #[test]
+    fn uri_id_takes_last_segment() {
+        assert_eq!(uri_id("spotify:track:abc"), "abc");
+        assert_eq!(uri_id("abc"), "abc");
+    }
+}
@@ -0,0 +1,204 @@
+//! Resolution of the `now` / `@np` target sentinel from current playback, and of pin
+//! aliases.
+
+use anyhow::bail;
+
+use crate::AppContext;
+use crate::domain::player::{PlayerStatus, PlayingEpisode, PlayingItem};
+use crate::domain::search::SearchType;
+use crate::error::{ErrorKind, Result};
+
+/// Arguments that stand for the item currently playing.
+pub const NOW_SENTINELS: [&str; 2] = ["now", "@np"];
+
+pub fn is_now(input: &str) -> bool {
+    NOW_SENTINELS.contains(&input.trim())
+}
+
+/// Resolve `now` / `@np` to the playing item's URI; any other input is returned unchanged.
+pub fn resolve_target(ctx: &AppContext, input: &str, kind: SearchType) -> Result<String> {
+    if !is_now(input) {
+        return Ok(input.to_string());
+    }
+    let status = ctx.spotify()?.playback().status()?;
+    now_uri(&status, kind)
+}
+
+/// A pin alias becomes its URL; anything else is returned unchanged.
+pub fn resolve_pin(ctx: &AppContext, input: &str) -> Result<String> {
+    let pins = ctx.cache.pin_store().load()?;
+    let lower = input.to_lowercase();
+    Ok(pins
+        .items
+        .into_iter()
+        .find(|pin| pin.name.to_lowercase() == lower)
+        .map(|pin| pin.url)
+        .unwrap_or_else(|| input.to_string()))
+}
+
+/// Resolve `now` / `@np` to the id of the playing item.
+pub fn now_id(ctx: &AppContext, kind: SearchType) -> Result<String> {
+    let status = ctx.spotify()?.playback().status()?;
+    let uri = now_uri(&status, kind)?;
+    Ok(uri.rsplit(':').next().unwrap_or_default().to_string())
+}
+
+/// Id of the active playback context of `kind` (`playlist`, `show`, `audiobook`, ...).
+pub fn now_context_id(ctx: &AppContext, kind: &str) -> Result<String> {
+    let status = ctx.spotify()?.playback().status()?;
+    context_id(&status, kind)
+}
+
+/// The episode currently playing.
+pub fn now_episode(ctx: &AppContext) -> Result<PlayingEpisode> {
+    match ctx.spotify()?.playback().current_episode()? {
+        Some(episode) => Ok(episode),
+        None => bail!(ErrorKind::NotFound.error("no episode is currently playing")),
+    }
+}
+
+/// The item currently playing, whatever its kind.
+pub fn now_item(ctx: &AppContext) -> Result<PlayingItem> {
+    match ctx.spotify()?.playback().current_item()? {
+        Some(item) => Ok(item),
+        None => bail!(ErrorKind::NotFound.error("nothing is currently playing")),
+    }
+}
+
+/// Id of the show whose episode is playing.
+pub fn now_show_id(ctx: &AppContext) -> Result<String> {
+    match now_item(ctx)? {
+        PlayingItem::Episode(PlayingEpisode {
+            show_id: Some(id), ..
+        }) => Ok(id),
+        _ => bail!(ErrorKind::NotFound.error("no podcast episode is currently playing")),
+    }
+}
+
+/// Id of the audiobook whose chapter is playing.
+pub fn now_audiobook_id(ctx: &AppContext) -> Result<String> {
+    match now_item(ctx)? {
+        PlayingItem::Chapter { audiobook_id } => Ok(audiobook_id),
+        _ => bail!(ErrorKind::NotFound.error("no audiobook is currently playing")),
+    }
+}
+
+pub fn context_id(status: &PlayerStatus, kind: &str) -> Result<String> {
+    let context = status
+        .context
+        .as_ref()
+        .filter(|context| context.kind == kind);
+    let Some(context) = context else {
+        bail!(ErrorKind::NotFound.error(format!("no {kind} context is active")));
+    };
+    Ok(context
+        .uri
+        .rsplit(':')
+        .next()
+        .unwrap_or_default()
+        .to_string())
+}
+
+pub fn now_uri(status: &PlayerStatus, kind: SearchType) -> Result<String> {
+    let track = status.track.as_ref();
+    let uri = match kind {
+        SearchType::Track | SearchType::All => {
+            track.map(|track| format!("spotify:track:{}", track.id))
+        }
+        SearchType::Album => track
+            .and_then(|track| track.album_id.as_ref())
+            .map(|id| format!("spotify:album:{id}")),
+        SearchType::Artist => track
+            .and_then(|track| track.artist_ids.first())
+            .map(|id| format!("spotify:artist:{id}")),
+        SearchType::Playlist => Some(format!(
+            "spotify:playlist:{}",
+            context_id(status, "playlist")?
+        )),
+    };
+    let Some(uri) = uri else {
+        bail!(ErrorKind::NotFound.error("no track is currently playing"));
+    };
+    Ok(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{context_id, is_now, now_uri};
+    use crate::domain::player::{PlaybackContext, PlayerStatus};
+    use crate::domain::search::SearchType;
+    use crate::domain::track::Track;
+
+    fn status(context: Option<PlaybackContext>) -> PlayerStatus {
+        PlayerStatus {
+            is_playing: true,
+            track: Some(Track {
+                id: "t1".to_string(),
+                name: "Roygbiv".to_string(),
+                artists: vec!["Boards of Canada".to_string()],
+                artist_ids: vec!["ar1".to_string()],
+                album: Some("Music Has the Right to Children".to_string()),
+                album_id: Some("al1".to_string()),
+                duration_ms: None,
+            }),
+            device: None,
+            context,
+            progress_ms: None,
+            repeat_state: None,
+            shuffle_state: None,
+        }
+    }
+
+    #[test]
+    fn is_now_matches_sentinels() {
+        assert!(is_now("now"));
+        assert!(is_now("@np"));
+        assert!(!is_now("nowhere"));
+    }
+
+    #[test]
+    fn now_uri_maps_each_kind() {
+        let status = status(Some(PlaybackContext {
+            kind: "playlist".to_string(),
+            uri: "spotify:user:alice:playlist:p1".to_string(),
+            name: None,
+        }));
+        assert_eq!(
+            now_uri(&status, SearchType::Track).unwrap(),
+            "spotify:track:t1"
+        );
+        assert_eq!(
+            now_uri(&status, SearchType::Album).unwrap(),
+            "spotify:album:al1"
+        );
+        assert_eq!(
+            now_uri(&status, SearchType::Artist).unwrap(),
+            "spotify:artist:ar1"
+        );
+        assert_eq!(
+            now_uri(&status, SearchType::Playlist).unwrap(),
+            "spotify:playlist:p1"
+        );
+    }
+
+    #[test]
+    fn now_uri_requires_playlist_context() {
+        let status = status(Some(PlaybackContext {
+            kind: "album".to_string(),
+            uri: "spotify:album:al1".to_string(),
+            name: None,
+        }));
+        assert!(now_uri(&status, SearchType::Playlist).is_err());
+    }
+
+    #[test]
+    fn context_id_matches_the_context_kind() {
+        let status = status(Some(PlaybackContext {
+            kind: "show".to_string(),
+            uri: "spotify:show:s1".to_string(),
+            name: None,
+        }));
+        assert_eq!(context_id(&status, "show").unwrap(), "s1");
+        assert!(context_id(&status, "audiobook").is_err());
+    }
+}
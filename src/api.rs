@@ -0,0 +1,230 @@
+//! Semver-stable facade over the Spotify clients, for embedding the crate.
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::cache::Cache;
+use crate::cache::metadata::MetadataStore;
+use crate::domain::auth::AuthStatus;
+use crate::domain::device::Device;
+use crate::domain::player::PlayerStatus;
+use crate::domain::playlist::{Playlist, PlaylistDetail, PlaylistTrack};
+use crate::domain::search::{SearchResults, SearchType};
+use crate::domain::track::SavedTrack;
+use crate::error::{ErrorKind, classify};
+use crate::spotify::auth::{AuthService, AuthToken};
+use crate::spotify::client;
+
+/// Result of a [`SpotifyClient`] call.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Error returned by [`SpotifyClient`]; [`Error::kind`] tells failures apart.
+///
+/// `Display` shows the outermost message, `{:#}` the whole cause chain.
+pub struct Error(anyhow::Error);
+
+impl Error {
+    /// Category of the failure, as used for the CLI exit code.
+    pub fn kind(&self) -> ErrorKind {
+        classify(&self.0)
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Authenticated Spotify Web API client with typed methods for common operations.
+///
+/// Ids are bare Spotify ids (`4uLU6hMCjMI75M1A2tKUQC`); URIs are `spotify:<kind>:<id>`.
+#[derive(Debug, Clone)]
+pub struct SpotifyClient {
+    auth: AuthService,
+    inner: client::SpotifyClient,
+}
+
+impl SpotifyClient {
+    /// Client sharing the login saved by `spotify-cli auth login`.
+    pub fn from_cli_login() -> Result<Self> {
+        let cache = Cache::new().map_err(Error)?;
+        Self::with_auth(AuthService::new(cache.metadata_store()))
+    }
+
+    /// Client keeping its login in the metadata file at `path`, created on first login.
+    pub fn with_metadata_path(path: impl Into<PathBuf>) -> Result<Self> {
+        Self::with_auth(AuthService::new(MetadataStore::new(path.into())))
+    }
+
+    fn with_auth(auth: AuthService) -> Result<Self> {
+        let inner = client::SpotifyClient::new(auth.clone()).map_err(Error)?;
+        Ok(Self { auth, inner })
+    }
+
+    /// Run the browser OAuth flow (PKCE) for a Spotify app's client id.
+    pub fn login_oauth(&self, client_id: impl Into<String>) -> Result<()> {
+        self.auth.login_oauth(client_id.into()).map_err(Error)
+    }
+
+    /// Store a token obtained elsewhere; it is refreshed when it has a refresh token.
+    pub fn login(&self, token: AuthToken) -> Result<()> {
+        self.auth.login(token).map_err(Error)
+    }
+
+    pub fn logout(&self) -> Result<()> {
+        self.auth.clear().map_err(Error)
+    }
+
+    pub fn auth_status(&self) -> Result<AuthStatus> {
+        self.auth.status().map_err(Error)
+    }
+
+    /// Current playback; `is_playing` is false and `track` empty when nothing plays.
+    pub fn player_status(&self) -> Result<PlayerStatus> {
+        self.inner.playback().status().map_err(Error)
+    }
+
+    pub fn play(&self) -> Result<()> {
+        self.inner.playback().play().map_err(Error)
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        self.inner.playback().pause().map_err(Error)
+    }
+
+    pub fn next(&self) -> Result<()> {
+        self.inner.playback().next().map_err(Error)
+    }
+
+    pub fn previous(&self) -> Result<()> {
+        self.inner.playback().previous().map_err(Error)
+    }
+
+    /// Play a track or episode URI, or start a playlist, album, artist, or show URI.
+    pub fn play_uri(&self, uri: &str) -> Result<()> {
+        let playback = self.inner.playback();
+        if is_single_item(uri) {
+            playback.play_track(uri).map_err(Error)
+        } else {
+            playback.play_context(uri).map_err(Error)
+        }
+    }
+
+    /// Add a track or episode URI to the playback queue.
+    pub fn queue(&self, uri: &str) -> Result<()> {
+        self.inner.playback().add_to_queue(uri).map_err(Error)
+    }
+
+    pub fn set_volume(&self, percent: u32) -> Result<()> {
+        self.inner
+            .playback()
+            .set_volume(percent.min(100))
+            .map_err(Error)
+    }
+
+    pub fn devices(&self) -> Result<Vec<Device>> {
+        self.inner.devices().list().map_err(Error)
+    }
+
+    /// Search one type, or every type with [`SearchType::All`].
+    pub fn search(&self, query: &str, kind: SearchType, limit: u32) -> Result<SearchResults> {
+        self.inner
+            .search()
+            .search(query, kind, limit, false)
+            .map_err(Error)
+    }
+
+    /// Every track in Liked Songs, newest first.
+    pub fn saved_tracks(&self) -> Result<Vec<SavedTrack>> {
+        self.inner.track().saved().map_err(Error)
+    }
+
+    /// Whether each track id is in Liked Songs, in the same order.
+    pub fn is_saved(&self, ids: &[String]) -> Result<Vec<bool>> {
+        self.inner.track().contains(ids).map_err(Error)
+    }
+
+    pub fn save_track(&self, id: &str) -> Result<()> {
+        self.inner.track().like(id).map_err(Error)
+    }
+
+    pub fn remove_saved_track(&self, id: &str) -> Result<()> {
+        self.inner.track().unlike(id).map_err(Error)
+    }
+
+    /// Playlists the user owns or follows.
+    pub fn playlists(&self) -> Result<Vec<Playlist>> {
+        self.inner.playlists().list_all().map_err(Error)
+    }
+
+    pub fn playlist(&self, id: &str) -> Result<PlaylistDetail> {
+        self.inner.playlists().get(id).map_err(Error)
+    }
+
+    /// Every item of a playlist, in playlist order.
+    pub fn playlist_tracks(&self, id: &str) -> Result<Vec<PlaylistTrack>> {
+        self.inner.playlists().items_all(id).map_err(Error)
+    }
+
+    /// Append up to 100 track or episode URIs; returns the new snapshot id when Spotify sends one.
+    pub fn add_to_playlist(&self, id: &str, uris: &[String]) -> Result<Option<String>> {
+        self.inner.playlists().add_tracks(id, uris).map_err(Error)
+    }
+
+    /// Remove every occurrence of up to 100 URIs; returns the new snapshot id when Spotify sends one.
+    pub fn remove_from_playlist(&self, id: &str, uris: &[String]) -> Result<Option<String>> {
+        self.inner
+            .playlists()
+            .remove_tracks(id, uris)
+            .map_err(Error)
+    }
+}
+
+/// Tracks and episodes play on their own; everything else plays as a context.
+fn is_single_item(uri: &str) -> bool {
+    uri.starts_with("spotify:track:") || uri.starts_with("spotify:episode:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, is_single_item};
+    use crate::error::ErrorKind;
+    use anyhow::{Context, anyhow};
+
+    #[test]
+    fn is_single_item_splits_items_from_contexts() {
+        assert!(is_single_item("spotify:track:abc"));
+        assert!(is_single_item("spotify:episode:abc"));
+        assert!(!is_single_item("spotify:album:abc"));
+        assert!(!is_single_item("spotify:playlist:abc"));
+    }
+
+    #[test]
+    fn error_keeps_kind_and_cause_chain() {
+        let err = Error(
+            Err::<(), _>(anyhow!(ErrorKind::NotFound.error("no such playlist")))
+                .context("playlist lookup failed")
+                .unwrap_err(),
+        );
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert_eq!(err.to_string(), "playlist lookup failed");
+        assert_eq!(
+            format!("{err:#}"),
+            "playlist lookup failed: no such playlist"
+        );
+        let source = std::error::Error::source(&err).expect("source");
+        assert_eq!(source.to_string(), "no such playlist");
+    }
+}
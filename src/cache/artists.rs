@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::domain::artist::FollowerTrend;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// JSON-backed store of follower counts observed by `info artist --full`.
+#[derive(Debug, Clone)]
+pub struct ArtistStatsStore {
+    path: PathBuf,
+}
+
+impl ArtistStatsStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<ArtistStats> {
+        if !self.path.exists() {
+            return Ok(ArtistStats::default());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let stats = SCHEMA.decode(&self.path, &contents)?;
+        Ok(stats)
+    }
+
+    pub fn save(&self, stats: &ArtistStats) -> Result<()> {
+        let payload = SCHEMA.encode(stats)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    /// Record a follower sample and return the trend against the earliest one.
+    pub fn record(&self, id: &str, followers: u64, at: u64) -> Result<Option<FollowerTrend>> {
+        let _lock = file::lock(&self.path)?;
+        let mut stats = self.load()?;
+        let trend = stats.trend(id, followers);
+        stats.record(id, followers, at);
+        self.save(&stats)?;
+        Ok(trend)
+    }
+}
+
+/// Follower samples keyed by artist id.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ArtistStats {
+    pub items: Vec<ArtistFollowers>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtistFollowers {
+    pub id: String,
+    pub samples: Vec<FollowerSample>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowerSample {
+    pub at: u64,
+    pub followers: u64,
+}
+
+impl ArtistStats {
+    pub fn trend(&self, id: &str, followers: u64) -> Option<FollowerTrend> {
+        let first = self
+            .items
+            .iter()
+            .find(|item| item.id == id)?
+            .samples
+            .first()?;
+        Some(FollowerTrend {
+            since: first.at,
+            from: first.followers,
+            change: followers as i64 - first.followers as i64,
+        })
+    }
+
+    pub fn record(&mut self, id: &str, followers: u64, at: u64) {
+        let sample = FollowerSample { at, followers };
+        match self.items.iter_mut().find(|item| item.id == id) {
+            Some(item) => item.samples.push(sample),
+            None => self.items.push(ArtistFollowers {
+                id: id.to_string(),
+                samples: vec![sample],
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArtistStatsStore;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    #[test]
+    fn artist_stats_trend_uses_first_sample() {
+        let path = temp_path("artist-stats");
+        let store = ArtistStatsStore::new(path.clone());
+        assert!(store.record("a", 100, 10).expect("record").is_none());
+        store.record("a", 120, 20).expect("record");
+        let trend = store.record("a", 90, 30).expect("record").expect("trend");
+        assert_eq!(trend.since, 10);
+        assert_eq!(trend.from, 100);
+        assert_eq!(trend.change, -10);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,150 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::domain::block::{BlockEntry, BlockKind};
+use crate::domain::track::Track;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// JSON-backed blocklist of artists and tracks to skip.
+#[derive(Debug, Clone)]
+pub struct BlockStore {
+    path: PathBuf,
+}
+
+impl BlockStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<Blocklist> {
+        if !self.path.exists() {
+            return Ok(Blocklist::default());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let blocks = SCHEMA.decode(&self.path, &contents)?;
+        Ok(blocks)
+    }
+
+    pub fn save(&self, blocks: &Blocklist) -> Result<()> {
+        let payload = SCHEMA.encode(blocks)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    /// Add an entry; returns false when it was already blocked.
+    pub fn add(&self, entry: BlockEntry) -> Result<bool> {
+        let _lock = file::lock(&self.path)?;
+        let mut blocks = self.load()?;
+        if blocks.find(entry.kind, &entry.id).is_some() {
+            return Ok(false);
+        }
+        blocks.items.push(entry);
+        self.save(&blocks)?;
+        Ok(true)
+    }
+
+    pub fn remove(&self, kind: BlockKind, id: &str) -> Result<bool> {
+        let _lock = file::lock(&self.path)?;
+        let mut blocks = self.load()?;
+        let before = blocks.items.len();
+        blocks
+            .items
+            .retain(|entry| !(entry.kind == kind && entry.id == id));
+        let removed = blocks.items.len() != before;
+        if removed {
+            self.save(&blocks)?;
+        }
+        Ok(removed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Blocklist {
+    pub items: Vec<BlockEntry>,
+}
+
+impl Blocklist {
+    pub fn find(&self, kind: BlockKind, id: &str) -> Option<&BlockEntry> {
+        self.items
+            .iter()
+            .find(|entry| entry.kind == kind && entry.id == id)
+    }
+
+    /// Entry that blocks a track, by its own id or any of its artists.
+    pub fn blocking(&self, track: &Track) -> Option<&BlockEntry> {
+        self.find(BlockKind::Track, &track.id).or_else(|| {
+            track
+                .artist_ids
+                .iter()
+                .find_map(|id| self.find(BlockKind::Artist, id))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockStore, Blocklist};
+    use crate::domain::block::{BlockEntry, BlockKind};
+    use crate::domain::track::Track;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    fn entry(kind: BlockKind, id: &str) -> BlockEntry {
+        BlockEntry {
+            kind,
+            id: id.to_string(),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn block_store_adds_once_and_removes() {
+        let path = temp_path("blocks");
+        let store = BlockStore::new(path.clone());
+        assert!(store.add(entry(BlockKind::Artist, "a1")).unwrap());
+        assert!(!store.add(entry(BlockKind::Artist, "a1")).unwrap());
+        assert!(store.add(entry(BlockKind::Track, "a1")).unwrap());
+        assert_eq!(store.load().unwrap().items.len(), 2);
+        assert!(store.remove(BlockKind::Artist, "a1").unwrap());
+        assert!(!store.remove(BlockKind::Artist, "a1").unwrap());
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn blocking_matches_track_or_any_artist() {
+        let blocks = Blocklist {
+            items: vec![
+                entry(BlockKind::Track, "t1"),
+                entry(BlockKind::Artist, "a2"),
+            ],
+        };
+        let track = |id: &str, artists: &[&str]| Track {
+            id: id.to_string(),
+            name: id.to_string(),
+            artists: Vec::new(),
+            artist_ids: artists.iter().map(|id| id.to_string()).collect(),
+            album: None,
+            album_id: None,
+            duration_ms: None,
+        };
+        assert!(blocks.blocking(&track("t1", &[])).is_some());
+        assert!(blocks.blocking(&track("t9", &["a1", "a2"])).is_some());
+        assert!(blocks.blocking(&track("t9", &["a1"])).is_none());
+    }
+}
@@ -0,0 +1,118 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// Resolved names are refreshed after a day, so renamed playlists catch up.
+const TTL_SECS: u64 = 24 * 60 * 60;
+/// Oldest entries are dropped past this many contexts.
+const MAX_ENTRIES: usize = 200;
+
+/// JSON-backed cache of playback context names keyed by context URI.
+#[derive(Debug, Clone)]
+pub struct ContextStore {
+    path: PathBuf,
+}
+
+impl ContextStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<ContextNames> {
+        if !self.path.exists() {
+            return Ok(ContextNames::default());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let names = SCHEMA.decode(&self.path, &contents)?;
+        Ok(names)
+    }
+
+    pub fn save(&self, names: &ContextNames) -> Result<()> {
+        let payload = SCHEMA.encode(names)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    /// Cached name for a context URI, unless it is older than the TTL.
+    pub fn get(&self, uri: &str, now: u64) -> Result<Option<String>> {
+        Ok(self.load()?.fresh(uri, now).map(str::to_string))
+    }
+
+    pub fn put(&self, uri: &str, name: &str, now: u64) -> Result<()> {
+        let _lock = file::lock(&self.path)?;
+        let mut names = self.load()?;
+        names.insert(uri, name, now);
+        self.save(&names)
+    }
+}
+
+/// Cached context names payload.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ContextNames {
+    pub items: Vec<ContextName>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextName {
+    pub uri: String,
+    pub name: String,
+    pub fetched_at: u64,
+}
+
+impl ContextNames {
+    pub fn fresh(&self, uri: &str, now: u64) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|item| item.uri == uri && now.saturating_sub(item.fetched_at) < TTL_SECS)
+            .map(|item| item.name.as_str())
+    }
+
+    pub fn insert(&mut self, uri: &str, name: &str, now: u64) {
+        self.items.retain(|item| item.uri != uri);
+        self.items.push(ContextName {
+            uri: uri.to_string(),
+            name: name.to_string(),
+            fetched_at: now,
+        });
+        if self.items.len() > MAX_ENTRIES {
+            let excess = self.items.len() - MAX_ENTRIES;
+            self.items.drain(..excess);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContextNames, MAX_ENTRIES, TTL_SECS};
+
+    #[test]
+    fn fresh_ignores_expired_entries() {
+        let mut names = ContextNames::default();
+        names.insert("spotify:playlist:p1", "Focus", 100);
+        assert_eq!(names.fresh("spotify:playlist:p1", 200), Some("Focus"));
+        assert_eq!(names.fresh("spotify:playlist:p1", 100 + TTL_SECS), None);
+        assert_eq!(names.fresh("spotify:album:a1", 200), None);
+    }
+
+    #[test]
+    fn insert_replaces_and_caps_entries() {
+        let mut names = ContextNames::default();
+        names.insert("spotify:playlist:p1", "Old", 1);
+        names.insert("spotify:playlist:p1", "New", 2);
+        assert_eq!(names.items.len(), 1);
+        assert_eq!(names.fresh("spotify:playlist:p1", 3), Some("New"));
+
+        for index in 0..MAX_ENTRIES {
+            names.insert(&format!("spotify:album:{index}"), "Album", 3);
+        }
+        assert_eq!(names.items.len(), MAX_ENTRIES);
+        assert_eq!(names.fresh("spotify:playlist:p1", 3), None);
+    }
+}
@@ -3,9 +3,13 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::cache::file;
+use crate::cache::schema::Schema;
 use crate::domain::device::Device;
 use crate::error::Result;
 
+const SCHEMA: Schema = Schema::new(&[]);
+
 /// JSON-backed device cache store.
 #[derive(Debug, Clone)]
 pub struct DeviceCache {
@@ -22,13 +26,13 @@ impl DeviceCache {
             return Ok(None);
         }
         let contents = fs::read_to_string(&self.path)?;
-        let snapshot = serde_json::from_str(&contents)?;
+        let snapshot = SCHEMA.decode(&self.path, &contents)?;
         Ok(Some(snapshot))
     }
 
     pub fn save(&self, snapshot: &CacheSnapshot<Device>) -> Result<()> {
-        let payload = serde_json::to_string_pretty(snapshot)?;
-        fs::write(&self.path, payload)?;
+        let payload = SCHEMA.encode(snapshot)?;
+        file::write_atomic(&self.path, payload)?;
         Ok(())
     }
 }
@@ -67,6 +71,7 @@ mod tests {
                 id: "1".to_string(),
                 name: "Office".to_string(),
                 volume_percent: Some(50),
+                supports_volume: None,
             }],
         };
         cache.save(&snapshot).expect("save");
@@ -0,0 +1,135 @@
+//! Atomic writes and advisory locks shared by the JSON stores.
+//!
+//! Saves go through a temporary file and a rename, so a reader never sees a
+//! half-written file. Read-modify-write methods hold a lock on a `.lock` file
+//! next to the store, so concurrent invocations do not drop each other's changes.
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::error::Result;
+
+/// Exclusive lock on a store, released when dropped.
+#[derive(Debug)]
+pub struct FileLock {
+    _file: File,
+}
+
+/// Block until no other process holds the lock for `path`.
+///
+/// Locks are per open file, so a holder must not lock the same store again.
+pub fn lock(path: &Path) -> Result<FileLock> {
+    let lock_path = sibling(path, "lock");
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("unable to open lock file {}", lock_path.display()))?;
+    file.lock()
+        .with_context(|| format!("unable to lock {}", path.display()))?;
+    Ok(FileLock { _file: file })
+}
+
+/// Take the lock for `path` only if nobody holds it, this process included.
+pub fn try_lock(path: &Path) -> Result<Option<FileLock>> {
+    let lock_path = sibling(path, "lock");
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("unable to open lock file {}", lock_path.display()))?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(FileLock { _file: file })),
+        Err(fs::TryLockError::WouldBlock) => Ok(None),
+        Err(fs::TryLockError::Error(err)) => {
+            Err(err).with_context(|| format!("unable to lock {}", path.display()))
+        }
+    }
+}
+
+/// Replace `path` with `contents` via a temporary file and a rename.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    write_with(path, contents.as_ref(), false)
+}
+
+/// Like `write_atomic`, readable by the owner only on Unix.
+pub fn write_private(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    write_with(path, contents.as_ref(), true)
+}
+
+fn write_with(path: &Path, contents: &[u8], private: bool) -> Result<()> {
+    let temp = sibling(path, &format!("tmp.{}", std::process::id()));
+    let result = (|| -> Result<()> {
+        let mut file = File::create(&temp)?;
+        #[cfg(unix)]
+        if private {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+        #[cfg(not(unix))]
+        let _ = private;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result.with_context(|| format!("unable to write {}", path.display()))
+}
+
+/// `store.json` -> `store.json.<suffix>`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lock, sibling, try_lock, write_atomic};
+    use std::path::{Path, PathBuf};
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    #[test]
+    fn write_atomic_replaces_contents_without_leftovers() {
+        let path = temp_path("atomic");
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        let temp = sibling(&path, &format!("tmp.{}", std::process::id()));
+        assert!(!temp.exists());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn lock_is_released_on_drop() {
+        let path = temp_path("lock");
+        drop(lock(&path).unwrap());
+        let second = lock(&path).unwrap();
+        assert!(sibling(&path, "lock").exists());
+        assert!(try_lock(&path).unwrap().is_none());
+        drop(second);
+        assert!(try_lock(&path).unwrap().is_some());
+        let _ = std::fs::remove_file(sibling(&path, "lock"));
+        assert_eq!(
+            sibling(Path::new("/tmp/pins.json"), "lock"),
+            Path::new("/tmp/pins.json.lock")
+        );
+    }
+}
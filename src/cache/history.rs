@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::domain::session::PlayedTrack;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// JSON-backed store of when tracks and artists were last played.
+///
+/// Spotify only keeps the last 50 plays, so the store grows from every
+/// recently played fetch and only covers plays since it was first filled.
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<ListeningHistory> {
+        if !self.path.exists() {
+            return Ok(ListeningHistory::default());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let history = SCHEMA.decode(&self.path, &contents)?;
+        Ok(history)
+    }
+
+    pub fn save(&self, history: &ListeningHistory) -> Result<()> {
+        let payload = SCHEMA.encode(history)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    /// Merge plays into the store and return the updated history.
+    pub fn record(&self, plays: &[PlayedTrack]) -> Result<ListeningHistory> {
+        let _lock = file::lock(&self.path)?;
+        let mut history = self.load()?;
+        // Oldest first, so each newer play of a track counts once.
+        let mut plays: Vec<&PlayedTrack> = plays.iter().collect();
+        plays.sort_by(|a, b| a.played_at.cmp(&b.played_at));
+        for play in plays {
+            history.record(play);
+        }
+        self.save(&history)?;
+        Ok(history)
+    }
+
+    /// Remember tracks the auto-like rule has dealt with.
+    pub fn mark_auto_liked(&self, ids: &[String]) -> Result<()> {
+        let _lock = file::lock(&self.path)?;
+        let mut history = self.load()?;
+        history.auto_liked.extend(ids.iter().cloned());
+        self.save(&history)
+    }
+}
+
+/// Last play per track and artist id, as ISO 8601 UTC timestamps from Spotify.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ListeningHistory {
+    /// Oldest play recorded; nothing earlier is known.
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub tracks: BTreeMap<String, String>,
+    #[serde(default)]
+    pub artists: BTreeMap<String, String>,
+    /// Plays per track id, counted from when counting began.
+    #[serde(default)]
+    pub plays: BTreeMap<String, u32>,
+    /// Tracks already liked, or found liked, by the auto-like rule; they are not liked again.
+    #[serde(default)]
+    pub auto_liked: BTreeSet<String>,
+}
+
+impl ListeningHistory {
+    pub fn record(&mut self, play: &PlayedTrack) {
+        let at = &play.played_at;
+        if self.since.as_ref().is_none_or(|since| at < since) {
+            self.since = Some(at.clone());
+        }
+        if keep_latest(&mut self.tracks, &play.item.id, at) {
+            *self.plays.entry(play.item.id.clone()).or_default() += 1;
+        }
+        for id in &play.artist_ids {
+            keep_latest(&mut self.artists, id, at);
+        }
+    }
+
+    pub fn track_played(&self, id: &str) -> Option<&str> {
+        self.tracks.get(id).map(String::as_str)
+    }
+
+    pub fn artist_played(&self, id: &str) -> Option<&str> {
+        self.artists.get(id).map(String::as_str)
+    }
+
+    pub fn play_count(&self, id: &str) -> u32 {
+        self.plays.get(id).copied().unwrap_or(0)
+    }
+
+    /// Tracks played at least `threshold` times that the auto-like rule has not handled.
+    pub fn auto_like_candidates(&self, threshold: u32) -> Vec<String> {
+        self.plays
+            .iter()
+            .filter(|(id, count)| **count >= threshold && !self.auto_liked.contains(*id))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+}
+
+/// Keep the later of the stored and new timestamps; true when `at` is a newer play.
+fn keep_latest(map: &mut BTreeMap<String, String>, id: &str, at: &str) -> bool {
+    match map.get_mut(id) {
+        Some(last) if last.as_str() >= at => false,
+        Some(last) => {
+            *last = at.to_string();
+            true
+        }
+        None => {
+            map.insert(id.to_string(), at.to_string());
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HistoryStore, ListeningHistory};
+    use crate::domain::search::{SearchItem, SearchType};
+    use crate::domain::session::PlayedTrack;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    fn play(id: &str, artist_id: &str, played_at: &str) -> PlayedTrack {
+        PlayedTrack {
+            item: SearchItem {
+                id: id.to_string(),
+                name: id.to_string(),
+                uri: format!("spotify:track:{id}"),
+                kind: SearchType::Track,
+                artists: vec![artist_id.to_string()],
+                album: None,
+                duration_ms: None,
+                owner: None,
+                score: None,
+                playable: None,
+                linked_from: None,
+                saved: None,
+                explicit: None,
+                popularity: None,
+            },
+            artist_ids: vec![artist_id.to_string()],
+            played_at: played_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn record_keeps_the_latest_play_and_the_oldest_start() {
+        let mut history = ListeningHistory::default();
+        history.record(&play("t1", "a1", "2024-03-02T10:00:00Z"));
+        history.record(&play("t1", "a1", "2024-01-05T10:00:00Z"));
+        history.record(&play("t2", "a1", "2024-02-01T10:00:00Z"));
+        assert_eq!(history.since.as_deref(), Some("2024-01-05T10:00:00Z"));
+        assert_eq!(history.track_played("t1"), Some("2024-03-02T10:00:00Z"));
+        assert_eq!(history.artist_played("a1"), Some("2024-03-02T10:00:00Z"));
+        assert_eq!(history.artist_played("a2"), None);
+    }
+
+    #[test]
+    fn history_store_merges_across_records() {
+        let path = temp_path("history");
+        let store = HistoryStore::new(path.clone());
+        store
+            .record(&[play("t1", "a1", "2024-01-05T10:00:00Z")])
+            .unwrap();
+        let history = store
+            .record(&[play("t2", "a2", "2024-02-01T10:00:00Z")])
+            .unwrap();
+        assert_eq!(history.tracks.len(), 2);
+        assert_eq!(store.load().unwrap().artists.len(), 2);
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("json.lock"));
+    }
+
+    #[test]
+    fn history_store_counts_each_play_once() {
+        let path = temp_path("history-plays");
+        let store = HistoryStore::new(path.clone());
+        // Spotify lists plays newest first; refetching the same page adds nothing.
+        let page = [
+            play("t1", "a1", "2024-01-07T10:00:00Z"),
+            play("t2", "a1", "2024-01-06T10:00:00Z"),
+            play("t1", "a1", "2024-01-05T10:00:00Z"),
+        ];
+        store.record(&page).unwrap();
+        let history = store.record(&page).unwrap();
+        assert_eq!(history.play_count("t1"), 2);
+        assert_eq!(history.play_count("t2"), 1);
+        assert_eq!(history.auto_like_candidates(2), vec!["t1".to_string()]);
+
+        store.mark_auto_liked(&["t1".to_string()]).unwrap();
+        assert!(store.load().unwrap().auto_like_candidates(2).is_empty());
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("json.lock"));
+    }
+}
@@ -1,11 +1,15 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::cache::file;
+use crate::cache::schema::Schema;
 use crate::domain::settings::Settings;
 use crate::error::Result;
 
+const SCHEMA: Schema = Schema::new(&[]);
+
 /// Persistent metadata for auth credentials and settings.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Metadata {
@@ -42,26 +46,30 @@ impl MetadataStore {
         Self { path }
     }
 
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     pub fn load(&self) -> Result<Metadata> {
         if !self.path.exists() {
             return Ok(Metadata::default());
         }
 
         let contents = fs::read_to_string(&self.path)?;
-        let metadata = serde_json::from_str(&contents)?;
+        let metadata = SCHEMA.decode(&self.path, &contents)?;
         Ok(metadata)
     }
 
     pub fn save(&self, metadata: &Metadata) -> Result<()> {
-        let payload = serde_json::to_string_pretty(metadata)?;
-        fs::write(&self.path, payload)?;
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let _ = fs::set_permissions(&self.path, fs::Permissions::from_mode(0o600));
-        }
+        let payload = SCHEMA.encode(metadata)?;
+        file::write_private(&self.path, payload)?;
         Ok(())
     }
+
+    /// Hold the metadata lock across a load and save.
+    pub fn lock(&self) -> Result<file::FileLock> {
+        file::lock(&self.path)
+    }
 }
 
 #[cfg(test)]
@@ -96,12 +104,28 @@ mod tests {
             settings: Settings {
                 country: Some("AU".to_string()),
                 user_name: Some("Me".to_string()),
+                default_market: Some("AU".to_string()),
+                max_volume: Some(70),
+                device_volumes: Vec::new(),
+                timeout_secs: None,
+                connect_timeout_secs: None,
+                proxy: None,
+                ca_bundle: None,
+                language: None,
+                no_explicit: None,
+                open_in: None,
+                log_level: None,
+                log_max_kb: None,
+                auto_like_plays: None,
+                aliases: Vec::new(),
+                hooks: Vec::new(),
             },
         };
         store.save(&metadata).expect("save");
         let loaded = store.load().expect("load");
         assert_eq!(loaded.settings.country.as_deref(), Some("AU"));
         assert_eq!(loaded.settings.user_name.as_deref(), Some("Me"));
+        assert_eq!(loaded.settings.max_volume, Some(70));
         let _ = fs::remove_file(path);
     }
 }
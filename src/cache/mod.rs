@@ -1,4 +1,4 @@
-//! Cache storage for devices, playlists, pins, search results, and metadata.
+//! Cache storage for devices, playlists, pins, search results, metadata, and other local state.
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -8,11 +8,23 @@ use crate::cache::metadata::MetadataStore;
 use crate::cache::playlists::PlaylistCache;
 use crate::error::Result;
 
+pub mod artists;
+pub mod blocks;
+pub mod contexts;
 pub mod devices;
+pub mod file;
+pub mod history;
 pub mod metadata;
+pub mod oplog;
+pub mod pending;
 pub mod pins;
 pub mod playlists;
+pub mod queues;
+pub mod recents;
+pub mod releases;
+pub mod schema;
 pub mod search;
+pub mod sessions;
 
 #[derive(Debug, Clone)]
 pub struct Cache {
@@ -46,13 +58,53 @@ impl Cache {
         PlaylistCache::new(self.root.join("playlists.json"))
     }
 
+    pub fn pending_store(&self) -> pending::PendingStore {
+        pending::PendingStore::new(self.root.join("pending.json"))
+    }
+
+    pub fn oplog_store(&self) -> oplog::OpLogStore {
+        oplog::OpLogStore::new(self.root.join("oplog.jsonl"))
+    }
+
     pub fn pin_store(&self) -> pins::PinStore {
         pins::PinStore::new(self.root.join("pins.json"))
     }
 
+    pub fn queue_store(&self) -> queues::QueueStore {
+        queues::QueueStore::new(self.root.join("queues.json"))
+    }
+
+    pub fn artist_stats_store(&self) -> artists::ArtistStatsStore {
+        artists::ArtistStatsStore::new(self.root.join("artist_stats.json"))
+    }
+
+    pub fn release_store(&self) -> releases::ReleaseStore {
+        releases::ReleaseStore::new(self.root.join("releases.json"))
+    }
+
+    pub fn block_store(&self) -> blocks::BlockStore {
+        blocks::BlockStore::new(self.root.join("blocks.json"))
+    }
+
     pub fn search_store(&self) -> search::SearchStore {
         search::SearchStore::new(self.root.join("search.json"))
     }
+
+    pub fn context_store(&self) -> contexts::ContextStore {
+        contexts::ContextStore::new(self.root.join("contexts.json"))
+    }
+
+    pub fn recent_context_store(&self) -> recents::RecentContextStore {
+        recents::RecentContextStore::new(self.root.join("recent_contexts.json"))
+    }
+
+    pub fn session_store(&self) -> sessions::SessionStore {
+        sessions::SessionStore::new(self.root.join("session.json"))
+    }
+
+    pub fn history_store(&self) -> history::HistoryStore {
+        history::HistoryStore::new(self.root.join("history.json"))
+    }
 }
 
 fn default_root() -> Result<PathBuf> {
@@ -0,0 +1,115 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::cache::file;
+use crate::domain::oplog::OpLogEntry;
+use crate::error::Result;
+
+/// Append-only JSONL log of mutating operations.
+#[derive(Debug, Clone)]
+pub struct OpLogStore {
+    path: PathBuf,
+}
+
+impl OpLogStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<Vec<OpLogEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let mut entries = Vec::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            entries.push(serde_json::from_str(line)?);
+        }
+        Ok(entries)
+    }
+
+    pub fn get(&self, id: u64) -> Result<Option<OpLogEntry>> {
+        Ok(self.load()?.into_iter().find(|entry| entry.id == id))
+    }
+
+    /// Whether a later entry already reverted `id`.
+    pub fn is_undone(&self, id: u64) -> Result<bool> {
+        Ok(self.load()?.iter().any(|entry| entry.undoes == Some(id)))
+    }
+
+    /// Append `entry`, assigning the next id; returns the assigned id.
+    pub fn append(&self, mut entry: OpLogEntry) -> Result<u64> {
+        let _lock = file::lock(&self.path)?;
+        entry.id = self.load()?.last().map_or(1, |last| last.id + 1);
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(entry.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OpLogStore;
+    use crate::domain::oplog::{LoggedOperation, OpLogEntry};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.jsonl"));
+        path
+    }
+
+    fn entry(operation: LoggedOperation, undoes: Option<u64>) -> OpLogEntry {
+        OpLogEntry {
+            id: 0,
+            at: 1,
+            operation,
+            snapshot_id: None,
+            undoes,
+        }
+    }
+
+    #[test]
+    fn oplog_store_appends_and_tracks_undo() {
+        let path = temp_path("oplog");
+        let store = OpLogStore::new(path.clone());
+        let add = LoggedOperation::PlaylistAdd {
+            playlist_id: "p".to_string(),
+            uris: vec!["spotify:track:1".to_string()],
+        };
+
+        assert_eq!(store.append(entry(add.clone(), None)).unwrap(), 1);
+        assert!(!store.is_undone(1).unwrap());
+        assert_eq!(
+            store
+                .append(entry(add.inverse().unwrap(), Some(1)))
+                .unwrap(),
+            2
+        );
+        assert!(store.is_undone(1).unwrap());
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(
+            loaded[1].operation,
+            LoggedOperation::PlaylistRemove {
+                playlist_id: "p".to_string(),
+                uris: vec!["spotify:track:1".to_string()],
+            }
+        );
+        assert_eq!(store.get(2).unwrap().unwrap().undoes, Some(1));
+
+        let _ = fs::remove_file(path);
+    }
+}
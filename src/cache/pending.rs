@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::domain::pending::PendingEntry;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// JSON-backed journal of mutations deferred while offline.
+#[derive(Debug, Clone)]
+pub struct PendingStore {
+    path: PathBuf,
+}
+
+impl PendingStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<PendingJournal> {
+        if !self.path.exists() {
+            return Ok(PendingJournal::default());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let journal = SCHEMA.decode(&self.path, &contents)?;
+        Ok(journal)
+    }
+
+    pub fn save(&self, journal: &PendingJournal) -> Result<()> {
+        let payload = SCHEMA.encode(journal)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    /// Hold the journal lock across a load and save.
+    pub fn lock(&self) -> Result<file::FileLock> {
+        file::lock(&self.path)
+    }
+
+    pub fn push(&self, entry: PendingEntry) -> Result<()> {
+        let _lock = file::lock(&self.path)?;
+        let mut journal = self.load()?;
+        journal.items.push(entry);
+        self.save(&journal)
+    }
+
+    pub fn clear(&self) -> Result<usize> {
+        let _lock = file::lock(&self.path)?;
+        let journal = self.load()?;
+        let count = journal.items.len();
+        if count > 0 {
+            self.save(&PendingJournal::default())?;
+        }
+        Ok(count)
+    }
+}
+
+/// Pending operations payload.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PendingJournal {
+    pub items: Vec<PendingEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PendingStore;
+    use crate::domain::pending::{PendingEntry, PendingOperation};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    #[test]
+    fn pending_store_push_and_clear() {
+        let path = temp_path("pending");
+        let store = PendingStore::new(path.clone());
+
+        store
+            .push(PendingEntry {
+                operation: PendingOperation::SaveTrack {
+                    id: "abc".to_string(),
+                },
+                queued_at: 1,
+            })
+            .unwrap();
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.items.len(), 1);
+        assert_eq!(
+            loaded.items[0].operation,
+            PendingOperation::SaveTrack {
+                id: "abc".to_string()
+            }
+        );
+
+        assert_eq!(store.clear().unwrap(), 1);
+        assert!(store.load().unwrap().items.is_empty());
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn pending_store_keeps_follow_artist_entries() {
+        let path = temp_path("pending-follow");
+        let store = PendingStore::new(path.clone());
+        let operation = PendingOperation::FollowArtist {
+            id: "artist1".to_string(),
+        };
+
+        store
+            .push(PendingEntry {
+                operation: operation.clone(),
+                queued_at: 1,
+            })
+            .unwrap();
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .contains("\"op\": \"follow_artist\"")
+        );
+        assert_eq!(store.load().unwrap().items[0].operation, operation);
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -1,10 +1,17 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
+use anyhow::bail;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::pin::PinnedPlaylist;
-use crate::error::Result;
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::domain::pin::{PinnedPlaylist, normalize_tag};
+use crate::domain::stats::Tally;
+use crate::error::{ErrorKind, Result};
+
+const SCHEMA: Schema = Schema::new(&[]);
 
 /// JSON-backed pin store for local playlist shortcuts.
 #[derive(Debug, Clone)]
@@ -22,17 +29,18 @@ impl PinStore {
             return Ok(Pins::default());
         }
         let contents = fs::read_to_string(&self.path)?;
-        let pins = serde_json::from_str(&contents)?;
+        let pins = SCHEMA.decode(&self.path, &contents)?;
         Ok(pins)
     }
 
     pub fn save(&self, pins: &Pins) -> Result<()> {
-        let payload = serde_json::to_string_pretty(pins)?;
-        fs::write(&self.path, payload)?;
+        let payload = SCHEMA.encode(pins)?;
+        file::write_atomic(&self.path, payload)?;
         Ok(())
     }
 
     pub fn add(&self, name: String, url: String) -> Result<()> {
+        let _lock = file::lock(&self.path)?;
         let mut pins = self.load()?;
         let lower = name.to_lowercase();
         if let Some(existing) = pins
@@ -43,12 +51,17 @@ impl PinStore {
             existing.url = url;
             existing.name = name;
         } else {
-            pins.items.push(PinnedPlaylist { name, url });
+            pins.items.push(PinnedPlaylist {
+                name,
+                url,
+                tags: Vec::new(),
+            });
         }
         self.save(&pins)
     }
 
     pub fn remove(&self, name: &str) -> Result<bool> {
+        let _lock = file::lock(&self.path)?;
         let mut pins = self.load()?;
         let before = pins.items.len();
         let lower = name.to_lowercase();
@@ -59,6 +72,80 @@ impl PinStore {
         }
         Ok(removed)
     }
+
+    /// Rename a pin; fails if the new alias belongs to a different pin.
+    pub fn rename(&self, old: &str, new: String) -> Result<bool> {
+        let _lock = file::lock(&self.path)?;
+        let mut pins = self.load()?;
+        let old_lower = old.to_lowercase();
+        let new_lower = new.to_lowercase();
+        if new_lower != old_lower && pins.find(&new_lower).is_some() {
+            bail!(ErrorKind::Validation.error(format!("pin already exists: {new}")));
+        }
+        let Some(pin) = pins
+            .items
+            .iter_mut()
+            .find(|item| item.name.to_lowercase() == old_lower)
+        else {
+            return Ok(false);
+        };
+        pin.name = new;
+        self.save(&pins)?;
+        Ok(true)
+    }
+
+    /// Tag a pin; returns false when no pin has the alias.
+    pub fn tag(&self, name: &str, tag: &str) -> Result<bool> {
+        self.update_tags(name, |tags| {
+            let tag = normalize_tag(tag);
+            if !tags.contains(&tag) {
+                tags.push(tag);
+                tags.sort();
+            }
+        })
+    }
+
+    /// Remove a tag from a pin; returns false when no pin has the alias.
+    pub fn untag(&self, name: &str, tag: &str) -> Result<bool> {
+        self.update_tags(name, |tags| {
+            let tag = normalize_tag(tag);
+            tags.retain(|own| *own != tag);
+        })
+    }
+
+    fn update_tags(&self, name: &str, update: impl FnOnce(&mut Vec<String>)) -> Result<bool> {
+        let _lock = file::lock(&self.path)?;
+        let mut pins = self.load()?;
+        let lower = name.to_lowercase();
+        let Some(pin) = pins
+            .items
+            .iter_mut()
+            .find(|item| item.name.to_lowercase() == lower)
+        else {
+            return Ok(false);
+        };
+        update(&mut pin.tags);
+        self.save(&pins)?;
+        Ok(true)
+    }
+
+    /// Keep one alias and drop the others it was merged with.
+    pub fn merge(&self, keep: &str, drop: &[String]) -> Result<()> {
+        let _lock = file::lock(&self.path)?;
+        let mut pins = self.load()?;
+        let keep_lower = keep.to_lowercase();
+        if pins.find(&keep_lower).is_none() {
+            bail!(ErrorKind::NotFound.error(format!("no pin found: {keep}")));
+        }
+        let drop: Vec<String> = drop
+            .iter()
+            .map(|name| name.to_lowercase())
+            .filter(|name| *name != keep_lower)
+            .collect();
+        pins.items
+            .retain(|item| !drop.contains(&item.name.to_lowercase()));
+        self.save(&pins)
+    }
 }
 
 /// Pin collection payload.
@@ -67,9 +154,80 @@ pub struct Pins {
     pub items: Vec<PinnedPlaylist>,
 }
 
+impl Pins {
+    fn find(&self, lower: &str) -> Option<&PinnedPlaylist> {
+        self.items
+            .iter()
+            .find(|item| item.name.to_lowercase() == lower)
+    }
+
+    /// Pins carrying `tag`, in pin order.
+    pub fn tagged(&self, tag: &str) -> Vec<PinnedPlaylist> {
+        self.items
+            .iter()
+            .filter(|pin| pin.has_tag(tag))
+            .cloned()
+            .collect()
+    }
+
+    /// Every tag with the number of pins carrying it, most used first.
+    pub fn tag_counts(&self) -> Vec<Tally> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for tag in self.items.iter().flat_map(|pin| &pin.tags) {
+            *counts.entry(tag).or_default() += 1;
+        }
+        let mut tallies: Vec<Tally> = counts
+            .into_iter()
+            .map(|(name, count)| Tally {
+                name: name.to_string(),
+                count,
+            })
+            .collect();
+        // Stable, so equal counts stay alphabetical.
+        tallies.sort_by_key(|tally| std::cmp::Reverse(tally.count));
+        tallies
+    }
+
+    /// Group pins whose aliases differ only in case/punctuation or that share a URL.
+    pub fn collisions(&self) -> Vec<Vec<PinnedPlaylist>> {
+        let mut groups: Vec<Vec<PinnedPlaylist>> = Vec::new();
+        for pin in &self.items {
+            let alias = normalize_alias(&pin.name);
+            let url = normalize_url(&pin.url);
+            let group = groups.iter_mut().find(|group| {
+                group.iter().any(|other| {
+                    normalize_alias(&other.name) == alias || normalize_url(&other.url) == url
+                })
+            });
+            match group {
+                Some(group) => group.push(pin.clone()),
+                None => groups.push(vec![pin.clone()]),
+            }
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+}
+
+fn normalize_alias(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn normalize_url(url: &str) -> String {
+    url.split('?')
+        .next()
+        .unwrap_or(url)
+        .trim_end_matches('/')
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::PinStore;
+    use super::{PinStore, Pins};
+    use crate::domain::pin::PinnedPlaylist;
     use std::fs;
     use std::path::PathBuf;
 
@@ -104,4 +262,101 @@ mod tests {
 
         let _ = fs::remove_file(path);
     }
+
+    #[test]
+    fn pin_store_rename_rejects_collisions() {
+        let path = temp_path("pins-rename");
+        let store = PinStore::new(path.clone());
+        store.add("Focus".to_string(), "a".to_string()).unwrap();
+        store.add("Chill".to_string(), "b".to_string()).unwrap();
+
+        assert!(store.rename("focus", "Chill".to_string()).is_err());
+        assert!(store.rename("focus", "FOCUS".to_string()).unwrap());
+        assert!(!store.rename("missing", "Other".to_string()).unwrap());
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.items[0].name, "FOCUS");
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn pin_store_merge_keeps_one_alias() {
+        let path = temp_path("pins-merge");
+        let store = PinStore::new(path.clone());
+        store.add("Focus".to_string(), "a".to_string()).unwrap();
+        store.add("focus!".to_string(), "a".to_string()).unwrap();
+
+        store.merge("Focus", &["focus!".to_string()]).unwrap();
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.items.len(), 1);
+        assert_eq!(loaded.items[0].name, "Focus");
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn pins_collisions_match_alias_or_url() {
+        let pin = |name: &str, url: &str| PinnedPlaylist {
+            name: name.to_string(),
+            url: url.to_string(),
+            tags: Vec::new(),
+        };
+        let pins = Pins {
+            items: vec![
+                pin("Deep Focus", "https://open.spotify.com/playlist/1"),
+                pin("deep-focus", "https://open.spotify.com/playlist/2"),
+                pin("Work", "https://open.spotify.com/playlist/3?si=x"),
+                pin("Office", "https://open.spotify.com/playlist/3"),
+                pin("Chill", "https://open.spotify.com/playlist/4"),
+            ],
+        };
+        let groups = pins.collisions();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].len(), 2);
+        assert_eq!(groups[1][1].name, "Office");
+    }
+
+    #[test]
+    fn pin_store_tags_normalize_and_keep_on_update() {
+        let path = temp_path("pins-tags");
+        let store = PinStore::new(path.clone());
+        store.add("Run".to_string(), "a".to_string()).unwrap();
+
+        assert!(store.tag("run", " Workout").unwrap());
+        assert!(store.tag("Run", "workout").unwrap());
+        assert!(store.tag("Run", "cardio").unwrap());
+        assert!(!store.tag("missing", "workout").unwrap());
+        store.add("Run".to_string(), "b".to_string()).unwrap();
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.items[0].tags, vec!["cardio", "workout"]);
+
+        assert!(store.untag("Run", "CARDIO").unwrap());
+        assert_eq!(store.load().unwrap().items[0].tags, vec!["workout"]);
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("json.lock"));
+    }
+
+    #[test]
+    fn pins_tag_counts_rank_by_use() {
+        let pin = |name: &str, tags: &[&str]| PinnedPlaylist {
+            name: name.to_string(),
+            url: name.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+        let pins = Pins {
+            items: vec![
+                pin("Run", &["workout", "cardio"]),
+                pin("Lift", &["workout"]),
+                pin("Focus", &["calm"]),
+            ],
+        };
+        let counts = pins.tag_counts();
+        let names: Vec<&str> = counts.iter().map(|tally| tally.name.as_str()).collect();
+        assert_eq!(names, vec!["workout", "calm", "cardio"]);
+        assert_eq!(counts[0].count, 2);
+        let tagged = pins.tagged("Workout");
+        assert_eq!(tagged.len(), 2);
+        assert_eq!(tagged[1].name, "Lift");
+    }
 }
@@ -3,9 +3,13 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::cache::file;
+use crate::cache::schema::Schema;
 use crate::domain::playlist::Playlist;
 use crate::error::Result;
 
+const SCHEMA: Schema = Schema::new(&[]);
+
 /// JSON-backed playlist cache store.
 #[derive(Debug, Clone)]
 pub struct PlaylistCache {
@@ -22,13 +26,13 @@ impl PlaylistCache {
             return Ok(None);
         }
         let contents = fs::read_to_string(&self.path)?;
-        let snapshot = serde_json::from_str(&contents)?;
+        let snapshot = SCHEMA.decode(&self.path, &contents)?;
         Ok(Some(snapshot))
     }
 
     pub fn save(&self, snapshot: &CacheSnapshot<Playlist>) -> Result<()> {
-        let payload = serde_json::to_string_pretty(snapshot)?;
-        fs::write(&self.path, payload)?;
+        let payload = SCHEMA.encode(snapshot)?;
+        file::write_atomic(&self.path, payload)?;
         Ok(())
     }
 }
@@ -69,6 +73,7 @@ mod tests {
                 owner: Some("Me".to_string()),
                 collaborative: false,
                 public: Some(false),
+                description: None,
             }],
         };
         cache.save(&snapshot).expect("save");
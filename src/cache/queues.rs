@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::domain::queue::SavedQueue;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// JSON-backed store for named queue snapshots.
+#[derive(Debug, Clone)]
+pub struct QueueStore {
+    path: PathBuf,
+}
+
+impl QueueStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<SavedQueues> {
+        if !self.path.exists() {
+            return Ok(SavedQueues::default());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let queues = SCHEMA.decode(&self.path, &contents)?;
+        Ok(queues)
+    }
+
+    pub fn save(&self, queues: &SavedQueues) -> Result<()> {
+        let payload = SCHEMA.encode(queues)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Result<Option<SavedQueue>> {
+        let queues = self.load()?;
+        let lower = name.to_lowercase();
+        Ok(queues
+            .items
+            .into_iter()
+            .find(|item| item.name.to_lowercase() == lower))
+    }
+
+    pub fn put(&self, queue: SavedQueue) -> Result<()> {
+        let _lock = file::lock(&self.path)?;
+        let mut queues = self.load()?;
+        let lower = queue.name.to_lowercase();
+        if let Some(existing) = queues
+            .items
+            .iter_mut()
+            .find(|item| item.name.to_lowercase() == lower)
+        {
+            *existing = queue;
+        } else {
+            queues.items.push(queue);
+        }
+        self.save(&queues)
+    }
+}
+
+/// Saved queue collection payload.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SavedQueues {
+    pub items: Vec<SavedQueue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueueStore;
+    use crate::domain::queue::SavedQueue;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    #[test]
+    fn queue_store_put_replaces_by_name() {
+        let path = temp_path("queues");
+        let store = QueueStore::new(path.clone());
+
+        store
+            .put(SavedQueue {
+                name: "Work".to_string(),
+                uris: vec!["spotify:track:1".to_string()],
+                saved_at: 1,
+            })
+            .unwrap();
+        store
+            .put(SavedQueue {
+                name: "work".to_string(),
+                uris: vec!["spotify:track:2".to_string()],
+                saved_at: 2,
+            })
+            .unwrap();
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.items.len(), 1);
+        let queue = store.get("WORK").unwrap().expect("queue");
+        assert_eq!(queue.uris, vec!["spotify:track:2".to_string()]);
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::domain::player::RecentContext;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// Oldest contexts are dropped past this many.
+const MAX_ENTRIES: usize = 50;
+
+/// JSON-backed store of recently played contexts, newest first.
+#[derive(Debug, Clone)]
+pub struct RecentContextStore {
+    path: PathBuf,
+}
+
+impl RecentContextStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<RecentContexts> {
+        if !self.path.exists() {
+            return Ok(RecentContexts::default());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let recent = SCHEMA.decode(&self.path, &contents)?;
+        Ok(recent)
+    }
+
+    pub fn save(&self, recent: &RecentContexts) -> Result<()> {
+        let payload = SCHEMA.encode(recent)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    pub fn record(&self, context: RecentContext) -> Result<()> {
+        let _lock = file::lock(&self.path)?;
+        let mut recent = self.load()?;
+        recent.record(context);
+        self.save(&recent)
+    }
+}
+
+/// Recent contexts payload.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RecentContexts {
+    pub items: Vec<RecentContext>,
+}
+
+impl RecentContexts {
+    /// Move the context to the front with its latest position.
+    pub fn record(&mut self, context: RecentContext) {
+        self.items.retain(|item| item.uri != context.uri);
+        self.items.insert(0, context);
+        self.items.truncate(MAX_ENTRIES);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_ENTRIES, RecentContextStore, RecentContexts};
+    use crate::domain::player::RecentContext;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    fn context(uri: &str, position_ms: u32) -> RecentContext {
+        RecentContext {
+            uri: uri.to_string(),
+            kind: "playlist".to_string(),
+            name: None,
+            track_uri: Some("spotify:track:t1".to_string()),
+            track_name: None,
+            position_ms,
+            seen_at: 1,
+        }
+    }
+
+    #[test]
+    fn record_moves_the_context_to_the_front_and_caps_entries() {
+        let mut recent = RecentContexts::default();
+        recent.record(context("spotify:playlist:a", 1_000));
+        recent.record(context("spotify:album:b", 0));
+        recent.record(context("spotify:playlist:a", 9_000));
+        assert_eq!(recent.items.len(), 2);
+        assert_eq!(recent.items[0].uri, "spotify:playlist:a");
+        assert_eq!(recent.items[0].position_ms, 9_000);
+
+        for index in 0..MAX_ENTRIES {
+            recent.record(context(&format!("spotify:album:{index}"), 0));
+        }
+        assert_eq!(recent.items.len(), MAX_ENTRIES);
+        assert!(
+            recent
+                .items
+                .iter()
+                .all(|item| item.uri != "spotify:playlist:a")
+        );
+    }
+
+    #[test]
+    fn recent_context_store_round_trip() {
+        let path = temp_path("recent-contexts");
+        let store = RecentContextStore::new(path.clone());
+        store.record(context("spotify:playlist:a", 5_000)).unwrap();
+        let recent = store.load().unwrap();
+        assert_eq!(recent.items[0].position_ms, 5_000);
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("json.lock"));
+    }
+}
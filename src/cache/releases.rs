@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// JSON-backed store of releases already reported by `releases check`.
+#[derive(Debug, Clone)]
+pub struct ReleaseStore {
+    path: PathBuf,
+}
+
+impl ReleaseStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<SeenReleases> {
+        if !self.path.exists() {
+            return Ok(SeenReleases::default());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let seen = SCHEMA.decode(&self.path, &contents)?;
+        Ok(seen)
+    }
+
+    pub fn save(&self, seen: &SeenReleases) -> Result<()> {
+        let payload = SCHEMA.encode(seen)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    /// Hold the seen releases lock across a load and save.
+    pub fn lock(&self) -> Result<file::FileLock> {
+        file::lock(&self.path)
+    }
+}
+
+/// Album ids seen on previous checks plus the last check time.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SeenReleases {
+    pub checked_at: Option<u64>,
+    pub ids: Vec<String>,
+}
+
+impl SeenReleases {
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.iter().any(|seen| seen == id)
+    }
+
+    pub fn insert(&mut self, id: &str) {
+        if !self.contains(id) {
+            self.ids.push(id.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReleaseStore, SeenReleases};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    #[test]
+    fn release_store_round_trip() {
+        let path = temp_path("releases");
+        let store = ReleaseStore::new(path.clone());
+        let mut seen = SeenReleases::default();
+        seen.insert("a");
+        seen.insert("a");
+        seen.checked_at = Some(5);
+        store.save(&seen).expect("save");
+        let loaded = store.load().expect("load");
+        assert_eq!(loaded.ids, vec!["a".to_string()]);
+        assert_eq!(loaded.checked_at, Some(5));
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,182 @@
+//! Schema versions and migrations for the JSON stores.
+//!
+//! Every store is saved with a top-level `version`. Files from before versioning
+//! count as version 1. On load, older files are upgraded one step at a time and
+//! the original is kept as `<file>.v<version>.bak`; files from a newer build are
+//! refused rather than misread.
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::cache::file;
+use crate::error::{ErrorKind, Result};
+
+const VERSION_KEY: &str = "version";
+/// Version of files written before stores were versioned.
+const UNVERSIONED: u64 = 1;
+
+/// Upgrades a payload from one version to the next, in place.
+pub type Migration = fn(&mut Value) -> Result<()>;
+
+/// Migrations for a store; `migrations[0]` upgrades version 1 to 2, and so on.
+#[derive(Debug, Clone, Copy)]
+pub struct Schema {
+    pub migrations: &'static [Migration],
+}
+
+impl Schema {
+    pub const fn new(migrations: &'static [Migration]) -> Self {
+        Self { migrations }
+    }
+
+    pub fn version(&self) -> u64 {
+        UNVERSIONED + self.migrations.len() as u64
+    }
+
+    /// Serialize `payload` with the current version stamped in.
+    pub fn encode<T: Serialize>(&self, payload: &T) -> Result<String> {
+        let mut value = serde_json::to_value(payload)?;
+        if let Value::Object(map) = &mut value {
+            map.insert(VERSION_KEY.to_string(), Value::from(self.version()));
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// Parse a store read from `path`, migrating and backing it up when it is older.
+    pub fn decode<T: DeserializeOwned>(&self, path: &Path, contents: &str) -> Result<T> {
+        let mut value: Value = serde_json::from_str(contents)?;
+        let version = value
+            .get(VERSION_KEY)
+            .and_then(Value::as_u64)
+            .unwrap_or(UNVERSIONED);
+        let current = self.version();
+        if version > current {
+            bail!(ErrorKind::Unsupported.error(format!("{} has schema version {version}, but this build reads up to {current}; upgrade spotify-cli",
+                path.display())));
+        }
+        if version < current {
+            // Private, since metadata.json holds tokens.
+            let backup = backup_path(path, version);
+            file::write_private(&backup, contents)
+                .with_context(|| format!("unable to back up {}", path.display()))?;
+            for migration in &self.migrations[(version - UNVERSIONED) as usize..] {
+                migration(&mut value)?;
+            }
+            if let Value::Object(map) = &mut value {
+                map.insert(VERSION_KEY.to_string(), Value::from(current));
+            }
+            // A locked store is mid-update (possibly by our own caller) and is saved at the
+            // current version by its holder; otherwise rewrite it unless it changed meanwhile.
+            if let Some(_lock) = file::try_lock(path)?
+                && std::fs::read_to_string(path).is_ok_and(|now| now == contents)
+            {
+                file::write_private(path, serde_json::to_string_pretty(&value)?)?;
+            }
+        }
+        if let Value::Object(map) = &mut value {
+            map.remove(VERSION_KEY);
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// `pins.json` -> `pins.json.v1.bak`.
+fn backup_path(path: &Path, version: u64) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".v{version}.bak"));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Schema, backup_path};
+    use crate::cache::file;
+    use crate::error::Result;
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Pins {
+        items: Vec<String>,
+    }
+
+    fn rename_names(value: &mut Value) -> Result<()> {
+        if let Some(names) = value.as_object_mut().and_then(|map| map.remove("names")) {
+            value["items"] = names;
+        }
+        Ok(())
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    #[test]
+    fn encode_stamps_version() {
+        let schema = Schema::new(&[]);
+        let json = schema.encode(&Pins { items: Vec::new() }).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["version"], 1);
+    }
+
+    #[test]
+    fn decode_migrates_and_backs_up_unversioned_files() {
+        const SCHEMA: Schema = Schema::new(&[rename_names]);
+        let path = temp_path("schema");
+        let old = r#"{"names":["Focus"]}"#;
+        std::fs::write(&path, old).unwrap();
+
+        let pins: Pins = SCHEMA.decode(&path, old).unwrap();
+        assert_eq!(pins.items, vec!["Focus".to_string()]);
+        let backup = backup_path(&path, 1);
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), old);
+        let upgraded: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(upgraded["version"], 2);
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&backup);
+    }
+
+    #[test]
+    fn decode_leaves_a_locked_file_to_its_holder() {
+        const SCHEMA: Schema = Schema::new(&[rename_names]);
+        let path = temp_path("schema-locked");
+        let old = r#"{"names":["Focus"]}"#;
+        std::fs::write(&path, old).unwrap();
+
+        let lock = file::lock(&path).unwrap();
+        let pins: Pins = SCHEMA.decode(&path, old).unwrap();
+        assert_eq!(pins.items, vec!["Focus".to_string()]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), old);
+        drop(lock);
+
+        let backup = backup_path(&path, 1);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&backup);
+        let _ = std::fs::remove_file(path.with_extension("json.lock"));
+    }
+
+    #[test]
+    fn decode_rejects_newer_versions() {
+        let schema = Schema::new(&[]);
+        let path = temp_path("schema-new");
+        let err = schema
+            .decode::<Pins>(&path, r#"{"version":3,"items":[]}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("upgrade spotify-cli"));
+        let current: Pins = schema.decode(&path, r#"{"version":1,"items":[]}"#).unwrap();
+        assert!(current.items.is_empty());
+        assert!(!path.exists());
+    }
+}
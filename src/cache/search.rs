@@ -3,9 +3,13 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::cache::file;
+use crate::cache::schema::Schema;
 use crate::domain::search::SearchResults;
 use crate::error::Result;
 
+const SCHEMA: Schema = Schema::new(&[]);
+
 /// JSON-backed cache for the last search results.
 #[derive(Debug, Clone)]
 pub struct SearchStore {
@@ -22,13 +26,13 @@ impl SearchStore {
             return Ok(None);
         }
         let contents = fs::read_to_string(&self.path)?;
-        let cached = serde_json::from_str(&contents)?;
+        let cached = SCHEMA.decode(&self.path, &contents)?;
         Ok(Some(cached))
     }
 
     pub fn save(&self, cached: &CachedSearch) -> Result<()> {
-        let payload = serde_json::to_string_pretty(cached)?;
-        fs::write(&self.path, payload)?;
+        let payload = SCHEMA.encode(cached)?;
+        file::write_atomic(&self.path, payload)?;
         Ok(())
     }
 }
@@ -0,0 +1,76 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cache::file;
+use crate::cache::schema::Schema;
+use crate::domain::session::Session;
+use crate::error::Result;
+
+const SCHEMA: Schema = Schema::new(&[]);
+
+/// JSON-backed store for the current or most recent listening session.
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    path: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Result<Option<Session>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        let session = SCHEMA.decode(&self.path, &contents)?;
+        Ok(Some(session))
+    }
+
+    pub fn save(&self, session: &Session) -> Result<()> {
+        let payload = SCHEMA.encode(session)?;
+        file::write_atomic(&self.path, payload)?;
+        Ok(())
+    }
+
+    /// Hold the session lock across a load and save.
+    pub fn lock(&self) -> Result<file::FileLock> {
+        file::lock(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SessionStore;
+    use crate::domain::session::Session;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!("spotify-cli-{name}-{stamp}.json"));
+        path
+    }
+
+    #[test]
+    fn session_store_round_trip() {
+        let path = temp_path("session");
+        let store = SessionStore::new(path.clone());
+        assert!(store.load().unwrap().is_none());
+        store
+            .save(&Session {
+                started_at: 100,
+                stopped_at: Some(200),
+            })
+            .unwrap();
+        let session = store.load().unwrap().unwrap();
+        assert_eq!(session.started_at, 100);
+        assert_eq!(session.stopped_at, Some(200));
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,172 @@
+//! Saved album command handlers.
+use anyhow::bail;
+use clap::{Subcommand, ValueEnum};
+
+use crate::AppContext;
+use crate::action::target;
+use crate::cli::play::{ResourceType, parse_id, start_context};
+use crate::domain::album::SavedAlbum;
+use crate::domain::search::SearchType;
+use crate::error::{ErrorKind, Result};
+
+#[derive(Subcommand, Debug)]
+pub enum AlbumCommand {
+    List {
+        #[arg(long, value_enum, default_value = "added")]
+        sort: AlbumSort,
+        #[arg(long, value_name = "NAME", help = "Only albums by a matching artist")]
+        artist: Option<String>,
+        #[arg(
+            long,
+            value_name = "YEAR[-YEAR]",
+            help = "Only albums released in a year or inclusive year range"
+        )]
+        year: Option<String>,
+    },
+    Play {
+        #[arg(value_name = "ALBUM", help = "Album URL, URI, id, pin alias, or `now`")]
+        album: String,
+        #[arg(long, help = "Turn shuffle on first")]
+        shuffle: bool,
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Start at the Nth track (1-based)"
+        )]
+        from_track: Option<u32>,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlbumSort {
+    Added,
+    Name,
+    Artist,
+    ReleaseDate,
+}
+
+pub fn handle(command: AlbumCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        AlbumCommand::List { sort, artist, year } => list(ctx, sort, artist, year),
+        AlbumCommand::Play {
+            album,
+            shuffle,
+            from_track,
+        } => play(ctx, &album, shuffle, from_track),
+    }
+}
+
+fn play(ctx: &AppContext, album: &str, shuffle: bool, from_track: Option<u32>) -> Result<()> {
+    let input = target::resolve_pin(ctx, album)?;
+    let input = target::resolve_target(ctx, &input, SearchType::Album)?;
+    let id = parse_id(&input, ResourceType::Album)?;
+    start_context(ctx, &format!("spotify:album:{id}"), shuffle, from_track)
+}
+
+fn list(
+    ctx: &AppContext,
+    sort: AlbumSort,
+    artist: Option<String>,
+    year: Option<String>,
+) -> Result<()> {
+    let years = year.as_deref().map(parse_year_range).transpose()?;
+    let mut albums = ctx.spotify()?.albums().saved()?;
+    if let Some(artist) = artist {
+        let needle = artist.to_lowercase();
+        albums.retain(|album| {
+            album
+                .artists
+                .iter()
+                .any(|name| name.to_lowercase().contains(&needle))
+        });
+    }
+    if let Some((from, to)) = years {
+        albums.retain(|album| release_year(album).is_some_and(|year| year >= from && year <= to));
+    }
+    sort_albums(&mut albums, sort);
+    ctx.output.saved_album_list(albums)
+}
+
+/// Parse `2001` or `1990-1999` into an inclusive year range.
+fn parse_year_range(input: &str) -> Result<(u32, u32)> {
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| ErrorKind::Validation.error(format!("invalid year: {value}")))
+    };
+    let (from, to) = match input.split_once('-') {
+        Some((from, to)) => (parse(from)?, parse(to)?),
+        None => {
+            let year = parse(input)?;
+            (year, year)
+        }
+    };
+    if from > to {
+        bail!(ErrorKind::Validation.error(format!("year range is reversed: {input}")));
+    }
+    Ok((from, to))
+}
+
+fn release_year(album: &SavedAlbum) -> Option<u32> {
+    album.release_date.as_deref()?.get(..4)?.parse().ok()
+}
+
+fn sort_albums(albums: &mut [SavedAlbum], sort: AlbumSort) {
+    match sort {
+        AlbumSort::Added => albums.sort_by(|a, b| b.added_at.cmp(&a.added_at)),
+        AlbumSort::Name => albums.sort_by_key(|a| a.name.to_lowercase()),
+        AlbumSort::Artist => albums.sort_by(|a, b| {
+            let a_artist = a.artists.first().map(|name| name.to_lowercase());
+            let b_artist = b.artists.first().map(|name| name.to_lowercase());
+            a_artist
+                .cmp(&b_artist)
+                .then_with(|| a.release_date.cmp(&b.release_date))
+        }),
+        AlbumSort::ReleaseDate => albums.sort_by(|a, b| b.release_date.cmp(&a.release_date)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlbumSort, parse_year_range, release_year, sort_albums};
+    use crate::domain::album::SavedAlbum;
+
+    fn album(name: &str, artist: &str, release_date: &str, added_at: &str) -> SavedAlbum {
+        SavedAlbum {
+            id: name.to_string(),
+            name: name.to_string(),
+            uri: format!("spotify:album:{name}"),
+            artists: vec![artist.to_string()],
+            release_date: Some(release_date.to_string()),
+            added_at: Some(added_at.to_string()),
+        }
+    }
+
+    #[test]
+    fn parse_year_range_accepts_single_and_range() {
+        assert_eq!(parse_year_range("2001").unwrap(), (2001, 2001));
+        assert_eq!(parse_year_range("1990-1999").unwrap(), (1990, 1999));
+        assert!(parse_year_range("1999-1990").is_err());
+        assert!(parse_year_range("nineties").is_err());
+    }
+
+    #[test]
+    fn release_year_handles_year_precision() {
+        assert_eq!(release_year(&album("a", "x", "1998", "")), Some(1998));
+        assert_eq!(release_year(&album("a", "x", "2002-02-18", "")), Some(2002));
+    }
+
+    #[test]
+    fn sort_albums_by_added_is_newest_first() {
+        let mut albums = vec![
+            album("Old", "A", "2000", "2020-01-01T00:00:00Z"),
+            album("New", "B", "1990", "2024-01-01T00:00:00Z"),
+        ];
+        sort_albums(&mut albums, AlbumSort::Added);
+        assert_eq!(albums[0].name, "New");
+        sort_albums(&mut albums, AlbumSort::ReleaseDate);
+        assert_eq!(albums[0].name, "Old");
+    }
+}
@@ -0,0 +1,262 @@
+//! User-defined command aliases, expanded before argument parsing.
+use anyhow::bail;
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::cache::Cache;
+use crate::domain::settings::{CommandAlias, Settings};
+use crate::error::{ErrorKind, Result};
+
+#[derive(Subcommand, Debug)]
+pub enum AliasCommand {
+    List,
+    Add {
+        name: String,
+        #[arg(
+            required = true,
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            help = "Command the alias expands to, e.g. player play"
+        )]
+        expansion: Vec<String>,
+    },
+    Remove {
+        name: String,
+    },
+}
+
+pub fn handle(command: AliasCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        AliasCommand::List => list(ctx),
+        AliasCommand::Add { name, expansion } => add(ctx, name, expansion.join(" ")),
+        AliasCommand::Remove { name } => remove(ctx, &name),
+    }
+}
+
+fn list(ctx: &AppContext) -> Result<()> {
+    let metadata = ctx.cache.metadata_store().load()?;
+    let settings = Settings {
+        aliases: metadata.settings.aliases,
+        ..Settings::default()
+    };
+    ctx.output.settings(settings)
+}
+
+fn add(ctx: &AppContext, name: String, expansion: String) -> Result<()> {
+    if name.is_empty() || name.starts_with('-') || name.chars().any(char::is_whitespace) {
+        bail!(ErrorKind::Validation.error(format!("invalid alias name: {name}")));
+    }
+    if super::command_names().contains(&name) {
+        bail!(ErrorKind::Validation.error(format!(
+            "{name} is a built-in command and cannot be aliased"
+        )));
+    }
+    split_words(&expansion)?;
+
+    let store = ctx.cache.metadata_store();
+    let _lock = store.lock()?;
+    let mut metadata = store.load()?;
+    let aliases = &mut metadata.settings.aliases;
+    match aliases.iter_mut().find(|alias| alias.name == name) {
+        Some(alias) => alias.expansion = expansion.clone(),
+        None => aliases.push(CommandAlias {
+            name: name.clone(),
+            expansion: expansion.clone(),
+        }),
+    }
+    store.save(&metadata)?;
+    let message = format!("Alias: {} = {}", name, expansion);
+    ctx.output.action("alias_add", &message)
+}
+
+fn remove(ctx: &AppContext, name: &str) -> Result<()> {
+    let store = ctx.cache.metadata_store();
+    let _lock = store.lock()?;
+    let mut metadata = store.load()?;
+    let before = metadata.settings.aliases.len();
+    metadata.settings.aliases.retain(|alias| alias.name != name);
+    if metadata.settings.aliases.len() == before {
+        bail!(ErrorKind::NotFound.error(format!("no alias named {name}")));
+    }
+    store.save(&metadata)?;
+    let message = format!("Removed alias: {}", name);
+    ctx.output.action("alias_remove", &message)
+}
+
+/// Process arguments with stored aliases expanded; unreadable settings leave them unchanged.
+pub fn expand_args(args: Vec<String>) -> Vec<String> {
+    let aliases = Cache::new()
+        .and_then(|cache| cache.metadata_store().load())
+        .map(|metadata| metadata.settings.aliases)
+        .unwrap_or_default();
+    match expand(&args, &aliases) {
+        Ok(Some(expanded)) => expanded,
+        Ok(None) => args,
+        Err(err) => {
+            eprintln!("ignoring alias: {err}");
+            args
+        }
+    }
+}
+
+/// Replace the command word with its alias expansion, once (aliases do not nest).
+fn expand(args: &[String], aliases: &[CommandAlias]) -> Result<Option<Vec<String>>> {
+    // Global flags that take a value, so alias lookup can skip past them.
+    let value_flags = super::global_value_flags();
+    let mut index = 1;
+    while index < args.len() && args[index].starts_with('-') {
+        let takes_value = value_flags.contains(&args[index]);
+        index += if takes_value { 2 } else { 1 };
+    }
+    let Some(word) = args.get(index) else {
+        return Ok(None);
+    };
+    let Some(alias) = aliases.iter().find(|alias| &alias.name == word) else {
+        return Ok(None);
+    };
+
+    let mut expanded = args[..index].to_vec();
+    expanded.extend(split_words(&alias.expansion)?);
+    expanded.extend_from_slice(&args[index + 1..]);
+    Ok(Some(expanded))
+}
+
+/// Split on whitespace, honoring single and double quotes.
+fn split_words(input: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    for c in input.chars() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        bail!(ErrorKind::Validation.error(format!("unterminated quote in alias: {input}")));
+    }
+    if in_word {
+        words.push(current);
+    }
+    if words.is_empty() {
+        bail!(ErrorKind::Validation.error("alias expansion is empty"));
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, split_words};
+    use crate::domain::settings::CommandAlias;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn split_words_honors_quotes() {
+        assert_eq!(
+            split_words(r#"search track "daft punk" --play"#).unwrap(),
+            args(&["search", "track", "daft punk", "--play"])
+        );
+        assert!(split_words("search 'open").is_err());
+        assert!(split_words("   ").is_err());
+    }
+
+    #[test]
+    fn expand_replaces_command_word_after_global_flags() {
+        let aliases = vec![CommandAlias {
+            name: "morning".to_string(),
+            expansion: "search playlist 'morning mix' --play".to_string(),
+        }];
+        let expanded = expand(
+            &args(&[
+                "spotify-cli",
+                "--market",
+                "DE",
+                "--json",
+                "morning",
+                "--pick",
+                "2",
+            ]),
+            &aliases,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            expanded,
+            args(&[
+                "spotify-cli",
+                "--market",
+                "DE",
+                "--json",
+                "search",
+                "playlist",
+                "morning mix",
+                "--play",
+                "--pick",
+                "2",
+            ])
+        );
+        assert!(
+            expand(&args(&["spotify-cli", "queue"]), &aliases)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn expand_skips_the_timeout_value() {
+        let aliases = vec![CommandAlias {
+            name: "np".to_string(),
+            expansion: "player status".to_string(),
+        }];
+        let expanded = expand(&args(&["spotify-cli", "--timeout", "5", "np"]), &aliases)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            expanded,
+            args(&["spotify-cli", "--timeout", "5", "player", "status"])
+        );
+    }
+
+    #[test]
+    fn expand_skips_the_har_file() {
+        let aliases = vec![CommandAlias {
+            name: "np".to_string(),
+            expansion: "player status".to_string(),
+        }];
+        let expanded = expand(
+            &args(&["spotify-cli", "--har", "out.har", "--json", "np"]),
+            &aliases,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            expanded,
+            args(&[
+                "spotify-cli",
+                "--har",
+                "out.har",
+                "--json",
+                "player",
+                "status"
+            ])
+        );
+    }
+}
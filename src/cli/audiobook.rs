@@ -0,0 +1,52 @@
+//! Audiobook command handlers.
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::target;
+use crate::cli::library::save_item;
+use crate::cli::play::{ResourceType, parse_id};
+use crate::cli::show::SavedListArgs;
+use crate::domain::oplog::SavedKind;
+use crate::error::Result;
+
+#[derive(Subcommand, Debug)]
+pub enum AudiobookCommand {
+    List(SavedListArgs),
+    Save {
+        #[arg(
+            value_name = "AUDIOBOOK",
+            required_unless_present = "now_playing",
+            help = "Audiobook URL, URI, id, or `now`"
+        )]
+        audiobook: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "audiobook",
+            help = "Save the audiobook being played"
+        )]
+        now_playing: bool,
+    },
+}
+
+pub fn handle(command: AudiobookCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        AudiobookCommand::List(args) => list(ctx, &args),
+        AudiobookCommand::Save {
+            audiobook,
+            now_playing,
+        } => {
+            let id = match audiobook {
+                Some(audiobook) if !now_playing && !target::is_now(&audiobook) => {
+                    parse_id(&audiobook, ResourceType::Audiobook)?
+                }
+                _ => target::now_audiobook_id(ctx)?,
+            };
+            save_item(ctx, SavedKind::Audiobook, id)
+        }
+    }
+}
+
+fn list(ctx: &AppContext, args: &SavedListArgs) -> Result<()> {
+    let audiobooks = ctx.spotify()?.audiobooks().saved(args.limit())?;
+    ctx.output.saved_audiobook_list(audiobooks, args.csv())
+}
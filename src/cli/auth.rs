@@ -3,7 +3,8 @@ use anyhow::bail;
 use clap::Subcommand;
 
 use crate::AppContext;
-use crate::error::Result;
+use crate::error::{ErrorKind, Result};
+use crate::spotify::auth::{LoginOptions, redirect_uri_for_port};
 
 #[derive(Subcommand, Debug)]
 pub enum AuthCommand {
@@ -12,6 +13,25 @@ pub enum AuthCommand {
         client_id: Option<String>,
         #[arg(long, help = "Redirect URI for OAuth")]
         redirect_uri: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "redirect_uri",
+            help = "Local callback port (default 8888)"
+        )]
+        port: Option<u16>,
+        #[arg(
+            long,
+            help = "Paste the redirect URL instead of running a callback listener"
+        )]
+        manual: bool,
+        #[arg(long, help = "Print the authorization URL without opening a browser")]
+        no_browser: bool,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            help = "Request only these scopes (comma-separated)"
+        )]
+        scopes: Option<Vec<String>>,
     },
     Check,
     Status,
@@ -24,7 +44,19 @@ pub fn handle(command: AuthCommand, ctx: &AppContext) -> Result<()> {
         AuthCommand::Login {
             client_id,
             redirect_uri,
-        } => login(client_id, redirect_uri, ctx),
+            port,
+            manual,
+            no_browser,
+            scopes,
+        } => {
+            let redirect_uri = redirect_uri.or_else(|| port.map(redirect_uri_for_port));
+            let options = LoginOptions {
+                manual,
+                no_browser,
+                scopes,
+            };
+            login(client_id, redirect_uri, options, ctx)
+        }
         AuthCommand::Check => status(ctx),
         AuthCommand::Status => status(ctx),
         AuthCommand::Scopes => scopes(ctx),
@@ -32,21 +64,33 @@ pub fn handle(command: AuthCommand, ctx: &AppContext) -> Result<()> {
     }
 }
 
-fn login(client_id: Option<String>, redirect_uri: Option<String>, ctx: &AppContext) -> Result<()> {
+fn login(
+    client_id: Option<String>,
+    redirect_uri: Option<String>,
+    options: LoginOptions,
+    ctx: &AppContext,
+) -> Result<()> {
     let client_id = match client_id.or_else(|| std::env::var("SPOTIFY_CLIENT_ID").ok()) {
         Some(value) => value,
-        None => bail!("missing client id; pass --client-id or set SPOTIFY_CLIENT_ID"),
+        None => bail!(
+            ErrorKind::Auth.error("missing client id; pass --client-id or set SPOTIFY_CLIENT_ID")
+        ),
     };
 
-    if let Some(redirect_uri) = redirect_uri {
-        ctx.auth.login_oauth_with_redirect(client_id, &redirect_uri)
-    } else {
-        ctx.auth.login_oauth(client_id)
-    }
+    let redirect_uri = redirect_uri.unwrap_or_else(|| redirect_uri_for_port(8888));
+    ctx.auth
+        .login_oauth_with_options(client_id, &redirect_uri, &options)
 }
 
 fn status(ctx: &AppContext) -> Result<()> {
-    let status = ctx.auth.status()?;
+    let mut status = ctx.auth.status()?;
+    if status.logged_in {
+        // The profile is a decoration; an unreachable API still reports the local token.
+        match ctx.auth.profile() {
+            Ok(profile) => status.profile = Some(profile),
+            Err(err) => eprintln!("warning: could not fetch profile: {err}"),
+        }
+    }
     ctx.output.auth_status(status)
 }
 
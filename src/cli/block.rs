@@ -0,0 +1,203 @@
+//! Blocklist command handlers.
+use std::thread;
+use std::time::Duration;
+
+use anyhow::bail;
+use clap::{Subcommand, ValueEnum};
+
+use crate::AppContext;
+use crate::action::target;
+use crate::cli::play::{self, ResourceType};
+use crate::domain::block::{BlockEntry, BlockKind};
+use crate::domain::search::SearchType;
+use crate::error::{ErrorKind, Result};
+
+/// Wait for Spotify to report the next track after a skip.
+const SKIP_SETTLE: Duration = Duration::from_millis(500);
+
+#[derive(Subcommand, Debug)]
+pub enum BlockCommand {
+    Add {
+        kind: BlockKindArg,
+        #[arg(help = "Id, URI, URL, or `now` for the playing track/artist")]
+        target: String,
+    },
+    Remove {
+        kind: BlockKindArg,
+        #[arg(help = "Id, URI, URL, or `now` for the playing track/artist")]
+        target: String,
+    },
+    List,
+    /// Skip the playing track while it, or one of its artists, is blocked.
+    Check {
+        #[arg(
+            long,
+            default_value_t = 10,
+            help = "Stop after this many skips in a row"
+        )]
+        max_skips: u32,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub(crate) enum BlockKindArg {
+    Artist,
+    Track,
+}
+
+impl BlockKindArg {
+    fn kind(self) -> BlockKind {
+        match self {
+            BlockKindArg::Artist => BlockKind::Artist,
+            BlockKindArg::Track => BlockKind::Track,
+        }
+    }
+
+    fn search_type(self) -> SearchType {
+        match self {
+            BlockKindArg::Artist => SearchType::Artist,
+            BlockKindArg::Track => SearchType::Track,
+        }
+    }
+
+    fn resource_type(self) -> ResourceType {
+        match self {
+            BlockKindArg::Artist => ResourceType::Artist,
+            BlockKindArg::Track => ResourceType::Track,
+        }
+    }
+}
+
+pub fn handle(command: BlockCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        BlockCommand::Add { kind, target } => add(ctx, kind, &target),
+        BlockCommand::Remove { kind, target } => remove(ctx, kind, &target),
+        BlockCommand::List => list(ctx),
+        BlockCommand::Check { max_skips } => check(ctx, max_skips),
+    }
+}
+
+fn add(ctx: &AppContext, kind: BlockKindArg, input: &str) -> Result<()> {
+    let id = resolve_id(ctx, kind, input)?;
+    let name = lookup_name(ctx, kind, &id);
+    let label = name.clone().unwrap_or_else(|| id.clone());
+    let added = ctx.cache.block_store().add(BlockEntry {
+        kind: kind.kind(),
+        id,
+        name,
+    })?;
+    let message = if added {
+        format!("Blocked {}: {}", kind.kind().as_str(), label)
+    } else {
+        format!("Already blocked: {}", label)
+    };
+    ctx.output.action("block_add", &message)
+}
+
+fn remove(ctx: &AppContext, kind: BlockKindArg, input: &str) -> Result<()> {
+    let id = resolve_id(ctx, kind, input)?;
+    let removed = ctx.cache.block_store().remove(kind.kind(), &id)?;
+    let message = if removed {
+        format!("Unblocked {}: {}", kind.kind().as_str(), id)
+    } else {
+        format!("Not blocked: {}", id)
+    };
+    ctx.output.action("block_remove", &message)
+}
+
+fn list(ctx: &AppContext) -> Result<()> {
+    let blocks = ctx.cache.block_store().load()?;
+    ctx.output.block_list(blocks.items)
+}
+
+fn check(ctx: &AppContext, max_skips: u32) -> Result<()> {
+    let blocks = ctx.cache.block_store().load()?;
+    if blocks.items.is_empty() {
+        return ctx.output.action("block_check", "Blocklist is empty");
+    }
+    let playback = ctx.spotify()?.playback();
+    let mut skipped = 0;
+    loop {
+        let status = playback.status()?;
+        let Some(track) = status.track else {
+            break;
+        };
+        let Some(entry) = blocks.blocking(&track) else {
+            break;
+        };
+        if skipped == max_skips {
+            bail!(ErrorKind::General.error(format!(
+                "still on a blocked track after {max_skips} skips: {}",
+                track.name
+            )));
+        }
+        let reason = entry.name.as_deref().unwrap_or(&entry.id);
+        let message = format!(
+            "Skipped {} (blocked {}: {})",
+            track.name,
+            entry.kind.as_str(),
+            reason
+        );
+        playback.next()?;
+        ctx.output.action("block_skip", &message)?;
+        skipped += 1;
+        thread::sleep(SKIP_SETTLE);
+    }
+    // No track-change hook here: `block check` is meant to be run from one.
+    if skipped == 0 {
+        return ctx
+            .output
+            .action("block_check", "Playing track is not blocked");
+    }
+    Ok(())
+}
+
+/// Resolve `now`, a URI/URL, or a bare id to the id of `kind`.
+fn resolve_id(ctx: &AppContext, kind: BlockKindArg, input: &str) -> Result<String> {
+    let input = target::resolve_target(ctx, input, kind.search_type())?;
+    parse_id(kind, &input)
+}
+
+fn parse_id(kind: BlockKindArg, input: &str) -> Result<String> {
+    play::parse_id(input, kind.resource_type())
+}
+
+/// Display name for the entry; the id alone is kept when the lookup fails.
+fn lookup_name(ctx: &AppContext, kind: BlockKindArg, id: &str) -> Option<String> {
+    let found = ctx.spotify().and_then(|spotify| {
+        spotify
+            .search()
+            .lookup(kind.search_type(), &[id.to_string()])
+    });
+    match found {
+        Ok(items) => items.into_iter().flatten().next().map(|item| item.name),
+        Err(err) => {
+            eprintln!("warning: could not look up {id}: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockKindArg, parse_id};
+
+    #[test]
+    fn parse_id_accepts_ids_uris_and_urls_of_the_kind() {
+        assert_eq!(parse_id(BlockKindArg::Artist, "abc").unwrap(), "abc");
+        assert_eq!(
+            parse_id(BlockKindArg::Artist, "spotify:artist:abc").unwrap(),
+            "abc"
+        );
+        assert_eq!(
+            parse_id(
+                BlockKindArg::Track,
+                "https://open.spotify.com/track/xyz?si=1"
+            )
+            .unwrap(),
+            "xyz"
+        );
+        assert!(parse_id(BlockKindArg::Artist, "spotify:track:xyz").is_err());
+        assert!(parse_id(BlockKindArg::Track, "").is_err());
+    }
+}
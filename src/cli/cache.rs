@@ -1,9 +1,9 @@
 //! Cache command handlers.
 use clap::Subcommand;
 
+use crate::AppContext;
 use crate::domain::cache::CacheStatus;
 use crate::error::Result;
-use crate::AppContext;
 
 #[derive(Subcommand, Debug)]
 pub enum CacheCommand {
@@ -43,6 +43,8 @@ fn country(ctx: &AppContext, code: Option<String>) -> Result<()> {
     let settings = crate::domain::settings::Settings {
         country,
         user_name: ctx.auth.user_name()?,
+        max_volume: ctx.auth.max_volume()?,
+        ..crate::domain::settings::Settings::default()
     };
     ctx.output.settings(settings)
 }
@@ -55,6 +57,8 @@ fn user(ctx: &AppContext, name: Option<String>) -> Result<()> {
     let settings = crate::domain::settings::Settings {
         country: ctx.auth.country()?,
         user_name,
+        max_volume: ctx.auth.max_volume()?,
+        ..crate::domain::settings::Settings::default()
     };
     ctx.output.settings(settings)
 }
@@ -1,8 +1,8 @@
 //! Hidden completion data endpoints for shell scripts.
 use clap::Subcommand;
 
-use crate::error::Result;
 use crate::AppContext;
+use crate::error::Result;
 
 #[derive(Subcommand, Debug)]
 pub enum CompleteCommand {
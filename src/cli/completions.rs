@@ -7,7 +7,7 @@ use crate::error::Result;
 
 #[derive(Args, Debug)]
 pub struct CompletionsCommand {
-    #[arg(value_name = "SHELL", value_parser = ["bash", "zsh", "fish"])]
+    #[arg(value_name = "SHELL", value_parser = ["bash", "zsh", "fish", "powershell", "elvish"])]
     shell: String,
     #[arg(long, help = "Install to the default completion directory")]
     install: bool,
@@ -22,6 +22,8 @@ pub fn handle(command: CompletionsCommand) -> Result<()> {
         "bash" => print_script(include_str!("../../completions/spotify.bash")),
         "zsh" => print_script(include_str!("../../completions/_spotify-cli")),
         "fish" => print_script(include_str!("../../completions/spotify.fish")),
+        "powershell" => print_script(include_str!("../../completions/spotify.ps1")),
+        "elvish" => print_script(include_str!("../../completions/spotify.elv")),
         _ => Ok(()),
     }
 }
@@ -46,6 +48,15 @@ fn install(shell: &str) -> Result<()> {
             ".config/fish/completions/spotify-cli.fish",
             include_str!("../../completions/spotify.fish"),
         ),
+        // PowerShell has no autoload directory; dot-source this from $PROFILE.
+        "powershell" => (
+            ".config/powershell/spotify-cli.ps1",
+            include_str!("../../completions/spotify.ps1"),
+        ),
+        "elvish" => (
+            ".config/elvish/lib/spotify-cli.elv",
+            include_str!("../../completions/spotify.elv"),
+        ),
         _ => return Ok(()),
     };
 
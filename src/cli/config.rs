@@ -0,0 +1,294 @@
+//! Config command handlers for locally stored settings.
+use anyhow::bail;
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::domain::settings::Settings;
+use crate::error::{ErrorKind, Result};
+use crate::output::i18n::Language;
+
+/// Setting keys accepted by `config get/set/unset`.
+const KEYS: [&str; 14] = [
+    "country",
+    "user_name",
+    "default_market",
+    "max_volume",
+    "timeout_secs",
+    "connect_timeout_secs",
+    "proxy",
+    "ca_bundle",
+    "language",
+    "no_explicit",
+    "open_in",
+    "log_level",
+    "log_max_kb",
+    "auto_like_plays",
+];
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    List,
+    Get { key: String },
+    Set { key: String, value: String },
+    Unset { key: String },
+    Path,
+    Edit,
+}
+
+pub fn handle(command: ConfigCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        ConfigCommand::List => list(ctx),
+        ConfigCommand::Get { key } => get(ctx, &key),
+        ConfigCommand::Set { key, value } => set(ctx, &key, Some(&value)),
+        ConfigCommand::Unset { key } => set(ctx, &key, None),
+        ConfigCommand::Path => path(ctx),
+        ConfigCommand::Edit => edit(ctx),
+    }
+}
+
+fn list(ctx: &AppContext) -> Result<()> {
+    let metadata = ctx.cache.metadata_store().load()?;
+    ctx.output.settings(metadata.settings)
+}
+
+fn get(ctx: &AppContext, key: &str) -> Result<()> {
+    let metadata = ctx.cache.metadata_store().load()?;
+    let value = get_setting(&metadata.settings, key)?.unwrap_or_default();
+    let message = format!("{}={}", key, value);
+    ctx.output.action("config_get", &message)
+}
+
+fn set(ctx: &AppContext, key: &str, value: Option<&str>) -> Result<()> {
+    let store = ctx.cache.metadata_store();
+    let _lock = store.lock()?;
+    let mut metadata = store.load()?;
+    set_setting(&mut metadata.settings, key, value)?;
+    store.save(&metadata)?;
+    let message = match value {
+        Some(_) => format!(
+            "{}={}",
+            key,
+            get_setting(&metadata.settings, key)?.unwrap_or_default()
+        ),
+        None => format!("Unset: {}", key),
+    };
+    ctx.output.action("config_set", &message)
+}
+
+fn path(ctx: &AppContext) -> Result<()> {
+    let store = ctx.cache.metadata_store();
+    let message = store.path().display().to_string();
+    ctx.output.action("config_path", &message)
+}
+
+fn edit(ctx: &AppContext) -> Result<()> {
+    use std::process::Command;
+
+    let store = ctx.cache.metadata_store();
+    if !store.path().exists() {
+        let _lock = store.lock()?;
+        store.save(&store.load()?)?;
+    }
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let status = Command::new(&editor).arg(store.path()).status()?;
+    if !status.success() {
+        bail!(ErrorKind::General.error(format!("{editor} exited with {status}")));
+    }
+    if let Err(err) = store.load() {
+        bail!(ErrorKind::Validation.error(format!("settings file is no longer valid JSON: {err}")));
+    }
+    ctx.output.action("config_edit", "Settings saved")
+}
+
+fn get_setting(settings: &Settings, key: &str) -> Result<Option<String>> {
+    let value = match key {
+        "country" => settings.country.clone(),
+        "user_name" => settings.user_name.clone(),
+        "default_market" => settings.default_market.clone(),
+        "max_volume" => settings.max_volume.map(|volume| volume.to_string()),
+        "timeout_secs" => settings.timeout_secs.map(|secs| secs.to_string()),
+        "connect_timeout_secs" => settings.connect_timeout_secs.map(|secs| secs.to_string()),
+        "proxy" => settings.proxy.clone(),
+        "ca_bundle" => settings.ca_bundle.clone(),
+        "language" => settings.language.clone(),
+        "no_explicit" => settings.no_explicit.map(|flag| flag.to_string()),
+        "open_in" => settings.open_in.clone(),
+        "log_level" => settings.log_level.clone(),
+        "log_max_kb" => settings.log_max_kb.map(|kb| kb.to_string()),
+        "auto_like_plays" => settings.auto_like_plays.map(|plays| plays.to_string()),
+        _ => bail!(ErrorKind::Validation.error(format!(
+            "unknown setting: {key}; expected one of {}",
+            KEYS.join(", ")
+        ))),
+    };
+    Ok(value)
+}
+
+fn set_setting(settings: &mut Settings, key: &str, value: Option<&str>) -> Result<()> {
+    match key {
+        "country" => settings.country = value.map(parse_market).transpose()?,
+        "user_name" => settings.user_name = value.map(str::to_string),
+        "default_market" => settings.default_market = value.map(parse_market).transpose()?,
+        "max_volume" => {
+            settings.max_volume = value
+                .map(|value| match value.parse::<u32>() {
+                    Ok(volume) if volume <= 100 => Ok(volume),
+                    _ => bail!(ErrorKind::Validation.error("max_volume must be between 0 and 100")),
+                })
+                .transpose()?
+        }
+        "timeout_secs" => settings.timeout_secs = value.map(parse_timeout).transpose()?,
+        "connect_timeout_secs" => {
+            settings.connect_timeout_secs = value.map(parse_timeout).transpose()?
+        }
+        "proxy" => settings.proxy = value.map(parse_proxy).transpose()?,
+        "ca_bundle" => settings.ca_bundle = value.map(parse_ca_bundle).transpose()?,
+        "language" => settings.language = value.map(parse_language).transpose()?,
+        "no_explicit" => settings.no_explicit = value.map(parse_flag).transpose()?,
+        "open_in" => settings.open_in = value.map(parse_open_in).transpose()?,
+        "log_level" => settings.log_level = value.map(parse_log_level).transpose()?,
+        "log_max_kb" => settings.log_max_kb = value.map(parse_log_max_kb).transpose()?,
+        "auto_like_plays" => {
+            settings.auto_like_plays = value.map(parse_auto_like_plays).transpose()?
+        }
+        _ => bail!(ErrorKind::Validation.error(format!(
+            "unknown setting: {key}; expected one of {}",
+            KEYS.join(", ")
+        ))),
+    }
+    Ok(())
+}
+
+fn parse_market(value: &str) -> Result<String> {
+    let value = value.trim();
+    if value.len() != 2 || !value.chars().all(|c| c.is_ascii_alphabetic()) {
+        bail!(ErrorKind::Validation.error(format!(
+            "market must be a two-letter country code, got {value}"
+        )));
+    }
+    Ok(value.to_uppercase())
+}
+
+fn parse_language(value: &str) -> Result<String> {
+    let value = value.trim();
+    if Language::from_code(value).is_none() {
+        bail!(ErrorKind::Validation.error(format!(
+            "unsupported language: {value}; expected en, es, or de"
+        )));
+    }
+    Ok(value.to_lowercase())
+}
+
+fn parse_flag(value: &str) -> Result<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "on" | "yes" | "1" => Ok(true),
+        "false" | "off" | "no" | "0" => Ok(false),
+        other => bail!(ErrorKind::Validation.error(format!("expected true or false, got {other}"))),
+    }
+}
+
+fn parse_open_in(value: &str) -> Result<String> {
+    match value.trim().to_lowercase().as_str() {
+        value @ ("app" | "web") => Ok(value.to_string()),
+        other => bail!(ErrorKind::Validation.error(format!("expected app or web, got {other}"))),
+    }
+}
+
+fn parse_log_level(value: &str) -> Result<String> {
+    match value.trim().to_lowercase().as_str() {
+        value @ ("off" | "error" | "info") => Ok(value.to_string()),
+        other => {
+            bail!(ErrorKind::Validation.error(format!("expected off, error, or info, got {other}")))
+        }
+    }
+}
+
+fn parse_log_max_kb(value: &str) -> Result<u64> {
+    match value.trim().parse::<u64>() {
+        Ok(kb) if (16..=102_400).contains(&kb) => Ok(kb),
+        _ => bail!(ErrorKind::Validation.error("log_max_kb must be between 16 and 102400")),
+    }
+}
+
+fn parse_auto_like_plays(value: &str) -> Result<u32> {
+    match value.trim().parse::<u32>() {
+        Ok(plays) if (1..=1000).contains(&plays) => Ok(plays),
+        _ => bail!(ErrorKind::Validation.error("auto_like_plays must be between 1 and 1000")),
+    }
+}
+
+fn parse_timeout(value: &str) -> Result<u64> {
+    match value.trim().parse::<u64>() {
+        Ok(secs) if (1..=300).contains(&secs) => Ok(secs),
+        _ => bail!(ErrorKind::Validation.error("timeout must be between 1 and 300 seconds")),
+    }
+}
+
+fn parse_proxy(value: &str) -> Result<String> {
+    let value = value.trim();
+    match url::Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(value.to_string()),
+        _ => {
+            bail!(ErrorKind::Validation.error(format!("proxy must be an http(s) URL, got {value}")))
+        }
+    }
+}
+
+fn parse_ca_bundle(value: &str) -> Result<String> {
+    let path = std::path::Path::new(value.trim());
+    if !path.is_file() {
+        bail!(ErrorKind::Validation.error(format!("CA bundle not found: {}", path.display())));
+    }
+    Ok(path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_setting, set_setting};
+    use crate::domain::settings::Settings;
+
+    #[test]
+    fn set_setting_validates_values() {
+        let mut settings = Settings::default();
+        set_setting(&mut settings, "default_market", Some("de")).unwrap();
+        set_setting(&mut settings, "max_volume", Some("70")).unwrap();
+        assert_eq!(settings.default_market.as_deref(), Some("DE"));
+        assert_eq!(
+            get_setting(&settings, "max_volume").unwrap().as_deref(),
+            Some("70")
+        );
+
+        assert!(set_setting(&mut settings, "default_market", Some("Germany")).is_err());
+        assert!(set_setting(&mut settings, "max_volume", Some("150")).is_err());
+        assert!(set_setting(&mut settings, "theme", Some("dark")).is_err());
+        assert!(set_setting(&mut settings, "connect_timeout_secs", Some("0")).is_err());
+        set_setting(&mut settings, "timeout_secs", Some("10")).unwrap();
+        assert_eq!(settings.timeout_secs, Some(10));
+        set_setting(&mut settings, "proxy", Some("http://proxy.local:3128")).unwrap();
+        assert!(set_setting(&mut settings, "proxy", Some("proxy.local")).is_err());
+        assert!(set_setting(&mut settings, "ca_bundle", Some("/nonexistent/ca.pem")).is_err());
+        set_setting(&mut settings, "language", Some("ES")).unwrap();
+        assert_eq!(settings.language.as_deref(), Some("es"));
+        assert!(set_setting(&mut settings, "language", Some("klingon")).is_err());
+        set_setting(&mut settings, "no_explicit", Some("on")).unwrap();
+        assert_eq!(settings.no_explicit, Some(true));
+        assert!(set_setting(&mut settings, "no_explicit", Some("maybe")).is_err());
+        set_setting(&mut settings, "open_in", Some("App")).unwrap();
+        assert_eq!(settings.open_in.as_deref(), Some("app"));
+        assert!(set_setting(&mut settings, "open_in", Some("browser")).is_err());
+        set_setting(&mut settings, "log_level", Some("Info")).unwrap();
+        assert_eq!(settings.log_level.as_deref(), Some("info"));
+        assert!(set_setting(&mut settings, "log_level", Some("trace")).is_err());
+        set_setting(&mut settings, "log_max_kb", Some("512")).unwrap();
+        assert_eq!(settings.log_max_kb, Some(512));
+        assert!(set_setting(&mut settings, "log_max_kb", Some("1")).is_err());
+        set_setting(&mut settings, "auto_like_plays", Some("5")).unwrap();
+        assert_eq!(settings.auto_like_plays, Some(5));
+        assert!(set_setting(&mut settings, "auto_like_plays", Some("0")).is_err());
+
+        set_setting(&mut settings, "max_volume", None).unwrap();
+        assert!(settings.max_volume.is_none());
+    }
+}
@@ -3,91 +3,106 @@ use anyhow::bail;
 use clap::Subcommand;
 
 use crate::AppContext;
-use crate::domain::device::Device;
-use crate::error::Result;
+use crate::action::device;
+use crate::error::{ErrorKind, Result};
 
 #[derive(Subcommand, Debug)]
 pub enum DeviceCommand {
     List {
-        #[arg(long, help = "Query Spotify directly instead of cache")]
+        #[arg(long, help = "Query Spotify directly and refresh the cache")]
         live: bool,
     },
     Set {
         name: String,
+        #[arg(long, help = "Skip applying the device's default volume")]
+        keep_volume: bool,
+        #[arg(
+            long,
+            help = "Skip the default volume on devices without volume control"
+        )]
+        ignore_unsupported: bool,
+    },
+    Volume {
+        name: String,
+        #[arg(
+            value_name = "PERCENT",
+            help = "Default volume (0-100) applied after switching. Omit to show it"
+        )]
+        percent: Option<u32>,
+        #[arg(long, conflicts_with = "percent", help = "Remove the default volume")]
+        clear: bool,
     },
 }
 
 pub fn handle(command: DeviceCommand, ctx: &AppContext) -> Result<()> {
     match command {
         DeviceCommand::List { live } => list(ctx, live),
-        DeviceCommand::Set { name } => set(ctx, &name),
+        DeviceCommand::Set {
+            name,
+            keep_volume,
+            ignore_unsupported,
+        } => set(ctx, &name, keep_volume, ignore_unsupported),
+        DeviceCommand::Volume {
+            name,
+            percent,
+            clear,
+        } => volume(ctx, &name, percent, clear),
     }
 }
 
 fn list(ctx: &AppContext, live: bool) -> Result<()> {
-    if live {
-        let devices = ctx.spotify()?.devices().list()?;
-        return ctx.output.device_list(devices);
-    }
-
-    let snapshot = ctx.cache.device_cache().load()?;
-    let Some(snapshot) = snapshot else {
-        bail!("device cache empty; run `spotify sync`");
+    let devices = if live {
+        device::refresh(ctx)?
+    } else {
+        device::cached(ctx)?
     };
-    ctx.output.device_list(snapshot.items)
+    ctx.output.device_list(devices)
 }
 
-fn set(ctx: &AppContext, name: &str) -> Result<()> {
-    let snapshot = ctx.cache.device_cache().load()?;
-    let Some(snapshot) = snapshot else {
-        bail!("device cache empty; run `spotify sync`");
-    };
+fn set(ctx: &AppContext, name: &str, keep_volume: bool, ignore_unsupported: bool) -> Result<()> {
+    let device = device::activate(ctx, name)?;
+    let message = format!("Switched device: {}", device.name);
+    ctx.output.action("device_set", &message)?;
 
-    let matches = find_devices(&snapshot.items, name);
-    if matches.is_empty() {
-        bail!("no device matches '{name}'");
+    if keep_volume {
+        return Ok(());
     }
-    if matches.len() > 1 {
-        let names: Vec<String> = matches.iter().map(|device| device.name.clone()).collect();
-        bail!("multiple devices match: {}", names.join(", "));
+    let Some(level) = ctx.auth.device_volume(&device.name)? else {
+        return Ok(());
+    };
+    let level = match ctx.auth.max_volume()? {
+        Some(cap) => level.min(cap),
+        None => level,
+    };
+    if !device::set_volume(ctx, level, ignore_unsupported)? {
+        return Ok(());
     }
-
-    let device = matches[0];
-    ctx.spotify()?.devices().set_active(&device.id)?;
-    let message = format!("Switched device: {}", device.name);
-    ctx.output.action("device_set", &message)
+    let message = format!("Volume: {}%", level);
+    ctx.output.action("player_volume", &message)
 }
 
-fn find_devices<'a>(devices: &'a [Device], query: &str) -> Vec<&'a Device> {
-    let query = query.to_lowercase();
-    devices
-        .iter()
-        .filter(|device| device.name.to_lowercase().contains(&query))
-        .collect()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::find_devices;
-    use crate::domain::device::Device;
-
-    #[test]
-    fn find_devices_matches_case_insensitive() {
-        let devices = vec![
-            Device {
-                id: "1".to_string(),
-                name: "Office Speaker".to_string(),
-                volume_percent: Some(50),
-            },
-            Device {
-                id: "2".to_string(),
-                name: "Phone".to_string(),
-                volume_percent: None,
-            },
-        ];
-
-        let matches = find_devices(&devices, "office");
-        assert_eq!(matches.len(), 1);
-        assert_eq!(matches[0].id, "1");
+fn volume(ctx: &AppContext, query: &str, percent: Option<u32>, clear: bool) -> Result<()> {
+    // Store the full cached device name so `device set` can look it up later.
+    let snapshot = ctx.cache.device_cache().load()?;
+    let cached = snapshot
+        .as_ref()
+        .map(|snapshot| device::find_devices(&snapshot.items, query))
+        .unwrap_or_default();
+    let name = match cached.as_slice() {
+        [device] => device.name.as_str(),
+        _ => query,
+    };
+    if let Some(level) = percent {
+        if level > 100 {
+            bail!(ErrorKind::Validation.error("volume must be between 0 and 100"));
+        }
+        ctx.auth.set_device_volume(name, Some(level))?;
+    } else if clear {
+        ctx.auth.set_device_volume(name, None)?;
     }
+    let message = match ctx.auth.device_volume(name)? {
+        Some(level) => format!("Default volume: {} {}%", name, level),
+        None => format!("Default volume: {} none", name),
+    };
+    ctx.output.action("device_volume", &message)
 }
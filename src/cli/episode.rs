@@ -0,0 +1,82 @@
+//! Podcast episode command handlers.
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::{hooks, target};
+use crate::cli::library::save_item;
+use crate::cli::now_playing;
+use crate::cli::play::{ResourceType, parse_id};
+use crate::cli::show::SavedListArgs;
+use crate::domain::oplog::SavedKind;
+use crate::error::Result;
+
+#[derive(Subcommand, Debug)]
+pub enum EpisodeCommand {
+    Get {
+        #[arg(value_name = "EPISODE", help = "Episode URL, URI, or id")]
+        episode: String,
+    },
+    Resume {
+        #[arg(value_name = "EPISODE", help = "Episode URL, URI, or id")]
+        episode: String,
+    },
+    List(SavedListArgs),
+    Save {
+        #[arg(
+            value_name = "EPISODE",
+            required_unless_present = "now_playing",
+            help = "Episode URL, URI, id, or `now`"
+        )]
+        episode: Option<String>,
+        #[arg(long, conflicts_with = "episode", help = "Save the playing episode")]
+        now_playing: bool,
+    },
+}
+
+pub fn handle(command: EpisodeCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        EpisodeCommand::Get { episode } => get(ctx, &episode),
+        EpisodeCommand::Resume { episode } => resume(ctx, &episode),
+        EpisodeCommand::List(args) => list(ctx, &args),
+        EpisodeCommand::Save {
+            episode,
+            now_playing,
+        } => {
+            let id = match episode {
+                Some(episode) if !now_playing && !target::is_now(&episode) => {
+                    parse_id(&episode, ResourceType::Episode)?
+                }
+                _ => target::now_episode(ctx)?.id,
+            };
+            save_item(ctx, SavedKind::Episode, id)
+        }
+    }
+}
+
+fn list(ctx: &AppContext, args: &SavedListArgs) -> Result<()> {
+    let episodes = ctx.spotify()?.episodes().saved(args.limit())?;
+    ctx.output.saved_episode_list(episodes, args.csv())
+}
+
+fn get(ctx: &AppContext, episode: &str) -> Result<()> {
+    let id = parse_id(episode, ResourceType::Episode)?;
+    let episode = ctx.spotify()?.episodes().get(&id)?;
+    ctx.output.episode_info(episode)
+}
+
+fn resume(ctx: &AppContext, episode: &str) -> Result<()> {
+    let id = parse_id(episode, ResourceType::Episode)?;
+    let episode = ctx.spotify()?.episodes().get(&id)?;
+    // A finished episode restarts from the top rather than its final second.
+    let position = if episode.fully_played {
+        0
+    } else {
+        episode.resume_position_ms.unwrap_or(0)
+    };
+    hooks::pre_play(ctx, Some(&episode.uri))?;
+    ctx.spotify()?
+        .playback()
+        .play_track_at(&episode.uri, position)?;
+    hooks::track_changed(ctx);
+    now_playing::show_with_delay(ctx, 100)
+}
@@ -0,0 +1,193 @@
+//! Followed artist command handlers.
+use anyhow::bail;
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::pending::{Applied, run_or_defer};
+use crate::action::{history, oplog};
+use crate::cache::history::ListeningHistory;
+use crate::cli::ensure_confirmed;
+use crate::cli::play::{ResourceType, parse_id};
+use crate::cli::releases::parse_since;
+use crate::domain::artist::{Artist, FollowedArtistPage};
+use crate::domain::history::PruneCandidate;
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::pending::PendingOperation;
+use crate::error::{ErrorKind, Result};
+use crate::time::{civil_date, unix_time};
+
+#[derive(Subcommand, Debug)]
+pub enum FollowCommand {
+    List {
+        #[arg(long, help = "Fetch every followed artist, following the page cursors")]
+        all: bool,
+        #[arg(long, default_value_t = 50, help = "Artists per page (1-50)")]
+        limit: u32,
+        #[arg(
+            long,
+            value_name = "ARTIST_ID",
+            help = "Start after this artist id (the cursor printed with each page)"
+        )]
+        after: Option<String>,
+    },
+    Artist {
+        #[arg(value_name = "ARTIST", help = "Artist URL, URI, or id")]
+        artist: String,
+    },
+    Prune {
+        #[arg(long, help = "Prune followed artists")]
+        artists: bool,
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "1y",
+            help = "Propose artists not played within this window (e.g. 26w, 1y)"
+        )]
+        not_played_since: String,
+    },
+}
+
+pub fn handle(command: FollowCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        FollowCommand::List { all, limit, after } => list(ctx, all, limit, after.as_deref()),
+        FollowCommand::Artist { artist } => follow_artist(ctx, &artist),
+        FollowCommand::Prune {
+            artists,
+            not_played_since,
+        } => prune(ctx, artists, &not_played_since),
+    }
+}
+
+fn list(ctx: &AppContext, all: bool, limit: u32, after: Option<&str>) -> Result<()> {
+    if !(1..=50).contains(&limit) {
+        bail!(ErrorKind::Validation.error(format!("--limit must be 1-50; got {limit}")));
+    }
+    let artists = ctx.spotify()?.artists();
+    let page = if all {
+        let items = artists.followed_from(after)?;
+        FollowedArtistPage {
+            total: Some(items.len() as u32),
+            items,
+            after: None,
+        }
+    } else {
+        artists.followed_page(limit, after)?
+    };
+    ctx.output.followed_artists(page)
+}
+
+fn follow_artist(ctx: &AppContext, artist: &str) -> Result<()> {
+    let id = parse_id(artist, ResourceType::Artist)?;
+    let operation = PendingOperation::FollowArtist { id: id.clone() };
+    let message = match run_or_defer(ctx, operation)? {
+        Applied::Now => format!("Followed: {id}"),
+        Applied::Deferred => format!("Offline; queued follow: {id}"),
+    };
+    ctx.output.action("artist_follow", &message)
+}
+
+/// Unfollow artists with no play in the local history since the cutoff.
+fn prune(ctx: &AppContext, artists: bool, not_played_since: &str) -> Result<()> {
+    if !artists {
+        bail!(ErrorKind::Validation.error("nothing to prune; pass --artists"));
+    }
+    let Some(window) = parse_since(not_played_since) else {
+        bail!(ErrorKind::Validation.error(format!(
+            "invalid --not-played-since value '{not_played_since}'; use a number with h, d, w, or y (e.g. 1y)"
+        )));
+    };
+    let cutoff = civil_date(unix_time().saturating_sub(window));
+    let history = history::refresh(ctx)?;
+    history::warn_if_shorter(&history, &cutoff);
+
+    let followed = ctx.spotify()?.artists().followed()?;
+    let candidates = stale_artists(followed, &history, &cutoff);
+    if candidates.is_empty() {
+        let message = format!("Nothing to prune: every followed artist was played since {cutoff}");
+        return ctx.output.action("follow_prune", &message);
+    }
+    let count = candidates.len();
+    ctx.output.prune_candidates(&candidates)?;
+    if !ctx.dry_run {
+        ensure_confirmed(
+            ctx,
+            &format!("Unfollow {count} artists?"),
+            "pass --yes, or --dry-run to preview",
+        )?;
+    }
+
+    let ids = candidates.into_iter().map(|artist| artist.id).collect();
+    oplog::perform(ctx, LoggedOperation::ArtistUnfollow { ids })?;
+    let message = format!("Unfollowed {count} artists not played since {cutoff}");
+    ctx.output.action("follow_prune", &message)
+}
+
+/// Followed artists with no recorded play on or after `cutoff` (`YYYY-MM-DD`).
+fn stale_artists(
+    artists: Vec<Artist>,
+    history: &ListeningHistory,
+    cutoff: &str,
+) -> Vec<PruneCandidate> {
+    artists
+        .into_iter()
+        .filter_map(|artist| {
+            let last_played = history.artist_played(&artist.id);
+            if last_played.is_some_and(|at| at >= cutoff) {
+                return None;
+            }
+            Some(PruneCandidate {
+                last_played: last_played.map(str::to_string),
+                id: artist.id,
+                name: artist.name,
+                artists: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stale_artists;
+    use crate::cache::history::ListeningHistory;
+    use crate::domain::artist::Artist;
+
+    fn artist(id: &str) -> Artist {
+        Artist {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            uri: format!("spotify:artist:{id}"),
+            genres: Vec::new(),
+            followers: None,
+            popularity: None,
+            images: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn stale_artists_keeps_unplayed_and_old_plays() {
+        let mut history = ListeningHistory::default();
+        history
+            .artists
+            .insert("recent".to_string(), "2024-06-01T08:00:00Z".to_string());
+        history
+            .artists
+            .insert("old".to_string(), "2023-01-01T08:00:00Z".to_string());
+        history
+            .artists
+            .insert("cutoff".to_string(), "2024-01-01T00:00:01Z".to_string());
+        let artists = vec![
+            artist("recent"),
+            artist("old"),
+            artist("cutoff"),
+            artist("never"),
+        ];
+        let stale = stale_artists(artists, &history, "2024-01-01");
+        let ids: Vec<&str> = stale.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["old", "never"]);
+        assert_eq!(
+            stale[0].last_played.as_deref(),
+            Some("2023-01-01T08:00:00Z")
+        );
+        assert!(stale[1].last_played.is_none());
+    }
+}
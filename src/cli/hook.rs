@@ -0,0 +1,83 @@
+//! Hook commands for running scripts on events.
+use anyhow::bail;
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::hooks::EVENTS;
+use crate::domain::settings::{Hook, Settings};
+use crate::error::{ErrorKind, Result};
+
+#[derive(Subcommand, Debug)]
+pub enum HookCommand {
+    List,
+    Add {
+        #[arg(value_parser = EVENTS)]
+        event: String,
+        #[arg(
+            required = true,
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            help = "Shell command; receives the event as JSON on stdin"
+        )]
+        command: Vec<String>,
+    },
+    Remove {
+        #[arg(value_parser = EVENTS)]
+        event: String,
+        #[arg(help = "Only remove this command (default: all hooks for the event)")]
+        command: Option<String>,
+    },
+}
+
+pub fn handle(command: HookCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        HookCommand::List => list(ctx),
+        HookCommand::Add { event, command } => add(ctx, event, command.join(" ")),
+        HookCommand::Remove { event, command } => remove(ctx, &event, command.as_deref()),
+    }
+}
+
+fn list(ctx: &AppContext) -> Result<()> {
+    let metadata = ctx.cache.metadata_store().load()?;
+    let settings = Settings {
+        hooks: metadata.settings.hooks,
+        ..Settings::default()
+    };
+    ctx.output.settings(settings)
+}
+
+fn add(ctx: &AppContext, event: String, command: String) -> Result<()> {
+    let store = ctx.cache.metadata_store();
+    let _lock = store.lock()?;
+    let mut metadata = store.load()?;
+    let hooks = &mut metadata.settings.hooks;
+    if !hooks
+        .iter()
+        .any(|hook| hook.event == event && hook.command == command)
+    {
+        hooks.push(Hook {
+            event: event.clone(),
+            command: command.clone(),
+        });
+        store.save(&metadata)?;
+    }
+    let message = format!("Hook: {} -> {}", event, command);
+    ctx.output.action("hook_add", &message)
+}
+
+fn remove(ctx: &AppContext, event: &str, command: Option<&str>) -> Result<()> {
+    let store = ctx.cache.metadata_store();
+    let _lock = store.lock()?;
+    let mut metadata = store.load()?;
+    let before = metadata.settings.hooks.len();
+    metadata.settings.hooks.retain(|hook| {
+        hook.event != event || command.is_some_and(|command| hook.command != command)
+    });
+    let removed = before - metadata.settings.hooks.len();
+    if removed == 0 {
+        bail!(ErrorKind::NotFound.error(format!("no matching {event} hook")));
+    }
+    store.save(&metadata)?;
+    let message = format!("Removed {} {} hook(s)", removed, event);
+    ctx.output.action("hook_remove", &message)
+}
@@ -3,11 +3,19 @@ use anyhow::bail;
 use clap::{Args, ValueEnum};
 
 use crate::AppContext;
+use crate::action::hooks;
+use crate::action::{saved, target};
 use crate::cli::now_playing;
+use crate::cli::play::{ResourceType, parse_id};
 use crate::cli::playlist;
 use crate::cli::search::{apply_fuzzy_scores, fuzzy_query, pick_best_match};
+use crate::cli::{open, show};
+use crate::domain::album::Album;
+use crate::domain::artist::Artist;
+use crate::domain::release::{Release, ReleaseGroup};
 use crate::domain::search::{SearchItem, SearchResults, SearchType};
-use crate::error::Result;
+use crate::error::{ErrorKind, Result};
+use crate::time::unix_time;
 
 #[derive(Args, Debug)]
 pub struct InfoCommand {
@@ -23,6 +31,63 @@ pub struct InfoCommand {
     last: bool,
     #[arg(long, help = "Play the best match result")]
     play: bool,
+    #[arg(long, help = "Show images, popularity, and follower trend (artist)")]
+    full: bool,
+    #[arg(long, help = "Open the artist image in the default viewer (artist)")]
+    open_image: bool,
+    #[arg(
+        long,
+        value_name = "ID,...",
+        value_delimiter = ',',
+        conflicts_with_all = ["query", "last", "pick", "play"],
+        help = "Look up several track, album, or artist ids, URIs, or URLs at once"
+    )]
+    ids: Vec<String>,
+    #[arg(long, help = "Skip checking which album tracks are saved")]
+    no_check: bool,
+    #[arg(
+        long,
+        help = "List the artist's releases grouped by album group (artist)"
+    )]
+    albums: bool,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "GROUP,...",
+        value_delimiter = ',',
+        help = "Album groups to list with --albums (default: all)"
+    )]
+    include_groups: Vec<AlbumGroupArg>,
+    #[arg(long, help = "Only the most recent release per group (with --albums)")]
+    latest: bool,
+}
+
+/// Spotify album groups, in the order Spotify lists them.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AlbumGroupArg {
+    Album,
+    Single,
+    Compilation,
+    #[value(name = "appears_on")]
+    AppearsOn,
+}
+
+impl AlbumGroupArg {
+    const ALL: [AlbumGroupArg; 4] = [
+        AlbumGroupArg::Album,
+        AlbumGroupArg::Single,
+        AlbumGroupArg::Compilation,
+        AlbumGroupArg::AppearsOn,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            AlbumGroupArg::Album => "album",
+            AlbumGroupArg::Single => "single",
+            AlbumGroupArg::Compilation => "compilation",
+            AlbumGroupArg::AppearsOn => "appears_on",
+        }
+    }
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy)]
@@ -31,14 +96,31 @@ enum InfoTypeArg {
     Artist,
     Track,
     Playlist,
+    Show,
+    Episode,
+    Audiobook,
 }
 
-pub fn handle(command: InfoCommand, ctx: &AppContext) -> Result<()> {
+pub fn handle(mut command: InfoCommand, ctx: &AppContext) -> Result<()> {
+    if command.query.as_deref().is_some_and(target::is_now) {
+        command.query = None;
+        command.kind.get_or_insert(InfoTypeArg::Track);
+    }
+    if !command.ids.is_empty() {
+        return info_many(ctx, command.kind, &command.ids);
+    }
     let Some(kind) = command.kind else {
         let Some(query) = command.query else {
-            bail!("missing info target; pass a type or query");
+            bail!(ErrorKind::Validation.error("missing info target; pass a type or query"));
         };
-        return info_any(ctx, &query, command.user, command.pick, command.play);
+        return info_any(
+            ctx,
+            &query,
+            command.user,
+            command.pick,
+            command.play,
+            !command.no_check,
+        );
     };
 
     match kind {
@@ -46,6 +128,9 @@ pub fn handle(command: InfoCommand, ctx: &AppContext) -> Result<()> {
         InfoTypeArg::Artist => info_artist(ctx, &command, command.play),
         InfoTypeArg::Track => info_track(ctx, &command, command.play),
         InfoTypeArg::Playlist => info_playlist(ctx, &command, command.play),
+        InfoTypeArg::Show => info_show(ctx, &command, command.play),
+        InfoTypeArg::Episode => info_episode(ctx, &command, command.play),
+        InfoTypeArg::Audiobook => info_audiobook(ctx, &command, command.play),
     }
 }
 
@@ -55,6 +140,7 @@ fn info_any(
     user: bool,
     pick: Option<usize>,
     play: bool,
+    check: bool,
 ) -> Result<()> {
     let limit = pick.map(|_| 10).unwrap_or(10);
     let search_query = fuzzy_query(query);
@@ -73,31 +159,62 @@ fn info_any(
     };
 
     let Some(item) = item else {
-        bail!("no results found");
+        bail!(ErrorKind::NotFound.error("no results found"));
     };
 
     if play {
         play_item(ctx, &item)?;
         now_playing::show_with_delay(ctx, 100)?;
     }
-    dispatch_item(ctx, item)
+    dispatch_item(ctx, item, check)
+}
+
+fn info_many(ctx: &AppContext, kind: Option<InfoTypeArg>, inputs: &[String]) -> Result<()> {
+    let (kind, resource_type) = match kind {
+        Some(InfoTypeArg::Track) => (SearchType::Track, ResourceType::Track),
+        Some(InfoTypeArg::Album) => (SearchType::Album, ResourceType::Album),
+        Some(InfoTypeArg::Artist) => (SearchType::Artist, ResourceType::Artist),
+        _ => bail!(ErrorKind::Validation.error("--ids needs a type: track, album, or artist")),
+    };
+    let ids = inputs
+        .iter()
+        .map(|input| {
+            let input = target::resolve_target(ctx, input, kind)?;
+            parse_id(&input, resource_type)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let found = ctx.spotify()?.search().lookup(kind, &ids)?;
+    let mut items = Vec::with_capacity(found.len());
+    for (id, item) in ids.iter().zip(found) {
+        match item {
+            Some(item) => items.push(item),
+            None => eprintln!("not found: {id}"),
+        }
+    }
+    if items.is_empty() {
+        bail!(ErrorKind::NotFound.error("none of the ids were found"));
+    }
+    ctx.output.search_results(SearchResults { kind, items })
 }
 
 fn info_album(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()> {
     if command.query.is_none() && !command.last {
         let status = ctx.spotify()?.playback().status()?;
         let Some(track) = status.track else {
-            bail!("no track is currently playing");
+            bail!(ErrorKind::NotFound.error("no track is currently playing"));
         };
         let Some(album_id) = track.album_id else {
-            bail!("current track has no album id; pass an album query");
+            bail!(ErrorKind::NotFound.error("current track has no album id; pass an album query"));
         };
         let album = ctx.spotify()?.albums().get(&album_id)?;
         if play {
+            hooks::pre_play(ctx, Some(&album.uri))?;
             ctx.spotify()?.playback().play_context(&album.uri)?;
+            hooks::track_changed(ctx);
             now_playing::show_with_delay(ctx, 100)?;
         }
-        return ctx.output.album_info(album);
+        return show_album(ctx, album, !command.no_check);
     }
 
     let item = resolve_item(
@@ -110,9 +227,18 @@ fn info_album(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()>
     )?;
     let album = ctx.spotify()?.albums().get(&item.id)?;
     if play {
+        hooks::pre_play(ctx, Some(&item.uri))?;
         ctx.spotify()?.playback().play_context(&item.uri)?;
+        hooks::track_changed(ctx);
         now_playing::show_with_delay(ctx, 100)?;
     }
+    show_album(ctx, album, !command.no_check)
+}
+
+fn show_album(ctx: &AppContext, mut album: Album, check: bool) -> Result<()> {
+    if check {
+        saved::mark_album(ctx, &mut album);
+    }
     ctx.output.album_info(album)
 }
 
@@ -120,17 +246,21 @@ fn info_artist(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()
     if command.query.is_none() && !command.last {
         let status = ctx.spotify()?.playback().status()?;
         let Some(track) = status.track else {
-            bail!("no track is currently playing");
+            bail!(ErrorKind::NotFound.error("no track is currently playing"));
         };
         let Some(artist_id) = track.artist_ids.first() else {
-            bail!("current track has no artist id; pass an artist query");
+            bail!(
+                ErrorKind::NotFound.error("current track has no artist id; pass an artist query")
+            );
         };
         let artist = ctx.spotify()?.artists().get(artist_id)?;
         if play {
+            hooks::pre_play(ctx, Some(&artist.uri))?;
             ctx.spotify()?.playback().play_context(&artist.uri)?;
+            hooks::track_changed(ctx);
             now_playing::show_with_delay(ctx, 100)?;
         }
-        return ctx.output.artist_info(artist);
+        return show_artist(ctx, command, artist);
     }
 
     let item = resolve_item(
@@ -143,27 +273,115 @@ fn info_artist(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()
     )?;
     let artist = ctx.spotify()?.artists().get(&item.id)?;
     if play {
+        hooks::pre_play(ctx, Some(&item.uri))?;
         ctx.spotify()?.playback().play_context(&item.uri)?;
+        hooks::track_changed(ctx);
         now_playing::show_with_delay(ctx, 100)?;
     }
-    ctx.output.artist_info(artist)
+    show_artist(ctx, command, artist)
+}
+
+fn show_artist(ctx: &AppContext, command: &InfoCommand, artist: Artist) -> Result<()> {
+    if command.albums || command.latest || !command.include_groups.is_empty() {
+        return artist_releases(ctx, command, artist);
+    }
+    if command.open_image {
+        let Some(url) = artist.images.first() else {
+            bail!(ErrorKind::NotFound.error(format!("no image available for {}", artist.name)));
+        };
+        open_url(url);
+    }
+    if !command.full {
+        return ctx.output.artist_info(artist);
+    }
+
+    let trend = match artist.followers {
+        Some(followers) => {
+            ctx.cache
+                .artist_stats_store()
+                .record(&artist.id, followers, unix_time())?
+        }
+        None => None,
+    };
+    ctx.output.artist_full(artist, trend)
+}
+
+fn artist_releases(ctx: &AppContext, command: &InfoCommand, artist: Artist) -> Result<()> {
+    let mut groups = command.include_groups.clone();
+    groups.sort();
+    groups.dedup();
+    let names: Vec<&str> = groups.iter().map(|group| group.as_str()).collect();
+    let releases = ctx.spotify()?.artists().albums_all(&artist.id, &names)?;
+    if groups.is_empty() {
+        groups = AlbumGroupArg::ALL.to_vec();
+    }
+    let groups = group_releases(releases, &groups, command.latest);
+    ctx.output.artist_releases(artist, groups)
+}
+
+/// Split releases by album group in the requested order, newest first within each group.
+fn group_releases(
+    releases: Vec<Release>,
+    groups: &[AlbumGroupArg],
+    latest: bool,
+) -> Vec<ReleaseGroup> {
+    let mut grouped: Vec<ReleaseGroup> = groups
+        .iter()
+        .map(|group| ReleaseGroup {
+            group: group.as_str().to_string(),
+            releases: Vec::new(),
+        })
+        .collect();
+    for release in releases {
+        // Fall back to album_type when Spotify omits album_group.
+        let group = release
+            .album_group
+            .as_deref()
+            .or(release.album_type.as_deref())
+            .unwrap_or_default();
+        if let Some(entry) = grouped.iter_mut().find(|entry| entry.group == group) {
+            entry.releases.push(release);
+        }
+    }
+    for entry in &mut grouped {
+        entry
+            .releases
+            .sort_by(|a, b| b.release_date.cmp(&a.release_date));
+        if latest {
+            entry.releases.truncate(1);
+        }
+    }
+    grouped.retain(|entry| !entry.releases.is_empty());
+    grouped
+}
+
+fn open_url(url: &str) {
+    if open::launch(url).is_err() {
+        eprintln!("unable to open image; url: {url}");
+    }
 }
 
 fn info_playlist(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()> {
     if command.query.is_none() && !command.last {
         let status = ctx.spotify()?.playback().status()?;
         let Some(context) = status.context else {
-            bail!("no playlist context is active; pass a playlist query");
+            bail!(
+                ErrorKind::NotFound.error("no playlist context is active; pass a playlist query")
+            );
         };
         if context.kind != "playlist" {
-            bail!("no playlist context is active; pass a playlist query");
+            bail!(
+                ErrorKind::NotFound.error("no playlist context is active; pass a playlist query")
+            );
         }
         let Some(id) = playlist::parse_playlist_id(&context.uri) else {
-            bail!("unable to parse playlist context uri");
+            bail!(ErrorKind::General.error("unable to parse playlist context uri"));
         };
         let playlist_detail = ctx.spotify()?.playlists().get(&id)?;
         if play {
+            hooks::pre_play(ctx, Some(&context.uri))?;
             ctx.spotify()?.playback().play_context(&context.uri)?;
+            hooks::track_changed(ctx);
             now_playing::show_with_delay(ctx, 100)?;
         }
         return ctx.output.playlist_info(playlist_detail);
@@ -179,18 +397,86 @@ fn info_playlist(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<
     )?;
     let playlist_detail = ctx.spotify()?.playlists().get(&item.id)?;
     if play {
+        hooks::pre_play(ctx, Some(&item.uri))?;
         ctx.spotify()?.playback().play_context(&item.uri)?;
+        hooks::track_changed(ctx);
         now_playing::show_with_delay(ctx, 100)?;
     }
     ctx.output.playlist_info(playlist_detail)
 }
 
+fn info_show(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()> {
+    let id = match direct_query(command)? {
+        Some(query) => show::resolve_show_id(ctx, query)?,
+        // The show being played from, else the show of the playing episode.
+        None => match target::now_context_id(ctx, "show") {
+            Ok(id) => id,
+            Err(_) => match target::now_episode(ctx)?.show_id {
+                Some(id) => id,
+                None => bail!(ErrorKind::NotFound.error("no show is currently playing")),
+            },
+        },
+    };
+    let info = ctx.spotify()?.shows().get(&id)?;
+    if play {
+        play_context(ctx, &info.uri)?;
+    }
+    ctx.output.show_info(info)
+}
+
+fn info_episode(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()> {
+    let id = match direct_query(command)? {
+        Some(query) => parse_id(query, ResourceType::Episode)?,
+        None => target::now_episode(ctx)?.id,
+    };
+    let info = ctx.spotify()?.episodes().get(&id)?;
+    if play {
+        hooks::pre_play(ctx, Some(&info.uri))?;
+        ctx.spotify()?.playback().play_track(&info.uri)?;
+        hooks::track_changed(ctx);
+        now_playing::show_with_delay(ctx, 100)?;
+    }
+    ctx.output.episode_info(info)
+}
+
+fn info_audiobook(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()> {
+    let id = match direct_query(command)? {
+        Some(query) => parse_id(query, ResourceType::Audiobook)?,
+        None => target::now_context_id(ctx, "audiobook")?,
+    };
+    let info = ctx.spotify()?.audiobooks().get(&id)?;
+    if play {
+        play_context(ctx, &info.uri)?;
+    }
+    ctx.output.audiobook_info(info)
+}
+
+/// Shows, episodes, and audiobooks take a URL, URI, or id rather than a search query.
+fn direct_query(command: &InfoCommand) -> Result<Option<&str>> {
+    if command.last || command.pick.is_some() {
+        bail!(
+            ErrorKind::Validation
+                .error("--last and --pick apply to track, album, artist, and playlist info")
+        );
+    }
+    Ok(command.query.as_deref())
+}
+
+fn play_context(ctx: &AppContext, uri: &str) -> Result<()> {
+    hooks::pre_play(ctx, Some(uri))?;
+    ctx.spotify()?.playback().play_context(uri)?;
+    hooks::track_changed(ctx);
+    now_playing::show_with_delay(ctx, 100)
+}
+
 fn info_track(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()> {
     if command.query.is_none() && !command.last {
         let status = ctx.spotify()?.playback().status()?;
         if play && let Some(track) = status.track.as_ref() {
             let uri = format!("spotify:track:{}", track.id);
+            hooks::pre_play(ctx, Some(&uri))?;
             ctx.spotify()?.playback().play_track(&uri)?;
+            hooks::track_changed(ctx);
             now_playing::show_with_delay(ctx, 100)?;
         }
         return ctx.output.player_status(status);
@@ -205,7 +491,9 @@ fn info_track(ctx: &AppContext, command: &InfoCommand, play: bool) -> Result<()>
         command.pick,
     )?;
     if play {
+        hooks::pre_play(ctx, Some(&item.uri))?;
         ctx.spotify()?.playback().play_track(&item.uri)?;
+        hooks::track_changed(ctx);
         now_playing::show_with_delay(ctx, 100)?;
     }
     ctx.output.search_results(SearchResults {
@@ -225,19 +513,22 @@ fn resolve_item(
     let (query_text, results) = if last {
         let cached = ctx.cache.search_store().load()?;
         let Some(cached) = cached else {
-            bail!("no cached search; run `spotify-cli search <query>`");
+            bail!(ErrorKind::NotFound.error("no cached search; run `spotify-cli search <query>`"));
         };
         if cached.results.kind != kind {
-            bail!(
+            bail!(ErrorKind::Validation.error(format!(
                 "cached search is {}; run `spotify-cli search {} <query>`",
                 search_type_label(cached.results.kind),
                 search_type_label(kind)
-            );
+            )));
         }
         (cached.query, cached.results)
     } else {
         let Some(query) = query else {
-            bail!("missing query; use --last to reuse cached search results");
+            bail!(
+                ErrorKind::Validation
+                    .error("missing query; use --last to reuse cached search results")
+            );
         };
         let limit = pick.map(|_| 10).unwrap_or(10);
         let search_query = fuzzy_query(query);
@@ -258,7 +549,7 @@ fn resolve_item(
         pick_best_match(&results, &query_text, owner_name.as_deref())
     };
     let Some(item) = item else {
-        bail!("no results found");
+        bail!(ErrorKind::NotFound.error("no results found"));
     };
 
     Ok(item)
@@ -266,6 +557,7 @@ fn resolve_item(
 
 fn play_item(ctx: &AppContext, item: &SearchItem) -> Result<()> {
     let playback = ctx.spotify()?.playback();
+    hooks::pre_play(ctx, Some(&item.uri))?;
     match item.kind {
         SearchType::Track => playback.play_track(&item.uri)?,
         SearchType::Album | SearchType::Artist | SearchType::Playlist => {
@@ -273,6 +565,7 @@ fn play_item(ctx: &AppContext, item: &SearchItem) -> Result<()> {
         }
         SearchType::All => {}
     }
+    hooks::track_changed(ctx);
     Ok(())
 }
 
@@ -286,11 +579,11 @@ fn search_type_label(kind: SearchType) -> &'static str {
     }
 }
 
-fn dispatch_item(ctx: &AppContext, item: SearchItem) -> Result<()> {
+fn dispatch_item(ctx: &AppContext, item: SearchItem, check: bool) -> Result<()> {
     match item.kind {
         SearchType::Album => {
             let album = ctx.spotify()?.albums().get(&item.id)?;
-            ctx.output.album_info(album)
+            show_album(ctx, album, check)
         }
         SearchType::Artist => {
             let artist = ctx.spotify()?.artists().get(&item.id)?;
@@ -310,8 +603,83 @@ fn dispatch_item(ctx: &AppContext, item: SearchItem) -> Result<()> {
 
 fn pick_item(items: &[SearchItem], pick: usize) -> Result<Option<SearchItem>> {
     if pick == 0 {
-        bail!("pick must be 1 or greater");
+        bail!(ErrorKind::Validation.error("pick must be 1 or greater"));
     }
     let index = pick - 1;
     Ok(items.get(index).cloned())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AlbumGroupArg, group_releases, parse_id};
+    use crate::cli::play::ResourceType;
+    use crate::domain::release::Release;
+
+    fn release(id: &str, group: Option<&str>, album_type: &str, date: &str) -> Release {
+        Release {
+            id: id.to_string(),
+            name: id.to_string(),
+            uri: format!("spotify:album:{id}"),
+            artists: Vec::new(),
+            album_type: Some(album_type.to_string()),
+            album_group: group.map(str::to_string),
+            release_date: Some(date.to_string()),
+        }
+    }
+
+    #[test]
+    fn parse_id_accepts_matching_uri_url_and_id() {
+        assert_eq!(
+            parse_id("spotify:album:abc", ResourceType::Album).unwrap(),
+            "abc"
+        );
+        assert_eq!(
+            parse_id("https://open.spotify.com/artist/xyz", ResourceType::Artist).unwrap(),
+            "xyz"
+        );
+        assert_eq!(parse_id("abc123", ResourceType::Track).unwrap(), "abc123");
+        assert!(parse_id("spotify:album:abc", ResourceType::Track).is_err());
+        assert!(parse_id("not an id", ResourceType::Track).is_err());
+    }
+
+    #[test]
+    fn group_releases_orders_groups_and_dates() {
+        let releases = vec![
+            release("old", Some("album"), "album", "2001-01-01"),
+            release("feat", Some("appears_on"), "album", "2020-01-01"),
+            release("new", Some("album"), "album", "2019-06-01"),
+            release("ep", None, "single", "2010"),
+        ];
+        let groups = group_releases(releases, &AlbumGroupArg::ALL, false);
+        let shape: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.group.as_str(),
+                    group.releases.iter().map(|r| r.id.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            shape,
+            vec![
+                ("album", vec!["new", "old"]),
+                ("single", vec!["ep"]),
+                ("appears_on", vec!["feat"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn group_releases_latest_keeps_newest_per_group() {
+        let releases = vec![
+            release("old", Some("album"), "album", "2001-01-01"),
+            release("new", Some("album"), "album", "2019-06-01"),
+            release("comp", Some("compilation"), "compilation", "2015"),
+        ];
+        let groups = group_releases(releases, &[AlbumGroupArg::Album], true);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].releases.len(), 1);
+        assert_eq!(groups[0].releases[0].id, "new");
+    }
+}
@@ -0,0 +1,527 @@
+//! Library (saved tracks) command handlers.
+use std::collections::HashSet;
+
+use anyhow::bail;
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::AppContext;
+use crate::action::pending::{Applied, run_or_defer};
+use crate::action::{history, oplog, target};
+use crate::cache::history::ListeningHistory;
+use crate::cli::ensure_confirmed;
+use crate::cli::play::{ResourceType, parse_id};
+use crate::cli::releases::parse_since;
+use crate::domain::history::PruneCandidate;
+use crate::domain::oplog::{LoggedOperation, SavedKind};
+use crate::domain::pending::PendingOperation;
+use crate::domain::player::PlayingItem;
+use crate::domain::track::SavedTrack;
+use crate::error::{ErrorKind, Result};
+use crate::time::{civil_date, unix_time};
+
+#[derive(Subcommand, Debug)]
+pub enum LibraryCommand {
+    List(LibraryListArgs),
+    Mirror {
+        #[arg(long, value_name = "NAME", default_value = "Liked Songs Mirror")]
+        playlist: String,
+        #[arg(long, help = "Sync an existing mirror playlist")]
+        update: bool,
+    },
+    Save {
+        #[arg(
+            value_name = "TRACK",
+            required_unless_present = "now_playing",
+            help = "Track URL, URI, or id; `now` saves whatever is playing"
+        )]
+        track: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "track",
+            help = "Save whatever is playing: a track, episode, or audiobook"
+        )]
+        now_playing: bool,
+    },
+    Prune {
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "1y",
+            help = "Propose tracks saved before and not played within this window (e.g. 26w, 1y)"
+        )]
+        not_played_since: String,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct LibraryListArgs {
+    #[arg(long, value_name = "NAME", help = "Only tracks by a matching artist")]
+    artist: Option<String>,
+    #[arg(long, value_name = "NAME", help = "Only tracks from a matching album")]
+    album: Option<String>,
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        help = "Only tracks saved on or after a date"
+    )]
+    added_after: Option<String>,
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        help = "Only tracks saved before a date"
+    )]
+    added_before: Option<String>,
+    #[arg(long, value_name = "M:SS", help = "Only tracks at least this long")]
+    min_duration: Option<String>,
+    #[arg(long, value_name = "M:SS", help = "Only tracks at most this long")]
+    max_duration: Option<String>,
+    #[arg(long, value_enum, default_value = "added")]
+    sort: LibrarySort,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibrarySort {
+    Added,
+    Name,
+    Artist,
+    Duration,
+}
+
+pub fn handle(command: LibraryCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        LibraryCommand::List(args) => list(ctx, args),
+        LibraryCommand::Mirror { playlist, update } => mirror(ctx, &playlist, update),
+        LibraryCommand::Save { track, now_playing } => match track {
+            Some(track) if !now_playing && !target::is_now(&track) => save(ctx, &track),
+            _ => save_now_playing(ctx),
+        },
+        LibraryCommand::Prune { not_played_since } => prune(ctx, &not_played_since),
+    }
+}
+
+fn list(ctx: &AppContext, args: LibraryListArgs) -> Result<()> {
+    let filter = TrackFilter::from_args(&args)?;
+    let mut progress = ctx.output.progress("Fetching liked songs", None);
+    let mut tracks = ctx
+        .spotify()?
+        .track()
+        .saved_with(|done, total| progress.update(done, total))?;
+    progress.finish();
+    tracks.retain(|saved| filter.matches(saved));
+    sort_tracks(&mut tracks, args.sort);
+    ctx.output.saved_track_list(tracks)
+}
+
+/// Spotify caps playlist track mutations at 100 items per request.
+const PLAYLIST_BATCH: usize = 100;
+
+fn mirror(ctx: &AppContext, name: &str, update: bool) -> Result<()> {
+    let spotify = ctx.spotify()?;
+    let playlists = spotify.playlists();
+    let mut progress = ctx.output.progress("Fetching liked songs", None);
+    let liked: Vec<String> = spotify
+        .track()
+        .saved_with(|done, total| progress.update(done, total))?
+        .into_iter()
+        .map(|saved| format!("spotify:track:{}", saved.track.id))
+        .collect();
+    progress.finish();
+
+    let lower = name.to_lowercase();
+    let existing = playlists
+        .list_all()?
+        .into_iter()
+        .find(|playlist| playlist.name.to_lowercase() == lower);
+
+    let (id, current) = match existing {
+        Some(playlist) => {
+            if !update {
+                bail!(ErrorKind::Validation.error(format!(
+                    "playlist exists: {}; pass --update to sync it",
+                    playlist.name
+                )));
+            }
+            let mut progress = ctx.output.progress("Fetching mirror", None);
+            let current = playlists
+                .items_all_with(&playlist.id, |done, total| progress.update(done, total))?
+                .into_iter()
+                .map(|item| format!("spotify:track:{}", item.track.id))
+                .collect();
+            progress.finish();
+            (playlist.id, current)
+        }
+        None => (
+            oplog::create_playlist(ctx, name, Some(false))?.id,
+            Vec::new(),
+        ),
+    };
+
+    let (to_add, to_remove) = mirror_diff(&liked, &current);
+    let changes = (to_add.len() + to_remove.len()) as u64;
+    let mut progress = ctx.output.progress("Updating mirror", Some(changes));
+    for batch in to_remove.chunks(PLAYLIST_BATCH) {
+        let operation = LoggedOperation::PlaylistRemove {
+            playlist_id: id.clone(),
+            uris: batch.to_vec(),
+        };
+        oplog::perform(ctx, operation)?;
+        progress.inc(batch.len() as u64);
+    }
+    for batch in to_add.chunks(PLAYLIST_BATCH) {
+        let operation = LoggedOperation::PlaylistAdd {
+            playlist_id: id.clone(),
+            uris: batch.to_vec(),
+        };
+        oplog::perform(ctx, operation)?;
+        progress.inc(batch.len() as u64);
+    }
+    progress.finish();
+
+    let message = format!(
+        "Mirrored: {} (+{} -{})",
+        name,
+        to_add.len(),
+        to_remove.len()
+    );
+    ctx.output.action("library_mirror", &message)
+}
+
+/// Tracks to add to and remove from the mirror so it matches liked songs.
+fn mirror_diff(liked: &[String], current: &[String]) -> (Vec<String>, Vec<String>) {
+    let liked_set: HashSet<&String> = liked.iter().collect();
+    let current_set: HashSet<&String> = current.iter().collect();
+    let to_add = liked
+        .iter()
+        .filter(|uri| !current_set.contains(uri))
+        .cloned()
+        .collect();
+    let to_remove = current
+        .iter()
+        .filter(|uri| !liked_set.contains(uri))
+        .cloned()
+        .collect();
+    (to_add, to_remove)
+}
+
+/// Client-side filters over fully paginated saved tracks.
+#[derive(Debug, Default)]
+struct TrackFilter {
+    artist: Option<String>,
+    album: Option<String>,
+    added_after: Option<String>,
+    added_before: Option<String>,
+    min_duration_ms: Option<u32>,
+    max_duration_ms: Option<u32>,
+}
+
+impl TrackFilter {
+    fn from_args(args: &LibraryListArgs) -> Result<Self> {
+        Ok(Self {
+            artist: args.artist.as_ref().map(|value| value.to_lowercase()),
+            album: args.album.as_ref().map(|value| value.to_lowercase()),
+            added_after: args.added_after.as_deref().map(parse_date).transpose()?,
+            added_before: args.added_before.as_deref().map(parse_date).transpose()?,
+            min_duration_ms: args
+                .min_duration
+                .as_deref()
+                .map(parse_duration)
+                .transpose()?,
+            max_duration_ms: args
+                .max_duration
+                .as_deref()
+                .map(parse_duration)
+                .transpose()?,
+        })
+    }
+
+    fn matches(&self, saved: &SavedTrack) -> bool {
+        let track = &saved.track;
+        if let Some(artist) = self.artist.as_ref()
+            && !track
+                .artists
+                .iter()
+                .any(|name| name.to_lowercase().contains(artist))
+        {
+            return false;
+        }
+        if let Some(album) = self.album.as_ref()
+            && !track
+                .album
+                .as_ref()
+                .is_some_and(|name| name.to_lowercase().contains(album))
+        {
+            return false;
+        }
+        let added = saved
+            .added_at
+            .as_deref()
+            .and_then(|value| value.get(..10))
+            .unwrap_or_default();
+        if self
+            .added_after
+            .as_deref()
+            .is_some_and(|after| added < after)
+        {
+            return false;
+        }
+        if self
+            .added_before
+            .as_deref()
+            .is_some_and(|before| added >= before)
+        {
+            return false;
+        }
+        let duration = track.duration_ms.unwrap_or(0);
+        if self.min_duration_ms.is_some_and(|min| duration < min) {
+            return false;
+        }
+        if self.max_duration_ms.is_some_and(|max| duration > max) {
+            return false;
+        }
+        true
+    }
+}
+
+fn sort_tracks(tracks: &mut [SavedTrack], sort: LibrarySort) {
+    match sort {
+        LibrarySort::Added => tracks.sort_by(|a, b| b.added_at.cmp(&a.added_at)),
+        LibrarySort::Name => tracks.sort_by_key(|saved| saved.track.name.to_lowercase()),
+        LibrarySort::Artist => tracks.sort_by(|a, b| {
+            let a_artist = a.track.artists.first().map(|name| name.to_lowercase());
+            let b_artist = b.track.artists.first().map(|name| name.to_lowercase());
+            a_artist.cmp(&b_artist).then_with(|| {
+                a.track
+                    .name
+                    .to_lowercase()
+                    .cmp(&b.track.name.to_lowercase())
+            })
+        }),
+        LibrarySort::Duration => tracks.sort_by_key(|saved| saved.track.duration_ms),
+    }
+}
+
+fn parse_date(input: &str) -> Result<String> {
+    let parts: Vec<&str> = input.split('-').collect();
+    let valid = parts.len() == 3
+        && parts[0].len() == 4
+        && parts[1].len() == 2
+        && parts[2].len() == 2
+        && parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()));
+    if !valid {
+        bail!(ErrorKind::Validation.error(format!("invalid date: {input}; expected YYYY-MM-DD")));
+    }
+    Ok(input.to_string())
+}
+
+/// Parse `M:SS` or plain seconds into milliseconds.
+pub(crate) fn parse_duration(input: &str) -> Result<u32> {
+    let seconds = match input.split_once(':') {
+        Some((minutes, seconds)) => match (minutes.parse::<u32>(), seconds.parse::<u32>()) {
+            (Ok(minutes), Ok(seconds)) if seconds < 60 => minutes * 60 + seconds,
+            _ => bail!(ErrorKind::Validation.error(format!(
+                "invalid duration: {input}; expected M:SS or seconds"
+            ))),
+        },
+        None => match input.parse::<u32>() {
+            Ok(seconds) => seconds,
+            Err(_) => bail!(ErrorKind::Validation.error(format!(
+                "invalid duration: {input}; expected M:SS or seconds"
+            ))),
+        },
+    };
+    Ok(seconds.saturating_mul(1000))
+}
+
+fn save(ctx: &AppContext, track: &str) -> Result<()> {
+    save_track(ctx, parse_id(track, ResourceType::Track)?)
+}
+
+/// Save the playing item through the library endpoint for its kind.
+fn save_now_playing(ctx: &AppContext) -> Result<()> {
+    match target::now_item(ctx)? {
+        PlayingItem::Track { id } => save_track(ctx, id),
+        PlayingItem::Episode(episode) => save_item(ctx, SavedKind::Episode, episode.id),
+        PlayingItem::Chapter { audiobook_id } => save_item(ctx, SavedKind::Audiobook, audiobook_id),
+    }
+}
+
+fn save_track(ctx: &AppContext, id: String) -> Result<()> {
+    let operation = PendingOperation::SaveTrack { id: id.clone() };
+    let message = match run_or_defer(ctx, operation)? {
+        Applied::Now => format!("Saved: {}", id),
+        Applied::Deferred => format!("Offline; queued save: {}", id),
+    };
+    ctx.output.action("library_save", &message)
+}
+
+/// Save an episode, show, or audiobook to the library.
+pub(crate) fn save_item(ctx: &AppContext, kind: SavedKind, id: String) -> Result<()> {
+    let message = format!("Saved {}: {id}", kind.as_str());
+    oplog::perform(
+        ctx,
+        LoggedOperation::ItemSave {
+            kind,
+            ids: vec![id],
+        },
+    )?;
+    ctx.output
+        .action(&format!("{}_save", kind.as_str()), &message)
+}
+
+/// Remove liked tracks saved before the cutoff with no play in the local history since.
+fn prune(ctx: &AppContext, not_played_since: &str) -> Result<()> {
+    let Some(window) = parse_since(not_played_since) else {
+        bail!(ErrorKind::Validation.error(format!(
+            "invalid --not-played-since value '{not_played_since}'; use a number with h, d, w, or y (e.g. 1y)"
+        )));
+    };
+    let cutoff = civil_date(unix_time().saturating_sub(window));
+    let history = history::refresh(ctx)?;
+    history::warn_if_shorter(&history, &cutoff);
+
+    let mut progress = ctx.output.progress("Fetching liked songs", None);
+    let tracks = ctx
+        .spotify()?
+        .track()
+        .saved_with(|done, total| progress.update(done, total))?;
+    progress.finish();
+    let candidates = stale_tracks(tracks, &history, &cutoff);
+    if candidates.is_empty() {
+        let message =
+            format!("Nothing to prune: no liked track saved before {cutoff} went unplayed");
+        return ctx.output.action("library_prune", &message);
+    }
+    let count = candidates.len();
+    ctx.output.prune_candidates(&candidates)?;
+    if !ctx.dry_run {
+        ensure_confirmed(
+            ctx,
+            &format!("Remove {count} tracks from Liked Songs?"),
+            "pass --yes, or --dry-run to preview",
+        )?;
+    }
+
+    let ids = candidates.into_iter().map(|track| track.id).collect();
+    oplog::perform(ctx, LoggedOperation::LibraryRemove { ids })?;
+    let message = format!("Removed {count} liked tracks not played since {cutoff}");
+    ctx.output.action("library_prune", &message)
+}
+
+/// Liked tracks saved before `cutoff` (`YYYY-MM-DD`) with no recorded play on or after it.
+fn stale_tracks(
+    tracks: Vec<SavedTrack>,
+    history: &ListeningHistory,
+    cutoff: &str,
+) -> Vec<PruneCandidate> {
+    tracks
+        .into_iter()
+        .filter(|saved| saved.added_at.as_deref().is_some_and(|at| at < cutoff))
+        .filter_map(|saved| {
+            let last_played = history.track_played(&saved.track.id);
+            if last_played.is_some_and(|at| at >= cutoff) {
+                return None;
+            }
+            Some(PruneCandidate {
+                last_played: last_played.map(str::to_string),
+                id: saved.track.id,
+                name: saved.track.name,
+                artists: saved.track.artists,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        LibrarySort, TrackFilter, mirror_diff, parse_date, parse_duration, sort_tracks,
+        stale_tracks,
+    };
+    use crate::cache::history::ListeningHistory;
+    use crate::domain::track::{SavedTrack, Track};
+
+    fn saved(name: &str, artist: &str, duration_ms: u32, added_at: &str) -> SavedTrack {
+        SavedTrack {
+            track: Track {
+                id: name.to_string(),
+                name: name.to_string(),
+                artists: vec![artist.to_string()],
+                artist_ids: Vec::new(),
+                album: Some("Album".to_string()),
+                album_id: None,
+                duration_ms: Some(duration_ms),
+            },
+            added_at: Some(added_at.to_string()),
+        }
+    }
+
+    #[test]
+    fn mirror_diff_adds_and_removes_changes_only() {
+        let liked = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let current = vec!["b".to_string(), "d".to_string()];
+        let (to_add, to_remove) = mirror_diff(&liked, &current);
+        assert_eq!(to_add, vec!["a".to_string(), "c".to_string()]);
+        assert_eq!(to_remove, vec!["d".to_string()]);
+    }
+
+    #[test]
+    fn parse_duration_accepts_minutes_and_seconds() {
+        assert_eq!(parse_duration("3:30").unwrap(), 210_000);
+        assert_eq!(parse_duration("90").unwrap(), 90_000);
+        assert!(parse_duration("3:75").is_err());
+        assert!(parse_duration("long").is_err());
+    }
+
+    #[test]
+    fn parse_date_requires_iso_format() {
+        assert!(parse_date("2023-01-31").is_ok());
+        assert!(parse_date("31/01/2023").is_err());
+    }
+
+    #[test]
+    fn track_filter_applies_dates_and_durations() {
+        let filter = TrackFilter {
+            added_before: Some("2023-01-01".to_string()),
+            min_duration_ms: Some(60_000),
+            ..TrackFilter::default()
+        };
+        assert!(filter.matches(&saved("Old", "A", 120_000, "2022-06-01T00:00:00Z")));
+        assert!(!filter.matches(&saved("New", "A", 120_000, "2023-06-01T00:00:00Z")));
+        assert!(!filter.matches(&saved("Short", "A", 30_000, "2022-06-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn sort_tracks_by_duration() {
+        let mut tracks = vec![
+            saved("Long", "A", 300_000, "2022-01-01T00:00:00Z"),
+            saved("Short", "B", 100_000, "2023-01-01T00:00:00Z"),
+        ];
+        sort_tracks(&mut tracks, LibrarySort::Duration);
+        assert_eq!(tracks[0].track.name, "Short");
+        sort_tracks(&mut tracks, LibrarySort::Added);
+        assert_eq!(tracks[0].track.name, "Short");
+    }
+
+    #[test]
+    fn stale_tracks_skips_recent_saves_and_plays() {
+        let mut history = ListeningHistory::default();
+        history
+            .tracks
+            .insert("played".to_string(), "2024-03-01T08:00:00Z".to_string());
+        history
+            .tracks
+            .insert("stale".to_string(), "2023-02-01T08:00:00Z".to_string());
+        let tracks = vec![
+            saved("played", "A", 1000, "2022-01-01T00:00:00Z"),
+            saved("stale", "A", 1000, "2022-01-01T00:00:00Z"),
+            saved("never", "B", 1000, "2023-06-01T00:00:00Z"),
+            saved("new", "B", 1000, "2024-02-01T00:00:00Z"),
+        ];
+        let stale = stale_tracks(tracks, &history, "2024-01-01");
+        let ids: Vec<&str> = stale.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["stale", "never"]);
+        assert_eq!(stale[1].artists, vec!["B".to_string()]);
+    }
+}
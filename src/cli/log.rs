@@ -0,0 +1,52 @@
+//! Operations log commands.
+use anyhow::bail;
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::oplog;
+use crate::error::{ErrorKind, Result};
+
+#[derive(Subcommand, Debug)]
+pub enum LogCommand {
+    List {
+        #[arg(long, default_value_t = 20, help = "Show the most recent N entries")]
+        limit: usize,
+    },
+    Undo {
+        #[arg(help = "Entry id from `log list`")]
+        id: u64,
+    },
+}
+
+pub fn handle(command: LogCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        LogCommand::List { limit } => list(ctx, limit),
+        LogCommand::Undo { id } => undo(ctx, id),
+    }
+}
+
+fn list(ctx: &AppContext, limit: usize) -> Result<()> {
+    let entries = ctx.cache.oplog_store().load()?;
+    let skip = entries.len().saturating_sub(limit);
+    let entries = entries.into_iter().skip(skip).collect();
+    ctx.output.oplog_list(entries)
+}
+
+fn undo(ctx: &AppContext, id: u64) -> Result<()> {
+    let store = ctx.cache.oplog_store();
+    let Some(entry) = store.get(id)? else {
+        bail!(ErrorKind::NotFound.error(format!("no log entry #{id}")));
+    };
+    if store.is_undone(id)? {
+        bail!(ErrorKind::Validation.error(format!("log entry #{id} was already undone")));
+    }
+
+    let Some(inverse) = entry.operation.inverse() else {
+        bail!(
+            ErrorKind::Unsupported.error(format!("{} cannot be undone", entry.operation.label()))
+        );
+    };
+    oplog::perform_undoing(ctx, inverse.clone(), Some(id))?;
+    let message = format!("Undone #{}: {} {}", id, inverse.label(), inverse.target());
+    ctx.output.action("log_undo", &message)
+}
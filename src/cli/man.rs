@@ -0,0 +1,282 @@
+//! Man page generation from the clap definitions.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::{Arg, Args, Command};
+
+use crate::error::Result;
+
+#[derive(Args, Debug)]
+pub struct ManCommand {
+    #[arg(
+        long,
+        value_name = "DIR",
+        default_value = ".",
+        help = "Directory to write the section 1 pages to"
+    )]
+    output_dir: PathBuf,
+}
+
+pub fn handle(command: ManCommand) -> Result<()> {
+    let mut root = super::cli_command();
+    root.build();
+    fs::create_dir_all(&command.output_dir).with_context(|| {
+        format!(
+            "unable to create man page directory {}",
+            command.output_dir.display()
+        )
+    })?;
+    let mut pages = Vec::new();
+    collect_pages(&root, &mut pages);
+    for (name, page) in pages {
+        let path = command.output_dir.join(format!("{name}.1"));
+        write_page(&path, &page)?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+fn write_page(path: &Path, page: &str) -> Result<()> {
+    fs::write(path, page).with_context(|| format!("unable to write {}", path.display()))?;
+    Ok(())
+}
+
+/// One page per visible command, named like `spotify-cli-playlist-stats`.
+fn collect_pages(command: &Command, pages: &mut Vec<(String, String)>) {
+    pages.push((page_name(command), render(command)));
+    for sub in visible_subcommands(command) {
+        collect_pages(sub, pages);
+    }
+}
+
+fn visible_subcommands(command: &Command) -> impl Iterator<Item = &Command> {
+    command.get_subcommands().filter(|sub| !sub.is_hide_set())
+}
+
+fn page_name(command: &Command) -> String {
+    command
+        .get_bin_name()
+        .unwrap_or_else(|| command.get_name())
+        .replace(' ', "-")
+}
+
+fn render(command: &Command) -> String {
+    let name = page_name(command);
+    // Global options are documented once, on the top-level page.
+    let is_root = command.get_bin_name() == Some(command.get_name());
+    let invocation = command.get_bin_name().unwrap_or_else(|| command.get_name());
+    let about = command
+        .get_about()
+        .map(|about| about.to_string())
+        .unwrap_or_else(|| format!("{} command", command.get_name()));
+
+    let mut page = format!(
+        ".TH {} 1 \"\" \"spotify-cli {}\"\n",
+        escape(&name.to_uppercase()),
+        env!("CARGO_PKG_VERSION")
+    );
+    page.push_str(&format!(
+        ".SH NAME\n{} \\- {}\n",
+        escape(&name),
+        escape(&about)
+    ));
+    page.push_str(&format!(
+        ".SH SYNOPSIS\n\\fB{}\\fR{}\n",
+        escape(invocation),
+        escape(&synopsis(command))
+    ));
+    if let Some(long) = command.get_long_about() {
+        page.push_str(&format!(".SH DESCRIPTION\n{}\n", escape(&long.to_string())));
+    }
+
+    let positionals: Vec<&Arg> = command
+        .get_positionals()
+        .filter(|arg| !arg.is_hide_set())
+        .collect();
+    if !positionals.is_empty() {
+        page.push_str(".SH ARGUMENTS\n");
+        for arg in positionals {
+            push_item(&mut page, &format!("<{}>", value_name(arg)), arg);
+        }
+    }
+
+    let options: Vec<&Arg> = command
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && !arg.is_hide_set())
+        .filter(|arg| is_root || !arg.is_global_set())
+        .collect();
+    if !options.is_empty() {
+        page.push_str(".SH OPTIONS\n");
+        for arg in options {
+            push_item(&mut page, &option_label(arg), arg);
+        }
+    }
+
+    let subcommands: Vec<&Command> = visible_subcommands(command).collect();
+    let mut see_also = Vec::new();
+    if !is_root {
+        see_also.push("\\fBspotify\\-cli\\fR(1)".to_string());
+    }
+    if !subcommands.is_empty() {
+        page.push_str(".SH COMMANDS\n");
+        for sub in &subcommands {
+            page.push_str(&format!(".TP\n\\fB{}\\fR\n", escape(sub.get_name())));
+            let about = sub.get_about().map(|about| about.to_string());
+            page.push_str(&format!(
+                "{}\n",
+                escape(&about.unwrap_or_else(|| format!("See {}.", page_name(sub))))
+            ));
+        }
+        see_also.extend(
+            subcommands
+                .iter()
+                .map(|sub| format!("\\fB{}\\fR(1)", escape(&page_name(sub)))),
+        );
+    }
+    if !see_also.is_empty() {
+        page.push_str(&format!(".SH SEE ALSO\n{}\n", see_also.join(", ")));
+    }
+    page
+}
+
+fn synopsis(command: &Command) -> String {
+    let mut parts = vec![" [OPTIONS]".to_string()];
+    for arg in command.get_positionals().filter(|arg| !arg.is_hide_set()) {
+        if arg.is_required_set() {
+            parts.push(format!(" <{}>", value_name(arg)));
+        } else {
+            parts.push(format!(" [{}]", value_name(arg)));
+        }
+    }
+    if command.has_subcommands() {
+        parts.push(" <COMMAND>".to_string());
+    }
+    parts.concat()
+}
+
+fn push_item(page: &mut String, label: &str, arg: &Arg) {
+    page.push_str(&format!(".TP\n\\fB{}\\fR\n", escape(label)));
+    let mut help = arg
+        .get_help()
+        .map(|help| help.to_string())
+        .unwrap_or_default();
+    let values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    if !values.is_empty() {
+        help.push_str(&format!(" [possible values: {}]", values.join(", ")));
+    }
+    let defaults: Vec<String> = arg
+        .get_default_values()
+        .iter()
+        .filter(|_| arg.get_action().takes_values())
+        .map(|value| value.to_string_lossy().into_owned())
+        .collect();
+    if !defaults.is_empty() {
+        help.push_str(&format!(" [default: {}]", defaults.join(", ")));
+    }
+    page.push_str(&format!("{}\n", escape(help.trim())));
+}
+
+fn option_label(arg: &Arg) -> String {
+    let mut names = Vec::new();
+    if let Some(short) = arg.get_short() {
+        names.push(format!("-{short}"));
+    }
+    if let Some(long) = arg.get_long() {
+        names.push(format!("--{long}"));
+    }
+    let mut label = names.join(", ");
+    if arg.get_action().takes_values() {
+        label.push_str(&format!(" <{}>", value_name(arg)));
+    }
+    label
+}
+
+fn value_name(arg: &Arg) -> String {
+    arg.get_value_names()
+        .and_then(|names| names.first())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| arg.get_id().as_str().to_uppercase())
+}
+
+/// Escape backslashes and dashes, and keep leading dots or quotes from reading as requests.
+fn escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    escaped
+        .lines()
+        .map(|line| {
+            if line.starts_with('.') || line.starts_with('\'') {
+                format!("\\&{line}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_pages, escape, render};
+    use clap::{Arg, ArgAction, Command};
+
+    fn sample() -> Command {
+        let mut command = Command::new("spotify-cli")
+            .about("Terminal-first Spotify control surface")
+            .disable_help_subcommand(true)
+            .arg(
+                Arg::new("json")
+                    .long("json")
+                    .action(ArgAction::SetTrue)
+                    .help("Output JSON"),
+            )
+            .subcommand(
+                Command::new("queue").arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .value_name("N")
+                        .default_value("10")
+                        .help("Maximum items"),
+                ),
+            )
+            .subcommand(Command::new("__complete").hide(true));
+        command.build();
+        command
+    }
+
+    #[test]
+    fn render_lists_options_and_commands() {
+        let page = render(&sample());
+        assert!(page.starts_with(".TH SPOTIFY\\-CLI 1 "));
+        assert!(
+            page.contains(".SH NAME\nspotify\\-cli \\- Terminal\\-first Spotify control surface\n")
+        );
+        assert!(page.contains(".TP\n\\fB\\-\\-json\\fR\nOutput JSON\n"));
+        assert!(page.contains(".SH COMMANDS\n.TP\n\\fBqueue\\fR\n"));
+        assert!(!page.contains("__complete"));
+    }
+
+    #[test]
+    fn collect_pages_names_subcommand_pages() {
+        let mut pages = Vec::new();
+        collect_pages(&sample(), &mut pages);
+        let names: Vec<&str> = pages.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["spotify-cli", "spotify-cli-queue"]);
+        assert!(
+            pages[1]
+                .1
+                .contains("\\fB\\-\\-limit <N>\\fR\nMaximum items [default: 10]\n")
+        );
+    }
+
+    #[test]
+    fn escape_guards_control_lines() {
+        assert_eq!(escape(".hidden\n'quoted"), "\\&.hidden\n\\&'quoted");
+        assert_eq!(escape("a\\b"), "a\\eb");
+    }
+}
@@ -0,0 +1,135 @@
+//! Market listing and availability checks.
+use clap::{Args, Subcommand};
+
+use crate::AppContext;
+use crate::action::target;
+use crate::cli::play::{ResourceType, parse_resource};
+use crate::constants::country_name;
+use crate::domain::market::{Market, MarketAvailability};
+use crate::domain::search::SearchType;
+use crate::error::Result;
+use crate::spotify::base;
+
+#[derive(Args, Debug)]
+pub struct MarketsCommand {
+    #[arg(
+        long,
+        global = true,
+        value_name = "PREFIX",
+        help = "Only markets whose code or country name starts with PREFIX"
+    )]
+    filter: Option<String>,
+    #[command(subcommand)]
+    action: Option<MarketsAction>,
+}
+
+#[derive(Subcommand, Debug)]
+enum MarketsAction {
+    Check {
+        #[arg(
+            value_name = "TARGET",
+            help = "Track or album URI, URL, or id (bare ids are tracks), or `now`"
+        )]
+        target: String,
+    },
+}
+
+pub fn handle(command: MarketsCommand, ctx: &AppContext) -> Result<()> {
+    let filter = command.filter.as_deref();
+    match command.action {
+        None => list(ctx, filter),
+        Some(MarketsAction::Check { target }) => check(ctx, &target, filter),
+    }
+}
+
+fn list(ctx: &AppContext, filter: Option<&str>) -> Result<()> {
+    let codes = ctx.spotify()?.markets().list()?;
+    ctx.output.market_list(markets(codes, filter))
+}
+
+fn check(ctx: &AppContext, input: &str, filter: Option<&str>) -> Result<()> {
+    let (kind, id) = check_target(ctx, input)?;
+    let (name, codes) = ctx.spotify()?.markets().availability(kind, &id)?;
+    let current = base::market().map(|market| {
+        let market = market.to_ascii_uppercase();
+        let playable = codes.iter().any(|code| code.eq_ignore_ascii_case(&market));
+        (market, playable)
+    });
+    ctx.output.market_availability(MarketAvailability {
+        uri: format!("spotify:{kind}:{id}"),
+        name,
+        markets: markets(codes, filter),
+        current,
+    })
+}
+
+/// Resource kind and id for `markets check`: a track or album.
+fn check_target(ctx: &AppContext, input: &str) -> Result<(&'static str, String)> {
+    let input = target::resolve_target(ctx, input, SearchType::Track)?;
+    let resource = parse_resource(&input, &[ResourceType::Track, ResourceType::Album])?;
+    Ok((resource.resource_type.as_str(), resource.id))
+}
+
+/// Sorted markets with country names, keeping those that match `filter`.
+fn markets(mut codes: Vec<String>, filter: Option<&str>) -> Vec<Market> {
+    codes.sort();
+    codes
+        .into_iter()
+        .map(|code| Market {
+            name: country_name(&code).map(str::to_string),
+            code,
+        })
+        .filter(|market| filter.is_none_or(|prefix| matches_prefix(market, prefix)))
+        .collect()
+}
+
+/// Case-insensitive prefix match on the code or the country name.
+fn matches_prefix(market: &Market, prefix: &str) -> bool {
+    let prefix = prefix.trim().to_lowercase();
+    market.code.to_lowercase().starts_with(&prefix)
+        || market
+            .name
+            .as_deref()
+            .is_some_and(|name| name.to_lowercase().starts_with(&prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::markets;
+
+    fn codes(items: &[&str]) -> Vec<String> {
+        items.iter().map(|code| code.to_string()).collect()
+    }
+
+    #[test]
+    fn markets_are_sorted_and_named() {
+        let listed = markets(codes(&["SE", "AD", "QQ"]), None);
+        let listed: Vec<(&str, Option<&str>)> = listed
+            .iter()
+            .map(|market| (market.code.as_str(), market.name.as_deref()))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("AD", Some("Andorra")),
+                ("QQ", None),
+                ("SE", Some("Sweden"))
+            ]
+        );
+    }
+
+    #[test]
+    fn markets_filter_matches_code_or_name_prefix() {
+        let all = codes(&["DE", "DK", "SE", "GB"]);
+        let by_code: Vec<String> = markets(all.clone(), Some("d"))
+            .into_iter()
+            .map(|market| market.code)
+            .collect();
+        assert_eq!(by_code, vec!["DE", "DK"]);
+        let by_name: Vec<String> = markets(all, Some("united"))
+            .into_iter()
+            .map(|market| market.code)
+            .collect();
+        assert_eq!(by_name, vec!["GB"]);
+    }
+}
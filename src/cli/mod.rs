@@ -1,67 +1,256 @@
 //! CLI parsing and command dispatch.
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::bail;
+use clap::{CommandFactory, Parser, Subcommand};
 
 use crate::AppContext;
+use crate::cli::album::{AlbumCommand, handle as handle_album};
+use crate::cli::alias::{AliasCommand, handle as handle_alias};
+use crate::cli::audiobook::{AudiobookCommand, handle as handle_audiobook};
 use crate::cli::auth::{AuthCommand, handle as handle_auth};
+use crate::cli::block::{BlockCommand, handle as handle_block};
+use crate::cli::complete::{CompleteCommand, handle as handle_complete};
 use crate::cli::completions::{CompletionsCommand, handle as handle_completions};
+use crate::cli::config::{ConfigCommand, handle as handle_config};
 use crate::cli::device::{DeviceCommand, handle as handle_device};
+use crate::cli::episode::{EpisodeCommand, handle as handle_episode};
+use crate::cli::follow::{FollowCommand, handle as handle_follow};
 use crate::cli::help::{HelpCommand, handle as handle_help};
+use crate::cli::hook::{HookCommand, handle as handle_hook};
 use crate::cli::info::{InfoCommand, handle as handle_info};
+use crate::cli::library::{LibraryCommand, handle as handle_library};
+use crate::cli::log::{LogCommand, handle as handle_log};
+use crate::cli::man::{ManCommand, handle as handle_man};
+use crate::cli::markets::{MarketsCommand, handle as handle_markets};
 use crate::cli::now_playing::{NowPlayingCommand, handle as handle_now_playing};
+use crate::cli::open::{OpenCommand, handle as handle_open};
+use crate::cli::pending::{PendingCommand, handle as handle_pending};
 use crate::cli::pin::{PinCommand, handle as handle_pin};
 use crate::cli::play::{PlayCommand, handle as handle_play};
 use crate::cli::player::{PlayerCommand, handle as handle_player};
 use crate::cli::playlist::{PlaylistCommand, handle as handle_playlist};
 use crate::cli::queue::{QueueCommand, handle as handle_queue};
 use crate::cli::recently_played::{RecentlyPlayedCommand, handle as handle_recently_played};
+use crate::cli::releases::{ReleasesCommand, handle as handle_releases};
 use crate::cli::search::{SearchCommand, handle as handle_search};
+use crate::cli::session::{SessionCommand, handle as handle_session};
+use crate::cli::show::{ShowCommand, handle as handle_show};
 use crate::cli::sync::{SyncCommand, handle as handle_sync};
-use crate::error::Result;
+use crate::cli::track::{TrackCommand, handle as handle_track};
+use crate::cli::user::{UserCommand, handle as handle_user};
+use crate::error::{ErrorKind, Result};
 
+pub mod album;
+pub mod alias;
+pub mod audiobook;
 pub mod auth;
+pub mod block;
+pub mod complete;
 pub mod completions;
+pub mod config;
 pub mod device;
+pub mod episode;
+pub mod follow;
 pub mod help;
+pub mod hook;
 pub mod info;
+pub mod library;
+pub mod log;
+pub mod man;
+pub mod markets;
 pub mod now_playing;
+pub mod open;
+pub mod pending;
 pub mod pin;
 pub mod play;
 pub mod player;
 pub mod playlist;
 pub mod queue;
 pub mod recently_played;
+pub mod releases;
 pub mod search;
+pub mod session;
+pub mod show;
 pub mod sync;
+pub mod track;
+pub mod user;
 
 /// Parsed CLI configuration plus resolved command.
 #[derive(Debug)]
 pub struct ParsedCli {
     pub json: bool,
+    pub json_envelope: bool,
+    pub fields: Vec<String>,
+    pub defer_offline: bool,
+    pub market: Option<String>,
+    pub no_explicit: bool,
+    pub insecure: bool,
+    pub trace_http: bool,
+    pub har: Option<PathBuf>,
+    pub dry_run: bool,
+    pub yes: bool,
+    pub max_width: Option<usize>,
+    pub no_trunc: bool,
+    pub column_widths: Vec<(String, usize)>,
+    pub timeout: Option<Duration>,
     pub command: Command,
 }
 
 #[derive(Parser)]
-#[command(name = "spotify-cli", disable_help_subcommand = true, version)]
+#[command(
+    name = "spotify-cli",
+    disable_help_subcommand = true,
+    arg_required_else_help = true,
+    version
+)]
 #[command(about = "Terminal-first Spotify control surface")]
 struct Cli {
     #[arg(long, global = true, help = "Output JSON")]
     json: bool,
+    #[arg(
+        long,
+        global = true,
+        requires = "json",
+        help = "Wrap JSON output in a versioned {schema_version, kind, data} envelope"
+    )]
+    json_envelope: bool,
+    #[arg(
+        long,
+        exclusive = true,
+        help = "Print the JSON Schema of every --json-envelope payload and exit"
+    )]
+    json_schema: bool,
+    #[arg(
+        long,
+        global = true,
+        value_delimiter = ',',
+        value_name = "FIELDS",
+        requires = "json",
+        help = "Project JSON output to comma-separated dotted paths"
+    )]
+    fields: Vec<String>,
+    #[arg(
+        long,
+        global = true,
+        help = "Journal supported mutations locally when Spotify is unreachable"
+    )]
+    defer_offline: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "CODE",
+        help = "Market (ISO country code) for playability and relinking"
+    )]
+    market: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        help = "Hide explicit tracks from search, radio, and top track results"
+    )]
+    no_explicit: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Skip TLS certificate verification (for intercepting proxies)"
+    )]
+    insecure: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Log each HTTP request (method, URL, status, timing, rate limits) to stderr"
+    )]
+    trace_http: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Write the command's HTTP requests to a HAR file (secrets redacted)"
+    )]
+    har: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        help = "Show the Spotify changes a command would make without making them"
+    )]
+    dry_run: bool,
+    #[arg(
+        short = 'y',
+        long,
+        global = true,
+        help = "Answer yes to confirmation prompts, for scripts"
+    )]
+    yes: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Truncate table cells wider than N columns"
+    )]
+    max_width: Option<usize>,
+    #[arg(long, global = true, help = "Never truncate table cells")]
+    no_trunc: bool,
+    #[arg(
+        long = "column-width",
+        global = true,
+        value_name = "COLUMN=N",
+        value_delimiter = ',',
+        value_parser = parse_column_width,
+        help = "Truncation width for a named table column (e.g. track=30)"
+    )]
+    column_widths: Vec<(String, usize)>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Stop the command after SECS seconds (exit code 124)"
+    )]
+    timeout: Option<u64>,
     #[command(subcommand)]
-    command: Command,
+    command: Option<Command>,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    #[command(subcommand)]
+    Album(AlbumCommand),
+    #[command(subcommand)]
+    Alias(AliasCommand),
+    #[command(subcommand)]
+    Audiobook(AudiobookCommand),
     #[command(subcommand)]
     Auth(AuthCommand),
+    #[command(subcommand)]
+    Block(BlockCommand),
     Completions(CompletionsCommand),
+    #[command(name = "__complete", hide = true, subcommand)]
+    Complete(CompleteCommand),
+    #[command(subcommand)]
+    Config(ConfigCommand),
     #[command(subcommand)]
     Device(DeviceCommand),
+    #[command(subcommand)]
+    Episode(EpisodeCommand),
+    #[command(subcommand)]
+    Follow(FollowCommand),
     #[command(name = "help")]
     Help(HelpCommand),
+    #[command(subcommand)]
+    Hook(HookCommand),
     Info(InfoCommand),
+    #[command(subcommand)]
+    Library(LibraryCommand),
+    #[command(subcommand)]
+    Log(LogCommand),
+    Man(ManCommand),
+    Markets(MarketsCommand),
     #[command(name = "nowplaying")]
     NowPlaying(NowPlayingCommand),
+    Open(OpenCommand),
+    #[command(subcommand)]
+    Pending(PendingCommand),
     #[command(subcommand)]
     Pin(PinCommand),
     #[command(hide = true)]
@@ -73,53 +262,196 @@ pub enum Command {
     Queue(QueueCommand),
     #[command(name = "recentlyplayed")]
     RecentlyPlayed(RecentlyPlayedCommand),
+    #[command(subcommand)]
+    Releases(ReleasesCommand),
     Search(SearchCommand),
+    #[command(subcommand)]
+    Session(SessionCommand),
+    #[command(subcommand)]
+    Show(ShowCommand),
     Sync(SyncCommand),
+    #[command(subcommand)]
+    Track(TrackCommand),
+    #[command(subcommand)]
+    User(UserCommand),
+    /// `--json-schema`, which has no subcommand of its own.
+    #[command(skip)]
+    JsonSchema,
 }
 
 pub fn parse() -> ParsedCli {
-    let cli = Cli::parse();
-    ParsedCli {
-        json: cli.json,
-        command: cli.command,
+    parse_from(alias::expand_args(std::env::args().collect()))
+}
+
+/// The clap definition, for man page generation.
+pub(crate) fn cli_command() -> clap::Command {
+    Cli::command()
+}
+
+/// Names of the built-in top-level commands.
+pub fn command_names() -> Vec<String> {
+    Cli::command()
+        .get_subcommands()
+        .map(|command| command.get_name().to_string())
+        .collect()
+}
+
+/// Global `--flags` that take a value, e.g. `--market`.
+pub fn global_value_flags() -> Vec<String> {
+    Cli::command()
+        .get_arguments()
+        .filter(|arg| arg.is_global_set() && arg.get_action().takes_values())
+        .filter_map(|arg| arg.get_long())
+        .map(|long| format!("--{long}"))
+        .collect()
+}
+
+/// Ask a yes/no question before a destructive step; the global `--yes` answers it.
+///
+/// Without a terminal there is nobody to ask, so this fails and suggests `hint`.
+pub(crate) fn confirm(ctx: &AppContext, prompt: &str, hint: &str) -> Result<bool> {
+    use std::io::{IsTerminal, Write};
+
+    if ctx.assume_yes {
+        return Ok(true);
     }
+    if !std::io::stdin().is_terminal() {
+        bail!(ErrorKind::Validation.error(format!("{prompt} needs confirmation; {hint}")));
+    }
+    eprint!("{prompt} [y/N]: ");
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// [`confirm`], failing with "cancelled" on a no.
+pub(crate) fn ensure_confirmed(ctx: &AppContext, prompt: &str, hint: &str) -> Result<()> {
+    if !confirm(ctx, prompt, hint)? {
+        bail!(ErrorKind::Interrupted.error("cancelled"));
+    }
+    Ok(())
+}
+
+/// List numbered `options` and ask for one; `None` when the answer is empty or `s`.
+pub(crate) fn choose(heading: &str, options: &[String], prompt: &str) -> Result<Option<usize>> {
+    use std::io::Write;
+
+    eprintln!("{heading}");
+    for (index, option) in options.iter().enumerate() {
+        eprintln!("  {}) {}", index + 1, option);
+    }
+    eprint!("{prompt} [1-{}, s to skip]: ", options.len());
+    std::io::stderr().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    let answer = answer.trim();
+    if answer.is_empty() || answer.eq_ignore_ascii_case("s") {
+        return Ok(None);
+    }
+    match answer.parse::<usize>() {
+        Ok(choice) if (1..=options.len()).contains(&choice) => Ok(Some(choice - 1)),
+        _ => bail!(ErrorKind::Validation.error(format!("invalid choice: {answer}"))),
+    }
+}
+
+fn parse_column_width(input: &str) -> std::result::Result<(String, usize), String> {
+    let Some((column, width)) = input.split_once('=') else {
+        return Err(format!("expected COLUMN=N, got '{input}'"));
+    };
+    let width = width
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid width '{width}'"))?;
+    Ok((column.trim().to_string(), width))
 }
 
-#[cfg(test)]
 pub(crate) fn parse_from<I, T>(args: I) -> ParsedCli
 where
     I: IntoIterator<Item = T>,
     T: Into<std::ffi::OsString> + Clone,
 {
     let cli = Cli::parse_from(args);
+    let command = match cli.command {
+        Some(_) if cli.json_schema => Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--json-schema cannot be used with a command",
+            )
+            .exit(),
+        Some(command) => command,
+        None if cli.json_schema => Command::JsonSchema,
+        None => Cli::command()
+            .error(
+                clap::error::ErrorKind::MissingSubcommand,
+                "a command is required; see --help",
+            )
+            .exit(),
+    };
     ParsedCli {
         json: cli.json,
-        command: cli.command,
+        json_envelope: cli.json_envelope,
+        fields: cli.fields,
+        defer_offline: cli.defer_offline,
+        market: cli.market,
+        no_explicit: cli.no_explicit,
+        insecure: cli.insecure,
+        trace_http: cli.trace_http,
+        har: cli.har,
+        dry_run: cli.dry_run,
+        yes: cli.yes,
+        max_width: cli.max_width,
+        no_trunc: cli.no_trunc,
+        column_widths: cli.column_widths,
+        timeout: cli.timeout.map(Duration::from_secs),
+        command,
     }
 }
 
 pub fn execute(parsed: ParsedCli, ctx: &AppContext) -> Result<()> {
     match parsed.command {
+        Command::Album(command) => handle_album(command, ctx),
+        Command::Alias(command) => handle_alias(command, ctx),
+        Command::Audiobook(command) => handle_audiobook(command, ctx),
         Command::Auth(command) => handle_auth(command, ctx),
+        Command::Block(command) => handle_block(command, ctx),
+        Command::Complete(command) => handle_complete(command, ctx),
         Command::Completions(command) => handle_completions(command),
+        Command::Config(command) => handle_config(command, ctx),
         Command::Device(command) => handle_device(command, ctx),
+        Command::Episode(command) => handle_episode(command, ctx),
+        Command::Follow(command) => handle_follow(command, ctx),
         Command::Help(command) => handle_help(command, ctx),
+        Command::Hook(command) => handle_hook(command, ctx),
         Command::Info(command) => handle_info(command, ctx),
+        Command::Library(command) => handle_library(command, ctx),
         Command::NowPlaying(command) => handle_now_playing(command, ctx),
+        Command::Log(command) => handle_log(command, ctx),
+        Command::Man(command) => handle_man(command),
+        Command::Open(command) => handle_open(command, ctx),
+        Command::Markets(command) => handle_markets(command, ctx),
+        Command::Pending(command) => handle_pending(command, ctx),
         Command::Pin(command) => handle_pin(command, ctx),
         Command::Play(command) => handle_play(command, ctx),
         Command::Player(command) => handle_player(command, ctx),
         Command::Playlist(command) => handle_playlist(command, ctx),
         Command::Queue(command) => handle_queue(command, ctx),
         Command::RecentlyPlayed(command) => handle_recently_played(command, ctx),
+        Command::Releases(command) => handle_releases(command, ctx),
         Command::Search(command) => handle_search(command, ctx),
+        Command::Session(command) => handle_session(command, ctx),
+        Command::Show(command) => handle_show(command, ctx),
         Command::Sync(command) => handle_sync(command, ctx),
+        Command::Track(command) => handle_track(command, ctx),
+        Command::User(command) => handle_user(command, ctx),
+        Command::JsonSchema => crate::output::schema::print_document(),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Command, parse_from};
+    use super::{Command, CompleteCommand, parse_from};
     use crate::cli::search::SearchCommand;
 
     #[test]
@@ -132,6 +464,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_fields_flag() {
+        let parsed = parse_from([
+            "spotify-cli",
+            "--json",
+            "--fields",
+            "id,items.name",
+            "queue",
+        ]);
+        assert_eq!(parsed.fields, vec!["id", "items.name"]);
+    }
+
+    #[test]
+    fn parse_table_flags() {
+        let parsed = parse_from([
+            "spotify-cli",
+            "--no-trunc",
+            "--max-width",
+            "30",
+            "--column-width",
+            "track=20,ARTIST=12",
+            "queue",
+        ]);
+        assert!(parsed.no_trunc);
+        assert_eq!(parsed.max_width, Some(30));
+        assert_eq!(
+            parsed.column_widths,
+            vec![("track".to_string(), 20), ("ARTIST".to_string(), 12)]
+        );
+    }
+
+    #[test]
+    fn parse_no_explicit_flag() {
+        let parsed = parse_from(["spotify-cli", "search", "track", "boards", "--no-explicit"]);
+        assert!(parsed.no_explicit);
+    }
+
     #[test]
     fn parse_completions_command() {
         let parsed = parse_from(["spotify-cli", "completions", "zsh"]);
@@ -140,4 +509,13 @@ mod tests {
             _ => panic!("expected completions command"),
         }
     }
+
+    #[test]
+    fn parse_hidden_complete_command() {
+        let parsed = parse_from(["spotify-cli", "__complete", "device"]);
+        match parsed.command {
+            Command::Complete(CompleteCommand::Device) => {}
+            _ => panic!("expected __complete device"),
+        }
+    }
 }
@@ -2,8 +2,10 @@
 use clap::{Args, Subcommand};
 
 use crate::AppContext;
+use crate::action::{context, oplog};
 use crate::cli::playlist;
-use crate::error::Result;
+use crate::domain::oplog::LoggedOperation;
+use crate::error::{ErrorKind, Result};
 
 #[derive(Args, Debug)]
 pub struct NowPlayingCommand {
@@ -31,6 +33,8 @@ enum NowPlayingAction {
         pick: Option<usize>,
         #[arg(long, help = "Use the last cached search results")]
         last: bool,
+        #[arg(long, help = "Change a playlist you do not own without asking")]
+        force: bool,
     },
 }
 
@@ -43,7 +47,8 @@ pub fn handle(command: NowPlayingCommand, ctx: &AppContext) -> Result<()> {
             user,
             pick,
             last,
-        }) => playlist::add_to(ctx, query.as_deref(), user, pick, last),
+            force,
+        }) => playlist::add_to(ctx, query.as_deref(), user, pick, last, force),
     }
 }
 
@@ -59,17 +64,24 @@ pub fn show_detailed_with_delay(ctx: &AppContext, delay_ms: u64) -> Result<()> {
     if delay_ms > 0 {
         std::thread::sleep(std::time::Duration::from_millis(delay_ms));
     }
-    let status = ctx.spotify()?.playback().status()?;
+    let mut status = ctx.spotify()?.playback().status()?;
+    if let Some(playing_from) = status.context.as_mut() {
+        context::resolve(ctx, playing_from);
+    }
+    context::remember(ctx, &status);
     ctx.output.player_status(status)
 }
 
 fn like(ctx: &AppContext) -> Result<()> {
     let status = ctx.spotify()?.playback().status()?;
     let Some(track) = status.track else {
-        anyhow::bail!("no track is currently playing");
+        anyhow::bail!(ErrorKind::NotFound.error("no track is currently playing"));
     };
 
-    ctx.spotify()?.track().like(&track.id)?;
+    let operation = LoggedOperation::LibrarySave {
+        ids: vec![track.id.clone()],
+    };
+    oplog::perform(ctx, operation)?;
     let message = format!("Liked: {}", format_track(&track));
     ctx.output.action("track_like", &message)
 }
@@ -0,0 +1,105 @@
+//! Open a Spotify resource in the desktop app or web player.
+use anyhow::bail;
+use clap::Args;
+
+use crate::AppContext;
+use crate::action::target;
+use crate::cli::play::{SpotifyResource, parse_spotify_url};
+use crate::domain::search::SearchType;
+use crate::error::{ErrorKind, Result};
+
+#[derive(Args, Debug)]
+pub struct OpenCommand {
+    #[arg(
+        value_name = "TARGET",
+        help = "Spotify URI or URL, pin alias, or `now` for the playing track"
+    )]
+    target: String,
+    #[arg(long, conflicts_with = "web", help = "Open in the desktop app")]
+    app: bool,
+    #[arg(long, help = "Open in the web player")]
+    web: bool,
+}
+
+/// Where `open` sends a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpenIn {
+    App,
+    Web,
+}
+
+pub fn handle(command: OpenCommand, ctx: &AppContext) -> Result<()> {
+    let open_in = if command.app {
+        OpenIn::App
+    } else if command.web {
+        OpenIn::Web
+    } else {
+        let settings = ctx.cache.metadata_store().load()?.settings;
+        match settings.open_in.as_deref() {
+            Some("app") => OpenIn::App,
+            _ => OpenIn::Web,
+        }
+    };
+
+    let input = target::resolve_pin(ctx, &command.target)?;
+    let input = target::resolve_target(ctx, &input, SearchType::Track)?;
+    let Some(resource) = parse_spotify_url(&input) else {
+        bail!(ErrorKind::Validation.error(format!(
+            "not a Spotify URI, URL, or pin: {}",
+            command.target
+        )));
+    };
+
+    let link = link_for(&resource, open_in);
+    launch(&link)?;
+    ctx.output.action("open", &format!("Opened {link}"))
+}
+
+fn link_for(resource: &SpotifyResource, open_in: OpenIn) -> String {
+    match open_in {
+        OpenIn::App => resource.to_uri(),
+        OpenIn::Web => format!(
+            "https://open.spotify.com/{}/{}",
+            resource.resource_type.as_str(),
+            resource.id
+        ),
+    }
+}
+
+/// Hand a URL or `spotify:` URI to the platform opener.
+pub(crate) fn launch(target: &str) -> Result<()> {
+    use std::process::Command;
+
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command.arg(target).status()?;
+    if !status.success() {
+        bail!(ErrorKind::General.error(format!("opener exited with {status} for {target}")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OpenIn, link_for};
+    use crate::cli::play::parse_spotify_url;
+
+    #[test]
+    fn link_for_picks_uri_or_web_url() {
+        let resource = parse_spotify_url("https://open.spotify.com/album/abc?si=1").unwrap();
+        assert_eq!(link_for(&resource, OpenIn::App), "spotify:album:abc");
+
+        let resource = parse_spotify_url("spotify:user:alice:playlist:p1").unwrap();
+        assert_eq!(
+            link_for(&resource, OpenIn::Web),
+            "https://open.spotify.com/playlist/p1"
+        );
+    }
+}
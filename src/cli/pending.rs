@@ -0,0 +1,41 @@
+//! Pending (offline) mutation journal commands.
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::pending;
+use crate::error::Result;
+
+#[derive(Subcommand, Debug)]
+pub enum PendingCommand {
+    List,
+    Flush,
+    Clear,
+}
+
+pub fn handle(command: PendingCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        PendingCommand::List => list(ctx),
+        PendingCommand::Flush => flush(ctx),
+        PendingCommand::Clear => clear(ctx),
+    }
+}
+
+fn list(ctx: &AppContext) -> Result<()> {
+    let journal = ctx.cache.pending_store().load()?;
+    ctx.output.pending_list(journal.items)
+}
+
+fn flush(ctx: &AppContext) -> Result<()> {
+    let flushed = pending::flush(ctx)?;
+    let message = format!(
+        "Flushed: applied={} dropped={} remaining={}",
+        flushed.applied, flushed.dropped, flushed.remaining
+    );
+    ctx.output.action("pending_flush", &message)
+}
+
+fn clear(ctx: &AppContext) -> Result<()> {
+    let cleared = ctx.cache.pending_store().clear()?;
+    let message = format!("Cleared: {} pending", cleared);
+    ctx.output.action("pending_clear", &message)
+}
@@ -1,25 +1,64 @@
 //! Pin command handlers.
+use anyhow::bail;
 use clap::Subcommand;
 
 use crate::AppContext;
-use crate::error::Result;
+use crate::action::target;
+use crate::cli::choose;
+use crate::domain::pin::{PinnedPlaylist, normalize_tag};
+use crate::domain::search::SearchType;
+use crate::error::{ErrorKind, Result};
 
 #[derive(Subcommand, Debug)]
 pub enum PinCommand {
-    Add { name: String, url: String },
-    Remove { name: String },
-    List,
+    Add {
+        name: String,
+        url: String,
+    },
+    Remove {
+        name: String,
+    },
+    Rename {
+        old: String,
+        new: String,
+    },
+    /// With `--yes`, keeps the first alias in each group without prompting.
+    #[command(name = "dedupe-aliases")]
+    DedupeAliases,
+    List {
+        #[arg(long, value_name = "TAG", help = "Only pins with this tag")]
+        tag: Option<String>,
+    },
+    Tag {
+        #[command(subcommand)]
+        action: PinTagAction,
+    },
+    Tags,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PinTagAction {
+    Add { name: String, tag: String },
+    Remove { name: String, tag: String },
 }
 
 pub fn handle(command: PinCommand, ctx: &AppContext) -> Result<()> {
     match command {
         PinCommand::Add { name, url } => add(ctx, name, url),
         PinCommand::Remove { name } => remove(ctx, &name),
-        PinCommand::List => list(ctx),
+        PinCommand::Rename { old, new } => rename(ctx, &old, new),
+        PinCommand::DedupeAliases => dedupe_aliases(ctx),
+        PinCommand::List { tag } => list(ctx, tag.as_deref()),
+        PinCommand::Tag { action } => match action {
+            PinTagAction::Add { name, tag } => tag_add(ctx, &name, &tag),
+            PinTagAction::Remove { name, tag } => tag_remove(ctx, &name, &tag),
+        },
+        PinCommand::Tags => tags(ctx),
     }
 }
 
 fn add(ctx: &AppContext, name: String, url: String) -> Result<()> {
+    let url = target::resolve_target(ctx, &url, SearchType::Playlist)?;
     ctx.cache.pin_store().add(name.clone(), url.clone())?;
     let message = format!("Pinned: {} -> {}", name, url);
     ctx.output.action("pin_add", &message)
@@ -35,7 +74,105 @@ fn remove(ctx: &AppContext, name: &str) -> Result<()> {
     ctx.output.action("pin_remove", &message)
 }
 
-fn list(ctx: &AppContext) -> Result<()> {
+fn rename(ctx: &AppContext, old: &str, new: String) -> Result<()> {
+    let renamed = ctx.cache.pin_store().rename(old, new.clone())?;
+    let message = if renamed {
+        format!("Renamed pin: {} -> {}", old, new)
+    } else {
+        format!("No pin found: {}", old)
+    };
+    ctx.output.action("pin_rename", &message)
+}
+
+fn dedupe_aliases(ctx: &AppContext) -> Result<()> {
+    let store = ctx.cache.pin_store();
+    let groups = store.load()?.collisions();
+    if groups.is_empty() {
+        return ctx.output.action("pin_dedupe", "No duplicate aliases");
+    }
+
+    let mut merged = 0;
+    for group in groups {
+        let keep = if ctx.assume_yes {
+            Some(0)
+        } else {
+            prompt_keep(&group)?
+        };
+        let Some(keep) = keep else {
+            continue;
+        };
+        let names: Vec<String> = group.iter().map(|pin| pin.name.clone()).collect();
+        store.merge(&names[keep], &names)?;
+        merged += names.len() - 1;
+        let message = format!("Merged: {} -> {}", names.join(", "), names[keep]);
+        ctx.output.action("pin_merge", &message)?;
+    }
+    let message = format!("Removed {} duplicate aliases", merged);
+    ctx.output.action("pin_dedupe", &message)
+}
+
+/// Ask which alias to keep; `None` skips the group.
+fn prompt_keep(group: &[PinnedPlaylist]) -> Result<Option<usize>> {
+    let options: Vec<String> = group
+        .iter()
+        .map(|pin| format!("{} -> {}", pin.name, pin.url))
+        .collect();
+    choose("Possible duplicates:", &options, "Keep which alias?")
+}
+
+fn list(ctx: &AppContext, tag: Option<&str>) -> Result<()> {
     let pins = ctx.cache.pin_store().load()?;
-    ctx.output.pin_list(pins.items)
+    let items = match tag {
+        Some(tag) => pins.tagged(tag),
+        None => pins.items,
+    };
+    ctx.output.pin_list(items)
+}
+
+fn tag_add(ctx: &AppContext, name: &str, tag: &str) -> Result<()> {
+    let tag = valid_tag(tag)?;
+    if !ctx.cache.pin_store().tag(name, &tag)? {
+        bail!(ErrorKind::NotFound.error(format!("no pin found: {name}")));
+    }
+    let message = format!("Tagged: {} +{}", name, tag);
+    ctx.output.action("pin_tag_add", &message)
+}
+
+fn tag_remove(ctx: &AppContext, name: &str, tag: &str) -> Result<()> {
+    let tag = valid_tag(tag)?;
+    if !ctx.cache.pin_store().untag(name, &tag)? {
+        bail!(ErrorKind::NotFound.error(format!("no pin found: {name}")));
+    }
+    let message = format!("Untagged: {} -{}", name, tag);
+    ctx.output.action("pin_tag_remove", &message)
+}
+
+fn tags(ctx: &AppContext) -> Result<()> {
+    let pins = ctx.cache.pin_store().load()?;
+    ctx.output.pin_tags(pins.tag_counts())
+}
+
+/// A tag is one word so `pin list --tag` and the TAGS column stay unambiguous.
+fn valid_tag(tag: &str) -> Result<String> {
+    let tag = normalize_tag(tag);
+    if tag.is_empty() || tag.contains(char::is_whitespace) || tag.contains(',') {
+        bail!(
+            ErrorKind::Validation
+                .error(format!("invalid tag '{tag}'; use one word without commas"))
+        );
+    }
+    Ok(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::valid_tag;
+
+    #[test]
+    fn valid_tag_normalizes_and_rejects_separators() {
+        assert_eq!(valid_tag(" Workout ").unwrap(), "workout");
+        assert!(valid_tag("deep focus").is_err());
+        assert!(valid_tag("a,b").is_err());
+        assert!(valid_tag("  ").is_err());
+    }
 }
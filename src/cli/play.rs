@@ -1,8 +1,13 @@
 //! Hidden play command for direct URL/URI playback.
+use anyhow::bail;
 use clap::Args;
 
 use crate::AppContext;
-use crate::error::Result;
+use crate::action::hooks;
+use crate::action::target;
+use crate::cli::now_playing;
+use crate::domain::search::SearchType;
+use crate::error::{ErrorKind, Result};
 
 #[derive(Args, Debug)]
 pub struct PlayCommand {
@@ -12,20 +17,39 @@ pub struct PlayCommand {
 
 /// Supported Spotify resource types for playback.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ResourceType {
+pub(crate) enum ResourceType {
     Track,
     Playlist,
     Album,
     Artist,
+    Episode,
+    Show,
+    Audiobook,
 }
 
 impl ResourceType {
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             ResourceType::Track => "track",
             ResourceType::Playlist => "playlist",
             ResourceType::Album => "album",
             ResourceType::Artist => "artist",
+            ResourceType::Episode => "episode",
+            ResourceType::Show => "show",
+            ResourceType::Audiobook => "audiobook",
+        }
+    }
+
+    /// "a track", "an album", ... for error messages.
+    pub(crate) fn with_article(&self) -> &'static str {
+        match self {
+            ResourceType::Track => "a track",
+            ResourceType::Playlist => "a playlist",
+            ResourceType::Album => "an album",
+            ResourceType::Artist => "an artist",
+            ResourceType::Episode => "an episode",
+            ResourceType::Show => "a show",
+            ResourceType::Audiobook => "an audiobook",
         }
     }
 
@@ -35,30 +59,63 @@ impl ResourceType {
             "playlist" => Some(ResourceType::Playlist),
             "album" => Some(ResourceType::Album),
             "artist" => Some(ResourceType::Artist),
+            "episode" => Some(ResourceType::Episode),
+            "show" => Some(ResourceType::Show),
+            "audiobook" => Some(ResourceType::Audiobook),
             _ => None,
         }
     }
 }
 
+/// How a resource was written, for error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ResourceForm {
+    Uri,
+    Url,
+    Id,
+}
+
 /// Parsed Spotify resource with type and ID.
-struct SpotifyResource {
-    resource_type: ResourceType,
-    id: String,
+pub(crate) struct SpotifyResource {
+    pub(crate) resource_type: ResourceType,
+    pub(crate) id: String,
+    pub(crate) form: ResourceForm,
 }
 
 impl SpotifyResource {
-    fn to_uri(&self) -> String {
+    pub(crate) fn to_uri(&self) -> String {
         format!("spotify:{}:{}", self.resource_type.as_str(), self.id)
     }
+
+    /// Error for a resource of a kind the command does not take, e.g.
+    /// "that's an album URL but this command needs a track".
+    pub(crate) fn wrong_kind(&self, accepted: &[ResourceType]) -> anyhow::Error {
+        let form = match self.form {
+            ResourceForm::Uri => "URI",
+            ResourceForm::Url => "URL",
+            ResourceForm::Id => "id",
+        };
+        let needed: Vec<&str> = accepted.iter().map(|kind| kind.with_article()).collect();
+        ErrorKind::Validation
+            .error(format!(
+                "that's {} {form} but this command needs {}",
+                self.resource_type.with_article(),
+                needed.join(" or ")
+            ))
+            .into()
+    }
 }
 
 pub fn handle(command: PlayCommand, ctx: &AppContext) -> Result<()> {
-    let resource = parse_spotify_url(&command.url)
-        .ok_or_else(|| anyhow::anyhow!("invalid Spotify URL or URI: {}", command.url))?;
+    let url = target::resolve_target(ctx, &command.url, SearchType::Track)?;
+    let resource = parse_spotify_url(&url).ok_or_else(|| {
+        ErrorKind::Validation.error(format!("invalid Spotify URL or URI: {}", command.url))
+    })?;
 
     let playback = ctx.spotify()?.playback();
     let uri = resource.to_uri();
 
+    hooks::pre_play(ctx, Some(&uri))?;
     match resource.resource_type {
         ResourceType::Track => {
             playback.play_track(&uri)?;
@@ -80,18 +137,103 @@ pub fn handle(command: PlayCommand, ctx: &AppContext) -> Result<()> {
             ctx.output
                 .action("play", &format!("Playing artist {}", resource.id))?;
         }
+        ResourceType::Episode => {
+            playback.play_track(&uri)?;
+            ctx.output
+                .action("play", &format!("Playing episode {}", resource.id))?;
+        }
+        ResourceType::Show => {
+            playback.play_context(&uri)?;
+            ctx.output
+                .action("play", &format!("Playing show {}", resource.id))?;
+        }
+        ResourceType::Audiobook => {
+            playback.play_context(&uri)?;
+            ctx.output
+                .action("play", &format!("Playing audiobook {}", resource.id))?;
+        }
     }
+    hooks::track_changed(ctx);
 
     Ok(())
 }
 
+/// Start a playlist, album, or show, shuffled or from its `from_track`th item (1-based).
+///
+/// Spotify only takes a starting item for playlists and albums.
+pub(crate) fn start_context(
+    ctx: &AppContext,
+    uri: &str,
+    shuffle: bool,
+    from_track: Option<u32>,
+) -> Result<()> {
+    let playback = ctx.spotify()?.playback();
+    hooks::pre_play(ctx, Some(uri))?;
+    if shuffle {
+        playback.shuffle(true)?;
+    }
+    match from_track {
+        Some(number) => playback.play_context_from(uri, number.saturating_sub(1))?,
+        None => playback.play_context(uri)?,
+    }
+    hooks::track_changed(ctx);
+    now_playing::show_with_delay(ctx, 100)
+}
+
+/// Id of `expected` from a URL, URI, or bare id.
+pub(crate) fn parse_id(input: &str, expected: ResourceType) -> Result<String> {
+    parse_resource(input, &[expected]).map(|resource| resource.id)
+}
+
+/// Parse a URL, URI, or bare id that must be one of `accepted`; a bare id is taken as the
+/// first kind.
+pub(crate) fn parse_resource(input: &str, accepted: &[ResourceType]) -> Result<SpotifyResource> {
+    if let Some(resource) = parse_spotify_url(input) {
+        if !accepted.contains(&resource.resource_type) {
+            return Err(resource.wrong_kind(accepted));
+        }
+        return Ok(resource);
+    }
+    let kind = accepted.first().map_or("Spotify", |kind| kind.as_str());
+    let trimmed = input.trim();
+    if trimmed.starts_with("spotify:") {
+        bail!(ErrorKind::Validation.error(format!(
+            "unrecognized Spotify URI: {trimmed}; expected spotify:{kind}:<id>"
+        )));
+    }
+    if trimmed.starts_with("http") {
+        let spotify = url::Url::parse(trimmed)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.ends_with("spotify.com")))
+            .unwrap_or(false);
+        if !spotify {
+            bail!(ErrorKind::Validation.error(format!("not a Spotify URL: {trimmed}")));
+        }
+        bail!(ErrorKind::Validation.error(format!(
+            "unrecognized Spotify URL: {trimmed}; expected https://open.spotify.com/{kind}/<id>"
+        )));
+    }
+    if trimmed.is_empty() || !trimmed.chars().all(|c| c.is_ascii_alphanumeric()) {
+        bail!(ErrorKind::Validation.error(format!(
+            "invalid {kind} id: {input}; expected a URL, URI, or id"
+        )));
+    }
+    Ok(SpotifyResource {
+        resource_type: accepted.first().copied().unwrap_or(ResourceType::Track),
+        id: trimmed.to_string(),
+        form: ResourceForm::Id,
+    })
+}
+
 /// Parse a Spotify URL or URI into a resource type and ID.
 ///
 /// Supports:
 /// - URIs: `spotify:track:ID`, `spotify:playlist:ID`, `spotify:album:ID`, `spotify:artist:ID`
 /// - URIs with user: `spotify:user:USER:playlist:ID`
-/// - URLs: `https://open.spotify.com/track/ID`, etc.
-fn parse_spotify_url(input: &str) -> Option<SpotifyResource> {
+/// - URLs: `https://open.spotify.com/track/ID`, etc., including locale
+///   (`/intl-de/track/ID`), embed (`/embed/track/ID`), and user playlist
+///   (`/user/USER/playlist/ID`) paths
+pub(crate) fn parse_spotify_url(input: &str) -> Option<SpotifyResource> {
     let cleaned: String = input.split_whitespace().collect();
     let cleaned = cleaned.trim();
 
@@ -117,13 +259,21 @@ fn parse_spotify_uri(uri: &str) -> Option<SpotifyResource> {
         if parts[1] == "user" && parts.len() >= 5 {
             let resource_type = ResourceType::from_str(parts[3])?;
             let id = split_id(parts[4]);
-            return Some(SpotifyResource { resource_type, id });
+            return Some(SpotifyResource {
+                resource_type,
+                id,
+                form: ResourceForm::Uri,
+            });
         }
 
         // Standard format: spotify:type:id
         let resource_type = ResourceType::from_str(parts[1])?;
         let id = split_id(parts[2]);
-        return Some(SpotifyResource { resource_type, id });
+        return Some(SpotifyResource {
+            resource_type,
+            id,
+            form: ResourceForm::Uri,
+        });
     }
 
     None
@@ -138,12 +288,26 @@ fn parse_http_url(url_str: &str) -> Option<SpotifyResource> {
         return None;
     }
 
-    let segments: Vec<_> = url.path_segments()?.collect();
+    let mut segments: Vec<_> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    // Skip `/intl-de/`, `/embed/`, and the legacy `/user/<name>/` prefix.
+    while let Some(first) = segments.first() {
+        if first.starts_with("intl-") || first.starts_with("embed") {
+            segments.remove(0);
+        } else if *first == "user" && segments.len() >= 4 {
+            segments.drain(..2);
+        } else {
+            break;
+        }
+    }
 
     if segments.len() >= 2 {
         let resource_type = ResourceType::from_str(segments[0])?;
         let id = segments[1].to_string();
-        return Some(SpotifyResource { resource_type, id });
+        return Some(SpotifyResource {
+            resource_type,
+            id,
+            form: ResourceForm::Url,
+        });
     }
 
     None
@@ -190,6 +354,13 @@ mod tests {
         assert_eq!(resource.id, "ghi012");
     }
 
+    #[test]
+    fn parse_episode_uri() {
+        let resource = parse_spotify_url("spotify:episode:ep345").unwrap();
+        assert_eq!(resource.resource_type, ResourceType::Episode);
+        assert_eq!(resource.id, "ep345");
+    }
+
     #[test]
     fn parse_user_playlist_uri() {
         let resource = parse_spotify_url("spotify:user:alice:playlist:abc123").unwrap();
@@ -243,6 +414,7 @@ mod tests {
         let resource = SpotifyResource {
             resource_type: ResourceType::Playlist,
             id: "abc123".to_string(),
+            form: ResourceForm::Uri,
         };
         assert_eq!(resource.to_uri(), "spotify:playlist:abc123");
     }
@@ -252,4 +424,73 @@ mod tests {
         assert!(parse_spotify_url("not-a-url").is_none());
         assert!(parse_spotify_url("https://example.com/playlist/123").is_none());
     }
+
+    #[test]
+    fn parse_locale_embed_and_user_urls() {
+        for url in [
+            "https://open.spotify.com/intl-de/track/abc?si=1",
+            "https://open.spotify.com/embed/track/abc",
+            "https://open.spotify.com/embed-podcast/track/abc",
+        ] {
+            let resource = parse_spotify_url(url).unwrap();
+            assert_eq!(resource.resource_type, ResourceType::Track);
+            assert_eq!(resource.id, "abc");
+            assert_eq!(resource.form, ResourceForm::Url);
+        }
+        let resource =
+            parse_spotify_url("https://open.spotify.com/user/alice/playlist/p1").unwrap();
+        assert_eq!(resource.resource_type, ResourceType::Playlist);
+        assert_eq!(resource.id, "p1");
+    }
+
+    #[test]
+    fn parse_id_accepts_uri_url_and_id() {
+        assert_eq!(
+            parse_id("spotify:track:abc", ResourceType::Track).unwrap(),
+            "abc"
+        );
+        assert_eq!(
+            parse_id(
+                "https://open.spotify.com/track/abc?si=1",
+                ResourceType::Track
+            )
+            .unwrap(),
+            "abc"
+        );
+        assert_eq!(parse_id(" abc123 ", ResourceType::Track).unwrap(), "abc123");
+        assert!(parse_id("not an id", ResourceType::Track).is_err());
+    }
+
+    #[test]
+    fn parse_id_names_the_wrong_kind() {
+        let err = parse_id("https://open.spotify.com/album/abc", ResourceType::Track).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "that's an album URL but this command needs a track"
+        );
+        let err = parse_id("spotify:episode:abc", ResourceType::Show).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "that's an episode URI but this command needs a show"
+        );
+        let resource = parse_resource(
+            "spotify:album:abc",
+            &[ResourceType::Track, ResourceType::Album],
+        )
+        .unwrap();
+        assert_eq!(resource.resource_type, ResourceType::Album);
+    }
+
+    #[test]
+    fn parse_id_explains_unrecognized_links() {
+        let err = parse_id("https://example.com/track/abc", ResourceType::Track).unwrap_err();
+        assert!(err.to_string().starts_with("not a Spotify URL"));
+        let err = parse_id("https://open.spotify.com/genre/abc", ResourceType::Track).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expected https://open.spotify.com/track/<id>")
+        );
+        let err = parse_id("spotify:bogus:abc", ResourceType::Track).unwrap_err();
+        assert!(err.to_string().contains("expected spotify:track:<id>"));
+    }
 }
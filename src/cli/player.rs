@@ -1,18 +1,110 @@
 //! Player command handlers.
+use std::time::Duration;
+
 use clap::{Subcommand, ValueEnum};
 
 use crate::AppContext;
+use crate::action::{context, device, explicit, fade, hooks, oplog, target};
+use crate::cli::library::parse_duration;
 use crate::cli::now_playing;
-use crate::error::Result;
+use crate::cli::play::{ResourceType, parse_spotify_url};
+use crate::cli::search::fuzzy_query;
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::player::PlayerStatus;
+use crate::domain::search::SearchType;
+use crate::error::{ErrorKind, Result};
+use crate::spotify::cancel;
+use crate::spotify::playback::PlaybackClient;
 
 #[derive(Subcommand, Debug)]
 pub enum PlayerCommand {
-    Play,
-    Pause,
+    Play {
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            value_parser = parse_fade,
+            help = "Fade the volume up over this many seconds"
+        )]
+        fade: Option<Duration>,
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with = "fade",
+            help = "Start playback on the device whose name matches"
+        )]
+        device: Option<String>,
+        #[arg(
+            long,
+            requires = "fade",
+            help = "Allow the fade to end above the configured max volume"
+        )]
+        override_cap: bool,
+    },
+    Pause {
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            value_parser = parse_fade,
+            help = "Fade the volume down over this many seconds before pausing"
+        )]
+        fade: Option<Duration>,
+    },
     Toggle,
     Next,
     Prev,
-    Status,
+    Seek {
+        #[arg(
+            value_name = "POSITION",
+            allow_hyphen_values = true,
+            help = "Position as M:SS or seconds; prefix + or - to move from the current position"
+        )]
+        position: String,
+    },
+    Restart,
+    Radio {
+        #[arg(
+            value_name = "TARGET",
+            help = "Seed track or artist: URI, URL, `now`, or a track search (default: current track)"
+        )]
+        target: Option<String>,
+        #[arg(long, default_value_t = 50, help = "Number of tracks (1-100)")]
+        limit: u32,
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Also save the station as a new private playlist"
+        )]
+        save: Option<String>,
+    },
+    Preview {
+        #[arg(
+            value_name = "TARGET",
+            help = "Track URI, URL, or search query to play"
+        )]
+        target: String,
+        #[arg(
+            long,
+            default_value_t = 30,
+            value_parser = clap::value_parser!(u64).range(1..=600),
+            help = "How long to play it before returning (1-600)"
+        )]
+        seconds: u64,
+    },
+    Status {
+        #[arg(long, help = "Print only the played fraction (0.000-1.000)")]
+        progress_only: bool,
+    },
+    /// Playlists, albums, and shows played recently, newest first.
+    Contexts,
+    /// Resume a recent context where it was left.
+    #[command(name = "back-to")]
+    BackTo {
+        #[arg(
+            value_name = "N",
+            help = "Number from `player contexts`; 1 is the most recent"
+        )]
+        index: usize,
+    },
     Shuffle {
         #[arg(value_enum, help = "Shuffle state")]
         state: ShuffleStateArg,
@@ -21,9 +113,36 @@ pub enum PlayerCommand {
         #[arg(value_enum, help = "Repeat state")]
         state: RepeatStateArg,
     },
+    #[command(group(clap::ArgGroup::new("mode").required(true).multiple(true).args(["shuffle", "repeat"])))]
+    Mode {
+        #[arg(long, value_enum, help = "Shuffle state")]
+        shuffle: Option<ShuffleStateArg>,
+        #[arg(long, value_enum, help = "Repeat state")]
+        repeat: Option<RepeatStateArg>,
+    },
     Volume {
-        #[arg(value_name = "PERCENT", help = "Volume level (0-100). Omit to show current volume")]
+        #[arg(
+            value_name = "PERCENT",
+            help = "Volume level (0-100). Omit to show current volume"
+        )]
+        percent: Option<u32>,
+        #[arg(long, help = "Allow a level above the configured max volume")]
+        override_cap: bool,
+        #[arg(
+            long,
+            help = "Succeed without changing anything on devices without volume control"
+        )]
+        ignore_unsupported: bool,
+    },
+    #[command(name = "max-volume")]
+    MaxVolume {
+        #[arg(
+            value_name = "PERCENT",
+            help = "Volume cap (0-100). Omit to show current cap"
+        )]
         percent: Option<u32>,
+        #[arg(long, conflicts_with = "percent", help = "Remove the volume cap")]
+        clear: bool,
     },
 }
 
@@ -31,12 +150,34 @@ pub fn handle(command: PlayerCommand, ctx: &AppContext) -> Result<()> {
     let playback = ctx.spotify()?.playback();
 
     match command {
-        PlayerCommand::Play => {
-            playback.play()?;
+        PlayerCommand::Play {
+            fade,
+            device,
+            override_cap,
+        } => {
+            hooks::pre_play(ctx, None)?;
+            match (fade, device) {
+                // Transferring to the device starts playback there.
+                (_, Some(name)) => {
+                    device::activate(ctx, &name)?;
+                }
+                (Some(duration), None) => {
+                    let cap = if override_cap {
+                        None
+                    } else {
+                        ctx.auth.max_volume()?
+                    };
+                    fade::play(&playback, duration, cap)?
+                }
+                (None, None) => playback.play()?,
+            }
             now_playing::show_with_delay(ctx, 100)
         }
-        PlayerCommand::Pause => {
-            playback.pause()?;
+        PlayerCommand::Pause { fade } => {
+            match fade {
+                Some(duration) => fade::pause(&playback, duration)?,
+                None => playback.pause()?,
+            }
             ctx.output.action("player_pause", "Paused")
         }
         PlayerCommand::Toggle => {
@@ -45,38 +186,87 @@ pub fn handle(command: PlayerCommand, ctx: &AppContext) -> Result<()> {
                 playback.pause()?;
                 return ctx.output.action("player_pause", "Paused");
             }
+            hooks::pre_play(ctx, None)?;
             playback.play()?;
             now_playing::show_with_delay(ctx, 100)
         }
         PlayerCommand::Next => {
             playback.next()?;
+            hooks::track_changed(ctx);
             now_playing::show_with_delay(ctx, 100)
         }
         PlayerCommand::Prev => {
             playback.previous()?;
+            hooks::track_changed(ctx);
             now_playing::show_with_delay(ctx, 100)
         }
-        PlayerCommand::Status => {
+        PlayerCommand::Seek { position } => {
             let status = playback.status()?;
+            let duration_ms = status.track.as_ref().and_then(|track| track.duration_ms);
+            let target = seek_target(&position, status.progress_ms, duration_ms)?;
+            playback.seek(target)?;
+            now_playing::show_with_delay(ctx, 100)
+        }
+        PlayerCommand::Restart => {
+            playback.seek(0)?;
+            now_playing::show_with_delay(ctx, 100)
+        }
+        PlayerCommand::Radio {
+            target,
+            limit,
+            save,
+        } => radio(ctx, target.as_deref(), limit, save.as_deref()),
+        PlayerCommand::Preview { target, seconds } => preview(ctx, &target, seconds),
+        PlayerCommand::Status { progress_only } => {
+            let mut status = playback.status()?;
+            if progress_only {
+                let Some(track) = status.track.as_ref() else {
+                    anyhow::bail!(ErrorKind::NotFound.error("no track is currently playing"));
+                };
+                return ctx
+                    .output
+                    .player_progress(status.progress_ms.unwrap_or(0), track.duration_ms);
+            }
+            if let Some(playing_from) = status.context.as_mut() {
+                context::resolve(ctx, playing_from);
+            }
+            context::remember(ctx, &status);
             ctx.output.player_status(status)
         }
+        PlayerCommand::Contexts => {
+            let mut recent = ctx.cache.recent_context_store().load()?.items;
+            context::resolve_recent(ctx, &mut recent);
+            ctx.output.recent_contexts(recent)
+        }
+        PlayerCommand::BackTo { index } => back_to(ctx, &playback, index),
         PlayerCommand::Shuffle { state } => {
-            let enabled = matches!(state, ShuffleStateArg::On);
-            playback.shuffle(enabled)?;
-            let message = format!("Shuffle: {}", state.as_str());
+            let message = set_mode(&playback, Some(state), None)?;
             ctx.output.action("player_shuffle", &message)
         }
         PlayerCommand::Repeat { state } => {
-            playback.repeat(state.as_str())?;
-            let message = format!("Repeat: {}", state.as_str());
+            let message = set_mode(&playback, None, Some(state))?;
             ctx.output.action("player_repeat", &message)
         }
-        PlayerCommand::Volume { percent } => match percent {
+        PlayerCommand::Mode { shuffle, repeat } => {
+            let message = set_mode(&playback, shuffle, repeat)?;
+            ctx.output.action("player_mode", &message)
+        }
+        PlayerCommand::Volume {
+            percent,
+            override_cap,
+            ignore_unsupported,
+        } => match percent {
             Some(level) => {
                 if level > 100 {
-                    anyhow::bail!("volume must be between 0 and 100");
+                    anyhow::bail!(ErrorKind::Validation.error("volume must be between 0 and 100"));
+                }
+                check_volume_cap(level, ctx.auth.max_volume()?, override_cap)?;
+                if !device::set_volume(ctx, level, ignore_unsupported)? {
+                    return ctx.output.action(
+                        "player_volume_skipped",
+                        "Volume unchanged: device does not support volume control",
+                    );
                 }
-                playback.set_volume(level)?;
                 let message = format!("Volume: {}%", level);
                 ctx.output.action("player_volume", &message)
             }
@@ -91,20 +281,278 @@ pub fn handle(command: PlayerCommand, ctx: &AppContext) -> Result<()> {
                 ctx.output.action("player_volume", &message)
             }
         },
+        PlayerCommand::MaxVolume { percent, clear } => {
+            if let Some(level) = percent {
+                if level > 100 {
+                    anyhow::bail!(
+                        ErrorKind::Validation.error("max volume must be between 0 and 100")
+                    );
+                }
+                ctx.auth.set_max_volume(Some(level))?;
+            } else if clear {
+                ctx.auth.set_max_volume(None)?;
+            }
+            let message = match ctx.auth.max_volume()? {
+                Some(level) => format!("Max volume: {}%", level),
+                None => "Max volume: none".to_string(),
+            };
+            ctx.output.action("player_max_volume", &message)
+        }
+    }
+}
+
+/// Resolve an absolute (`90`, `1:30`) or relative (`+30`, `-0:15`) seek to milliseconds.
+///
+/// Relative seeks start from the current progress and stop at the track bounds.
+fn seek_target(input: &str, progress_ms: Option<u32>, duration_ms: Option<u32>) -> Result<u32> {
+    let input = input.trim();
+    let target = if let Some(offset) = input.strip_prefix('+') {
+        current_progress(progress_ms)?.saturating_add(parse_duration(offset)?)
+    } else if let Some(offset) = input.strip_prefix('-') {
+        current_progress(progress_ms)?.saturating_sub(parse_duration(offset)?)
+    } else {
+        parse_duration(input)?
+    };
+    Ok(match duration_ms {
+        Some(duration_ms) => target.min(duration_ms),
+        None => target,
+    })
+}
+
+fn current_progress(progress_ms: Option<u32>) -> Result<u32> {
+    match progress_ms {
+        Some(progress_ms) => Ok(progress_ms),
+        None => anyhow::bail!(
+            ErrorKind::NotFound.error("nothing is playing; relative seeks need a current position")
+        ),
+    }
+}
+
+/// Parse `--fade` seconds; fractions are allowed, up to a minute.
+fn parse_fade(input: &str) -> std::result::Result<Duration, String> {
+    match input.trim().parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds <= 60.0 => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!(
+            "invalid fade '{input}'; expected seconds between 0 and 60"
+        )),
+    }
+}
+
+/// Play recommendations seeded from a track or artist, like the app's song radio.
+fn radio(ctx: &AppContext, target: Option<&str>, limit: u32, save: Option<&str>) -> Result<()> {
+    if !(1..=100).contains(&limit) {
+        anyhow::bail!(ErrorKind::Validation.error(format!("--limit must be 1-100; got {limit}")));
+    }
+    let (seed, seed_id) = radio_seed(ctx, target)?;
+    let mut items = ctx
+        .spotify()?
+        .search()
+        .recommendations(seed, &seed_id, limit)?;
+    explicit::filter(ctx, &mut items);
+    if items.is_empty() {
+        anyhow::bail!(ErrorKind::NotFound.error("no recommendations for this seed"));
+    }
+    let uris: Vec<String> = items.iter().map(|item| item.uri.clone()).collect();
+
+    if let Some(name) = save {
+        let playlist = oplog::create_playlist(ctx, name, Some(false))?;
+        for batch in uris.chunks(100) {
+            oplog::perform(
+                ctx,
+                LoggedOperation::PlaylistAdd {
+                    playlist_id: playlist.id.clone(),
+                    uris: batch.to_vec(),
+                },
+            )?;
+        }
+        let message = format!("Saved radio: {} ({} tracks)", playlist.name, uris.len());
+        ctx.output.action("player_radio_save", &message)?;
+    }
+
+    hooks::pre_play(ctx, uris.first().map(String::as_str))?;
+    ctx.spotify()?.playback().play_uris(&uris)?;
+    hooks::track_changed(ctx);
+    now_playing::show_with_delay(ctx, 100)
+}
+
+/// Seed kind and id from a URI or URL, `now`, a track search, or the current track.
+fn radio_seed(ctx: &AppContext, target: Option<&str>) -> Result<(SearchType, String)> {
+    let Some(target) = target.filter(|target| !target::is_now(target)) else {
+        return Ok((SearchType::Track, target::now_id(ctx, SearchType::Track)?));
+    };
+    if let Some(resource) = parse_spotify_url(target) {
+        return match resource.resource_type {
+            ResourceType::Track => Ok((SearchType::Track, resource.id)),
+            ResourceType::Artist => Ok((SearchType::Artist, resource.id)),
+            _ => Err(resource.wrong_kind(&[ResourceType::Track, ResourceType::Artist])),
+        };
+    }
+    let results =
+        ctx.spotify()?
+            .search()
+            .search(&fuzzy_query(target), SearchType::Track, 1, false)?;
+    match results.items.into_iter().next() {
+        Some(item) => Ok((SearchType::Track, item.id)),
+        None => anyhow::bail!(ErrorKind::NotFound.error(format!("no track matches '{target}'"))),
     }
 }
 
+/// Resume the `index`th recent context (1-based) at the track and position it was left.
+fn back_to(ctx: &AppContext, playback: &PlaybackClient, index: usize) -> Result<()> {
+    let recent = ctx.cache.recent_context_store().load()?.items;
+    let Some(entry) = index.checked_sub(1).and_then(|index| recent.get(index)) else {
+        anyhow::bail!(ErrorKind::NotFound.error(format!(
+            "no recent context #{index}; `player contexts` lists {}",
+            recent.len()
+        )));
+    };
+    hooks::pre_play(ctx, Some(&entry.uri))?;
+    context::resume(playback, entry)?;
+    hooks::track_changed(ctx);
+    now_playing::show_with_delay(ctx, 100)
+}
+
+/// Play a track for a while, then go back to what was playing before.
+fn preview(ctx: &AppContext, target: &str, seconds: u64) -> Result<()> {
+    let (uri, name) = preview_track(ctx, target)?;
+    let playback = ctx.spotify()?.playback();
+    let resume = resume_point(&playback.status()?);
+
+    hooks::pre_play(ctx, Some(&uri))?;
+    playback.play_track(&uri)?;
+    hooks::track_changed(ctx);
+    // Ctrl-C or `--timeout` cuts the preview short but still resumes.
+    let finished = cancel::sleep(Duration::from_secs(seconds));
+
+    match &resume {
+        Some(resume) => {
+            match &resume.context_uri {
+                Some(context_uri) => {
+                    playback.play_context_at(context_uri, &resume.track_uri, resume.position_ms)?
+                }
+                None => playback.play_track_at(&resume.track_uri, resume.position_ms)?,
+            }
+            if !resume.playing {
+                playback.pause()?;
+            }
+            hooks::track_changed(ctx);
+        }
+        None => playback.pause()?,
+    }
+    let length = if finished {
+        format!("{seconds}s")
+    } else {
+        "stopped early".to_string()
+    };
+    let message = match resume {
+        Some(_) => format!("Previewed: {name} ({length}); resumed previous playback"),
+        None => format!("Previewed: {name} ({length}); paused"),
+    };
+    ctx.output.action("player_preview", &message)
+}
+
+/// Track URI and display name from a URI or URL, or the best track search match.
+fn preview_track(ctx: &AppContext, target: &str) -> Result<(String, String)> {
+    if let Some(resource) = parse_spotify_url(target) {
+        if resource.resource_type != ResourceType::Track {
+            return Err(resource.wrong_kind(&[ResourceType::Track]));
+        }
+        let uri = resource.to_uri();
+        return Ok((uri.clone(), uri));
+    }
+    let results =
+        ctx.spotify()?
+            .search()
+            .search(&fuzzy_query(target), SearchType::Track, 1, false)?;
+    match results.items.into_iter().next() {
+        Some(item) => Ok((item.uri, item.name)),
+        None => anyhow::bail!(ErrorKind::NotFound.error(format!("no track matches '{target}'"))),
+    }
+}
+
+/// Where playback was before a preview.
+#[derive(Debug, PartialEq)]
+struct Resume {
+    /// Only set for contexts that can be restarted at a given track.
+    context_uri: Option<String>,
+    track_uri: String,
+    position_ms: u32,
+    playing: bool,
+}
+
+fn resume_point(status: &PlayerStatus) -> Option<Resume> {
+    let track = status.track.as_ref()?;
+    let context_uri = status
+        .context
+        .as_ref()
+        .filter(|context| context::resumes_at_track(&context.kind))
+        .map(|context| context.uri.clone());
+    Some(Resume {
+        context_uri,
+        track_uri: format!("spotify:track:{}", track.id),
+        position_ms: status.progress_ms.unwrap_or(0),
+        playing: status.is_playing,
+    })
+}
+
+/// Apply shuffle and repeat changes, reading playback state only when toggling or cycling.
+fn set_mode(
+    playback: &PlaybackClient,
+    shuffle: Option<ShuffleStateArg>,
+    repeat: Option<RepeatStateArg>,
+) -> Result<String> {
+    let needs_status = matches!(shuffle, Some(ShuffleStateArg::Toggle))
+        || matches!(repeat, Some(RepeatStateArg::Cycle));
+    let status = if needs_status {
+        Some(playback.status()?)
+    } else {
+        None
+    };
+
+    let mut parts = Vec::new();
+    if let Some(state) = shuffle {
+        let current = status.as_ref().and_then(|status| status.shuffle_state);
+        let enabled = state.resolve(current);
+        playback.shuffle(enabled)?;
+        parts.push(format!("Shuffle: {}", if enabled { "on" } else { "off" }));
+    }
+    if let Some(state) = repeat {
+        let current = status
+            .as_ref()
+            .and_then(|status| status.repeat_state.as_deref());
+        let mode = state.resolve(current);
+        playback.repeat(mode)?;
+        parts.push(format!("Repeat: {}", mode));
+    }
+    Ok(parts.join("; "))
+}
+
+fn check_volume_cap(level: u32, cap: Option<u32>, override_cap: bool) -> Result<()> {
+    if let Some(cap) = cap
+        && level > cap
+        && !override_cap
+    {
+        anyhow::bail!(ErrorKind::Validation.error(format!(
+            "volume {level}% exceeds max volume {cap}%; pass --override-cap to allow it"
+        )));
+    }
+    Ok(())
+}
+
 #[derive(ValueEnum, Debug, Clone, Copy)]
 pub(crate) enum ShuffleStateArg {
     On,
     Off,
+    Toggle,
 }
 
 impl ShuffleStateArg {
-    fn as_str(&self) -> &'static str {
+    /// Shuffle flag to send; `toggle` flips the current state (off when unknown).
+    fn resolve(self, current: Option<bool>) -> bool {
         match self {
-            ShuffleStateArg::On => "on",
-            ShuffleStateArg::Off => "off",
+            ShuffleStateArg::On => true,
+            ShuffleStateArg::Off => false,
+            ShuffleStateArg::Toggle => !current.unwrap_or(false),
         }
     }
 }
@@ -114,14 +562,128 @@ pub(crate) enum RepeatStateArg {
     Off,
     Track,
     Context,
+    /// Advance off -> context -> track -> off.
+    Cycle,
 }
 
 impl RepeatStateArg {
-    fn as_str(&self) -> &'static str {
+    /// Repeat mode to send; `cycle` steps from the current mode.
+    fn resolve(self, current: Option<&str>) -> &'static str {
         match self {
             RepeatStateArg::Off => "off",
             RepeatStateArg::Track => "track",
             RepeatStateArg::Context => "context",
+            RepeatStateArg::Cycle => match current {
+                Some("context") => "track",
+                Some("track") => "off",
+                _ => "context",
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        RepeatStateArg, Resume, ShuffleStateArg, check_volume_cap, parse_fade, resume_point,
+        seek_target,
+    };
+    use crate::domain::player::{PlaybackContext, PlayerStatus};
+    use crate::domain::track::Track;
+    use std::time::Duration;
+
+    fn status(kind: &str) -> PlayerStatus {
+        PlayerStatus {
+            is_playing: true,
+            track: Some(Track {
+                id: "t1".to_string(),
+                name: "One".to_string(),
+                artists: Vec::new(),
+                artist_ids: Vec::new(),
+                album: None,
+                album_id: None,
+                duration_ms: None,
+            }),
+            device: None,
+            context: Some(PlaybackContext {
+                kind: kind.to_string(),
+                uri: format!("spotify:{kind}:c1"),
+                name: None,
+            }),
+            progress_ms: Some(42_000),
+            repeat_state: None,
+            shuffle_state: None,
         }
     }
+
+    #[test]
+    fn resume_point_keeps_playlist_and_album_contexts() {
+        assert_eq!(
+            resume_point(&status("playlist")),
+            Some(Resume {
+                context_uri: Some("spotify:playlist:c1".to_string()),
+                track_uri: "spotify:track:t1".to_string(),
+                position_ms: 42_000,
+                playing: true,
+            })
+        );
+        let artist = resume_point(&status("artist")).unwrap();
+        assert_eq!(artist.context_uri, None);
+        assert_eq!(artist.track_uri, "spotify:track:t1");
+        let idle = PlayerStatus {
+            track: None,
+            ..status("album")
+        };
+        assert_eq!(resume_point(&idle), None);
+    }
+
+    #[test]
+    fn repeat_cycle_steps_through_modes() {
+        assert_eq!(RepeatStateArg::Cycle.resolve(Some("off")), "context");
+        assert_eq!(RepeatStateArg::Cycle.resolve(Some("context")), "track");
+        assert_eq!(RepeatStateArg::Cycle.resolve(Some("track")), "off");
+        assert_eq!(RepeatStateArg::Cycle.resolve(None), "context");
+        assert_eq!(RepeatStateArg::Track.resolve(Some("off")), "track");
+    }
+
+    #[test]
+    fn shuffle_toggle_flips_current_state() {
+        assert!(ShuffleStateArg::Toggle.resolve(Some(false)));
+        assert!(!ShuffleStateArg::Toggle.resolve(Some(true)));
+        assert!(ShuffleStateArg::Toggle.resolve(None));
+        assert!(!ShuffleStateArg::Off.resolve(Some(false)));
+    }
+
+    #[test]
+    fn seek_target_handles_absolute_and_relative_positions() {
+        assert_eq!(seek_target("1:30", None, None).unwrap(), 90_000);
+        assert_eq!(
+            seek_target("+30", Some(10_000), Some(200_000)).unwrap(),
+            40_000
+        );
+        assert_eq!(seek_target("-15", Some(10_000), Some(200_000)).unwrap(), 0);
+        assert_eq!(
+            seek_target("+5:00", Some(10_000), Some(200_000)).unwrap(),
+            200_000
+        );
+        assert!(seek_target("+30", None, None).is_err());
+        assert!(seek_target("later", Some(0), None).is_err());
+    }
+
+    #[test]
+    fn parse_fade_accepts_fractional_seconds() {
+        assert_eq!(parse_fade("1.5").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_fade("3").unwrap(), Duration::from_secs(3));
+        assert!(parse_fade("0").is_err());
+        assert!(parse_fade("90").is_err());
+        assert!(parse_fade("soon").is_err());
+    }
+
+    #[test]
+    fn check_volume_cap_blocks_levels_above_cap() {
+        assert!(check_volume_cap(80, Some(70), false).is_err());
+        assert!(check_volume_cap(80, Some(70), true).is_ok());
+        assert!(check_volume_cap(70, Some(70), false).is_ok());
+        assert!(check_volume_cap(100, None, false).is_ok());
+    }
 }
@@ -1,13 +1,30 @@
 //! Playlist command handlers.
+use std::collections::HashMap;
+
 use anyhow::bail;
 use clap::Subcommand;
 
 use crate::AppContext;
-use crate::action::playlist::resolve_for_write;
-use crate::domain::playlist::Playlist;
+use crate::action::oplog;
+use crate::action::playlist::{PlaylistSelection, resolve_for_write};
+use crate::action::target;
+use crate::cache::playlists::CacheSnapshot;
+use crate::cli::ensure_confirmed;
+use crate::cli::play::{ResourceType, parse_id, parse_spotify_url, start_context};
+use crate::domain::cuesheet::{CueEntry, CueFormat, CueItem, Cuesheet};
+use crate::domain::oplog::{LoggedOperation, PlaylistVisibility};
+use crate::domain::playlist::{
+    AuditEntry, AuditStatus, MatchField, Playlist, PlaylistAudit, PlaylistMatch, PlaylistTrack,
+    PlaylistTrackPage, UserPlaylist, UserPlaylistPage,
+};
 use crate::domain::search::SearchItem;
 use crate::domain::search::SearchType;
-use crate::error::Result;
+use crate::domain::stats::{PlaylistStats, Tally};
+use crate::error::{ErrorKind, Result};
+use crate::time::unix_time;
+
+/// `playlist find` ranks a description match below an equally good name match.
+const DESCRIPTION_WEIGHT: f32 = 0.8;
 
 #[derive(Subcommand, Debug)]
 pub enum PlaylistCommand {
@@ -23,6 +40,18 @@ pub enum PlaylistCommand {
         #[arg(long, value_enum, default_value = "name", help = "Sort playlists")]
         sort: PlaylistSort,
     },
+    Find {
+        #[arg(value_name = "TEXT")]
+        text: String,
+        #[arg(long, help = "Only search playlists you own")]
+        owned: bool,
+        #[arg(long, default_value_t = 20, help = "Maximum matches")]
+        limit: usize,
+        #[arg(long, help = "Print only the playlist ids, one per line")]
+        ids: bool,
+        #[arg(long, help = "Fetch the playlists from Spotify and refresh the cache")]
+        refresh: bool,
+    },
     #[command(name = "addto")]
     AddTo {
         #[arg(value_name = "QUERY")]
@@ -33,6 +62,8 @@ pub enum PlaylistCommand {
         pick: Option<usize>,
         #[arg(long, help = "Use the last cached search results")]
         last: bool,
+        #[arg(long, help = "Change a playlist you do not own without asking")]
+        force: bool,
     },
     Create {
         name: String,
@@ -41,6 +72,8 @@ pub enum PlaylistCommand {
         #[arg(long, conflicts_with = "public", help = "Create as a private playlist")]
         private: bool,
     },
+    // A lone argument is the new name, for the currently playing playlist.
+    #[command(allow_missing_positional = true)]
     Rename {
         #[arg(value_name = "QUERY")]
         query: Option<String>,
@@ -51,6 +84,37 @@ pub enum PlaylistCommand {
         pick: Option<usize>,
         #[arg(long, help = "Use the last cached search results")]
         last: bool,
+        #[arg(long, help = "Change a playlist you do not own without asking")]
+        force: bool,
+    },
+    #[command(group(clap::ArgGroup::new("change").required(true).multiple(true).args(["collaborative", "no_collaborative", "public", "private"])))]
+    Edit {
+        #[arg(value_name = "QUERY")]
+        query: Option<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["no_collaborative", "public"],
+            help = "Let followers add tracks (also makes the playlist private)"
+        )]
+        collaborative: bool,
+        #[arg(long, help = "Only the owner can add tracks")]
+        no_collaborative: bool,
+        #[arg(
+            long,
+            conflicts_with = "private",
+            help = "Make the playlist public (also turns collaboration off)"
+        )]
+        public: bool,
+        #[arg(long, help = "Make the playlist private")]
+        private: bool,
+        #[arg(long, help = "Use market from token")]
+        user: bool,
+        #[arg(long, help = "Pick a specific result (1-based)")]
+        pick: Option<usize>,
+        #[arg(long, help = "Use the last cached search results")]
+        last: bool,
+        #[arg(long, help = "Change a playlist you do not own without asking")]
+        force: bool,
     },
     Delete {
         #[arg(value_name = "QUERY")]
@@ -62,6 +126,156 @@ pub enum PlaylistCommand {
         #[arg(long, help = "Use the last cached search results")]
         last: bool,
     },
+    Cuesheet {
+        #[arg(value_name = "QUERY")]
+        query: Option<String>,
+        #[arg(long, help = "Use market from token")]
+        user: bool,
+        #[arg(long, help = "Pick a specific result (1-based)")]
+        pick: Option<usize>,
+        #[arg(long, help = "Use the last cached search results")]
+        last: bool,
+        #[arg(long, value_enum, default_value = "cue", help = "Output format")]
+        format: CueFormatArg,
+    },
+    Play {
+        #[arg(value_name = "QUERY")]
+        query: Option<String>,
+        #[arg(long, help = "Use market from token")]
+        user: bool,
+        #[arg(long, help = "Pick a specific result (1-based)")]
+        pick: Option<usize>,
+        #[arg(long, help = "Use the last cached search results")]
+        last: bool,
+        #[arg(long, help = "Turn shuffle on first")]
+        shuffle: bool,
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Start at the Nth track (1-based)"
+        )]
+        from_track: Option<u32>,
+    },
+    Tracks {
+        #[arg(value_name = "QUERY")]
+        query: Option<String>,
+        #[arg(long, help = "Use market from token")]
+        user: bool,
+        #[arg(long, help = "Pick a specific result (1-based)")]
+        pick: Option<usize>,
+        #[arg(long, help = "Use the last cached search results")]
+        last: bool,
+        #[arg(long, conflicts_with_all = ["limit", "offset"], help = "Fetch every track")]
+        all: bool,
+        #[arg(long, default_value_t = 50, help = "Tracks per page (max 100)")]
+        limit: u32,
+        #[arg(long, default_value_t = 0, help = "Skip the first N tracks")]
+        offset: u32,
+        #[arg(
+            long,
+            value_name = "TRACK",
+            conflicts_with_all = ["all", "limit", "offset"],
+            help = "Check whether a track URL, URI, id, or `now` is in the playlist"
+        )]
+        contains: Option<String>,
+    },
+    Stats {
+        #[arg(value_name = "QUERY")]
+        query: Option<String>,
+        #[arg(long, help = "Use market from token")]
+        user: bool,
+        #[arg(long, help = "Pick a specific result (1-based)")]
+        pick: Option<usize>,
+        #[arg(long, help = "Use the last cached search results")]
+        last: bool,
+        #[arg(long, default_value_t = 10, help = "Artists and genres to list")]
+        top: usize,
+    },
+    Audit {
+        #[arg(value_name = "QUERY")]
+        query: Option<String>,
+        #[arg(long, help = "Use market from token")]
+        user: bool,
+        #[arg(long, help = "Pick a specific result (1-based)")]
+        pick: Option<usize>,
+        #[arg(long, help = "Use the last cached search results")]
+        last: bool,
+        #[arg(
+            long,
+            help = "Replace unavailable tracks with a playable version of the same recording"
+        )]
+        replace: bool,
+        #[arg(
+            long,
+            requires = "replace",
+            help = "Change a playlist you do not own without asking"
+        )]
+        force: bool,
+    },
+    User {
+        #[arg(value_name = "USER_ID")]
+        user_id: String,
+        #[arg(long, conflicts_with_all = ["limit", "offset"], help = "Fetch every playlist")]
+        all: bool,
+        #[arg(long, default_value_t = 20, help = "Playlists per page (max 50)")]
+        limit: u32,
+        #[arg(long, default_value_t = 0, help = "Skip the first N playlists")]
+        offset: u32,
+        #[arg(long, help = "Exclude playlists the user follows but does not own")]
+        owned_only: bool,
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Only show playlists with at least N tracks"
+        )]
+        min_tracks: Option<u32>,
+        #[arg(
+            long,
+            value_name = "INDEX",
+            conflicts_with = "pin",
+            help = "Follow the listed playlist at this index (1-based)"
+        )]
+        follow: Option<usize>,
+        #[arg(
+            long,
+            value_name = "INDEX",
+            help = "Pin the listed playlist at this index (1-based)"
+        )]
+        pin: Option<usize>,
+    },
+    Move {
+        #[arg(value_name = "QUERY")]
+        query: Option<String>,
+        #[arg(
+            long,
+            value_name = "TRACK",
+            help = "Track to move: URI, URL, id, `now`, or a name"
+        )]
+        track: String,
+        #[arg(
+            long,
+            value_name = "TRACK|POSITION",
+            conflicts_with = "after",
+            required_unless_present = "after",
+            help = "Place it before this track or 1-based position"
+        )]
+        before: Option<String>,
+        #[arg(
+            long,
+            value_name = "TRACK|POSITION",
+            help = "Place it after this track or 1-based position"
+        )]
+        after: Option<String>,
+        #[arg(long, help = "Use market from token")]
+        user: bool,
+        #[arg(long, help = "Pick a specific result (1-based)")]
+        pick: Option<usize>,
+        #[arg(long, help = "Use the last cached search results")]
+        last: bool,
+        #[arg(long, help = "Change a playlist you do not own without asking")]
+        force: bool,
+    },
 }
 
 pub fn handle(command: PlaylistCommand, ctx: &AppContext) -> Result<()> {
@@ -73,12 +287,20 @@ pub fn handle(command: PlaylistCommand, ctx: &AppContext) -> Result<()> {
             private,
             sort,
         } => list(ctx, collaborative, owned, public, private, sort),
+        PlaylistCommand::Find {
+            text,
+            owned,
+            limit,
+            ids,
+            refresh,
+        } => find(ctx, &text, owned, limit, ids, refresh),
         PlaylistCommand::AddTo {
             query,
             user,
             pick,
             last,
-        } => add_to(ctx, query.as_deref(), user, pick, last),
+            force,
+        } => add_to(ctx, query.as_deref(), user, pick, last, force),
         PlaylistCommand::Create {
             name,
             public,
@@ -90,13 +312,148 @@ pub fn handle(command: PlaylistCommand, ctx: &AppContext) -> Result<()> {
             user,
             pick,
             last,
-        } => rename(ctx, query.as_deref(), &new_name, user, pick, last),
+            force,
+        } => rename(ctx, query.as_deref(), &new_name, user, pick, last, force),
+        PlaylistCommand::Edit {
+            query,
+            collaborative,
+            no_collaborative,
+            public,
+            private,
+            user,
+            pick,
+            last,
+            force,
+        } => {
+            let selection = resolve_for_write(ctx, query.as_deref(), last, user, pick)?;
+            confirm_foreign(ctx, &selection, force)?;
+            let collaborative = flag(collaborative, no_collaborative);
+            let public = flag(public, private);
+            edit(ctx, &selection.id, public, collaborative)
+        }
         PlaylistCommand::Delete {
             query,
             user,
             pick,
             last,
         } => delete(ctx, query.as_deref(), user, pick, last),
+        PlaylistCommand::Cuesheet {
+            query,
+            user,
+            pick,
+            last,
+            format,
+        } => cuesheet(ctx, query.as_deref(), user, pick, last, format),
+        PlaylistCommand::Play {
+            query,
+            user,
+            pick,
+            last,
+            shuffle,
+            from_track,
+        } => {
+            let query = query
+                .as_deref()
+                .map(|query| target::resolve_pin(ctx, query))
+                .transpose()?;
+            let id = match query.as_deref().and_then(parse_playlist_id) {
+                Some(id) => id,
+                None => resolve_playlist(ctx, query.as_deref(), last, user, pick)?.id,
+            };
+            start_context(ctx, &format!("spotify:playlist:{id}"), shuffle, from_track)
+        }
+        PlaylistCommand::Tracks {
+            query,
+            user,
+            pick,
+            last,
+            all,
+            limit,
+            offset,
+            contains,
+        } => {
+            let id = match query.as_deref().and_then(parse_playlist_id) {
+                Some(id) => id,
+                None => resolve_playlist(ctx, query.as_deref(), last, user, pick)?.id,
+            };
+            match contains {
+                Some(track) => contains_track(ctx, &id, &track),
+                None => tracks(ctx, &id, all, limit, offset),
+            }
+        }
+        PlaylistCommand::Stats {
+            query,
+            user,
+            pick,
+            last,
+            top,
+        } => {
+            let id = match query.as_deref().and_then(parse_playlist_id) {
+                Some(id) => id,
+                None => resolve_playlist(ctx, query.as_deref(), last, user, pick)?.id,
+            };
+            stats(ctx, &id, top)
+        }
+        PlaylistCommand::Audit {
+            query,
+            user,
+            pick,
+            last,
+            replace,
+            force,
+        } => {
+            if replace {
+                let selection = resolve_for_write(ctx, query.as_deref(), last, user, pick)?;
+                confirm_foreign(ctx, &selection, force)?;
+                return audit(ctx, &selection.id, selection.name, true);
+            }
+            let id = match query.as_deref().and_then(parse_playlist_id) {
+                Some(id) => id,
+                None => resolve_playlist(ctx, query.as_deref(), last, user, pick)?.id,
+            };
+            let name = ctx.spotify()?.playlists().get(&id)?.name;
+            audit(ctx, &id, name, false)
+        }
+        PlaylistCommand::User {
+            user_id,
+            all,
+            limit,
+            offset,
+            owned_only,
+            min_tracks,
+            follow,
+            pin,
+        } => {
+            let filter = UserFilter {
+                owned_only,
+                min_tracks,
+            };
+            let action = match (follow, pin) {
+                (Some(index), _) => Some(UserAction::Follow(index)),
+                (None, Some(index)) => Some(UserAction::Pin(index)),
+                (None, None) => None,
+            };
+            user_playlists(ctx, &user_id, all, limit, offset, filter, action)
+        }
+        PlaylistCommand::Move {
+            query,
+            track,
+            before,
+            after,
+            user,
+            pick,
+            last,
+            force,
+        } => {
+            let selection = resolve_for_write(ctx, query.as_deref(), last, user, pick)?;
+            confirm_foreign(ctx, &selection, force)?;
+            let anchor = match (before, after) {
+                (Some(anchor), _) => MoveAnchor::Before(anchor),
+                (None, Some(anchor)) => MoveAnchor::After(anchor),
+                (None, None) => bail!(ErrorKind::Validation.error("pass --before or --after")),
+            };
+            move_track(ctx, &selection.id, &track, anchor)
+        }
     }
 }
 
@@ -110,7 +467,7 @@ fn list(
 ) -> Result<()> {
     let snapshot = ctx.cache.playlist_cache().load()?;
     let Some(snapshot) = snapshot else {
-        bail!("playlist cache empty; run `spotify sync`");
+        bail!(ErrorKind::NotFound.error("playlist cache empty; run `spotify sync`"));
     };
     let mut playlists = snapshot.items;
     if collaborative {
@@ -123,38 +480,118 @@ fn list(
         playlists.retain(|playlist| playlist.public == Some(false));
     }
     if owned {
-        let Some(owner_name) = ctx.auth.user_name()? else {
-            bail!("missing user name; run `spotify sync` or `spotify cache user <name>`");
-        };
-        let owner_name = owner_name.to_lowercase();
-        playlists.retain(|playlist| {
-            playlist
-                .owner
-                .as_ref()
-                .is_some_and(|owner| owner.to_lowercase() == owner_name)
-        });
+        retain_owned(ctx, &mut playlists)?;
     }
     sort_playlists(&mut playlists, sort);
     let pins = ctx.cache.pin_store().load()?.items;
     ctx.output.playlist_list_with_pins(playlists, pins)
 }
 
+pub(crate) fn retain_owned(ctx: &AppContext, playlists: &mut Vec<Playlist>) -> Result<()> {
+    let Some(owner_name) = ctx.auth.user_name()? else {
+        bail!(
+            ErrorKind::NotFound
+                .error("missing user name; run `spotify sync` or `spotify cache user <name>`")
+        );
+    };
+    let owner_name = owner_name.to_lowercase();
+    playlists.retain(|playlist| {
+        playlist
+            .owner
+            .as_ref()
+            .is_some_and(|owner| owner.to_lowercase() == owner_name)
+    });
+    Ok(())
+}
+
+/// Search the user's own and followed playlists, from the cache unless it is missing or
+/// `--refresh` is given.
+fn find(
+    ctx: &AppContext,
+    text: &str,
+    owned: bool,
+    limit: usize,
+    ids: bool,
+    refresh: bool,
+) -> Result<()> {
+    let mut playlists = cached_playlists(ctx, refresh)?;
+    if owned {
+        retain_owned(ctx, &mut playlists)?;
+    }
+    let mut matches = find_playlists(text, playlists);
+    matches.truncate(limit);
+    ctx.output.playlist_matches(matches, ids)
+}
+
+/// The user's own and followed playlists from the cache, fetching and caching them when the
+/// cache is missing or `refresh` is set.
+pub(crate) fn cached_playlists(ctx: &AppContext, refresh: bool) -> Result<Vec<Playlist>> {
+    let cache = ctx.cache.playlist_cache();
+    let cached = if refresh { None } else { cache.load()? };
+    if let Some(snapshot) = cached {
+        return Ok(snapshot.items);
+    }
+    let playlists = ctx.spotify()?.playlists().list_all()?;
+    cache.save(&CacheSnapshot {
+        updated_at: unix_time(),
+        items: playlists.clone(),
+    })?;
+    Ok(playlists)
+}
+
+/// Playlists whose name or description fuzzy-matches `text`, best first.
+fn find_playlists(text: &str, playlists: Vec<Playlist>) -> Vec<PlaylistMatch> {
+    let mut matches: Vec<PlaylistMatch> = playlists
+        .into_iter()
+        .filter_map(|playlist| {
+            let name = match_score(text, &playlist.name).map(|score| (score, MatchField::Name));
+            let description = playlist
+                .description
+                .as_deref()
+                .and_then(|description| match_score(text, description))
+                .map(|score| (score * DESCRIPTION_WEIGHT, MatchField::Description));
+            let (score, field) = match (name, description) {
+                (Some(name), Some(description)) if description.0 > name.0 => description,
+                (Some(name), _) => name,
+                (None, description) => description?,
+            };
+            Some(PlaylistMatch {
+                playlist,
+                score,
+                field,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.playlist.name.cmp(&b.playlist.name))
+    });
+    matches
+}
+
 pub(crate) fn add_to(
     ctx: &AppContext,
     query: Option<&str>,
     user: bool,
     pick: Option<usize>,
     last: bool,
+    force: bool,
 ) -> Result<()> {
     let status = ctx.spotify()?.playback().status()?;
     let Some(track) = status.track else {
-        bail!("no track is currently playing");
+        bail!(ErrorKind::NotFound.error("no track is currently playing"));
     };
     let selection = resolve_for_write(ctx, query, last, user, pick)?;
+    confirm_foreign(ctx, &selection, force)?;
     let uri = format!("spotify:track:{}", track.id);
-    ctx.spotify()?
-        .playlists()
-        .add_tracks(&selection.id, &[uri])?;
+    oplog::perform(
+        ctx,
+        LoggedOperation::PlaylistAdd {
+            playlist_id: selection.id.clone(),
+            uris: vec![uri],
+        },
+    )?;
     let message = format!("Added: {} -> {}", format_track(&track), selection.name);
     ctx.output.action("playlist_add", &message)?;
     Ok(())
@@ -168,7 +605,7 @@ fn create(ctx: &AppContext, name: &str, public: bool, private: bool) -> Result<(
     } else {
         None
     };
-    let playlist = ctx.spotify()?.playlists().create(name, public)?;
+    let playlist = oplog::create_playlist(ctx, name, public)?;
     let message = format!("Created: {} ({})", playlist.name, playlist.id);
     ctx.output.action("playlist_create", &message)
 }
@@ -180,13 +617,113 @@ fn rename(
     user: bool,
     pick: Option<usize>,
     last: bool,
+    force: bool,
 ) -> Result<()> {
     let selection = resolve_for_write(ctx, query, last, user, pick)?;
-    ctx.spotify()?.playlists().rename(&selection.id, new_name)?;
+    confirm_foreign(ctx, &selection, force)?;
+    oplog::perform(
+        ctx,
+        LoggedOperation::PlaylistRename {
+            playlist_id: selection.id.clone(),
+            from: selection.name.clone(),
+            to: new_name.to_string(),
+        },
+    )?;
     let message = format!("Renamed: {} -> {}", selection.name, new_name);
     ctx.output.action("playlist_rename", &message)
 }
 
+/// Ask before changing a collaborative playlist someone else owns, unless `force` is set.
+///
+/// Collaborative playlists accept writes from every collaborator, so a fuzzy match can
+/// silently land on a friend's playlist.
+pub(crate) fn confirm_foreign(
+    ctx: &AppContext,
+    selection: &PlaylistSelection,
+    force: bool,
+) -> Result<()> {
+    let Some(owner) = selection.foreign_owner.as_deref() else {
+        return Ok(());
+    };
+    if force || ctx.dry_run {
+        return Ok(());
+    }
+    let prompt = format!("{} is owned by {owner}; change it anyway?", selection.name);
+    ensure_confirmed(
+        ctx,
+        &prompt,
+        "pass --force to change a playlist you do not own",
+    )
+}
+
+/// `Some(true)` for the on flag, `Some(false)` for the off flag, `None` for neither.
+fn flag(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (false, true) => Some(false),
+        (false, false) => None,
+    }
+}
+
+fn edit(
+    ctx: &AppContext,
+    playlist_id: &str,
+    public: Option<bool>,
+    collaborative: Option<bool>,
+) -> Result<()> {
+    let playlist = ctx.spotify()?.playlists().get(playlist_id)?;
+    let from = PlaylistVisibility {
+        public: playlist.public,
+        collaborative: playlist.collaborative,
+    };
+    let (to, note) = resolve_visibility(from, public, collaborative);
+    if let Some(note) = note {
+        eprintln!("note: {note}");
+    }
+    if to == from {
+        let message = format!("Unchanged: {} ({})", playlist.name, to.describe());
+        return ctx.output.action("playlist_edit", &message);
+    }
+    oplog::perform(
+        ctx,
+        LoggedOperation::PlaylistEdit {
+            playlist_id: playlist.id.clone(),
+            from,
+            to,
+        },
+    )?;
+    let message = format!("Edited: {} ({})", playlist.name, to.describe());
+    ctx.output.action("playlist_edit", &message)
+}
+
+/// Apply requested flags, keeping collaborative playlists private as Spotify requires.
+///
+/// Turning collaboration on makes the playlist private; making it public turns
+/// collaboration off. The note explains any flag changed implicitly.
+fn resolve_visibility(
+    current: PlaylistVisibility,
+    public: Option<bool>,
+    collaborative: Option<bool>,
+) -> (PlaylistVisibility, Option<&'static str>) {
+    let mut next = PlaylistVisibility {
+        public: public.or(current.public),
+        collaborative: collaborative.unwrap_or(current.collaborative),
+    };
+    let mut note = None;
+    if next.collaborative && next.public != Some(false) {
+        if collaborative == Some(true) {
+            next.public = Some(false);
+            if current.public != Some(false) {
+                note = Some("collaborative playlists must be private; making it private");
+            }
+        } else {
+            next.collaborative = false;
+            note = Some("public playlists cannot be collaborative; turning collaboration off");
+        }
+    }
+    (next, note)
+}
+
 fn delete(
     ctx: &AppContext,
     query: Option<&str>,
@@ -195,11 +732,521 @@ fn delete(
     last: bool,
 ) -> Result<()> {
     let item = resolve_playlist(ctx, query, last, user, pick)?;
-    ctx.spotify()?.playlists().delete(&item.id)?;
+    if !ctx.dry_run {
+        ensure_confirmed(
+            ctx,
+            &format!("Delete (unfollow) {}?", item.name),
+            "pass --yes, or --dry-run to preview",
+        )?;
+    }
+    oplog::perform(
+        ctx,
+        LoggedOperation::PlaylistUnfollow {
+            playlist_id: item.id.clone(),
+        },
+    )?;
     let message = format!("Deleted (unfollowed): {}", item.name);
     ctx.output.action("playlist_delete", &message)
 }
 
+fn cuesheet(
+    ctx: &AppContext,
+    query: Option<&str>,
+    user: bool,
+    pick: Option<usize>,
+    last: bool,
+    format: CueFormatArg,
+) -> Result<()> {
+    let id = match query.and_then(parse_playlist_id) {
+        Some(id) => id,
+        None => resolve_playlist(ctx, query, last, user, pick)?.id,
+    };
+    let detail = ctx.spotify()?.playlists().get(&id)?;
+    let items = ctx.spotify()?.playlists().cue_items(&id)?;
+    let sheet = build_cuesheet(detail.name, detail.owner, items);
+    ctx.output.cuesheet(sheet, format.into())
+}
+
+fn tracks(ctx: &AppContext, playlist_id: &str, all: bool, limit: u32, offset: u32) -> Result<()> {
+    let playlists = ctx.spotify()?.playlists();
+    let page = if all {
+        let mut progress = ctx.output.progress("Fetching tracks", None);
+        let items =
+            playlists.items_all_with(playlist_id, |done, total| progress.update(done, total))?;
+        progress.finish();
+        PlaylistTrackPage {
+            total: items.len() as u32,
+            items,
+        }
+    } else {
+        playlists.items(playlist_id, limit.clamp(1, 100), offset)?
+    };
+    let offset = if all { 0 } else { offset };
+    ctx.output.playlist_tracks(page, offset)
+}
+
+fn stats(ctx: &AppContext, playlist_id: &str, top: usize) -> Result<()> {
+    let spotify = ctx.spotify()?;
+    let detail = spotify.playlists().get(playlist_id)?;
+    let mut progress = ctx.output.progress("Fetching tracks", None);
+    let items = spotify
+        .playlists()
+        .items_all_with(playlist_id, |done, total| progress.update(done, total))?;
+    progress.finish();
+
+    let mut artist_ids: Vec<String> = items
+        .iter()
+        .flat_map(|item| item.track.artist_ids.iter().cloned())
+        .collect();
+    artist_ids.sort();
+    artist_ids.dedup();
+    let genres: HashMap<String, Vec<String>> = spotify
+        .artists()
+        .several(&artist_ids)?
+        .into_iter()
+        .map(|artist| (artist.id, artist.genres))
+        .collect();
+
+    let stats = playlist_stats(detail.id, detail.name, &items, &genres, top);
+    ctx.output.playlist_stats(stats)
+}
+
+/// Flag tracks that do not play in the user's market, or play as a relinked version.
+fn audit(ctx: &AppContext, playlist_id: &str, name: String, replace: bool) -> Result<()> {
+    let (tracks, length) = ctx.spotify()?.playlists().availability(playlist_id)?;
+    let checked = tracks.len();
+    let mut replacements: HashMap<String, Option<String>> = HashMap::new();
+    let mut entries = Vec::new();
+    for item in tracks {
+        let status = if !item.playable {
+            let replacement = match item.isrc {
+                Some(isrc) => match replacements.get(&isrc) {
+                    Some(found) => found.clone(),
+                    None => {
+                        let found = find_replacement(ctx, &isrc, &item.uri)?;
+                        replacements.insert(isrc, found.clone());
+                        found
+                    }
+                },
+                None => None,
+            };
+            AuditStatus::Unavailable {
+                reason: item.restriction,
+                replacement,
+                replaced: false,
+            }
+        } else if let Some(from) = item.linked_from {
+            AuditStatus::Relinked { from }
+        } else {
+            continue;
+        };
+        entries.push(AuditEntry {
+            position: item.position,
+            uri: item.uri,
+            track: item.track,
+            status,
+        });
+    }
+
+    if replace {
+        for operation in replacement_operations(playlist_id, length, &entries) {
+            oplog::perform(ctx, operation)?;
+        }
+        for entry in &mut entries {
+            if let AuditStatus::Unavailable {
+                replacement: Some(_),
+                replaced,
+                ..
+            } = &mut entry.status
+            {
+                *replaced = !ctx.dry_run;
+            }
+        }
+    }
+
+    ctx.output.playlist_audit(PlaylistAudit {
+        playlist_id: playlist_id.to_string(),
+        name,
+        checked,
+        entries,
+    })
+}
+
+/// A playable track with the same ISRC (the same recording) as an unavailable one.
+fn find_replacement(ctx: &AppContext, isrc: &str, dead_uri: &str) -> Result<Option<String>> {
+    let (items, _) = ctx.spotify()?.search().search_page(
+        &format!("isrc:{isrc}"),
+        SearchType::Track,
+        10,
+        0,
+        true,
+    )?;
+    Ok(pick_replacement(items, dead_uri).map(|item| item.uri))
+}
+
+fn pick_replacement(items: Vec<SearchItem>, dead_uri: &str) -> Option<SearchItem> {
+    items
+        .into_iter()
+        .find(|item| item.uri != dead_uri && item.playable != Some(false))
+}
+
+/// Operations putting each replacement where its unavailable track first appears.
+///
+/// The replacement is appended, moved into place, and every copy of the dead track
+/// removed. Going from the end of the playlist keeps earlier positions valid.
+fn replacement_operations(
+    playlist_id: &str,
+    length: usize,
+    entries: &[AuditEntry],
+) -> Vec<LoggedOperation> {
+    // (first 1-based position, dead uri, copies, replacement uri)
+    let mut groups: Vec<(usize, &str, usize, &str)> = Vec::new();
+    for entry in entries {
+        let AuditStatus::Unavailable {
+            replacement: Some(replacement),
+            ..
+        } = &entry.status
+        else {
+            continue;
+        };
+        match groups.iter_mut().find(|group| group.1 == entry.uri) {
+            Some(group) => group.2 += 1,
+            None => groups.push((entry.position, &entry.uri, 1, replacement)),
+        }
+    }
+
+    let mut length = length;
+    let mut operations = Vec::new();
+    for (position, dead, copies, replacement) in groups.into_iter().rev() {
+        operations.push(LoggedOperation::PlaylistAdd {
+            playlist_id: playlist_id.to_string(),
+            uris: vec![replacement.to_string()],
+        });
+        operations.push(LoggedOperation::PlaylistMove {
+            playlist_id: playlist_id.to_string(),
+            range_start: length as u32,
+            insert_before: (position - 1) as u32,
+        });
+        operations.push(LoggedOperation::PlaylistRemove {
+            playlist_id: playlist_id.to_string(),
+            uris: vec![dead.to_string()],
+        });
+        length = length + 1 - copies;
+    }
+    operations
+}
+
+fn playlist_stats(
+    id: String,
+    name: String,
+    items: &[PlaylistTrack],
+    genres: &HashMap<String, Vec<String>>,
+    top: usize,
+) -> PlaylistStats {
+    let mut artists = HashMap::new();
+    let mut decades = HashMap::new();
+    let mut genre_counts = HashMap::new();
+    let mut duration_ms = 0u64;
+    let mut popularity = (0u64, 0u64);
+    let mut explicit = 0;
+    for item in items {
+        duration_ms += u64::from(item.track.duration_ms.unwrap_or(0));
+        for artist in &item.track.artists {
+            *artists.entry(artist.clone()).or_insert(0) += 1;
+        }
+        if let Some(decade) = item.release_date.as_deref().and_then(decade) {
+            *decades.entry(decade).or_insert(0) += 1;
+        }
+        let mut track_genres: Vec<&String> = item
+            .track
+            .artist_ids
+            .iter()
+            .filter_map(|id| genres.get(id))
+            .flatten()
+            .collect();
+        track_genres.sort();
+        track_genres.dedup();
+        for genre in track_genres {
+            *genre_counts.entry(genre.clone()).or_insert(0) += 1;
+        }
+        if let Some(value) = item.popularity {
+            popularity.0 += u64::from(value);
+            popularity.1 += 1;
+        }
+        if item.explicit == Some(true) {
+            explicit += 1;
+        }
+    }
+
+    let mut decades = tallies(decades);
+    decades.sort_by(|a, b| a.name.cmp(&b.name));
+    PlaylistStats {
+        id,
+        name,
+        tracks: items.len(),
+        duration_ms,
+        artists: top_tallies(artists, top),
+        decades,
+        average_popularity: (popularity.1 > 0).then(|| popularity.0 as f32 / popularity.1 as f32),
+        explicit,
+        genres: top_tallies(genre_counts, top),
+    }
+}
+
+/// `1994-05-01` (or `1994`) -> `1990s`.
+fn decade(release_date: &str) -> Option<String> {
+    let year: u32 = release_date.get(..4)?.parse().ok()?;
+    Some(format!("{}s", year / 10 * 10))
+}
+
+fn tallies(counts: HashMap<String, usize>) -> Vec<Tally> {
+    counts
+        .into_iter()
+        .map(|(name, count)| Tally { name, count })
+        .collect()
+}
+
+/// Highest counts first, ties by name, cut to `top`.
+fn top_tallies(counts: HashMap<String, usize>, top: usize) -> Vec<Tally> {
+    let mut tallies = tallies(counts);
+    tallies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    tallies.truncate(top);
+    tallies
+}
+
+#[derive(Debug, Clone, Copy)]
+struct UserFilter {
+    owned_only: bool,
+    min_tracks: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UserAction {
+    Follow(usize),
+    Pin(usize),
+}
+
+fn user_playlists(
+    ctx: &AppContext,
+    user_id: &str,
+    all: bool,
+    limit: u32,
+    offset: u32,
+    filter: UserFilter,
+    action: Option<UserAction>,
+) -> Result<()> {
+    let playlists = ctx.spotify()?.playlists();
+    let mut page = if all {
+        let items = playlists.user_all(user_id)?;
+        UserPlaylistPage {
+            total: items.len() as u32,
+            items,
+        }
+    } else {
+        playlists.user_page(user_id, limit.clamp(1, 50), offset)?
+    };
+    let fetched = page.items.len() as u32;
+    filter_user_playlists(&mut page.items, user_id, filter);
+
+    let Some(action) = action else {
+        let next_offset = Some(offset + fetched).filter(|next| !all && *next < page.total);
+        return ctx.output.user_playlists(page, next_offset);
+    };
+    let index = match action {
+        UserAction::Follow(index) | UserAction::Pin(index) => index,
+    };
+    validate_pick(index, page.items.len())?;
+    let playlist = page.items.swap_remove(index - 1).playlist;
+    match action {
+        UserAction::Follow(_) => {
+            oplog::perform(
+                ctx,
+                LoggedOperation::PlaylistFollow {
+                    playlist_id: playlist.id.clone(),
+                },
+            )?;
+            let message = format!("Followed: {}", playlist.name);
+            ctx.output.action("playlist_follow", &message)
+        }
+        UserAction::Pin(_) => {
+            let url = format!("https://open.spotify.com/playlist/{}", playlist.id);
+            ctx.cache
+                .pin_store()
+                .add(playlist.name.clone(), url.clone())?;
+            let message = format!("Pinned: {} -> {}", playlist.name, url);
+            ctx.output.action("pin_add", &message)
+        }
+    }
+}
+
+/// Apply `--owned-only` and `--min-tracks`; indexes refer to the filtered list.
+fn filter_user_playlists(items: &mut Vec<UserPlaylist>, user_id: &str, filter: UserFilter) {
+    if filter.owned_only {
+        items.retain(|item| item.owner_id.as_deref() == Some(user_id));
+    }
+    if let Some(min) = filter.min_tracks {
+        items.retain(|item| item.tracks_total.unwrap_or(0) >= min);
+    }
+}
+
+fn contains_track(ctx: &AppContext, playlist_id: &str, track: &str) -> Result<()> {
+    let track = target::resolve_target(ctx, track, SearchType::Track)?;
+    let track_id = parse_id(&track, ResourceType::Track)?;
+    let mut progress = ctx.output.progress("Scanning playlist", None);
+    let items = ctx
+        .spotify()?
+        .playlists()
+        .items_all_with(playlist_id, |done, total| progress.update(done, total))?;
+    progress.finish();
+    let positions = track_positions(&items, &track_id);
+    ctx.output
+        .playlist_contains(playlist_id, &format!("spotify:track:{track_id}"), positions)
+}
+
+enum MoveAnchor {
+    Before(String),
+    After(String),
+}
+
+/// Where a `playlist move` argument landed in the playlist.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TrackMatch {
+    Exact(usize),
+    Fuzzy(usize),
+}
+
+impl TrackMatch {
+    fn index(self) -> usize {
+        match self {
+            TrackMatch::Exact(index) | TrackMatch::Fuzzy(index) => index,
+        }
+    }
+}
+
+fn move_track(ctx: &AppContext, playlist_id: &str, track: &str, anchor: MoveAnchor) -> Result<()> {
+    let mut progress = ctx.output.progress("Scanning playlist", None);
+    let items = ctx
+        .spotify()?
+        .playlists()
+        .items_all_with(playlist_id, |done, total| progress.update(done, total))?;
+    progress.finish();
+
+    let from = find_move_track(ctx, &items, track)?;
+    let (anchor, after) = match &anchor {
+        MoveAnchor::Before(anchor) => (anchor.as_str(), false),
+        MoveAnchor::After(anchor) => (anchor.as_str(), true),
+    };
+    let to = match anchor.trim().parse::<usize>() {
+        Ok(position) if (1..=items.len()).contains(&position) => position - 1,
+        Ok(position) => bail!(ErrorKind::Validation.error(format!(
+            "position out of range; got {position}, max {}",
+            items.len()
+        ))),
+        Err(_) => find_move_track(ctx, &items, anchor)?,
+    };
+
+    let label = format_track(&items[from].track);
+    let Some((range_start, insert_before)) = move_slots(from, to, after) else {
+        let message = format!("Already in place: {} (#{})", label, from + 1);
+        return ctx.output.action("playlist_move", &message);
+    };
+    let operation = LoggedOperation::PlaylistMove {
+        playlist_id: playlist_id.to_string(),
+        range_start,
+        insert_before,
+    };
+    let landed = match operation.inverse() {
+        Some(LoggedOperation::PlaylistMove { range_start, .. }) => range_start,
+        _ => insert_before,
+    };
+    oplog::perform(ctx, operation)?;
+    let message = format!("Moved: {} #{} -> #{}", label, from + 1, landed + 1);
+    ctx.output.action("playlist_move", &message)
+}
+
+/// Resolve a track argument to its index, confirming fuzzy name matches.
+fn find_move_track(ctx: &AppContext, items: &[PlaylistTrack], input: &str) -> Result<usize> {
+    let input = target::resolve_target(ctx, input, SearchType::Track)?;
+    let Some(found) = locate_track(items, &input) else {
+        bail!(ErrorKind::NotFound.error(format!("no track in the playlist matches '{input}'")));
+    };
+    let index = found.index();
+    if let TrackMatch::Fuzzy(_) = found {
+        let label = format_track(&items[index].track);
+        let prompt = format!("Use '{}' (#{})?", label, index + 1);
+        ensure_confirmed(ctx, &prompt, "pass --yes or an exact track URI")?;
+    }
+    Ok(index)
+}
+
+/// Find a track by URI, URL, or id first, then by best fuzzy name match.
+fn locate_track(items: &[PlaylistTrack], input: &str) -> Option<TrackMatch> {
+    if let Ok(id) = parse_id(input, ResourceType::Track)
+        && let Some(index) = items.iter().position(|item| item.track.id == id)
+    {
+        return Some(TrackMatch::Exact(index));
+    }
+
+    let mut best: Option<(usize, f32)> = None;
+    for (index, item) in items.iter().enumerate() {
+        let score = [item.track.name.clone(), format_track(&item.track)]
+            .iter()
+            .filter_map(|candidate| match_score(input, candidate))
+            .fold(0.0, f32::max);
+        if score > best.map_or(0.0, |(_, best)| best) {
+            best = Some((index, score));
+        }
+    }
+    best.map(|(index, score)| {
+        if score >= 1.0 {
+            TrackMatch::Exact(index)
+        } else {
+            TrackMatch::Fuzzy(index)
+        }
+    })
+}
+
+/// Spotify `range_start` / `insert_before` for moving `from` next to `to`; `None` when it would not move.
+fn move_slots(from: usize, to: usize, after: bool) -> Option<(u32, u32)> {
+    let insert_before = to + usize::from(after);
+    if insert_before == from || insert_before == from + 1 {
+        return None;
+    }
+    Some((from as u32, insert_before as u32))
+}
+
+pub(crate) fn track_positions(items: &[PlaylistTrack], track_id: &str) -> Vec<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.track.id == track_id)
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+fn build_cuesheet(title: String, performer: Option<String>, items: Vec<CueItem>) -> Cuesheet {
+    let mut start_ms = 0u64;
+    let entries = items
+        .into_iter()
+        .map(|item| {
+            let entry = CueEntry {
+                title: item.title,
+                performer: item.performer,
+                uri: item.uri,
+                start_ms,
+                duration_ms: item.duration_ms,
+            };
+            start_ms += item.duration_ms;
+            entry
+        })
+        .collect();
+    Cuesheet {
+        title,
+        performer,
+        entries,
+    }
+}
+
 fn resolve_playlist(
     ctx: &AppContext,
     query: Option<&str>,
@@ -207,6 +1254,26 @@ fn resolve_playlist(
     user: bool,
     pick: Option<usize>,
 ) -> Result<crate::domain::search::SearchItem> {
+    if !last && query.is_some_and(target::is_now) {
+        let id = target::now_id(ctx, SearchType::Playlist)?;
+        let detail = ctx.spotify()?.playlists().get(&id)?;
+        return Ok(SearchItem {
+            id: detail.id,
+            name: detail.name,
+            uri: detail.uri,
+            kind: SearchType::Playlist,
+            artists: Vec::new(),
+            album: None,
+            duration_ms: None,
+            owner: detail.owner,
+            score: None,
+            playable: None,
+            linked_from: None,
+            saved: None,
+            explicit: None,
+            popularity: None,
+        });
+    }
     if user
         && !last
         && let Some(query) = query
@@ -220,7 +1287,7 @@ fn resolve_playlist(
         validate_pick(pick, results.items.len())?;
     }
     let Some(item) = pick_result(&results.items, pick).cloned() else {
-        bail!("no playlist results");
+        bail!(ErrorKind::NotFound.error("no playlist results"));
     };
     Ok(item)
 }
@@ -237,7 +1304,10 @@ fn resolve_results(
     }
 
     let Some(query) = query else {
-        bail!("missing playlist query; use --last to reuse cached search results");
+        bail!(
+            ErrorKind::Validation
+                .error("missing playlist query; use --last to reuse cached search results")
+        );
     };
 
     let query = crate::action::playlist::build_query(query);
@@ -275,6 +1345,11 @@ fn resolve_cached_playlist_match(
                         duration_ms: None,
                         owner: playlist.owner,
                         score: Some(score),
+                        playable: None,
+                        linked_from: None,
+                        saved: None,
+                        explicit: None,
+                        popularity: None,
                     },
                     score,
                     name_lower: name.to_lowercase(),
@@ -302,6 +1377,11 @@ fn resolve_cached_playlist_match(
                     duration_ms: None,
                     owner: None,
                     score: Some(score),
+                    playable: None,
+                    linked_from: None,
+                    saved: None,
+                    explicit: None,
+                    popularity: None,
                 },
                 score,
                 name_lower: name.to_lowercase(),
@@ -402,13 +1482,13 @@ fn load_cached(
 ) -> Result<crate::domain::search::SearchResults> {
     let cached = ctx.cache.search_store().load()?;
     let Some(cached) = cached else {
-        bail!("no cached search; run `spotify-cli search <query>`");
+        bail!(ErrorKind::NotFound.error("no cached search; run `spotify-cli search <query>`"));
     };
     if cached.results.kind != expected {
-        bail!(
+        bail!(ErrorKind::Validation.error(format!(
             "cached search is {}; run `spotify-cli search playlist <query>`",
             search_type_label(cached.results.kind)
-        );
+        )));
     }
     Ok(cached.results)
 }
@@ -438,47 +1518,18 @@ fn pick_result(
 
 fn validate_pick(pick: usize, len: usize) -> Result<()> {
     if pick == 0 {
-        bail!("pick must be 1 or greater");
+        bail!(ErrorKind::Validation.error("pick must be 1 or greater"));
     }
     if pick > len {
-        bail!("pick out of range; got {pick}, max {len}");
+        bail!(ErrorKind::Validation.error(format!("pick out of range; got {pick}, max {len}")));
     }
     Ok(())
 }
 
 pub(crate) fn parse_playlist_id(input: &str) -> Option<String> {
-    let cleaned: String = input.split_whitespace().collect();
-    let cleaned = cleaned.trim();
-
-    if cleaned.starts_with("spotify:") {
-        if let Some(uri) = cleaned.strip_prefix("spotify:playlist:") {
-            return Some(split_playlist_id(uri));
-        }
-        if let Some(index) = cleaned.find(":playlist:") {
-            let uri = &cleaned[index + ":playlist:".len()..];
-            return Some(split_playlist_id(uri));
-        }
-    }
-
-    if cleaned.starts_with("http")
-        && let Ok(url) = url::Url::parse(cleaned)
-        && let Some(segments) = url.path_segments()
-    {
-        let segments: Vec<_> = segments.collect();
-        if segments.len() >= 2 && segments[0] == "playlist" {
-            return Some(segments[1].to_string());
-        }
-    }
-
-    None
-}
-
-fn split_playlist_id(value: &str) -> String {
-    value
-        .split([':', '?', '#'])
-        .next()
-        .unwrap_or(value)
-        .to_string()
+    parse_spotify_url(input)
+        .filter(|resource| resource.resource_type == ResourceType::Playlist)
+        .map(|resource| resource.id)
 }
 
 fn format_track(track: &crate::domain::track::Track) -> String {
@@ -489,6 +1540,21 @@ fn format_track(track: &crate::domain::track::Track) -> String {
     }
 }
 
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CueFormatArg {
+    Cue,
+    Tracklist,
+}
+
+impl From<CueFormatArg> for CueFormat {
+    fn from(value: CueFormatArg) -> Self {
+        match value {
+            CueFormatArg::Cue => CueFormat::Cue,
+            CueFormatArg::Tracklist => CueFormat::Tracklist,
+        }
+    }
+}
+
 #[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum PlaylistSort {
     Name,
@@ -499,9 +1565,7 @@ pub(crate) enum PlaylistSort {
 
 fn sort_playlists(playlists: &mut [Playlist], sort: PlaylistSort) {
     match sort {
-        PlaylistSort::Name => {
-            playlists.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
-        }
+        PlaylistSort::Name => playlists.sort_by_key(|a| a.name.to_lowercase()),
         PlaylistSort::Owner => playlists.sort_by(|a, b| {
             let a_owner = a.owner.as_deref().unwrap_or("").to_lowercase();
             let b_owner = b.owner.as_deref().unwrap_or("").to_lowercase();
@@ -526,10 +1590,66 @@ fn sort_playlists(playlists: &mut [Playlist], sort: PlaylistSort) {
 
 #[cfg(test)]
 mod tests {
-    use super::{PlaylistSort, parse_playlist_id, sort_playlists};
+    use super::{
+        PlaylistSort, TrackMatch, UserFilter, build_cuesheet, decade, filter_user_playlists,
+        find_playlists, locate_track, move_slots, parse_playlist_id, pick_replacement,
+        playlist_stats, replacement_operations, resolve_visibility, sort_playlists,
+        track_positions,
+    };
     use crate::action::playlist::{build_query, match_from_items};
-    use crate::domain::playlist::Playlist;
+    use crate::domain::cuesheet::CueItem;
+    use crate::domain::oplog::{LoggedOperation, PlaylistVisibility};
+    use crate::domain::playlist::{
+        AuditEntry, AuditStatus, MatchField, Playlist, PlaylistTrack, UserPlaylist,
+    };
+    use crate::domain::search::{SearchItem, SearchType};
+    use crate::domain::stats::Tally;
+    use crate::domain::track::Track;
     use crate::error::Result;
+    use std::collections::HashMap;
+
+    fn visibility(public: Option<bool>, collaborative: bool) -> PlaylistVisibility {
+        PlaylistVisibility {
+            public,
+            collaborative,
+        }
+    }
+
+    #[test]
+    fn resolve_visibility_keeps_collaborative_playlists_private() {
+        let public = visibility(Some(true), false);
+        let (next, note) = resolve_visibility(public, None, Some(true));
+        assert_eq!(next, visibility(Some(false), true));
+        assert!(note.is_some());
+
+        let shared = visibility(Some(false), true);
+        let (next, note) = resolve_visibility(shared, Some(true), None);
+        assert_eq!(next, visibility(Some(true), false));
+        assert!(note.is_some());
+
+        let (next, note) = resolve_visibility(shared, None, Some(false));
+        assert_eq!(next, visibility(Some(false), false));
+        assert!(note.is_none());
+
+        let (next, note) = resolve_visibility(visibility(Some(false), false), None, Some(true));
+        assert_eq!(next, visibility(Some(false), true));
+        assert!(note.is_none());
+    }
+
+    #[test]
+    fn playlist_edit_inverse_restores_flags() {
+        let operation = LoggedOperation::PlaylistEdit {
+            playlist_id: "p".to_string(),
+            from: visibility(Some(true), false),
+            to: visibility(Some(false), true),
+        };
+        assert_eq!(operation.detail(), "public -> private, collaborative");
+        let Some(LoggedOperation::PlaylistEdit { from, to, .. }) = operation.inverse() else {
+            panic!("expected a playlist edit");
+        };
+        assert_eq!(from, visibility(Some(false), true));
+        assert_eq!(to, visibility(Some(true), false));
+    }
 
     #[test]
     fn build_query_wraps_tokens() {
@@ -545,6 +1665,7 @@ mod tests {
                 owner: None,
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
             Playlist {
                 id: "1".to_string(),
@@ -552,6 +1673,7 @@ mod tests {
                 owner: None,
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
         ];
         sort_playlists(&mut playlists, PlaylistSort::Name);
@@ -567,6 +1689,7 @@ mod tests {
                 owner: Some("Zed".to_string()),
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
             Playlist {
                 id: "2".to_string(),
@@ -574,6 +1697,7 @@ mod tests {
                 owner: Some("Amy".to_string()),
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
         ];
         sort_playlists(&mut playlists, PlaylistSort::Owner);
@@ -589,6 +1713,7 @@ mod tests {
                 owner: None,
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
             Playlist {
                 id: "2".to_string(),
@@ -596,6 +1721,7 @@ mod tests {
                 owner: None,
                 collaborative: false,
                 public: Some(false),
+                description: None,
             },
         ];
         sort_playlists(&mut playlists, PlaylistSort::Public);
@@ -611,6 +1737,7 @@ mod tests {
                 owner: None,
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
             Playlist {
                 id: "2".to_string(),
@@ -618,6 +1745,7 @@ mod tests {
                 owner: None,
                 collaborative: true,
                 public: Some(true),
+                description: None,
             },
         ];
         sort_playlists(&mut playlists, PlaylistSort::Collaborative);
@@ -633,6 +1761,7 @@ mod tests {
                 owner: Some("Me".to_string()),
                 collaborative: false,
                 public: Some(false),
+                description: None,
             },
             Playlist {
                 id: "2".to_string(),
@@ -640,6 +1769,7 @@ mod tests {
                 owner: Some("Other".to_string()),
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
         ];
 
@@ -658,6 +1788,7 @@ mod tests {
                 owner: Some("Me".to_string()),
                 collaborative: false,
                 public: Some(false),
+                description: None,
             },
             Playlist {
                 id: "2".to_string(),
@@ -665,6 +1796,7 @@ mod tests {
                 owner: Some("Other".to_string()),
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
         ];
 
@@ -683,6 +1815,7 @@ mod tests {
                 owner: Some("Me".to_string()),
                 collaborative: false,
                 public: Some(false),
+                description: None,
             },
             Playlist {
                 id: "2".to_string(),
@@ -690,6 +1823,7 @@ mod tests {
                 owner: Some("Me".to_string()),
                 collaborative: false,
                 public: Some(false),
+                description: None,
             },
         ];
 
@@ -708,6 +1842,7 @@ mod tests {
                 owner: Some("Other".to_string()),
                 collaborative: false,
                 public: Some(true),
+                description: None,
             },
             Playlist {
                 id: "2".to_string(),
@@ -715,6 +1850,7 @@ mod tests {
                 owner: Some("Me".to_string()),
                 collaborative: false,
                 public: Some(false),
+                description: None,
             },
         ];
 
@@ -724,6 +1860,127 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn build_cuesheet_accumulates_offsets() {
+        let item = |uri: &str, duration_ms: u64| CueItem {
+            title: format!("Track {uri}"),
+            performer: "Artist".to_string(),
+            uri: uri.to_string(),
+            duration_ms,
+        };
+        let sheet = build_cuesheet(
+            "Mix".to_string(),
+            None,
+            vec![
+                item("spotify:track:1", 1000),
+                item("", 0),
+                item("spotify:local:Artist:Album:Demo:700", 700),
+                item("spotify:episode:3", 500),
+            ],
+        );
+        let starts: Vec<u64> = sheet.entries.iter().map(|entry| entry.start_ms).collect();
+        assert_eq!(starts, vec![0, 1000, 1000, 1700]);
+        assert_eq!(sheet.entries[3].uri, "spotify:episode:3");
+        assert_eq!(sheet.duration_ms(), 2200);
+    }
+
+    #[test]
+    fn track_positions_finds_duplicates() {
+        let item = |id: &str| PlaylistTrack {
+            track: Track {
+                id: id.to_string(),
+                name: format!("Track {id}"),
+                artists: Vec::new(),
+                artist_ids: Vec::new(),
+                album: None,
+                album_id: None,
+                duration_ms: None,
+            },
+            added_at: None,
+            added_by: None,
+            popularity: None,
+            explicit: None,
+            release_date: None,
+        };
+        let items = vec![item("1"), item("2"), item("1")];
+        assert_eq!(track_positions(&items, "1"), vec![1, 3]);
+        assert!(track_positions(&items, "9").is_empty());
+    }
+
+    fn named(id: &str, name: &str, artist: &str) -> PlaylistTrack {
+        PlaylistTrack {
+            track: Track {
+                id: id.to_string(),
+                name: name.to_string(),
+                artists: vec![artist.to_string()],
+                artist_ids: Vec::new(),
+                album: None,
+                album_id: None,
+                duration_ms: None,
+            },
+            added_at: None,
+            added_by: None,
+            popularity: None,
+            explicit: None,
+            release_date: None,
+        }
+    }
+
+    #[test]
+    fn locate_track_prefers_ids_then_fuzzy_names() {
+        let items = vec![
+            named("a1", "Roygbiv", "Boards of Canada"),
+            named("b2", "Dayvan Cowboy", "Boards of Canada"),
+        ];
+        assert_eq!(
+            locate_track(&items, "spotify:track:b2"),
+            Some(TrackMatch::Exact(1))
+        );
+        assert_eq!(locate_track(&items, "roygbiv"), Some(TrackMatch::Exact(0)));
+        assert_eq!(locate_track(&items, "dayvan"), Some(TrackMatch::Fuzzy(1)));
+        assert_eq!(locate_track(&items, "nothing here"), None);
+    }
+
+    #[test]
+    fn move_slots_and_inverse_round_trip() {
+        use crate::domain::oplog::LoggedOperation;
+
+        let apply = |list: &mut Vec<char>, start: u32, before: u32| {
+            let item = list.remove(start as usize);
+            let before = before as usize;
+            let at = if before > start as usize {
+                before - 1
+            } else {
+                before
+            };
+            list.insert(at, item);
+        };
+        let original: Vec<char> = "ABCDE".chars().collect();
+        for (from, to, after) in [(0, 3, false), (4, 1, true), (1, 4, true), (3, 0, false)] {
+            let (range_start, insert_before) = move_slots(from, to, after).unwrap();
+            let mut list = original.clone();
+            apply(&mut list, range_start, insert_before);
+            let Some(LoggedOperation::PlaylistMove {
+                range_start,
+                insert_before,
+                ..
+            }) = (LoggedOperation::PlaylistMove {
+                playlist_id: "p".to_string(),
+                range_start,
+                insert_before,
+            })
+            .inverse()
+            else {
+                panic!("move has an inverse");
+            };
+            apply(&mut list, range_start, insert_before);
+            assert_eq!(list, original);
+        }
+        assert_eq!(move_slots(2, 2, false), None);
+        assert_eq!(move_slots(2, 1, true), None);
+        assert_eq!(move_slots(2, 3, false), None);
+    }
+
     #[test]
     fn parse_playlist_id_from_uri() {
         let id = parse_playlist_id("spotify:playlist:abc123");
@@ -753,4 +2010,213 @@ mod tests {
         let id = parse_playlist_id("not a playlist");
         assert!(id.is_none());
     }
+
+    #[test]
+    fn filter_user_playlists_applies_owner_and_min_tracks() {
+        let item = |id: &str, owner_id: &str, tracks_total: Option<u32>| UserPlaylist {
+            playlist: Playlist {
+                id: id.to_string(),
+                name: format!("List {id}"),
+                owner: None,
+                collaborative: false,
+                public: Some(true),
+                description: None,
+            },
+            owner_id: Some(owner_id.to_string()),
+            tracks_total,
+        };
+        let items = vec![
+            item("1", "alice", Some(30)),
+            item("2", "bob", Some(40)),
+            item("3", "alice", Some(5)),
+            item("4", "alice", None),
+        ];
+
+        let mut owned = items.clone();
+        let filter = UserFilter {
+            owned_only: true,
+            min_tracks: None,
+        };
+        filter_user_playlists(&mut owned, "alice", filter);
+        let ids: Vec<_> = owned.iter().map(|item| item.playlist.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3", "4"]);
+
+        let mut sized = items;
+        let filter = UserFilter {
+            owned_only: true,
+            min_tracks: Some(10),
+        };
+        filter_user_playlists(&mut sized, "alice", filter);
+        let ids: Vec<_> = sized.iter().map(|item| item.playlist.id.as_str()).collect();
+        assert_eq!(ids, vec!["1"]);
+    }
+
+    #[test]
+    fn decade_reads_the_release_year() {
+        assert_eq!(decade("1994-05-01").as_deref(), Some("1990s"));
+        assert_eq!(decade("2020").as_deref(), Some("2020s"));
+        assert_eq!(decade("n/a"), None);
+    }
+
+    #[test]
+    fn playlist_stats_aggregates_tracks() {
+        let track = |id: &str, artist: &str, date: &str, popularity: u32, explicit: bool| {
+            let mut item = named(id, id, artist);
+            item.track.artist_ids = vec![artist.to_lowercase()];
+            item.track.duration_ms = Some(60_000);
+            item.release_date = Some(date.to_string());
+            item.popularity = Some(popularity);
+            item.explicit = Some(explicit);
+            item
+        };
+        let items = vec![
+            track("1", "A", "1994-01-01", 40, false),
+            track("2", "A", "1999", 60, true),
+            track("3", "B", "2005-03", 80, false),
+        ];
+        let genres = HashMap::from([
+            (
+                "a".to_string(),
+                vec!["trip hop".to_string(), "idm".to_string()],
+            ),
+            ("b".to_string(), vec!["idm".to_string()]),
+        ]);
+
+        let stats = playlist_stats("p1".to_string(), "Mix".to_string(), &items, &genres, 1);
+        assert_eq!(stats.tracks, 3);
+        assert_eq!(stats.duration_ms, 180_000);
+        assert_eq!(stats.explicit, 1);
+        assert_eq!(stats.average_popularity, Some(60.0));
+        assert_eq!(
+            stats.artists,
+            vec![Tally {
+                name: "A".to_string(),
+                count: 2
+            }]
+        );
+        assert_eq!(
+            stats.decades,
+            vec![
+                Tally {
+                    name: "1990s".to_string(),
+                    count: 2
+                },
+                Tally {
+                    name: "2000s".to_string(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(
+            stats.genres,
+            vec![Tally {
+                name: "idm".to_string(),
+                count: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn find_playlists_ranks_name_matches_above_descriptions() {
+        let playlist = |id: &str, name: &str, description: Option<&str>| Playlist {
+            id: id.to_string(),
+            name: name.to_string(),
+            owner: None,
+            collaborative: false,
+            public: None,
+            description: description.map(str::to_string),
+        };
+        let matches = find_playlists(
+            "focus",
+            vec![
+                playlist("1", "Morning", Some("Focus music for work")),
+                playlist("2", "Focus", None),
+                playlist("3", "Gym", Some("loud")),
+            ],
+        );
+        let ids: Vec<&str> = matches
+            .iter()
+            .map(|item| item.playlist.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["2", "1"]);
+        assert_eq!(matches[0].field, MatchField::Name);
+        assert_eq!(matches[1].field, MatchField::Description);
+    }
+
+    fn search_track(id: &str, playable: Option<bool>) -> SearchItem {
+        SearchItem {
+            id: id.to_string(),
+            name: id.to_string(),
+            uri: format!("spotify:track:{id}"),
+            kind: SearchType::Track,
+            artists: Vec::new(),
+            album: None,
+            duration_ms: None,
+            owner: None,
+            score: None,
+            playable,
+            linked_from: None,
+            saved: None,
+            explicit: None,
+            popularity: None,
+        }
+    }
+
+    fn dead(position: usize, id: &str, replacement: Option<&str>) -> AuditEntry {
+        AuditEntry {
+            position,
+            uri: format!("spotify:track:{id}"),
+            track: named(id, id, "Artist").track,
+            status: AuditStatus::Unavailable {
+                reason: Some("market".to_string()),
+                replacement: replacement.map(|id| format!("spotify:track:{id}")),
+                replaced: false,
+            },
+        }
+    }
+
+    #[test]
+    fn pick_replacement_skips_the_dead_track_and_unplayable_versions() {
+        let items = vec![
+            search_track("dead", None),
+            search_track("other", Some(false)),
+            search_track("live", Some(true)),
+        ];
+        let picked = pick_replacement(items, "spotify:track:dead").unwrap();
+        assert_eq!(picked.id, "live");
+        assert!(pick_replacement(Vec::new(), "spotify:track:dead").is_none());
+    }
+
+    #[test]
+    fn replacement_operations_work_back_from_the_end() {
+        // [x, a, x, b, a, x]: a and b are dead; a appears twice; c has no replacement.
+        let entries = vec![
+            dead(2, "a", Some("ra")),
+            dead(3, "c", None),
+            dead(4, "b", Some("rb")),
+            dead(5, "a", Some("ra")),
+        ];
+        let operations = replacement_operations("p", 6, &entries);
+        let expected = |uri: &str, dead: &str, start: u32, before: u32| {
+            vec![
+                LoggedOperation::PlaylistAdd {
+                    playlist_id: "p".to_string(),
+                    uris: vec![format!("spotify:track:{uri}")],
+                },
+                LoggedOperation::PlaylistMove {
+                    playlist_id: "p".to_string(),
+                    range_start: start,
+                    insert_before: before,
+                },
+                LoggedOperation::PlaylistRemove {
+                    playlist_id: "p".to_string(),
+                    uris: vec![format!("spotify:track:{dead}")],
+                },
+            ]
+        };
+        let mut want = expected("rb", "b", 6, 3);
+        // Replacing b keeps the length; a's two copies then shrink it by one.
+        want.extend(expected("ra", "a", 6, 1));
+        assert_eq!(operations, want);
+    }
 }
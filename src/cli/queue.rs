@@ -1,35 +1,428 @@
 //! Queue command handlers.
-use clap::Args;
+use anyhow::bail;
+use clap::{Args, Subcommand, ValueEnum};
 
 use crate::AppContext;
-use crate::error::Result;
+use crate::action::pending::{Applied, run_or_defer};
+use crate::action::{context, explicit, hooks, oplog, saved, target};
+use crate::cli::play::{ResourceType, parse_resource};
+use crate::cli::search::{apply_fuzzy_scores, fuzzy_query, search_item_label, validate_pick};
+use crate::cli::{choose, now_playing};
+use crate::domain::episode::Episode;
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::pending::PendingOperation;
+use crate::domain::queue::{QueueItem, QueueItemKind, SavedQueue};
+use crate::domain::search::SearchType;
+use crate::domain::track::Track;
+use crate::error::{ErrorKind, Result};
+use crate::time::unix_time;
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 100;
+/// Results offered by `queue add --search`.
+const SEARCH_LIMIT: u32 = 10;
 
 #[derive(Args, Debug)]
 pub struct QueueCommand {
     #[arg(long, value_name = "N", default_value_t = 10)]
     limit: u32,
+    #[arg(long, help = "Skip checking which tracks are saved")]
+    no_check: bool,
+    #[command(subcommand)]
+    action: Option<QueueAction>,
+}
+
+#[derive(Subcommand, Debug)]
+enum QueueAction {
+    List {
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        limit: u32,
+        #[arg(long, help = "Skip checking which tracks are saved")]
+        no_check: bool,
+    },
+    /// Queue a track or episode by link, or pick one from a search.
+    Add {
+        #[arg(
+            value_name = "TARGET",
+            required_unless_present = "search",
+            conflicts_with = "search",
+            help = "Track or episode URL, URI, id, or `now`"
+        )]
+        target: Option<String>,
+        #[arg(long, value_name = "QUERY", help = "Search for the item to queue")]
+        search: Option<String>,
+        #[arg(long = "type", value_enum, default_value_t = QueueKind::Track)]
+        kind: QueueKind,
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "search",
+            conflicts_with = "target",
+            help = "Queue the Nth search result instead of asking"
+        )]
+        pick: Option<usize>,
+    },
+    Play {
+        #[arg(value_name = "N", help = "Queue position from `queue list` to skip to")]
+        position: usize,
+    },
+    Clear {
+        #[arg(long, help = "Skip through queued tracks until the context resumes")]
+        hard: bool,
+    },
+    Save {
+        name: String,
+    },
+    Load {
+        name: String,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum QueueKind {
+    Track,
+    Episode,
 }
 
 pub fn handle(command: QueueCommand, ctx: &AppContext) -> Result<()> {
-    let limit = clamp_limit(command.limit);
-    let state = ctx.spotify()?.playback().queue(limit)?;
+    match command.action {
+        None => show(ctx, command.limit, !command.no_check),
+        Some(QueueAction::List { limit, no_check }) => {
+            show(ctx, limit, !(no_check || command.no_check))
+        }
+        Some(QueueAction::Add {
+            target,
+            search,
+            kind,
+            pick,
+        }) => match search {
+            Some(query) => add_from_search(ctx, &query, kind, pick),
+            None => add(ctx, target.as_deref().unwrap_or_default()),
+        },
+        Some(QueueAction::Play { position }) => play(ctx, position),
+        Some(QueueAction::Clear { hard }) => clear(ctx, hard),
+        Some(QueueAction::Save { name }) => save(ctx, name),
+        Some(QueueAction::Load { name }) => load(ctx, &name),
+    }
+}
+
+fn show(ctx: &AppContext, limit: u32, check: bool) -> Result<()> {
+    let limit = clamp_limit(limit);
+    let playback = ctx.spotify()?.playback();
+    let state = playback.queue(limit)?;
+    // The queue endpoint has no context; it is a decoration, so a failed lookup is skipped.
+    let status = playback.status().ok();
+    let progress_ms = status.as_ref().and_then(|status| status.progress_ms);
+    let playing_from = status.and_then(|status| status.context);
+    let playing_from = playing_from.map(|mut playing_from| {
+        context::resolve(ctx, &mut playing_from);
+        playing_from
+    });
     let mut items = Vec::new();
-    let now_playing_id = state.now_playing.as_ref().map(|track| track.id.clone());
+    let now_playing_id = state.now_playing.as_ref().map(|item| item.track.id.clone());
 
-    if let Some(track) = state.now_playing {
-        items.push(track);
+    if let Some(item) = state.now_playing {
+        items.push(item);
     }
-    for track in state.queue {
+    for item in state.queue {
         if items.len() >= limit as usize {
             break;
         }
-        items.push(track);
+        items.push(item);
+    }
+
+    let saved = if check {
+        check_saved_tracks(ctx, &items)
+    } else {
+        Vec::new()
+    };
+    let tracks: Vec<Track> = items.iter().map(|item| item.track.clone()).collect();
+    let starts = start_offsets(&tracks, now_playing_id.is_some(), progress_ms);
+    ctx.output.queue(
+        now_playing_id.as_deref(),
+        playing_from,
+        items,
+        saved,
+        starts,
+    )
+}
+
+/// Saved state of each queued track, aligned with `items`; episodes are left unknown.
+fn check_saved_tracks(ctx: &AppContext, items: &[QueueItem]) -> Vec<Option<bool>> {
+    let ids: Vec<String> = items
+        .iter()
+        .filter(|item| item.kind == QueueItemKind::Track)
+        .map(|item| item.track.id.clone())
+        .collect();
+    let mut checked = saved::check(ctx, &ids).into_iter();
+    items
+        .iter()
+        .map(|item| match item.kind {
+            QueueItemKind::Track => checked.next().flatten(),
+            QueueItemKind::Episode => None,
+        })
+        .collect()
+}
+
+/// Milliseconds from now until each item starts playing; the current track starts at 0.
+///
+/// Offsets after a track with an unknown duration are unknown too.
+fn start_offsets(
+    items: &[Track],
+    has_now_playing: bool,
+    progress_ms: Option<u32>,
+) -> Vec<Option<u64>> {
+    let mut next = Some(0u64);
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, track)| {
+            let start = next;
+            let remaining = if index == 0 && has_now_playing {
+                track
+                    .duration_ms
+                    .map(|ms| ms.saturating_sub(progress_ms.unwrap_or(0)))
+            } else {
+                track.duration_ms
+            };
+            next = next.zip(remaining).map(|(at, ms)| at + ms as u64);
+            start
+        })
+        .collect()
+}
+
+fn add(ctx: &AppContext, input: &str) -> Result<()> {
+    let input = target::resolve_target(ctx, input, SearchType::Track)?;
+    let resource = parse_resource(&input, &[ResourceType::Track, ResourceType::Episode])?;
+    let uri = resource.to_uri();
+    queue_uri(ctx, uri.clone(), &uri)
+}
+
+fn add_from_search(
+    ctx: &AppContext,
+    query: &str,
+    kind: QueueKind,
+    pick: Option<usize>,
+) -> Result<()> {
+    let mut candidates = search_candidates(ctx, query, kind)?;
+    if candidates.is_empty() {
+        bail!(ErrorKind::NotFound.error(format!("no results for '{query}'")));
+    }
+    let index = match pick {
+        Some(pick) => {
+            validate_pick(pick, candidates.len())?;
+            pick - 1
+        }
+        None => {
+            use std::io::IsTerminal;
+            if !std::io::stdin().is_terminal() {
+                bail!(
+                    ErrorKind::Validation.error("--search needs --pick N when not on a terminal")
+                );
+            }
+            let labels: Vec<String> = candidates.iter().map(|(_, label)| label.clone()).collect();
+            match choose(&format!("Results for '{query}':"), &labels, "Queue which?")? {
+                Some(index) => index,
+                None => return ctx.output.action("queue_add", "Nothing queued"),
+            }
+        }
+    };
+    let (uri, label) = candidates.swap_remove(index);
+    queue_uri(ctx, uri, &label)
+}
+
+/// URIs and labels of the search results, numbered as `search` lists them.
+fn search_candidates(
+    ctx: &AppContext,
+    query: &str,
+    kind: QueueKind,
+) -> Result<Vec<(String, String)>> {
+    let spotify = ctx.spotify()?;
+    match kind {
+        QueueKind::Track => {
+            let mut results = spotify.search().search(
+                &fuzzy_query(query),
+                SearchType::Track,
+                SEARCH_LIMIT,
+                false,
+            )?;
+            explicit::filter(ctx, &mut results.items);
+            apply_fuzzy_scores(query, &mut results);
+            Ok(results
+                .items
+                .iter()
+                .map(|item| (item.uri.clone(), search_item_label(item)))
+                .collect())
+        }
+        QueueKind::Episode => Ok(spotify
+            .episodes()
+            .search(query, SEARCH_LIMIT)?
+            .iter()
+            .map(|episode| (episode.uri.clone(), episode_label(episode)))
+            .collect()),
+    }
+}
+
+fn episode_label(episode: &Episode) -> String {
+    match episode.show.as_ref() {
+        Some(show) => format!("{} ({})", episode.name, show),
+        None => episode.name.clone(),
+    }
+}
+
+fn queue_uri(ctx: &AppContext, uri: String, label: &str) -> Result<()> {
+    let operation = PendingOperation::QueueTrack { uri };
+    let message = match run_or_defer(ctx, operation)? {
+        Applied::Now => format!("Queued: {label}"),
+        Applied::Deferred => format!("Offline; queued for later: {label}"),
+    };
+    ctx.output.action("queue_add", &message)
+}
+
+fn play(ctx: &AppContext, position: usize) -> Result<()> {
+    if position == 0 {
+        bail!(ErrorKind::Validation.error("position must be 1 or greater; 0 is the current track"));
+    }
+    let playback = ctx.spotify()?.playback();
+    let state = playback.queue(MAX_LIMIT)?;
+    if position > state.queue.len() {
+        bail!(ErrorKind::Validation.error(format!(
+            "position out of range; got {position}, max {}",
+            state.queue.len()
+        )));
+    }
+
+    eprintln!("warning: Spotify cannot jump within the queue; skipping {position} track(s)");
+    let count = position as u32;
+    oplog::perform(ctx, LoggedOperation::PlayerSkip { count })?;
+    if ctx.dry_run {
+        return Ok(());
+    }
+    hooks::track_changed(ctx);
+    now_playing::show_with_delay(ctx, 100)
+}
+
+fn clear(ctx: &AppContext, hard: bool) -> Result<()> {
+    if !hard {
+        bail!(ErrorKind::Unsupported.error("Spotify's API cannot remove queued tracks; use `queue clear --hard` to skip through them"));
+    }
+
+    let spotify = ctx.spotify()?;
+    let playback = spotify.playback();
+    let status = playback.status()?;
+    let state = playback.queue(MAX_LIMIT)?;
+    let context_ids = match status.context.as_ref() {
+        Some(context) => context_track_ids(ctx, &context.kind, &context.uri)?,
+        None => None,
+    };
+    let skips = clear_skips(
+        state.now_playing.as_ref(),
+        &state.queue,
+        context_ids.as_deref(),
+    );
+    if skips == 0 {
+        return ctx.output.action("queue_clear", "Queue already empty");
+    }
+
+    eprintln!(
+        "warning: emulating queue clear by skipping {skips} track(s); they will appear in recently played"
+    );
+    let count = skips as u32;
+    oplog::perform(ctx, LoggedOperation::PlayerSkip { count })?;
+    if ctx.dry_run {
+        return Ok(());
     }
+    hooks::track_changed(ctx);
+    let message = format!("Cleared queue: skipped {} tracks", skips);
+    ctx.output.action("queue_clear", &message)
+}
+
+/// Track ids of the playing context, when it is a playlist or album.
+fn context_track_ids(ctx: &AppContext, kind: &str, uri: &str) -> Result<Option<Vec<String>>> {
+    let id = uri.rsplit(':').next().unwrap_or_default();
+    let ids = match kind {
+        "playlist" => ctx
+            .spotify()?
+            .playlists()
+            .tracks(id)?
+            .into_iter()
+            .map(|track| track.id)
+            .collect(),
+        "album" => ctx
+            .spotify()?
+            .albums()
+            .get(id)?
+            .tracks
+            .into_iter()
+            .filter_map(|track| track.uri.rsplit(':').next().map(str::to_string))
+            .collect(),
+        _ => return Ok(None),
+    };
+    Ok(Some(ids))
+}
+
+/// Skips that leave the player on the first context item.
+///
+/// A manually queued current item needs one more skip, or the last manual entry keeps playing.
+fn clear_skips(
+    now_playing: Option<&QueueItem>,
+    queue: &[QueueItem],
+    context_ids: Option<&[String]>,
+) -> usize {
+    let queued = queued_before_context(queue, context_ids);
+    let current_is_manual = now_playing
+        .zip(context_ids)
+        .is_some_and(|(item, ids)| !ids.iter().any(|id| id == &item.track.id));
+    queued + usize::from(current_is_manual)
+}
 
-    ctx.output.queue(now_playing_id.as_deref(), items)
+/// Count leading queue entries that were queued manually rather than coming from the context.
+///
+/// Without a known context every entry is treated as manually queued.
+fn queued_before_context(queue: &[QueueItem], context_ids: Option<&[String]>) -> usize {
+    let Some(context_ids) = context_ids else {
+        return queue.len();
+    };
+    queue
+        .iter()
+        .take_while(|item| !context_ids.iter().any(|id| id == &item.track.id))
+        .count()
+}
+
+fn save(ctx: &AppContext, name: String) -> Result<()> {
+    let state = ctx.spotify()?.playback().queue(MAX_LIMIT)?;
+    let uris: Vec<String> = state.queue.into_iter().map(|item| item.uri).collect();
+    if uris.is_empty() {
+        bail!(ErrorKind::NotFound.error("queue is empty; nothing to save"));
+    }
+
+    let count = uris.len();
+    ctx.cache.queue_store().put(SavedQueue {
+        name: name.clone(),
+        uris,
+        saved_at: unix_time(),
+    })?;
+    let message = format!("Saved queue: {} ({} tracks)", name, count);
+    ctx.output.action("queue_save", &message)
+}
+
+fn load(ctx: &AppContext, name: &str) -> Result<()> {
+    let Some(saved) = ctx.cache.queue_store().get(name)? else {
+        bail!(ErrorKind::NotFound.error(format!("no saved queue named '{name}'")));
+    };
+
+    let mut deferred = 0;
+    for uri in &saved.uris {
+        let operation = PendingOperation::QueueTrack { uri: uri.clone() };
+        if run_or_defer(ctx, operation)? == Applied::Deferred {
+            deferred += 1;
+        }
+    }
+    let mut message = format!("Loaded queue: {} ({} tracks)", saved.name, saved.uris.len());
+    if deferred > 0 {
+        message.push_str(&format!("; {} deferred while offline", deferred));
+    }
+    ctx.output.action("queue_load", &message)
 }
 
 fn clamp_limit(limit: u32) -> u32 {
@@ -38,3 +431,106 @@ fn clamp_limit(limit: u32) -> u32 {
     }
     limit.min(MAX_LIMIT)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{clear_skips, episode_label, queued_before_context, start_offsets};
+    use crate::domain::episode::Episode;
+    use crate::domain::queue::{QueueItem, QueueItemKind};
+    use crate::domain::track::Track;
+
+    fn track(id: &str) -> Track {
+        Track {
+            id: id.to_string(),
+            name: id.to_string(),
+            artists: Vec::new(),
+            artist_ids: Vec::new(),
+            album: None,
+            album_id: None,
+            duration_ms: None,
+        }
+    }
+
+    fn queued(id: &str) -> QueueItem {
+        QueueItem {
+            kind: QueueItemKind::Track,
+            uri: format!("spotify:track:{id}"),
+            track: track(id),
+        }
+    }
+
+    fn timed(id: &str, duration_ms: u32) -> Track {
+        Track {
+            duration_ms: Some(duration_ms),
+            ..track(id)
+        }
+    }
+
+    #[test]
+    fn start_offsets_count_down_the_current_track() {
+        let items = vec![timed("now", 200_000), timed("q1", 180_000), timed("q2", 1)];
+        assert_eq!(
+            start_offsets(&items, true, Some(50_000)),
+            vec![Some(0), Some(150_000), Some(330_000)]
+        );
+    }
+
+    #[test]
+    fn start_offsets_without_now_playing_start_immediately() {
+        let items = vec![timed("q1", 180_000), timed("q2", 1)];
+        assert_eq!(
+            start_offsets(&items, false, Some(50_000)),
+            vec![Some(0), Some(180_000)]
+        );
+    }
+
+    #[test]
+    fn start_offsets_unknown_after_missing_duration() {
+        let items = vec![timed("now", 200_000), track("q1"), timed("q2", 1)];
+        assert_eq!(
+            start_offsets(&items, true, None),
+            vec![Some(0), Some(200_000), None]
+        );
+    }
+
+    #[test]
+    fn episode_label_names_the_show_when_known() {
+        let mut episode = Episode {
+            id: "e1".to_string(),
+            name: "Monday".to_string(),
+            uri: "spotify:episode:e1".to_string(),
+            show: Some("Daily".to_string()),
+            description: None,
+            duration_ms: 1000,
+            release_date: None,
+            resume_position_ms: None,
+            fully_played: false,
+        };
+        assert_eq!(episode_label(&episode), "Monday (Daily)");
+        episode.show = None;
+        assert_eq!(episode_label(&episode), "Monday");
+    }
+
+    #[test]
+    fn queued_before_context_stops_at_context_track() {
+        let queue = vec![queued("q1"), queued("q2"), queued("c1"), queued("c2")];
+        let context = vec!["c1".to_string(), "c2".to_string()];
+        assert_eq!(queued_before_context(&queue, Some(&context)), 2);
+    }
+
+    #[test]
+    fn queued_before_context_without_context_skips_all() {
+        let queue = vec![queued("q1"), queued("q2")];
+        assert_eq!(queued_before_context(&queue, None), 2);
+    }
+
+    #[test]
+    fn clear_skips_passes_a_manually_queued_current_item() {
+        let queue = vec![queued("q1"), queued("q2"), queued("c2")];
+        let context = vec!["c1".to_string(), "c2".to_string()];
+        assert_eq!(clear_skips(Some(&queued("q0")), &queue, Some(&context)), 3);
+        assert_eq!(clear_skips(Some(&queued("c1")), &queue, Some(&context)), 2);
+        assert_eq!(clear_skips(None, &queue, Some(&context)), 2);
+        assert_eq!(clear_skips(Some(&queued("q0")), &queue, None), 3);
+    }
+}
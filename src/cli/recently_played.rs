@@ -1,37 +1,204 @@
 //! Recently played command handlers.
+use anyhow::bail;
 use clap::Args;
 
 use crate::AppContext;
-use crate::error::Result;
+use crate::action::history;
+use crate::domain::search::SearchItem;
+use crate::domain::session::PlayedTrack;
+use crate::error::{ErrorKind, Result};
+use crate::output::human::local_utc_offset;
+use crate::spotify::cancel;
+use crate::time::{days_from_civil, unix_time};
 
 const DEFAULT_LIMIT: u32 = 10;
-const MAX_LIMIT: u32 = 100;
+/// Spotify returns at most 50 plays per request.
+const PAGE_LIMIT: usize = 50;
+const DAY_SECS: i64 = 86_400;
 
 #[derive(Args, Debug)]
 pub struct RecentlyPlayedCommand {
     #[arg(long, value_name = "N", default_value_t = 10)]
     limit: u32,
+    #[arg(long, help = "Page through all the history Spotify keeps")]
+    all: bool,
+    #[arg(
+        long,
+        value_name = "TIME",
+        help = "Only plays after TIME (unix milliseconds or YYYY-MM-DD[THH:MM[:SS]] UTC)"
+    )]
+    after: Option<String>,
+    #[arg(long, value_name = "TIME", help = "Only plays before TIME")]
+    before: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["after", "before"],
+        help = "Only plays since local midnight"
+    )]
+    today: bool,
+    #[arg(long, help = "Collapse repeat plays of a track into the latest one")]
+    unique: bool,
+}
+
+/// Which plays to keep while paging back through the history.
+struct Window {
+    limit: usize,
+    after_ms: Option<u64>,
+    unique: bool,
+    /// The playing track, listed separately at the top.
+    skip_id: Option<String>,
 }
 
 pub fn handle(command: RecentlyPlayedCommand, ctx: &AppContext) -> Result<()> {
-    let limit = clamp_limit(command.limit);
-    let status = ctx.spotify()?.playback().status()?;
-    let now_playing = status.track.map(map_track);
-    let mut items = ctx.spotify()?.search().recently_played(limit)?;
+    let (after_ms, before_ms) = if command.today {
+        let midnight = local_midnight(unix_time(), local_utc_offset());
+        (Some(midnight * 1000), None)
+    } else {
+        (
+            command.after.as_deref().map(parse_time).transpose()?,
+            command.before.as_deref().map(parse_time).transpose()?,
+        )
+    };
+    if let (Some(after), Some(before)) = (after_ms, before_ms)
+        && after >= before
+    {
+        bail!(ErrorKind::Validation.error("--after must be earlier than --before"));
+    }
+
+    let spotify = ctx.spotify()?;
+    // A window that ends in the past has nothing playing now.
+    let now_playing = match before_ms {
+        Some(_) => None,
+        None => spotify.playback().status()?.track.map(map_track),
+    };
+    let window = Window {
+        limit: if command.all {
+            usize::MAX
+        } else {
+            clamp_limit(command.limit) as usize
+        },
+        after_ms,
+        unique: command.unique,
+        skip_id: now_playing.as_ref().map(|item| item.id.clone()),
+    };
+
+    // Spotify takes either an `after` or a `before` cursor, so page back with `before`
+    // and stop at the `after` bound locally.
+    let mut items = Vec::new();
+    let mut cursor = before_ms;
+    // After Ctrl-C or `--timeout`, list what was read so far.
+    while cancel::reason().is_none() {
+        let page_limit = (window.limit - items.len()).clamp(1, PAGE_LIMIT);
+        let page = spotify.search().played_before(cursor, page_limit as u32)?;
+        let exhausted = page.plays.len() < page_limit;
+        history::record(ctx, &page.plays);
+        if take_plays(&mut items, page.plays, &window) || exhausted {
+            break;
+        }
+        match page.before {
+            Some(before) if Some(before) != cursor => cursor = Some(before),
+            _ => break,
+        }
+    }
+
+    let now_playing_id = now_playing.as_ref().map(|item| item.id.clone());
     if let Some(now_playing) = now_playing {
-        let now_id = now_playing.id.clone();
-        items.retain(|item| item.id != now_id);
         items.insert(0, now_playing);
     }
-    let now_playing_id = items.first().map(|item| item.id.clone());
     ctx.output.recently_played(now_playing_id.as_deref(), items)
 }
 
+/// Add a page of plays, newest first; true once the window is full or the page went past
+/// `after_ms`.
+fn take_plays(items: &mut Vec<SearchItem>, plays: Vec<PlayedTrack>, window: &Window) -> bool {
+    for play in plays {
+        if items.len() >= window.limit {
+            return true;
+        }
+        if let Some(after_ms) = window.after_ms
+            && parse_time(&play.played_at).is_ok_and(|played| played <= after_ms)
+        {
+            return true;
+        }
+        if window.skip_id.as_deref() == Some(play.item.id.as_str())
+            || (window.unique && items.iter().any(|item| item.id == play.item.id))
+        {
+            continue;
+        }
+        items.push(play.item);
+    }
+    items.len() >= window.limit
+}
+
+/// Parse unix milliseconds or a UTC `YYYY-MM-DD[THH:MM[:SS[.fff]]][Z]` into unix milliseconds.
+fn parse_time(value: &str) -> Result<u64> {
+    let value = value.trim();
+    if !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Ok(value.parse()?);
+    }
+    match parse_iso(value.strip_suffix('Z').unwrap_or(value)) {
+        Some(ms) => Ok(ms),
+        None => {
+            bail!(ErrorKind::Validation.error(format!(
+                "invalid time '{value}'; use unix milliseconds or YYYY-MM-DD[THH:MM[:SS]] (UTC)"
+            )))
+        }
+    }
+}
+
+fn parse_iso(value: &str) -> Option<u64> {
+    let (date, time) = value.split_once('T').unwrap_or((value, "00:00"));
+    let mut date_parts = date.split('-');
+    let year: i64 = number(date_parts.next()?, 4)?;
+    let month: i64 = number(date_parts.next()?, 2)?;
+    let day: i64 = number(date_parts.next()?, 2)?;
+    if date_parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let (clock, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut clock_parts = clock.split(':');
+    let hours: i64 = number(clock_parts.next()?, 2)?;
+    let minutes: i64 = number(clock_parts.next()?, 2)?;
+    let seconds: i64 = match clock_parts.next() {
+        Some(seconds) => number(seconds, 2)?,
+        None => 0,
+    };
+    if clock_parts.next().is_some() || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    let millis: i64 = match fraction {
+        "" => 0,
+        digits if digits.bytes().all(|byte| byte.is_ascii_digit()) => {
+            format!("{:0<3}", &digits[..digits.len().min(3)])
+                .parse()
+                .ok()?
+        }
+        _ => return None,
+    };
+
+    let secs = days_from_civil(year, month, day) * DAY_SECS + hours * 3600 + minutes * 60 + seconds;
+    u64::try_from(secs * 1000 + millis).ok()
+}
+
+fn number(text: &str, width: usize) -> Option<i64> {
+    if text.len() != width || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Unix seconds of the last local midnight at or before `now`.
+fn local_midnight(now: u64, offset: i64) -> u64 {
+    let local = now as i64 + offset;
+    (local - local.rem_euclid(DAY_SECS) - offset).max(0) as u64
+}
+
 fn clamp_limit(limit: u32) -> u32 {
     if limit == 0 {
         return DEFAULT_LIMIT;
     }
-    limit.min(MAX_LIMIT)
+    limit
 }
 
 fn map_track(track: crate::domain::track::Track) -> crate::domain::search::SearchItem {
@@ -46,5 +213,114 @@ fn map_track(track: crate::domain::track::Track) -> crate::domain::search::Searc
         duration_ms: track.duration_ms,
         owner: None,
         score: None,
+        playable: None,
+        linked_from: None,
+        saved: None,
+        explicit: None,
+        popularity: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Window, local_midnight, parse_time, take_plays};
+    use crate::domain::search::{SearchItem, SearchType};
+    use crate::domain::session::PlayedTrack;
+
+    fn play(id: &str, played_at: &str) -> PlayedTrack {
+        PlayedTrack {
+            item: SearchItem {
+                id: id.to_string(),
+                name: id.to_string(),
+                uri: format!("spotify:track:{id}"),
+                kind: SearchType::Track,
+                artists: Vec::new(),
+                album: None,
+                duration_ms: None,
+                owner: None,
+                score: None,
+                playable: None,
+                linked_from: None,
+                saved: None,
+                explicit: None,
+                popularity: None,
+            },
+            artist_ids: Vec::new(),
+            played_at: played_at.to_string(),
+        }
+    }
+
+    fn limited(limit: usize) -> Window {
+        Window {
+            limit,
+            after_ms: None,
+            unique: false,
+            skip_id: None,
+        }
+    }
+
+    fn ids(items: &[SearchItem]) -> Vec<&str> {
+        items.iter().map(|item| item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn parse_time_accepts_millis_and_utc_dates() {
+        assert_eq!(parse_time("1700000000000").unwrap(), 1_700_000_000_000);
+        assert_eq!(parse_time("1970-01-02").unwrap(), 86_400_000);
+        assert_eq!(parse_time("2024-03-01T12:30").unwrap(), 1_709_296_200_000);
+        assert_eq!(
+            parse_time("2024-03-01T12:30:05.25Z").unwrap(),
+            1_709_296_205_250
+        );
+        assert!(parse_time("2024-13-01").is_err());
+        assert!(parse_time("yesterday").is_err());
+    }
+
+    #[test]
+    fn take_plays_skips_repeats_and_the_playing_track() {
+        let plays = vec![
+            play("a", "2024-03-01T12:03:00Z"),
+            play("now", "2024-03-01T12:02:00Z"),
+            play("a", "2024-03-01T12:01:00Z"),
+            play("b", "2024-03-01T12:00:00Z"),
+        ];
+        let mut items = Vec::new();
+        let window = Window {
+            unique: true,
+            skip_id: Some("now".to_string()),
+            ..limited(10)
+        };
+        assert!(!take_plays(&mut items, plays.clone(), &window));
+        assert_eq!(ids(&items), vec!["a", "b"]);
+
+        let mut items = Vec::new();
+        assert!(take_plays(&mut items, plays, &limited(3)));
+        assert_eq!(ids(&items), vec!["a", "now", "a"]);
+    }
+
+    #[test]
+    fn take_plays_stops_at_the_after_bound() {
+        let plays = vec![
+            play("a", "2024-03-01T12:01:00Z"),
+            play("b", "2024-03-01T11:59:00Z"),
+        ];
+        let mut items = Vec::new();
+        let window = Window {
+            after_ms: Some(parse_time("2024-03-01T12:00").unwrap()),
+            ..limited(10)
+        };
+        assert!(take_plays(&mut items, plays, &window));
+        assert_eq!(ids(&items), vec!["a"]);
+    }
+
+    #[test]
+    fn local_midnight_applies_the_offset() {
+        let noon = 1_709_294_400; // 2024-03-01T12:00:00Z
+        assert_eq!(local_midnight(noon, 0), 1_709_251_200);
+        // 23:00 on the previous day at -13:00.
+        assert_eq!(
+            local_midnight(noon, -13 * 3600),
+            1_709_251_200 - 86_400 + 13 * 3600
+        );
     }
 }
@@ -0,0 +1,193 @@
+//! Release monitoring for followed artists.
+use anyhow::bail;
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::{hooks, oplog};
+use crate::cli::now_playing;
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::release::Release;
+use crate::error::{ErrorKind, Result};
+use crate::time::{civil_date, unix_time};
+
+const ALBUMS_PER_ARTIST: u32 = 10;
+
+#[derive(Subcommand, Debug)]
+pub enum ReleasesCommand {
+    Check {
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "7d",
+            help = "Only report releases newer than this (e.g. 24h, 7d, 2w)"
+        )]
+        since: String,
+        #[arg(long, help = "Send a desktop notification when new releases are found")]
+        notify: bool,
+        #[arg(long, conflicts_with = "queue", help = "Play the newest release")]
+        play: bool,
+        #[arg(long, help = "Queue every track from the new releases")]
+        queue: bool,
+    },
+}
+
+pub fn handle(command: ReleasesCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        ReleasesCommand::Check {
+            since,
+            notify,
+            play,
+            queue,
+        } => check(ctx, &since, notify, play, queue),
+    }
+}
+
+fn check(ctx: &AppContext, since: &str, notify: bool, play: bool, queue: bool) -> Result<()> {
+    let Some(window) = parse_since(since) else {
+        bail!(ErrorKind::Validation.error(format!(
+            "invalid --since value '{since}'; use a number with h, d, or w (e.g. 7d)"
+        )));
+    };
+    let cutoff = civil_date(unix_time().saturating_sub(window));
+
+    let store = ctx.cache.release_store();
+    let seen = store.load()?;
+    let artists = ctx.spotify()?.artists().followed()?;
+
+    let mut releases: Vec<Release> = Vec::new();
+    for artist in artists {
+        for release in ctx
+            .spotify()?
+            .artists()
+            .albums(&artist.id, ALBUMS_PER_ARTIST)?
+        {
+            if seen.contains(&release.id) || releases.iter().any(|item| item.id == release.id) {
+                continue;
+            }
+            if is_released_since(release.release_date.as_deref(), &cutoff) {
+                releases.push(release);
+            }
+        }
+    }
+    releases.sort_by(|a, b| b.release_date.cmp(&a.release_date));
+
+    // Re-read under the lock: a concurrent check may have reported some of these meanwhile.
+    {
+        let _lock = store.lock()?;
+        let mut seen = store.load()?;
+        releases.retain(|release| !seen.contains(&release.id));
+        for release in &releases {
+            seen.insert(&release.id);
+        }
+        seen.checked_at = Some(unix_time());
+        store.save(&seen)?;
+    }
+
+    if notify && !releases.is_empty() {
+        desktop_notify(&notification_text(&releases));
+    }
+
+    if play && let Some(release) = releases.first() {
+        hooks::pre_play(ctx, Some(&release.uri))?;
+        let context_uri = release.uri.clone();
+        oplog::perform(ctx, LoggedOperation::PlayerPlay { context_uri })?;
+        if !ctx.dry_run {
+            hooks::track_changed(ctx);
+            now_playing::show_with_delay(ctx, 100)?;
+        }
+    }
+
+    if queue {
+        for release in &releases {
+            let album = ctx.spotify()?.albums().get(&release.id)?;
+            for track in album.tracks {
+                oplog::perform(ctx, LoggedOperation::QueueAdd { uri: track.uri })?;
+            }
+        }
+    }
+
+    ctx.output.release_list(releases)
+}
+
+/// `24h`, `7d`, `2w`, or `1y` (365 days) in seconds.
+pub(crate) fn parse_since(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let unit = value.chars().last()?;
+    let amount: u64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    let seconds = match unit {
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        'y' => 365 * 24 * 60 * 60,
+        _ => return None,
+    };
+    amount.checked_mul(seconds)
+}
+
+/// Compare a Spotify release date (`YYYY`, `YYYY-MM`, or `YYYY-MM-DD`) to a cutoff day.
+fn is_released_since(release_date: Option<&str>, cutoff: &str) -> bool {
+    let Some(date) = release_date else {
+        return false;
+    };
+    let padded = match date.len() {
+        4 => format!("{date}-01-01"),
+        7 => format!("{date}-01"),
+        _ => date.to_string(),
+    };
+    padded.as_str() >= cutoff
+}
+
+fn notification_text(releases: &[Release]) -> String {
+    let names: Vec<String> = releases
+        .iter()
+        .take(3)
+        .map(|release| format!("{} - {}", release.name, release.artists.join(", ")))
+        .collect();
+    let mut text = names.join("\n");
+    if releases.len() > names.len() {
+        text.push_str(&format!("\n+{} more", releases.len() - names.len()));
+    }
+    text
+}
+
+fn desktop_notify(body: &str) {
+    use std::process::Command;
+
+    let title = "New releases";
+    let result = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {:?} with title {:?}",
+            body.replace('\n', ", "),
+            title
+        );
+        Command::new("osascript").args(["-e", &script]).status()
+    } else {
+        Command::new("notify-send").args([title, body]).status()
+    };
+    if result.is_err() {
+        eprintln!("desktop notification unavailable");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_released_since, parse_since};
+
+    #[test]
+    fn parse_since_units() {
+        assert_eq!(parse_since("24h"), Some(86_400));
+        assert_eq!(parse_since("7d"), Some(604_800));
+        assert_eq!(parse_since("2w"), Some(1_209_600));
+        assert_eq!(parse_since("1y"), Some(31_536_000));
+        assert_eq!(parse_since("7"), None);
+        assert_eq!(parse_since("d"), None);
+    }
+
+    #[test]
+    fn is_released_since_handles_precision() {
+        assert!(is_released_since(Some("2024-05-02"), "2024-05-01"));
+        assert!(!is_released_since(Some("2024-04"), "2024-05-01"));
+        assert!(!is_released_since(Some("2023"), "2024-05-01"));
+        assert!(!is_released_since(None, "2024-05-01"));
+    }
+}
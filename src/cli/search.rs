@@ -3,10 +3,15 @@ use anyhow::bail;
 use clap::{Args, ValueEnum};
 
 use crate::AppContext;
+use crate::action::{explicit, hooks, saved};
+use crate::cli::library::parse_duration;
 use crate::cli::now_playing;
+use crate::cli::play::{ResourceType, parse_spotify_url};
 use crate::cli::playlist::parse_playlist_id;
-use crate::domain::search::{SearchItem, SearchResults, SearchType};
-use crate::error::Result;
+use crate::domain::pin::PinnedPlaylist;
+use crate::domain::search::{SearchItem, SearchResults, SearchTotal, SearchType};
+use crate::error::{ErrorKind, Result};
+use crate::spotify::concurrent::{MAX_IN_FLIGHT, fetch_all};
 
 #[derive(Args, Debug)]
 pub struct SearchCommand {
@@ -16,14 +21,59 @@ pub struct SearchCommand {
     pub query: Option<String>,
     #[arg(long, help = "Use market from token")]
     user: bool,
-    #[arg(long, default_value_t = 10, help = "Limit results")]
-    limit: u32,
+    #[arg(
+        long,
+        value_name = "N|TYPE=N",
+        value_delimiter = ',',
+        default_value = "10",
+        value_parser = parse_limit,
+        help = "Limit results, overall or per type (e.g. track=10,album=5)"
+    )]
+    limit: Vec<LimitSpec>,
+    #[arg(
+        long,
+        default_value_t = 0,
+        help = "Skip the first N results of each type"
+    )]
+    offset: u32,
     #[arg(long, help = "Pick a specific result (1-based)")]
     pick: Option<usize>,
     #[arg(long, help = "Use the last cached search results")]
     last: bool,
     #[arg(long, help = "Play the best match result")]
     play: bool,
+    #[arg(
+        long,
+        value_name = "ALIAS",
+        help = "Pin the picked result under an alias"
+    )]
+    pin_result: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["last", "user"],
+        help = "Only search pin aliases, without calling Spotify"
+    )]
+    pins_only: bool,
+    #[arg(
+        long,
+        value_name = "TAG",
+        requires = "pins_only",
+        help = "Only pins with this tag"
+    )]
+    tag: Option<String>,
+    #[arg(long, help = "Skip checking which tracks are saved")]
+    no_check: bool,
+    #[arg(long, value_name = "M:SS", help = "Only tracks at least this long")]
+    min_duration: Option<String>,
+    #[arg(long, value_name = "M:SS", help = "Only tracks at most this long")]
+    max_duration: Option<String>,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(0..=100),
+        help = "Only tracks and artists with Spotify popularity of at least N (0-100)"
+    )]
+    min_popularity: Option<u32>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -31,14 +81,248 @@ pub struct SearchArgs {
     pub query: Option<String>,
     #[arg(long, help = "Use market from token")]
     user: bool,
-    #[arg(long, default_value_t = 10, help = "Limit results")]
-    limit: u32,
+    #[arg(
+        long,
+        value_name = "N|TYPE=N",
+        value_delimiter = ',',
+        default_value = "10",
+        value_parser = parse_limit,
+        help = "Limit results, overall or per type (e.g. track=10,album=5)"
+    )]
+    limit: Vec<LimitSpec>,
+    #[arg(
+        long,
+        default_value_t = 0,
+        help = "Skip the first N results of each type"
+    )]
+    offset: u32,
     #[arg(long, help = "Pick a specific result (1-based)")]
     pick: Option<usize>,
     #[arg(long, help = "Use the last cached search results")]
     last: bool,
     #[arg(long, help = "Play the best match result")]
     play: bool,
+    #[arg(
+        long,
+        value_name = "ALIAS",
+        help = "Pin the picked result under an alias"
+    )]
+    pin_result: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["last", "user"],
+        help = "Only search pin aliases, without calling Spotify"
+    )]
+    pins_only: bool,
+    #[arg(
+        long,
+        value_name = "TAG",
+        requires = "pins_only",
+        help = "Only pins with this tag"
+    )]
+    tag: Option<String>,
+    #[arg(long, help = "Skip checking which tracks are saved")]
+    no_check: bool,
+    #[arg(long, value_name = "M:SS", help = "Only tracks at least this long")]
+    min_duration: Option<String>,
+    #[arg(long, value_name = "M:SS", help = "Only tracks at most this long")]
+    max_duration: Option<String>,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(0..=100),
+        help = "Only tracks and artists with Spotify popularity of at least N (0-100)"
+    )]
+    min_popularity: Option<u32>,
+}
+
+/// A `--limit` entry: a default for every type, or a limit for one type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LimitSpec {
+    All(u32),
+    Kind(SearchType, u32),
+}
+
+fn parse_limit(input: &str) -> std::result::Result<LimitSpec, String> {
+    let parse_count = |value: &str| {
+        value
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|count| *count <= 50)
+            .ok_or_else(|| format!("invalid limit '{value}'; expected 0-50"))
+    };
+    let Some((kind, count)) = input.split_once('=') else {
+        return parse_count(input).map(LimitSpec::All);
+    };
+    let kind = match kind.trim().trim_end_matches('s') {
+        "track" => SearchType::Track,
+        "album" => SearchType::Album,
+        "artist" => SearchType::Artist,
+        "playlist" => SearchType::Playlist,
+        other => return Err(format!("unknown search type '{other}'")),
+    };
+    Ok(LimitSpec::Kind(kind, parse_count(count)?))
+}
+
+/// Limit for one type: a per-type entry wins over an overall one; the last entry wins.
+fn limit_for(specs: &[LimitSpec], kind: SearchType) -> u32 {
+    let per_kind = specs.iter().rev().find_map(|spec| match spec {
+        LimitSpec::Kind(spec_kind, count) if *spec_kind == kind => Some(*count),
+        _ => None,
+    });
+    let overall = specs.iter().rev().find_map(|spec| match spec {
+        LimitSpec::All(count) => Some(*count),
+        _ => None,
+    });
+    per_kind.or(overall).unwrap_or(10)
+}
+
+/// Pages fetched per type, at most, while filling a filtered page.
+const MAX_FILL_PAGES: u32 = 5;
+/// Spotify rejects search offsets past this.
+const MAX_SEARCH_OFFSET: u32 = 1000;
+
+/// Client-side post-filters on fetched results.
+///
+/// Items without the filtered attribute (e.g. albums under a duration filter) are dropped.
+#[derive(Debug, Clone, Copy, Default)]
+struct ResultFilter {
+    min_duration_ms: Option<u32>,
+    max_duration_ms: Option<u32>,
+    min_popularity: Option<u32>,
+}
+
+impl ResultFilter {
+    fn from_args(args: &SearchArgs) -> Result<Self> {
+        Ok(Self {
+            min_duration_ms: args
+                .min_duration
+                .as_deref()
+                .map(parse_duration)
+                .transpose()?,
+            max_duration_ms: args
+                .max_duration
+                .as_deref()
+                .map(parse_duration)
+                .transpose()?,
+            min_popularity: args.min_popularity,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.min_duration_ms.is_none()
+            && self.max_duration_ms.is_none()
+            && self.min_popularity.is_none()
+    }
+
+    fn matches(&self, item: &SearchItem) -> bool {
+        if self.min_duration_ms.is_some() || self.max_duration_ms.is_some() {
+            let Some(duration) = item.duration_ms else {
+                return false;
+            };
+            if self.min_duration_ms.is_some_and(|min| duration < min)
+                || self.max_duration_ms.is_some_and(|max| duration > max)
+            {
+                return false;
+            }
+        }
+        if let Some(min) = self.min_popularity
+            && item.popularity.is_none_or(|popularity| popularity < min)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// One filtered page: keeps fetching from `offset` until `limit` items match, results run
+/// out, or `MAX_FILL_PAGES` pages have been read.
+///
+/// Returns the kept items, Spotify's total, and how many results were scanned, so the next
+/// page starts at `offset + scanned`.
+fn fill_page(
+    limit: u32,
+    offset: u32,
+    filter: ResultFilter,
+    mut fetch: impl FnMut(u32) -> Result<(Vec<SearchItem>, u32)>,
+) -> Result<(Vec<SearchItem>, u32, u32)> {
+    let mut kept = Vec::new();
+    let mut next = offset;
+    let mut total;
+    let mut pages = 0;
+    loop {
+        let (page, page_total) = fetch(next)?;
+        total = page_total;
+        pages += 1;
+        let fetched = page.len() as u32;
+        for (index, item) in page.into_iter().enumerate() {
+            if kept.len() < limit as usize && filter.matches(&item) {
+                kept.push(item);
+                if kept.len() == limit as usize {
+                    // Stop counting at the last kept item so the next page resumes after it.
+                    return Ok((kept, total, next + index as u32 + 1 - offset));
+                }
+            }
+        }
+        next += fetched;
+        if filter.is_empty()
+            || fetched < limit
+            || next >= total
+            || next + limit > MAX_SEARCH_OFFSET
+            || pages >= MAX_FILL_PAGES
+        {
+            return Ok((kept, total, next - offset));
+        }
+    }
+}
+
+/// Run a search one page per type, keeping Spotify's total for each.
+///
+/// Types are searched concurrently; results keep the track, album, artist, playlist order.
+fn search_pages(
+    ctx: &AppContext,
+    query: &str,
+    kind: SearchType,
+    limits: &[LimitSpec],
+    offset: u32,
+    user: bool,
+    filter: ResultFilter,
+) -> Result<(SearchResults, Vec<SearchTotal>)> {
+    let kinds = match kind {
+        SearchType::All => SearchType::EACH.to_vec(),
+        kind => vec![kind],
+    };
+    let kinds: Vec<(SearchType, u32)> = kinds
+        .into_iter()
+        .map(|kind| (kind, limit_for(limits, kind)))
+        .filter(|(_, limit)| *limit > 0)
+        .collect();
+    let search = ctx.spotify()?.search();
+    let pages = fetch_all(
+        &kinds,
+        MAX_IN_FLIGHT,
+        |&(page_kind, limit)| {
+            let (items, total, scanned) = fill_page(limit, offset, filter, |page_offset| {
+                search.search_page(query, page_kind, limit, page_offset, user)
+            })?;
+            Ok((page_kind, items, total, scanned))
+        },
+        |_| {},
+    )?;
+    let mut items = Vec::new();
+    let mut totals = Vec::new();
+    for (page_kind, page, total, scanned) in pages {
+        // With filters, `count` is the scanned range so the next-page hint stays correct.
+        totals.push(SearchTotal {
+            kind: page_kind,
+            total,
+            offset,
+            count: scanned,
+        });
+        items.extend(page);
+    }
+    Ok((SearchResults { kind, items }, totals))
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy)]
@@ -62,9 +346,17 @@ pub fn handle(command: SearchCommand, ctx: &AppContext) -> Result<()> {
         query: command.query,
         user: command.user,
         limit: command.limit,
+        offset: command.offset,
         pick: command.pick,
         last: command.last,
         play: command.play,
+        pin_result: command.pin_result,
+        pins_only: command.pins_only,
+        tag: command.tag,
+        no_check: command.no_check,
+        min_duration: command.min_duration,
+        max_duration: command.max_duration,
+        min_popularity: command.min_popularity,
     };
     handle_inner(kind, args, ctx, false)
 }
@@ -75,23 +367,37 @@ fn handle_inner(
     ctx: &AppContext,
     enforce_kind: bool,
 ) -> Result<()> {
-    let (raw_query, mut results) = if command.last || command.query.is_none() {
+    let filter = ResultFilter::from_args(&command)?;
+    let mut totals = Vec::new();
+    let (raw_query, mut results) = if command.pins_only {
+        let raw_query = command.query.clone().unwrap_or_default();
+        let pins = ctx.cache.pin_store().load()?.items;
+        let limit = limit_for(&command.limit, kind);
+        let results = pin_results(pins, &raw_query, command.tag.as_deref(), kind, limit);
+        let cached = crate::cache::search::CachedSearch {
+            query: raw_query.clone(),
+            results: results.clone(),
+        };
+        ctx.cache.search_store().save(&cached)?;
+        (raw_query, results)
+    } else if command.last || command.query.is_none() {
         let cached = ctx.cache.search_store().load()?;
         let Some(cached) = cached else {
-            bail!("no cached search; run `spotify-cli search <query>`");
+            bail!(ErrorKind::NotFound.error("no cached search; run `spotify-cli search <query>`"));
         };
         if enforce_kind && cached.results.kind != kind {
-            bail!(
+            bail!(ErrorKind::Validation.error(format!(
                 "cached search is {}; run `spotify-cli search {} <query>`",
                 search_type_label(cached.results.kind),
                 search_type_label(kind)
-            );
+            )));
         }
         (cached.query, cached.results)
     } else {
         let raw_query = command.query.clone().unwrap_or_default();
         if kind == SearchType::Playlist && command.user {
-            if let Some(results) = local_playlist_results(ctx, &raw_query, command.limit)? {
+            let limit = limit_for(&command.limit, kind);
+            if let Some(results) = local_playlist_results(ctx, &raw_query, limit)? {
                 let cached = crate::cache::search::CachedSearch {
                     query: raw_query.clone(),
                     results: results.clone(),
@@ -100,10 +406,16 @@ fn handle_inner(
                 (raw_query, results)
             } else {
                 let query = fuzzy_query(&raw_query);
-                let results =
-                    ctx.spotify()?
-                        .search()
-                        .search(&query, kind, command.limit, command.user)?;
+                let (results, page_totals) = search_pages(
+                    ctx,
+                    &query,
+                    kind,
+                    &command.limit,
+                    command.offset,
+                    command.user,
+                    filter,
+                )?;
+                totals = page_totals;
                 let cached = crate::cache::search::CachedSearch {
                     query: raw_query.clone(),
                     results: results.clone(),
@@ -113,10 +425,16 @@ fn handle_inner(
             }
         } else {
             let query = fuzzy_query(&raw_query);
-            let results =
-                ctx.spotify()?
-                    .search()
-                    .search(&query, kind, command.limit, command.user)?;
+            let (results, page_totals) = search_pages(
+                ctx,
+                &query,
+                kind,
+                &command.limit,
+                command.offset,
+                command.user,
+                filter,
+            )?;
+            totals = page_totals;
             let cached = crate::cache::search::CachedSearch {
                 query: raw_query.clone(),
                 results: results.clone(),
@@ -126,6 +444,9 @@ fn handle_inner(
         }
     };
 
+    explicit::filter(ctx, &mut results.items);
+    // Cached and local results were not filtered while fetching.
+    results.items.retain(|item| filter.matches(item));
     if !raw_query.is_empty() {
         apply_fuzzy_scores(&raw_query, &mut results);
     }
@@ -140,14 +461,27 @@ fn handle_inner(
         None
     };
 
+    if command.pin_result.is_some() && picked.is_none() {
+        bail!(
+            ErrorKind::Validation.error("--pin-result needs a picked result; use --pick or --play")
+        );
+    }
+
     if let Some(item) = picked.clone() {
+        let kind = if results.kind == SearchType::All {
+            item.kind
+        } else {
+            results.kind
+        };
+        if let Some(alias) = command.pin_result.as_ref() {
+            let url = item_url(kind, &item.id);
+            ctx.cache.pin_store().add(alias.clone(), url.clone())?;
+            let message = format!("Pinned: {} -> {}", alias, url);
+            ctx.output.action("pin_add", &message)?;
+        }
         if command.play {
             let playback = ctx.spotify()?.playback();
-            let kind = if results.kind == SearchType::All {
-                item.kind
-            } else {
-                results.kind
-            };
+            hooks::pre_play(ctx, Some(&item.uri))?;
             match kind {
                 SearchType::Track => playback.play_track(&item.uri)?,
                 SearchType::Album | SearchType::Artist | SearchType::Playlist => {
@@ -155,22 +489,32 @@ fn handle_inner(
                 }
                 SearchType::All => {}
             }
+            hooks::track_changed(ctx);
             let label = search_item_label(&item);
             let message = format!("Playing: {}", label);
             ctx.output.action("search_play", &message)?;
             now_playing::show_with_delay(ctx, 100)?;
         }
     } else if command.play {
-        bail!("no results to play");
+        bail!(ErrorKind::NotFound.error("no results to play"));
     }
 
     if let Some(pick) = command.pick
         && let Some(item) = pick_item(&results.items, pick)?
     {
         results.items = vec![item];
+        totals.clear();
+    }
+
+    if !command.no_check {
+        saved::mark_items(ctx, &mut results.items);
     }
 
-    ctx.output.search_results(results)
+    if totals.is_empty() {
+        ctx.output.search_results(results)
+    } else {
+        ctx.output.search_results_paged(results, totals)
+    }
 }
 
 pub(crate) fn fuzzy_query(query: &str) -> String {
@@ -185,7 +529,15 @@ pub(crate) fn fuzzy_query(query: &str) -> String {
     }
 }
 
-fn search_item_label(item: &crate::domain::search::SearchItem) -> String {
+fn item_url(kind: SearchType, id: &str) -> String {
+    format!(
+        "https://open.spotify.com/{}/{}",
+        search_type_label(kind),
+        id
+    )
+}
+
+pub(crate) fn search_item_label(item: &crate::domain::search::SearchItem) -> String {
     if !item.artists.is_empty() {
         return format!("{} - {}", item.name, item.artists.join(", "));
     }
@@ -202,18 +554,18 @@ fn pick_item(
     pick: usize,
 ) -> Result<Option<crate::domain::search::SearchItem>> {
     if pick == 0 {
-        bail!("pick must be 1 or greater");
+        bail!(ErrorKind::Validation.error("pick must be 1 or greater"));
     }
     let index = pick - 1;
     Ok(items.get(index).cloned())
 }
 
-fn validate_pick(pick: usize, len: usize) -> Result<()> {
+pub(crate) fn validate_pick(pick: usize, len: usize) -> Result<()> {
     if pick == 0 {
-        bail!("pick must be 1 or greater");
+        bail!(ErrorKind::Validation.error("pick must be 1 or greater"));
     }
     if pick > len {
-        bail!("pick out of range; got {pick}, max {len}");
+        bail!(ErrorKind::Validation.error(format!("pick out of range; got {pick}, max {len}")));
     }
     Ok(())
 }
@@ -292,6 +644,11 @@ fn local_playlist_results(
                         duration_ms: None,
                         owner: playlist.owner,
                         score: None,
+                        playable: None,
+                        linked_from: None,
+                        saved: None,
+                        explicit: None,
+                        popularity: None,
                     },
                     score,
                     name_lower: name.to_lowercase(),
@@ -319,6 +676,11 @@ fn local_playlist_results(
                     duration_ms: None,
                     owner: Some("pinned".to_string()),
                     score: None,
+                    playable: None,
+                    linked_from: None,
+                    saved: None,
+                    explicit: None,
+                    popularity: None,
                 },
                 score,
                 name_lower: name.to_lowercase(),
@@ -349,6 +711,84 @@ fn local_playlist_results(
     }))
 }
 
+/// Pins whose alias matches `query` (every pin when it is empty), best match first.
+///
+/// Pins to shows, episodes, or audiobooks are left out; search results cannot hold them.
+fn pin_results(
+    pins: Vec<PinnedPlaylist>,
+    query: &str,
+    tag: Option<&str>,
+    kind: SearchType,
+    limit: u32,
+) -> SearchResults {
+    let mut matches = Vec::new();
+    for pin in pins {
+        if tag.is_some_and(|tag| !pin.has_tag(tag)) {
+            continue;
+        }
+        let score = if query.trim().is_empty() {
+            0.0
+        } else {
+            match playlist_match_score(query, &pin.name) {
+                Some(score) => score,
+                None => continue,
+            }
+        };
+        let Some(resource) = parse_spotify_url(&pin.url) else {
+            continue;
+        };
+        let item_kind = match resource.resource_type {
+            ResourceType::Track => SearchType::Track,
+            ResourceType::Album => SearchType::Album,
+            ResourceType::Artist => SearchType::Artist,
+            ResourceType::Playlist => SearchType::Playlist,
+            ResourceType::Episode | ResourceType::Show | ResourceType::Audiobook => continue,
+        };
+        if kind != SearchType::All && kind != item_kind {
+            continue;
+        }
+        let uri = format!(
+            "spotify:{}:{}",
+            resource.resource_type.as_str(),
+            resource.id
+        );
+        matches.push(LocalPlaylistMatch {
+            item: SearchItem {
+                id: resource.id,
+                name: pin.name.clone(),
+                uri,
+                kind: item_kind,
+                artists: Vec::new(),
+                album: None,
+                duration_ms: None,
+                owner: Some("pinned".to_string()),
+                score: None,
+                playable: None,
+                linked_from: None,
+                saved: None,
+                explicit: None,
+                popularity: None,
+            },
+            score,
+            name_lower: pin.name.to_lowercase(),
+        });
+    }
+
+    matches.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name_lower.cmp(&b.name_lower))
+    });
+    if limit > 0 {
+        matches.truncate(limit as usize);
+    }
+    SearchResults {
+        kind,
+        items: matches.into_iter().map(|item| item.item).collect(),
+    }
+}
+
 fn playlist_match_score(query: &str, candidate: &str) -> Option<f32> {
     let query_lower = query.to_lowercase();
     let candidate_lower = candidate.to_lowercase();
@@ -433,7 +873,50 @@ fn search_type_label(kind: SearchType) -> &'static str {
 
 #[cfg(test)]
 mod tests {
-    use super::{fuzzy_query, fuzzy_score, validate_pick};
+    use super::{
+        LimitSpec, ResultFilter, fill_page, fuzzy_query, fuzzy_score, item_url, limit_for,
+        parse_limit, pin_results, validate_pick,
+    };
+    use crate::domain::pin::PinnedPlaylist;
+    use crate::domain::search::{SearchItem, SearchType};
+
+    fn track(id: u32, duration_ms: u32, popularity: u32) -> SearchItem {
+        SearchItem {
+            id: id.to_string(),
+            name: format!("Track {id}"),
+            uri: format!("spotify:track:{id}"),
+            kind: SearchType::Track,
+            artists: Vec::new(),
+            album: None,
+            duration_ms: Some(duration_ms),
+            owner: None,
+            score: None,
+            playable: None,
+            linked_from: None,
+            saved: None,
+            explicit: None,
+            popularity: Some(popularity),
+        }
+    }
+
+    #[test]
+    fn parse_limit_accepts_overall_and_per_type() {
+        assert_eq!(parse_limit("5"), Ok(LimitSpec::All(5)));
+        assert_eq!(
+            parse_limit("tracks=10"),
+            Ok(LimitSpec::Kind(SearchType::Track, 10))
+        );
+        assert!(parse_limit("show=3").is_err());
+        assert!(parse_limit("album=99").is_err());
+    }
+
+    #[test]
+    fn limit_for_prefers_per_type_entries() {
+        let specs = [LimitSpec::All(3), LimitSpec::Kind(SearchType::Album, 7)];
+        assert_eq!(limit_for(&specs, SearchType::Album), 7);
+        assert_eq!(limit_for(&specs, SearchType::Track), 3);
+        assert_eq!(limit_for(&[], SearchType::Track), 10);
+    }
 
     #[test]
     fn fuzzy_query_wraps_tokens() {
@@ -447,10 +930,21 @@ mod tests {
         assert!(fuzzy_score("my radar", "Radar Only").unwrap_or(0.0) < 0.5);
     }
 
+    #[test]
+    fn item_url_uses_result_kind() {
+        assert_eq!(
+            item_url(SearchType::Album, "abc123"),
+            "https://open.spotify.com/album/abc123"
+        );
+    }
+
     #[test]
     fn validate_pick_rejects_zero() {
-        let result = validate_pick(0, 10);
-        assert!(result.is_err());
+        let err = validate_pick(0, 10).unwrap_err();
+        assert_eq!(
+            crate::error::classify(&err),
+            crate::error::ErrorKind::Validation
+        );
     }
 
     #[test]
@@ -458,4 +952,106 @@ mod tests {
         let result = validate_pick(11, 10);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn result_filter_checks_bounds_and_missing_values() {
+        let filter = ResultFilter {
+            min_duration_ms: Some(120_000),
+            max_duration_ms: Some(300_000),
+            min_popularity: Some(50),
+        };
+        assert!(filter.matches(&track(1, 200_000, 60)));
+        assert!(!filter.matches(&track(2, 60_000, 60)));
+        assert!(!filter.matches(&track(3, 400_000, 60)));
+        assert!(!filter.matches(&track(4, 200_000, 10)));
+
+        let mut album = track(5, 200_000, 60);
+        album.duration_ms = None;
+        assert!(!filter.matches(&album));
+        assert!(ResultFilter::default().matches(&album));
+    }
+
+    #[test]
+    fn fill_page_fetches_until_limit_is_met() {
+        let filter = ResultFilter {
+            min_popularity: Some(50),
+            ..ResultFilter::default()
+        };
+        let mut offsets = Vec::new();
+        let (items, total, scanned) = fill_page(2, 0, filter, |offset| {
+            offsets.push(offset);
+            // Only every third track passes the filter.
+            let page = (offset..offset + 2)
+                .map(|id| track(id, 200_000, if id % 3 == 2 { 80 } else { 10 }))
+                .collect();
+            Ok((page, 100))
+        })
+        .expect("fill");
+        assert_eq!(offsets, vec![0, 2, 4]);
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2", "5"]
+        );
+        assert_eq!(total, 100);
+        assert_eq!(scanned, 6);
+    }
+
+    #[test]
+    fn fill_page_stops_when_results_run_out() {
+        let filter = ResultFilter {
+            min_popularity: Some(50),
+            ..ResultFilter::default()
+        };
+        let mut calls = 0;
+        let (items, _, scanned) = fill_page(5, 0, filter, |_| {
+            calls += 1;
+            Ok((vec![track(1, 200_000, 10), track(2, 200_000, 90)], 2))
+        })
+        .expect("fill");
+        assert_eq!(calls, 1);
+        assert_eq!(items.len(), 1);
+        assert_eq!(scanned, 2);
+    }
+
+    fn pin(name: &str, url: &str, tags: &[&str]) -> PinnedPlaylist {
+        PinnedPlaylist {
+            name: name.to_string(),
+            url: url.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn pin_results_filter_by_tag_and_kind() {
+        let pins = vec![
+            pin(
+                "Run Fast",
+                "https://open.spotify.com/playlist/p1",
+                &["workout"],
+            ),
+            pin("Run Album", "spotify:album:a1", &["workout"]),
+            pin(
+                "Run Slow",
+                "https://open.spotify.com/playlist/p2",
+                &["calm"],
+            ),
+            pin("Run Show", "spotify:show:s1", &["workout"]),
+        ];
+        let results = pin_results(pins.clone(), "run", Some("Workout"), SearchType::All, 10);
+        let mut uris: Vec<&str> = results.items.iter().map(|item| item.uri.as_str()).collect();
+        uris.sort();
+        assert_eq!(uris, vec!["spotify:album:a1", "spotify:playlist:p1"]);
+
+        let results = pin_results(pins, "", None, SearchType::Playlist, 10);
+        assert_eq!(results.items.len(), 2);
+        assert!(
+            results
+                .items
+                .iter()
+                .all(|item| item.kind == SearchType::Playlist)
+        );
+    }
 }
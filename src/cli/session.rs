@@ -0,0 +1,274 @@
+//! Listening session command handlers.
+//!
+//! A session is only a recorded time window; the plays inside it come from
+//! Spotify's recently played history when the report is built.
+use std::collections::HashMap;
+
+use anyhow::bail;
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::{history, oplog};
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::session::{ArtistCount, PlayedTrack, Session, SessionReport};
+use crate::error::{ErrorKind, Result};
+use crate::time::{civil_date, unix_time};
+
+/// Largest page the recently played endpoint returns.
+const HISTORY_LIMIT: u32 = 50;
+
+#[derive(Subcommand, Debug)]
+pub enum SessionCommand {
+    /// Start recording a listening session.
+    Start,
+    /// Stop the running session and print its report.
+    Stop {
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Save the session tracks as a private playlist"
+        )]
+        save: Option<String>,
+        #[arg(long, default_value_t = 5, help = "Number of top artists to show")]
+        top: usize,
+    },
+    /// Report on the running or most recent session.
+    Show {
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Save the session tracks as a private playlist"
+        )]
+        save: Option<String>,
+        #[arg(long, default_value_t = 5, help = "Number of top artists to show")]
+        top: usize,
+    },
+}
+
+pub fn handle(command: SessionCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        SessionCommand::Start => start(ctx),
+        SessionCommand::Stop { save, top } => stop(ctx, save.as_deref(), top),
+        SessionCommand::Show { save, top } => show(ctx, save.as_deref(), top),
+    }
+}
+
+fn start(ctx: &AppContext) -> Result<()> {
+    let store = ctx.cache.session_store();
+    let _lock = store.lock()?;
+    if let Some(session) = store.load()?.filter(|session| session.stopped_at.is_none()) {
+        bail!(ErrorKind::Validation.error(format!(
+            "a session is already running since {}; stop it first",
+            iso_timestamp(session.started_at)
+        )));
+    }
+    let session = Session {
+        started_at: unix_time(),
+        stopped_at: None,
+    };
+    store.save(&session)?;
+    let message = format!("Session started at {}", iso_timestamp(session.started_at));
+    ctx.output.action("session_start", &message)
+}
+
+fn stop(ctx: &AppContext, save: Option<&str>, top: usize) -> Result<()> {
+    let store = ctx.cache.session_store();
+    let session = {
+        let _lock = store.lock()?;
+        let Some(mut session) = store.load()?.filter(|session| session.stopped_at.is_none()) else {
+            bail!(ErrorKind::NotFound.error("no session is running"));
+        };
+        session.stopped_at = Some(unix_time());
+        store.save(&session)?;
+        session
+    };
+    report(ctx, &session, save, top)
+}
+
+fn show(ctx: &AppContext, save: Option<&str>, top: usize) -> Result<()> {
+    let Some(session) = ctx.cache.session_store().load()? else {
+        bail!(ErrorKind::NotFound.error("no session recorded; run `session start` first"));
+    };
+    report(ctx, &session, save, top)
+}
+
+fn report(ctx: &AppContext, session: &Session, save: Option<&str>, top: usize) -> Result<()> {
+    let plays = ctx
+        .spotify()?
+        .search()
+        .played_after(session.started_at * 1000, HISTORY_LIMIT)?;
+    history::record(ctx, &plays);
+    let truncated = plays.len() as u32 == HISTORY_LIMIT;
+    let report = summarize(session, plays, top, truncated);
+
+    if let Some(name) = save {
+        let uris = session_uris(&report.tracks);
+        if uris.is_empty() {
+            bail!(ErrorKind::NotFound.error("no tracks played during the session"));
+        }
+        let playlist = oplog::create_playlist(ctx, name, Some(false))?;
+        for batch in uris.chunks(100) {
+            oplog::perform(
+                ctx,
+                LoggedOperation::PlaylistAdd {
+                    playlist_id: playlist.id.clone(),
+                    uris: batch.to_vec(),
+                },
+            )?;
+        }
+        let message = format!("Saved session: {} ({} tracks)", playlist.name, uris.len());
+        ctx.output.action("session_save", &message)?;
+    }
+
+    ctx.output.session_report(report)
+}
+
+/// Plays inside the session window, oldest first, with totals and top artists.
+fn summarize(
+    session: &Session,
+    mut plays: Vec<PlayedTrack>,
+    top: usize,
+    truncated: bool,
+) -> SessionReport {
+    let started_at = iso_timestamp(session.started_at);
+    let stopped_at = session.stopped_at.map(iso_timestamp);
+    // Spotify timestamps carry optional fractional seconds; compare whole seconds.
+    plays.retain(|play| {
+        let second = play.played_at.get(..19).unwrap_or(&play.played_at);
+        second >= started_at.as_str()
+            && stopped_at
+                .as_deref()
+                .is_none_or(|stopped_at| second <= stopped_at)
+    });
+    plays.sort_by(|a, b| a.played_at.cmp(&b.played_at));
+
+    let total_ms = plays
+        .iter()
+        .map(|play| u64::from(play.item.duration_ms.unwrap_or(0)))
+        .sum();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for play in &plays {
+        for artist in &play.item.artists {
+            *counts.entry(artist.as_str()).or_default() += 1;
+        }
+    }
+    let mut top_artists: Vec<ArtistCount> = counts
+        .into_iter()
+        .map(|(name, plays)| ArtistCount {
+            name: name.to_string(),
+            plays,
+        })
+        .collect();
+    top_artists.sort_by(|a, b| b.plays.cmp(&a.plays).then_with(|| a.name.cmp(&b.name)));
+    top_artists.truncate(top);
+
+    SessionReport {
+        started_at,
+        stopped_at,
+        tracks: plays,
+        total_ms,
+        top_artists,
+        truncated,
+    }
+}
+
+/// Track URIs in play order, each once.
+fn session_uris(tracks: &[PlayedTrack]) -> Vec<String> {
+    let mut uris: Vec<String> = Vec::new();
+    for track in tracks {
+        if !uris.contains(&track.item.uri) {
+            uris.push(track.item.uri.clone());
+        }
+    }
+    uris
+}
+
+/// Format unix seconds as `YYYY-MM-DDTHH:MM:SS` in UTC, matching Spotify's `played_at`.
+fn iso_timestamp(timestamp: u64) -> String {
+    let seconds = timestamp % 86_400;
+    format!(
+        "{}T{:02}:{:02}:{:02}",
+        civil_date(timestamp),
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{iso_timestamp, session_uris, summarize};
+    use crate::domain::search::{SearchItem, SearchType};
+    use crate::domain::session::{PlayedTrack, Session};
+
+    fn play(id: &str, artists: &[&str], played_at: &str) -> PlayedTrack {
+        PlayedTrack {
+            item: SearchItem {
+                id: id.to_string(),
+                name: id.to_string(),
+                uri: format!("spotify:track:{id}"),
+                kind: SearchType::Track,
+                artists: artists.iter().map(|artist| artist.to_string()).collect(),
+                album: None,
+                duration_ms: Some(60_000),
+                owner: None,
+                score: None,
+                playable: None,
+                linked_from: None,
+                saved: None,
+                explicit: None,
+                popularity: None,
+            },
+            artist_ids: Vec::new(),
+            played_at: played_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn iso_timestamp_formats_utc_seconds() {
+        assert_eq!(iso_timestamp(0), "1970-01-01T00:00:00");
+        assert_eq!(iso_timestamp(1_700_000_000), "2023-11-14T22:13:20");
+    }
+
+    #[test]
+    fn summarize_keeps_window_in_order_and_ranks_artists() {
+        let session = Session {
+            started_at: 1_700_000_000,
+            stopped_at: Some(1_700_003_600),
+        };
+        let plays = vec![
+            play("late", &["B"], "2023-11-14T23:13:21.000Z"),
+            play("c", &["A", "B"], "2023-11-14T22:50:00.500Z"),
+            play("b", &["A"], "2023-11-14T22:30:00Z"),
+            play("a", &["C"], "2023-11-14T22:13:20.100Z"),
+        ];
+        let report = summarize(&session, plays, 2, false);
+        let ids: Vec<&str> = report
+            .tracks
+            .iter()
+            .map(|play| play.item.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert_eq!(report.total_ms, 180_000);
+        let top: Vec<(&str, usize)> = report
+            .top_artists
+            .iter()
+            .map(|artist| (artist.name.as_str(), artist.plays))
+            .collect();
+        assert_eq!(top, vec![("A", 2), ("B", 1)]);
+        assert_eq!(report.stopped_at.as_deref(), Some("2023-11-14T23:13:20"));
+    }
+
+    #[test]
+    fn session_uris_drops_repeats() {
+        let tracks = vec![
+            play("a", &[], "2023-11-14T22:00:00Z"),
+            play("b", &[], "2023-11-14T22:05:00Z"),
+            play("a", &[], "2023-11-14T22:10:00Z"),
+        ];
+        assert_eq!(
+            session_uris(&tracks),
+            vec!["spotify:track:a", "spotify:track:b"]
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Podcast show command handlers.
+use anyhow::bail;
+use clap::{Args, Subcommand};
+
+use crate::AppContext;
+use crate::action::pending::{Applied, run_or_defer};
+use crate::action::{hooks, target};
+use crate::cli::library::save_item;
+use crate::cli::now_playing;
+use crate::cli::play::{ResourceType, parse_id, start_context};
+use crate::domain::episode::Episode;
+use crate::domain::oplog::SavedKind;
+use crate::domain::pending::PendingOperation;
+use crate::error::{ErrorKind, Result};
+
+#[derive(Subcommand, Debug)]
+pub enum ShowCommand {
+    Latest {
+        #[arg(value_name = "SHOW", help = "Show URL, URI, id, or pin alias")]
+        show: String,
+        #[arg(long, conflicts_with = "queue", help = "Play the newest episode")]
+        play: bool,
+        #[arg(long, help = "Queue the newest episode")]
+        queue: bool,
+    },
+    Episodes {
+        #[arg(value_name = "SHOW", help = "Show URL, URI, id, or pin alias")]
+        show: String,
+        #[arg(
+            long,
+            value_name = "TEXT",
+            help = "Only episodes whose title or description matches"
+        )]
+        filter: Option<String>,
+    },
+    List(SavedListArgs),
+    Play {
+        #[arg(value_name = "SHOW", help = "Show URL, URI, id, or pin alias")]
+        show: String,
+        #[arg(long, help = "Turn shuffle on first")]
+        shuffle: bool,
+    },
+    Save {
+        #[arg(
+            value_name = "SHOW",
+            required_unless_present = "now_playing",
+            help = "Show URL, URI, id, pin alias, or `now`"
+        )]
+        show: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "show",
+            help = "Save the show of the playing episode"
+        )]
+        now_playing: bool,
+    },
+}
+
+// Paging and export options shared by the saved show, episode, and audiobook lists. Not a
+// doc comment: clap would print it as each list's help text.
+#[derive(Args, Debug)]
+pub struct SavedListArgs {
+    #[arg(
+        long,
+        default_value_t = 20,
+        value_parser = clap::value_parser!(u32).range(1..=50),
+        help = "Number of items to list (1-50)"
+    )]
+    limit: u32,
+    #[arg(long, conflicts_with = "limit", help = "List every saved item")]
+    all: bool,
+    #[arg(long, help = "Print CSV instead of a table or JSON, for backups")]
+    csv: bool,
+}
+
+impl SavedListArgs {
+    /// `None` under `--all`, meaning every page.
+    pub(crate) fn limit(&self) -> Option<u32> {
+        (!self.all).then_some(self.limit)
+    }
+
+    pub(crate) fn csv(&self) -> bool {
+        self.csv
+    }
+}
+
+pub fn handle(command: ShowCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        ShowCommand::Latest { show, play, queue } => latest(ctx, &show, play, queue),
+        ShowCommand::Episodes { show, filter } => episodes(ctx, &show, filter.as_deref()),
+        ShowCommand::List(args) => list(ctx, &args),
+        ShowCommand::Play { show, shuffle } => {
+            let id = resolve_show_id(ctx, &show)?;
+            start_context(ctx, &format!("spotify:show:{id}"), shuffle, None)
+        }
+        ShowCommand::Save { show, now_playing } => {
+            let id = match show {
+                Some(show) if !now_playing && !target::is_now(&show) => {
+                    resolve_show_id(ctx, &show)?
+                }
+                _ => target::now_show_id(ctx)?,
+            };
+            save_item(ctx, SavedKind::Show, id)
+        }
+    }
+}
+
+fn list(ctx: &AppContext, args: &SavedListArgs) -> Result<()> {
+    let shows = ctx.spotify()?.shows().saved(args.limit())?;
+    ctx.output.saved_show_list(shows, args.csv())
+}
+
+fn latest(ctx: &AppContext, show: &str, play: bool, queue: bool) -> Result<()> {
+    let show_id = resolve_show_id(ctx, show)?;
+    let Some(episode) = ctx.spotify()?.episodes().latest(&show_id)? else {
+        bail!(ErrorKind::NotFound.error("show has no episodes"));
+    };
+
+    if play {
+        hooks::pre_play(ctx, Some(&episode.uri))?;
+        ctx.spotify()?.playback().play_track(&episode.uri)?;
+        hooks::track_changed(ctx);
+        return now_playing::show_with_delay(ctx, 100);
+    }
+    if queue {
+        let operation = PendingOperation::QueueTrack {
+            uri: episode.uri.clone(),
+        };
+        let message = match run_or_defer(ctx, operation)? {
+            Applied::Now => format!("Queued: {}", episode.name),
+            Applied::Deferred => format!("Offline; queued for later: {}", episode.name),
+        };
+        return ctx.output.action("show_queue", &message);
+    }
+    ctx.output.episode_info(episode)
+}
+
+fn episodes(ctx: &AppContext, show: &str, filter: Option<&str>) -> Result<()> {
+    let show_id = resolve_show_id(ctx, show)?;
+    let mut episodes = ctx.spotify()?.episodes().for_show(&show_id)?;
+    if let Some(filter) = filter {
+        episodes.retain(|episode| episode_matches(episode, filter));
+    }
+    ctx.output.episode_list(episodes)
+}
+
+/// Resolve a show from a URL, URI, pin alias, or bare id.
+pub(crate) fn resolve_show_id(ctx: &AppContext, input: &str) -> Result<String> {
+    parse_id(&target::resolve_pin(ctx, input)?, ResourceType::Show)
+}
+
+fn episode_matches(episode: &Episode, filter: &str) -> bool {
+    let needle = filter.to_lowercase();
+    episode.name.to_lowercase().contains(&needle)
+        || episode
+            .description
+            .as_ref()
+            .is_some_and(|text| text.to_lowercase().contains(&needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::episode_matches;
+    use crate::domain::episode::Episode;
+
+    #[test]
+    fn episode_matches_title_or_description() {
+        let episode = Episode {
+            id: "e1".to_string(),
+            name: "Interview with Jane".to_string(),
+            uri: "spotify:episode:e1".to_string(),
+            show: None,
+            description: Some("We talk about synthesizers".to_string()),
+            duration_ms: 1000,
+            release_date: None,
+            resume_position_ms: None,
+            fully_played: false,
+        };
+        assert!(episode_matches(&episode, "jane"));
+        assert!(episode_matches(&episode, "SYNTH"));
+        assert!(!episode_matches(&episode, "guitar"));
+    }
+}
@@ -5,6 +5,7 @@ use crate::AppContext;
 use crate::cache::devices::CacheSnapshot as DeviceSnapshot;
 use crate::cache::playlists::CacheSnapshot as PlaylistSnapshot;
 use crate::error::Result;
+use crate::time::unix_time;
 
 #[derive(Args, Debug)]
 pub struct SyncCommand;
@@ -33,11 +34,3 @@ pub fn handle(_command: SyncCommand, ctx: &AppContext) -> Result<()> {
     );
     ctx.output.action("sync", &message)
 }
-
-fn unix_time() -> u64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("time")
-        .as_secs()
-}
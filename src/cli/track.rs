@@ -0,0 +1,133 @@
+//! Track command handlers.
+use clap::Subcommand;
+
+use crate::AppContext;
+use crate::action::target;
+use crate::cli::play::{ResourceType, parse_id};
+use crate::cli::playlist::{cached_playlists, retain_owned, track_positions};
+use crate::domain::playlist::{Playlist, PlaylistHit, PlaylistTrack, TrackLocation};
+use crate::domain::search::SearchType;
+use crate::error::Result;
+use crate::spotify::cancel;
+
+#[derive(Subcommand, Debug)]
+pub enum TrackCommand {
+    Locate {
+        #[arg(value_name = "TRACK", help = "Track URL, URI, id, or `now`")]
+        track: String,
+        #[arg(long, help = "Only scan playlists you own")]
+        owned: bool,
+        #[arg(long, help = "Refetch the playlist list instead of using the cache")]
+        refresh: bool,
+    },
+}
+
+pub fn handle(command: TrackCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        TrackCommand::Locate {
+            track,
+            owned,
+            refresh,
+        } => locate(ctx, &track, owned, refresh),
+    }
+}
+
+fn locate(ctx: &AppContext, input: &str, owned: bool, refresh: bool) -> Result<()> {
+    let input = target::resolve_target(ctx, input, SearchType::Track)?;
+    let id = parse_id(&input, ResourceType::Track)?;
+    let spotify = ctx.spotify()?;
+    let liked = spotify
+        .track()
+        .contains(std::slice::from_ref(&id))?
+        .first()
+        .copied()
+        .unwrap_or(false);
+
+    let mut playlists = cached_playlists(ctx, refresh)?;
+    if owned {
+        retain_owned(ctx, &mut playlists)?;
+    }
+    let mut progress = ctx
+        .output
+        .progress("Scanning playlists", Some(playlists.len() as u64));
+    let mut hits = Vec::new();
+    let mut skipped = 0;
+    for playlist in playlists {
+        cancel::check()?;
+        match spotify.playlists().items_all(&playlist.id) {
+            Ok(items) => hits.extend(playlist_hit(playlist, &items, &id)),
+            // Spotify-owned playlists are not readable by every app; one is not worth failing for.
+            Err(err) if cancel::reason().is_none() => {
+                eprintln!("warning: skipped playlist {}: {err}", playlist.name);
+                skipped += 1;
+            }
+            Err(err) => return Err(err),
+        }
+        progress.inc(1);
+    }
+    progress.finish();
+
+    ctx.output.track_location(TrackLocation {
+        uri: format!("spotify:track:{id}"),
+        liked,
+        playlists: hits,
+        skipped,
+    })
+}
+
+fn playlist_hit(playlist: Playlist, items: &[PlaylistTrack], id: &str) -> Option<PlaylistHit> {
+    let positions = track_positions(items, id);
+    if positions.is_empty() {
+        return None;
+    }
+    Some(PlaylistHit {
+        playlist,
+        positions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::playlist_hit;
+    use crate::domain::playlist::{Playlist, PlaylistTrack};
+    use crate::domain::track::Track;
+
+    fn playlist() -> Playlist {
+        Playlist {
+            id: "pl".to_string(),
+            name: "Mix".to_string(),
+            owner: None,
+            collaborative: false,
+            public: None,
+            description: None,
+        }
+    }
+
+    fn item(id: &str) -> PlaylistTrack {
+        PlaylistTrack {
+            track: Track {
+                id: id.to_string(),
+                name: id.to_string(),
+                artists: Vec::new(),
+                artist_ids: Vec::new(),
+                album: None,
+                album_id: None,
+                duration_ms: None,
+            },
+            added_at: None,
+            added_by: None,
+            popularity: None,
+            explicit: None,
+            release_date: None,
+        }
+    }
+
+    #[test]
+    fn playlist_hit_reports_every_position() {
+        let items = vec![item("a"), item("b"), item("a")];
+        let hit = playlist_hit(playlist(), &items, "a").unwrap();
+        assert_eq!(hit.playlist.id, "pl");
+        assert_eq!(hit.positions, vec![1, 3]);
+        assert!(playlist_hit(playlist(), &items, "c").is_none());
+    }
+}
@@ -0,0 +1,321 @@
+//! User profile command handlers.
+use std::collections::HashMap;
+
+use anyhow::bail;
+use clap::{Subcommand, ValueEnum};
+
+use crate::AppContext;
+use crate::action::playlist::resolve_for_write;
+use crate::action::{explicit, oplog};
+use crate::cli::playlist::confirm_foreign;
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::search::{SearchItem, SearchResults, SearchType};
+use crate::domain::stats::{GenreShare, ListeningStats, RangeTop};
+use crate::error::{ErrorKind, Result};
+
+/// Spotify returns at most 50 top items per page.
+const PAGE_LIMIT: u32 = 50;
+/// Spotify only ranks the first 99 top items.
+const TOP_WINDOW: u32 = 99;
+
+#[derive(Subcommand, Debug)]
+pub enum UserCommand {
+    Top {
+        #[arg(value_enum, default_value = "tracks")]
+        kind: TopKindArg,
+        #[arg(
+            long = "range",
+            visible_alias = "time-range",
+            value_enum,
+            default_value = "medium",
+            help = "Time range (short, medium, long, or Spotify's *_term values)"
+        )]
+        range: TimeRangeArg,
+        #[arg(long, default_value_t = 10, help = "Limit results")]
+        limit: u32,
+        #[arg(long, default_value_t = 0, help = "Skip the first N items")]
+        offset: u32,
+        #[arg(long, conflicts_with_all = ["limit", "offset"], help = "Fetch the full top-items window")]
+        all: bool,
+        #[arg(
+            long,
+            value_name = "PLAYLIST",
+            help = "Add the top tracks to a playlist"
+        )]
+        save_to: Option<String>,
+        #[arg(
+            long,
+            requires = "save_to",
+            help = "Save to a playlist you do not own without asking"
+        )]
+        force: bool,
+    },
+    Stats {
+        #[arg(long, default_value_t = 5, help = "Items per section")]
+        limit: u32,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopKindArg {
+    Tracks,
+    Artists,
+    /// Ranked from the genre tags of the top artists.
+    Genres,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeRangeArg {
+    #[value(alias = "short_term")]
+    Short,
+    #[value(alias = "medium_term")]
+    Medium,
+    #[value(alias = "long_term")]
+    Long,
+}
+
+impl TimeRangeArg {
+    fn as_param(&self) -> &'static str {
+        match self {
+            TimeRangeArg::Short => "short_term",
+            TimeRangeArg::Medium => "medium_term",
+            TimeRangeArg::Long => "long_term",
+        }
+    }
+}
+
+pub fn handle(command: UserCommand, ctx: &AppContext) -> Result<()> {
+    match command {
+        UserCommand::Top {
+            kind,
+            range,
+            limit,
+            offset,
+            all,
+            save_to,
+            force,
+        } => {
+            let save_to = save_to.map(|playlist| SaveTo { playlist, force });
+            top(ctx, kind, range, limit, offset, all, save_to)
+        }
+        UserCommand::Stats { limit } => stats(ctx, limit),
+    }
+}
+
+/// `--save-to` destination for top tracks.
+struct SaveTo {
+    playlist: String,
+    force: bool,
+}
+
+fn top(
+    ctx: &AppContext,
+    kind: TopKindArg,
+    range: TimeRangeArg,
+    limit: u32,
+    offset: u32,
+    all: bool,
+    save_to: Option<SaveTo>,
+) -> Result<()> {
+    let kind = match kind {
+        TopKindArg::Tracks => SearchType::Track,
+        TopKindArg::Artists => SearchType::Artist,
+        TopKindArg::Genres => {
+            if save_to.is_some() {
+                bail!(ErrorKind::Validation.error("--save-to only works with top tracks"));
+            }
+            return top_genres(ctx, range, if all { usize::MAX } else { limit as usize });
+        }
+    };
+    if save_to.is_some() && kind != SearchType::Track {
+        bail!(ErrorKind::Validation.error("--save-to only works with top tracks"));
+    }
+
+    let (limit, offset) = if all {
+        (TOP_WINDOW, 0)
+    } else {
+        (limit, offset)
+    };
+    let search = ctx.spotify()?.search();
+    let mut items: Vec<SearchItem> = Vec::new();
+    for (page_offset, page_limit) in pages(offset, limit) {
+        let page = search.top(kind, range.as_param(), page_limit, page_offset)?;
+        let done = (page.len() as u32) < page_limit;
+        items.extend(page);
+        if done {
+            break;
+        }
+    }
+    explicit::filter(ctx, &mut items);
+
+    if let Some(SaveTo { playlist, force }) = save_to {
+        let selection = resolve_for_write(ctx, Some(&playlist), false, true, None)?;
+        confirm_foreign(ctx, &selection, force)?;
+        let uris: Vec<String> = items.iter().map(|item| item.uri.clone()).collect();
+        for batch in uris.chunks(100) {
+            oplog::perform(
+                ctx,
+                LoggedOperation::PlaylistAdd {
+                    playlist_id: selection.id.clone(),
+                    uris: batch.to_vec(),
+                },
+            )?;
+        }
+        let message = format!("Added {} tracks -> {}", uris.len(), selection.name);
+        ctx.output.action("user_top_save", &message)?;
+    }
+
+    ctx.output.search_results(SearchResults { kind, items })
+}
+
+/// Spotify has no top-genres endpoint, so rank the genres of the whole top-artists window.
+fn top_genres(ctx: &AppContext, range: TimeRangeArg, limit: usize) -> Result<()> {
+    let spotify = ctx.spotify()?;
+    let mut ids = Vec::new();
+    for (page_offset, page_limit) in pages(0, TOP_WINDOW) {
+        let page = spotify.search().top(
+            SearchType::Artist,
+            range.as_param(),
+            page_limit,
+            page_offset,
+        )?;
+        let done = (page.len() as u32) < page_limit;
+        ids.extend(page.into_iter().map(|item| item.id));
+        if done {
+            break;
+        }
+    }
+    // Top artist items carry no genres; look them up in batches.
+    let mut artists = spotify.artists().several(&ids)?;
+    artists.sort_by_key(|artist| ids.iter().position(|id| *id == artist.id));
+    let ranked: Vec<Vec<String>> = artists.into_iter().map(|artist| artist.genres).collect();
+    let mut genres = genre_shares(&ranked);
+    genres.truncate(limit);
+    ctx.output.top_genres(genres)
+}
+
+/// Weight artists by rank (the top of `n` artists counts `n`, the last counts 1) and split
+/// each artist's weight evenly across its genres; highest share first.
+fn genre_shares(ranked: &[Vec<String>]) -> Vec<GenreShare> {
+    let mut weights: HashMap<&str, (f64, usize)> = HashMap::new();
+    let mut total = 0.0;
+    for (rank, genres) in ranked.iter().enumerate() {
+        if genres.is_empty() {
+            continue;
+        }
+        let weight = (ranked.len() - rank) as f64;
+        total += weight;
+        for genre in genres {
+            let entry = weights.entry(genre.as_str()).or_default();
+            entry.0 += weight / genres.len() as f64;
+            entry.1 += 1;
+        }
+    }
+    let mut shares: Vec<GenreShare> = weights
+        .into_iter()
+        .map(|(genre, (weight, artists))| GenreShare {
+            genre: genre.to_string(),
+            percent: weight / total * 100.0,
+            artists,
+        })
+        .collect();
+    shares.sort_by(|a, b| {
+        b.percent
+            .total_cmp(&a.percent)
+            .then_with(|| a.genre.cmp(&b.genre))
+    });
+    shares
+}
+
+fn stats(ctx: &AppContext, limit: u32) -> Result<()> {
+    let limit = limit.clamp(1, PAGE_LIMIT);
+    let spotify = ctx.spotify()?;
+    let ranges = [
+        TimeRangeArg::Short,
+        TimeRangeArg::Medium,
+        TimeRangeArg::Long,
+    ];
+
+    // Each endpoint is independent, so fetch them all at once.
+    let stats = std::thread::scope(|scope| -> Result<ListeningStats> {
+        let tops: Vec<_> = ranges
+            .iter()
+            .map(|range| {
+                let range = range.as_param();
+                let tracks =
+                    scope.spawn(move || spotify.search().top(SearchType::Track, range, limit, 0));
+                let artists =
+                    scope.spawn(move || spotify.search().top(SearchType::Artist, range, limit, 0));
+                (range, tracks, artists)
+            })
+            .collect();
+        let recent = scope.spawn(|| spotify.search().recently_played(limit));
+        let followed = scope.spawn(|| spotify.artists().followed());
+
+        let mut stats = ListeningStats {
+            ranges: Vec::new(),
+            recently_played: join(recent)?,
+            followed_artists: join(followed)?.len(),
+        };
+        for (range, tracks, artists) in tops {
+            let mut tracks = join(tracks)?;
+            explicit::filter(ctx, &mut tracks);
+            stats.ranges.push(RangeTop {
+                range: range.to_string(),
+                tracks,
+                artists: join(artists)?,
+            });
+        }
+        Ok(stats)
+    })?;
+
+    ctx.output.listening_stats(stats)
+}
+
+fn join<T>(handle: std::thread::ScopedJoinHandle<'_, Result<T>>) -> Result<T> {
+    match handle.join() {
+        Ok(result) => result,
+        Err(_) => bail!(ErrorKind::General.error("stats request thread panicked")),
+    }
+}
+
+/// Split an offset/limit request into API-sized `(offset, limit)` pages.
+fn pages(offset: u32, limit: u32) -> Vec<(u32, u32)> {
+    let mut pages = Vec::new();
+    let mut start = offset;
+    let end = offset.saturating_add(limit);
+    while start < end {
+        let size = (end - start).min(PAGE_LIMIT);
+        pages.push((start, size));
+        start += size;
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{genre_shares, pages};
+
+    #[test]
+    fn pages_split_into_api_sized_chunks() {
+        assert_eq!(pages(0, 10), vec![(0, 10)]);
+        assert_eq!(pages(0, 99), vec![(0, 50), (50, 49)]);
+        assert_eq!(pages(20, 60), vec![(20, 50), (70, 10)]);
+        assert!(pages(0, 0).is_empty());
+    }
+
+    #[test]
+    fn genre_shares_weight_by_rank_and_split_across_genres() {
+        let genres = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        let ranked: Vec<Vec<String>> =
+            vec![genres(&["indie", "rock"]), genres(&[]), genres(&["indie"])];
+        let shares = genre_shares(&ranked);
+        // Weights: indie 3/2 + 1, rock 3/2, out of 3 + 1.
+        assert_eq!(shares[0].genre, "indie");
+        assert_eq!(shares[0].artists, 2);
+        assert!((shares[0].percent - 62.5).abs() < 1e-9);
+        assert_eq!(shares[1].genre, "rock");
+        assert!((shares[1].percent - 37.5).abs() < 1e-9);
+        assert!(genre_shares(&[]).is_empty());
+    }
+}
@@ -17,6 +17,21 @@ pub struct Album {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlbumTrack {
     pub name: String,
+    pub uri: String,
     pub duration_ms: u32,
     pub track_number: u32,
+    /// Whether the track is in the user's library, when checked.
+    #[serde(default)]
+    pub saved: Option<bool>,
+}
+
+/// Album saved to the user's library.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedAlbum {
+    pub id: String,
+    pub name: String,
+    pub uri: String,
+    pub artists: Vec<String>,
+    pub release_date: Option<String>,
+    pub added_at: Option<String>,
 }
@@ -8,4 +8,26 @@ pub struct Artist {
     pub uri: String,
     pub genres: Vec<String>,
     pub followers: Option<u64>,
+    #[serde(default)]
+    pub popularity: Option<u32>,
+    /// Image URLs, largest first.
+    #[serde(default)]
+    pub images: Vec<String>,
+}
+
+/// Change in follower count since the earliest locally recorded sample.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowerTrend {
+    pub since: u64,
+    pub from: u64,
+    pub change: i64,
+}
+
+/// One cursor-paged slice of the user's followed artists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowedArtistPage {
+    pub items: Vec<Artist>,
+    pub total: Option<u32>,
+    /// Cursor for the next page (`--after`), absent on the last page.
+    pub after: Option<String>,
 }
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Audiobook metadata for info output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Audiobook {
+    pub id: String,
+    pub name: String,
+    pub uri: String,
+    pub authors: Vec<String>,
+    pub narrators: Vec<String>,
+    pub publisher: Option<String>,
+    pub description: Option<String>,
+    pub total_chapters: Option<u32>,
+}
+
+/// Audiobook saved to the user's library.
+///
+/// Spotify does not report when audiobooks were saved, so `added_at` is usually empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedAudiobook {
+    pub audiobook: Audiobook,
+    pub added_at: Option<String>,
+}
@@ -1,8 +1,26 @@
 /// Authentication status returned by `spotify-cli auth status`.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
 pub struct AuthStatus {
     pub logged_in: bool,
     pub expires_at: Option<u64>,
+    /// Client id used for login and refresh.
+    pub client_id: Option<String>,
+    pub granted_scopes: Option<Vec<String>>,
+    /// File holding the tokens; there is no keychain backend.
+    pub token_path: String,
+    /// Account behind the token; `None` when logged out or the lookup failed.
+    pub profile: Option<AccountProfile>,
+}
+
+/// Spotify account from `GET /me`.
+#[derive(Debug, Clone)]
+pub struct AccountProfile {
+    pub id: String,
+    pub display_name: Option<String>,
+    /// `premium`, `free`, or `open`.
+    pub product: Option<String>,
+    pub country: Option<String>,
 }
 
 /// Scope inspection payload for `spotify-cli auth scopes`.
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// What a blocklist entry matches against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockKind {
+    Artist,
+    Track,
+}
+
+impl BlockKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockKind::Artist => "artist",
+            BlockKind::Track => "track",
+        }
+    }
+}
+
+/// Artist or track that should be skipped when it comes up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockEntry {
+    pub kind: BlockKind,
+    pub id: String,
+    /// Display name captured when the entry was added.
+    #[serde(default)]
+    pub name: Option<String>,
+}
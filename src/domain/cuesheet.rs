@@ -0,0 +1,44 @@
+/// Text layout for `playlist cuesheet` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CueFormat {
+    Cue,
+    Tracklist,
+}
+
+/// Continuous-playback tracklist with cumulative start offsets.
+#[derive(Debug, Clone)]
+pub struct Cuesheet {
+    pub title: String,
+    pub performer: Option<String>,
+    pub entries: Vec<CueEntry>,
+}
+
+/// Single track entry within a cue sheet.
+#[derive(Debug, Clone)]
+pub struct CueEntry {
+    pub title: String,
+    pub performer: String,
+    pub uri: String,
+    pub start_ms: u64,
+    pub duration_ms: u64,
+}
+
+/// Playlist item feeding a cue sheet; local files and unavailable items keep their slot.
+#[derive(Debug, Clone)]
+pub struct CueItem {
+    pub title: String,
+    pub performer: String,
+    /// Empty for an item Spotify no longer returns.
+    pub uri: String,
+    pub duration_ms: u64,
+}
+
+impl Cuesheet {
+    /// Total length of the sheet in milliseconds.
+    pub fn duration_ms(&self) -> u64 {
+        self.entries
+            .last()
+            .map(|entry| entry.start_ms + entry.duration_ms)
+            .unwrap_or(0)
+    }
+}
@@ -2,8 +2,12 @@ use serde::{Deserialize, Serialize};
 
 /// Playback device metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Device {
     pub id: String,
     pub name: String,
     pub volume_percent: Option<u32>,
+    /// `Some(false)` when Spotify says the device's volume cannot be changed remotely.
+    #[serde(default)]
+    pub supports_volume: Option<bool>,
 }
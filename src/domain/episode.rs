@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// Podcast episode metadata with the user's saved position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Episode {
+    pub id: String,
+    pub name: String,
+    pub uri: String,
+    pub show: Option<String>,
+    pub description: Option<String>,
+    pub duration_ms: u32,
+    pub release_date: Option<String>,
+    pub resume_position_ms: Option<u32>,
+    pub fully_played: bool,
+}
+
+/// Episode saved to the user's library.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedEpisode {
+    pub episode: Episode,
+    pub added_at: Option<String>,
+}
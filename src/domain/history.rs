@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// A followed artist or liked track that `prune` proposes to remove.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneCandidate {
+    pub id: String,
+    pub name: String,
+    /// Artist names, for tracks.
+    pub artists: Vec<String>,
+    /// Last play in the local history (ISO 8601 UTC); `None` when never recorded.
+    pub last_played: Option<String>,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Spotify market (ISO 3166-1 alpha-2 country code) with its country name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Market {
+    pub code: String,
+    pub name: Option<String>,
+}
+
+/// Markets in which a track or album is playable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketAvailability {
+    pub uri: String,
+    pub name: String,
+    pub markets: Vec<Market>,
+    /// Active market (`--market` or the configured default) and whether it is among `markets`.
+    pub current: Option<(String, bool)>,
+}
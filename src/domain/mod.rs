@@ -1,12 +1,25 @@
 //! Domain models shared across CLI, output, cache, and Spotify API layers.
 pub mod album;
 pub mod artist;
+pub mod audiobook;
 pub mod auth;
+pub mod block;
 pub mod cache;
+pub mod cuesheet;
 pub mod device;
+pub mod episode;
+pub mod history;
+pub mod market;
+pub mod oplog;
+pub mod pending;
 pub mod pin;
 pub mod player;
 pub mod playlist;
+pub mod queue;
+pub mod release;
 pub mod search;
+pub mod session;
 pub mod settings;
+pub mod show;
+pub mod stats;
 pub mod track;
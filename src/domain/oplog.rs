@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+
+/// Mutating Spotify call recorded in the operations log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum LoggedOperation {
+    PlaylistAdd {
+        playlist_id: String,
+        uris: Vec<String>,
+    },
+    PlaylistRemove {
+        playlist_id: String,
+        uris: Vec<String>,
+    },
+    PlaylistCreate {
+        playlist_id: String,
+        name: String,
+    },
+    PlaylistRename {
+        playlist_id: String,
+        from: String,
+        to: String,
+    },
+    /// Change visibility; `from` is what `log undo` restores.
+    PlaylistEdit {
+        playlist_id: String,
+        from: PlaylistVisibility,
+        to: PlaylistVisibility,
+    },
+    /// Move one track: the entry at `range_start` is reinserted before `insert_before`.
+    PlaylistMove {
+        playlist_id: String,
+        range_start: u32,
+        insert_before: u32,
+    },
+    PlaylistFollow {
+        playlist_id: String,
+    },
+    PlaylistUnfollow {
+        playlist_id: String,
+    },
+    LibrarySave {
+        ids: Vec<String>,
+    },
+    LibraryRemove {
+        ids: Vec<String>,
+    },
+    /// Save episodes, shows, or audiobooks; tracks use `LibrarySave`.
+    ItemSave {
+        kind: SavedKind,
+        ids: Vec<String>,
+    },
+    ItemRemove {
+        kind: SavedKind,
+        ids: Vec<String>,
+    },
+    ArtistFollow {
+        ids: Vec<String>,
+    },
+    ArtistUnfollow {
+        ids: Vec<String>,
+    },
+    QueueAdd {
+        uri: String,
+    },
+    /// Skip ahead `count` tracks.
+    PlayerSkip {
+        count: u32,
+    },
+    /// Start playing a context from its first track.
+    PlayerPlay {
+        context_uri: String,
+    },
+}
+
+impl LoggedOperation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LoggedOperation::PlaylistAdd { .. } => "playlist add",
+            LoggedOperation::PlaylistRemove { .. } => "playlist remove",
+            LoggedOperation::PlaylistCreate { .. } => "playlist create",
+            LoggedOperation::PlaylistRename { .. } => "playlist rename",
+            LoggedOperation::PlaylistEdit { .. } => "playlist edit",
+            LoggedOperation::PlaylistMove { .. } => "playlist move",
+            LoggedOperation::PlaylistFollow { .. } => "playlist follow",
+            LoggedOperation::PlaylistUnfollow { .. } => "playlist unfollow",
+            LoggedOperation::LibrarySave { .. } => "library save",
+            LoggedOperation::LibraryRemove { .. } => "library remove",
+            LoggedOperation::ItemSave { kind, .. } => match kind {
+                SavedKind::Episode => "episode save",
+                SavedKind::Show => "show save",
+                SavedKind::Audiobook => "audiobook save",
+            },
+            LoggedOperation::ItemRemove { kind, .. } => match kind {
+                SavedKind::Episode => "episode remove",
+                SavedKind::Show => "show remove",
+                SavedKind::Audiobook => "audiobook remove",
+            },
+            LoggedOperation::ArtistFollow { .. } => "artist follow",
+            LoggedOperation::ArtistUnfollow { .. } => "artist unfollow",
+            LoggedOperation::QueueAdd { .. } => "queue add",
+            LoggedOperation::PlayerSkip { .. } => "player skip",
+            LoggedOperation::PlayerPlay { .. } => "player play",
+        }
+    }
+
+    /// Playlist id, track, artist, or saved item ids, or the uri the operation touched.
+    pub fn target(&self) -> String {
+        match self {
+            LoggedOperation::PlaylistAdd { playlist_id, .. }
+            | LoggedOperation::PlaylistRemove { playlist_id, .. }
+            | LoggedOperation::PlaylistCreate { playlist_id, .. }
+            | LoggedOperation::PlaylistRename { playlist_id, .. }
+            | LoggedOperation::PlaylistEdit { playlist_id, .. }
+            | LoggedOperation::PlaylistMove { playlist_id, .. }
+            | LoggedOperation::PlaylistFollow { playlist_id }
+            | LoggedOperation::PlaylistUnfollow { playlist_id } => playlist_id.clone(),
+            LoggedOperation::LibrarySave { ids }
+            | LoggedOperation::LibraryRemove { ids }
+            | LoggedOperation::ItemSave { ids, .. }
+            | LoggedOperation::ItemRemove { ids, .. }
+            | LoggedOperation::ArtistFollow { ids }
+            | LoggedOperation::ArtistUnfollow { ids } => ids.join(","),
+            LoggedOperation::QueueAdd { uri } => uri.clone(),
+            LoggedOperation::PlayerSkip { .. } => String::new(),
+            LoggedOperation::PlayerPlay { context_uri } => context_uri.clone(),
+        }
+    }
+
+    pub fn detail(&self) -> String {
+        match self {
+            LoggedOperation::PlaylistAdd { uris, .. }
+            | LoggedOperation::PlaylistRemove { uris, .. } => format!("{} tracks", uris.len()),
+            LoggedOperation::PlaylistCreate { name, .. } => name.clone(),
+            LoggedOperation::PlaylistRename { from, to, .. } => format!("{from} -> {to}"),
+            LoggedOperation::PlaylistEdit { from, to, .. } => {
+                format!("{} -> {}", from.describe(), to.describe())
+            }
+            LoggedOperation::PlaylistMove {
+                range_start,
+                insert_before,
+                ..
+            } => format!("#{} -> before #{}", range_start + 1, insert_before + 1),
+            LoggedOperation::PlaylistFollow { .. }
+            | LoggedOperation::PlaylistUnfollow { .. }
+            | LoggedOperation::QueueAdd { .. }
+            | LoggedOperation::PlayerPlay { .. } => String::new(),
+            LoggedOperation::PlayerSkip { count } => format!("{count} tracks"),
+            LoggedOperation::LibrarySave { ids } | LoggedOperation::LibraryRemove { ids } => {
+                format!("{} tracks", ids.len())
+            }
+            LoggedOperation::ItemSave { kind, ids } | LoggedOperation::ItemRemove { kind, ids } => {
+                format!("{} {}s", ids.len(), kind.as_str())
+            }
+            LoggedOperation::ArtistFollow { ids } | LoggedOperation::ArtistUnfollow { ids } => {
+                format!("{} artists", ids.len())
+            }
+        }
+    }
+
+    /// Operation that reverses this one, if Spotify offers one.
+    pub fn inverse(&self) -> Option<LoggedOperation> {
+        let inverse = match self.clone() {
+            LoggedOperation::PlaylistAdd { playlist_id, uris } => {
+                LoggedOperation::PlaylistRemove { playlist_id, uris }
+            }
+            LoggedOperation::PlaylistRemove { playlist_id, uris } => {
+                LoggedOperation::PlaylistAdd { playlist_id, uris }
+            }
+            LoggedOperation::PlaylistCreate { playlist_id, .. }
+            | LoggedOperation::PlaylistFollow { playlist_id } => {
+                LoggedOperation::PlaylistUnfollow { playlist_id }
+            }
+            LoggedOperation::PlaylistRename {
+                playlist_id,
+                from,
+                to,
+            } => LoggedOperation::PlaylistRename {
+                playlist_id,
+                from: to,
+                to: from,
+            },
+            LoggedOperation::PlaylistEdit {
+                playlist_id,
+                from,
+                to,
+            } => LoggedOperation::PlaylistEdit {
+                playlist_id,
+                from: to,
+                to: from,
+            },
+            LoggedOperation::PlaylistMove {
+                playlist_id,
+                range_start,
+                insert_before,
+            } => {
+                // Where the track landed, and the slot that puts it back.
+                let landed = if insert_before > range_start {
+                    insert_before - 1
+                } else {
+                    insert_before
+                };
+                let back = if range_start > landed {
+                    range_start + 1
+                } else {
+                    range_start
+                };
+                LoggedOperation::PlaylistMove {
+                    playlist_id,
+                    range_start: landed,
+                    insert_before: back,
+                }
+            }
+            LoggedOperation::PlaylistUnfollow { playlist_id } => {
+                LoggedOperation::PlaylistFollow { playlist_id }
+            }
+            LoggedOperation::LibrarySave { ids } => LoggedOperation::LibraryRemove { ids },
+            LoggedOperation::LibraryRemove { ids } => LoggedOperation::LibrarySave { ids },
+            LoggedOperation::ItemSave { kind, ids } => LoggedOperation::ItemRemove { kind, ids },
+            LoggedOperation::ItemRemove { kind, ids } => LoggedOperation::ItemSave { kind, ids },
+            LoggedOperation::ArtistFollow { ids } => LoggedOperation::ArtistUnfollow { ids },
+            LoggedOperation::ArtistUnfollow { ids } => LoggedOperation::ArtistFollow { ids },
+            // Queued items cannot be removed, nor skips or playback taken back, through the Web API.
+            LoggedOperation::QueueAdd { .. }
+            | LoggedOperation::PlayerSkip { .. }
+            | LoggedOperation::PlayerPlay { .. } => return None,
+        };
+        Some(inverse)
+    }
+}
+
+/// Kind of non-track item saved to the library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedKind {
+    Episode,
+    Show,
+    Audiobook,
+}
+
+impl SavedKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SavedKind::Episode => "episode",
+            SavedKind::Show => "show",
+            SavedKind::Audiobook => "audiobook",
+        }
+    }
+}
+
+/// Public and collaborative flags of a playlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaylistVisibility {
+    /// `None` when Spotify did not report it.
+    pub public: Option<bool>,
+    pub collaborative: bool,
+}
+
+impl PlaylistVisibility {
+    pub fn describe(&self) -> String {
+        let public = match self.public {
+            Some(true) => "public",
+            Some(false) => "private",
+            None => "unknown visibility",
+        };
+        if self.collaborative {
+            format!("{public}, collaborative")
+        } else {
+            public.to_string()
+        }
+    }
+}
+
+/// Append-only operations log entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpLogEntry {
+    pub id: u64,
+    pub at: u64,
+    #[serde(flatten)]
+    pub operation: LoggedOperation,
+    /// Playlist snapshot after the change, when Spotify returned one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+    /// Entry this one reverted via `log undo`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<u64>,
+}
+
+/// Whether a dry-run diff line adds or removes a track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffChange {
+    Added,
+    Removed,
+}
+
+impl DiffChange {
+    pub fn sign(&self) -> &'static str {
+        match self {
+            DiffChange::Added => "+",
+            DiffChange::Removed => "-",
+        }
+    }
+}
+
+/// One track line in a dry-run playlist diff.
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    pub change: DiffChange,
+    pub uri: String,
+    /// `Track - Artist` when the track could be resolved.
+    pub label: Option<String>,
+    /// Added while already in the playlist, or added twice by the plan.
+    pub duplicate: bool,
+}
+
+/// Planned track changes for one playlist under `--dry-run`.
+#[derive(Debug, Clone)]
+pub struct PlaylistDiff {
+    pub playlist_id: String,
+    pub name: Option<String>,
+    pub lines: Vec<DiffLine>,
+}
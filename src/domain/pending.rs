@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Mutation that can be replayed once Spotify is reachable again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PendingOperation {
+    SaveTrack { id: String },
+    QueueTrack { uri: String },
+    FollowArtist { id: String },
+}
+
+impl PendingOperation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PendingOperation::SaveTrack { .. } => "library save",
+            PendingOperation::QueueTrack { .. } => "queue add",
+            PendingOperation::FollowArtist { .. } => "artist follow",
+        }
+    }
+
+    pub fn target(&self) -> &str {
+        match self {
+            PendingOperation::SaveTrack { id } => id,
+            PendingOperation::QueueTrack { uri } => uri,
+            PendingOperation::FollowArtist { id } => id,
+        }
+    }
+}
+
+/// Journal entry for a deferred mutation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingEntry {
+    #[serde(flatten)]
+    pub operation: PendingOperation,
+    pub queued_at: u64,
+}
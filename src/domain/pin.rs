@@ -5,4 +5,19 @@ use serde::{Deserialize, Serialize};
 pub struct PinnedPlaylist {
     pub name: String,
     pub url: String,
+    /// Lowercase labels for grouping pins, e.g. `workout`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl PinnedPlaylist {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = normalize_tag(tag);
+        self.tags.contains(&tag)
+    }
+}
+
+/// Tags are kept trimmed and lowercase so `Workout` and `workout ` are one tag.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
 }
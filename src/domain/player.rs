@@ -1,14 +1,41 @@
+use serde::{Deserialize, Serialize};
+
 use crate::domain::{device::Device, track::Track};
 
 /// Playback context for the current player session.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct PlaybackContext {
     pub kind: String,
     pub uri: String,
+    /// Display name of the context resource, when it has been resolved.
+    pub name: Option<String>,
+}
+
+/// Podcast episode in the player, which `PlayerStatus` does not report.
+#[derive(Debug, Clone)]
+pub struct PlayingEpisode {
+    pub id: String,
+    pub uri: String,
+    pub show_id: Option<String>,
+}
+
+/// What the player is playing, by kind.
+#[derive(Debug, Clone)]
+pub enum PlayingItem {
+    Track {
+        id: String,
+    },
+    Episode(PlayingEpisode),
+    /// A chapter of the audiobook `audiobook_id`.
+    Chapter {
+        audiobook_id: String,
+    },
 }
 
 /// Playback status from the Spotify player endpoint.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct PlayerStatus {
     pub is_playing: bool,
     pub track: Option<Track>,
@@ -18,3 +45,15 @@ pub struct PlayerStatus {
     pub repeat_state: Option<String>,
     pub shuffle_state: Option<bool>,
 }
+
+/// Playback context seen in the player, and where in it playback was last seen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentContext {
+    pub uri: String,
+    pub kind: String,
+    pub name: Option<String>,
+    pub track_uri: Option<String>,
+    pub track_name: Option<String>,
+    pub position_ms: u32,
+    pub seen_at: u64,
+}
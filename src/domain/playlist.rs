@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::track::Track;
+
 /// Minimal playlist representation for listing and selection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Playlist {
     pub id: String,
     pub name: String,
@@ -10,10 +13,94 @@ pub struct Playlist {
     pub collaborative: bool,
     #[serde(default)]
     pub public: Option<bool>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Where a `playlist find` query matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchField {
+    Name,
+    Description,
+}
+
+/// One of the user's playlists matching a `playlist find` query.
+#[derive(Debug, Clone)]
+pub struct PlaylistMatch {
+    pub playlist: Playlist,
+    pub score: f32,
+    pub field: MatchField,
+}
+
+/// One of the user's playlists holding a located track, with its 1-based positions.
+#[derive(Debug, Clone)]
+pub struct PlaylistHit {
+    pub playlist: Playlist,
+    pub positions: Vec<usize>,
+}
+
+/// Where a track is saved, for `track locate`.
+#[derive(Debug, Clone)]
+pub struct TrackLocation {
+    pub uri: String,
+    pub liked: bool,
+    pub playlists: Vec<PlaylistHit>,
+    /// Playlists that could not be read, such as Spotify-owned ones.
+    pub skipped: usize,
+}
+
+/// Playlist track as the user's market sees it.
+#[derive(Debug, Clone)]
+pub struct TrackAvailability {
+    /// 1-based position in the playlist.
+    pub position: usize,
+    pub uri: String,
+    /// The track Spotify plays, a different version from the saved one when relinked.
+    pub track: Track,
+    pub playable: bool,
+    /// URI of the saved track when Spotify relinked it.
+    pub linked_from: Option<String>,
+    /// Why the track does not play: `market`, `product`, or `explicit`.
+    pub restriction: Option<String>,
+    pub isrc: Option<String>,
+}
+
+/// Track flagged by `playlist audit`.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    /// 1-based position in the playlist.
+    pub position: usize,
+    pub uri: String,
+    pub track: Track,
+    pub status: AuditStatus,
+}
+
+#[derive(Debug, Clone)]
+pub enum AuditStatus {
+    /// Plays as a relinked version of the saved track `from`.
+    Relinked { from: String },
+    /// Does not play; `replacement` is the URI of a playable version with the same ISRC.
+    Unavailable {
+        reason: Option<String>,
+        replacement: Option<String>,
+        replaced: bool,
+    },
+}
+
+/// Result of `playlist audit`.
+#[derive(Debug, Clone)]
+pub struct PlaylistAudit {
+    pub playlist_id: String,
+    pub name: String,
+    /// Track entries checked.
+    pub checked: usize,
+    pub entries: Vec<AuditEntry>,
 }
 
 /// Detailed playlist metadata for info commands.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct PlaylistDetail {
     pub id: String,
     pub name: String,
@@ -25,3 +112,39 @@ pub struct PlaylistDetail {
     #[serde(default)]
     pub public: Option<bool>,
 }
+
+/// Track entry in a playlist, with who added it and when.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PlaylistTrack {
+    pub track: Track,
+    pub added_at: Option<String>,
+    pub added_by: Option<String>,
+    /// Track popularity, 0-100.
+    pub popularity: Option<u32>,
+    pub explicit: Option<bool>,
+    /// Album release date as Spotify reports it (`YYYY`, `YYYY-MM`, or `YYYY-MM-DD`).
+    pub release_date: Option<String>,
+}
+
+/// One page of playlist tracks.
+#[derive(Debug, Clone)]
+pub struct PlaylistTrackPage {
+    pub items: Vec<PlaylistTrack>,
+    pub total: u32,
+}
+
+/// Playlist in another user's public listing.
+#[derive(Debug, Clone)]
+pub struct UserPlaylist {
+    pub playlist: Playlist,
+    pub owner_id: Option<String>,
+    pub tracks_total: Option<u32>,
+}
+
+/// One page of a user's public playlists.
+#[derive(Debug, Clone)]
+pub struct UserPlaylistPage {
+    pub items: Vec<UserPlaylist>,
+    pub total: u32,
+}
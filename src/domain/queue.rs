@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::track::Track;
+
+/// Kind of item in the player queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueItemKind {
+    Track,
+    Episode,
+}
+
+impl QueueItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueItemKind::Track => "track",
+            QueueItemKind::Episode => "episode",
+        }
+    }
+}
+
+/// Entry of the player queue; an episode's show is listed as its artist.
+#[derive(Debug, Clone)]
+pub struct QueueItem {
+    pub kind: QueueItemKind,
+    pub uri: String,
+    pub track: Track,
+}
+
+/// Locally saved snapshot of upcoming queue items.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQueue {
+    pub name: String,
+    pub uris: Vec<String>,
+    pub saved_at: u64,
+}
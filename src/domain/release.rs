@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// Album or single released by an artist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub id: String,
+    pub name: String,
+    pub uri: String,
+    pub artists: Vec<String>,
+    pub album_type: Option<String>,
+    /// How the artist relates to the release (`album`, `single`, `compilation`, `appears_on`).
+    #[serde(default)]
+    pub album_group: Option<String>,
+    pub release_date: Option<String>,
+}
+
+/// An artist's releases in one album group, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseGroup {
+    pub group: String,
+    pub releases: Vec<Release>,
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 /// Search result kinds supported by Spotify search.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum SearchType {
     All,
     Track,
@@ -10,8 +11,19 @@ pub enum SearchType {
     Playlist,
 }
 
+impl SearchType {
+    /// Every concrete type, in the order mixed results are listed.
+    pub const EACH: [SearchType; 4] = [
+        SearchType::Track,
+        SearchType::Album,
+        SearchType::Artist,
+        SearchType::Playlist,
+    ];
+}
+
 /// Normalized search item across Spotify result types.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct SearchItem {
     pub id: String,
     pub name: String,
@@ -28,10 +40,35 @@ pub struct SearchItem {
     pub owner: Option<String>,
     /// Optional fuzzy score, 0.0..=1.0.
     pub score: Option<f32>,
+    /// Whether the track is playable in the requested market.
+    #[serde(default)]
+    pub playable: Option<bool>,
+    /// Original track id when Spotify relinked the result for the market.
+    #[serde(default)]
+    pub linked_from: Option<String>,
+    /// Whether the track is in the user's library, when checked.
+    #[serde(default)]
+    pub saved: Option<bool>,
+    /// Whether the track is marked explicit.
+    #[serde(default)]
+    pub explicit: Option<bool>,
+    /// Spotify popularity, 0-100, for track and artist results.
+    #[serde(default)]
+    pub popularity: Option<u32>,
+}
+
+/// Match count for one result type of a paged search.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SearchTotal {
+    pub kind: SearchType,
+    pub total: u32,
+    pub offset: u32,
+    pub count: u32,
 }
 
 /// Aggregated search results with a kind discriminator.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct SearchResults {
     pub kind: SearchType,
     pub items: Vec<SearchItem>,
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::search::SearchItem;
+
+/// Listening session window recorded by `session start` and `session stop`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// Unix seconds.
+    pub started_at: u64,
+    /// Unix seconds; `None` while the session is running.
+    #[serde(default)]
+    pub stopped_at: Option<u64>,
+}
+
+/// Track from the recently played history with its play time.
+#[derive(Debug, Clone)]
+pub struct PlayedTrack {
+    pub item: SearchItem,
+    /// Artist ids, in the order of `item.artists`.
+    pub artist_ids: Vec<String>,
+    /// ISO 8601 UTC timestamp from Spotify.
+    pub played_at: String,
+}
+
+/// Play count for one artist within a session.
+#[derive(Debug, Clone)]
+pub struct ArtistCount {
+    pub name: String,
+    pub plays: usize,
+}
+
+/// Summary of the plays inside a session window.
+#[derive(Debug, Clone)]
+pub struct SessionReport {
+    pub started_at: String,
+    /// `None` while the session is running.
+    pub stopped_at: Option<String>,
+    /// Plays in chronological order.
+    pub tracks: Vec<PlayedTrack>,
+    pub total_ms: u64,
+    pub top_artists: Vec<ArtistCount>,
+    /// Spotify returned a full page, so earlier plays may be missing.
+    pub truncated: bool,
+}
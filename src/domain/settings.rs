@@ -5,4 +5,70 @@ use serde::{Deserialize, Serialize};
 pub struct Settings {
     pub country: Option<String>,
     pub user_name: Option<String>,
+    /// Market used when `--market` is not passed.
+    #[serde(default)]
+    pub default_market: Option<String>,
+    /// Upper bound enforced by `player volume` unless overridden.
+    #[serde(default)]
+    pub max_volume: Option<u32>,
+    /// Volumes applied after switching to a named device.
+    #[serde(default)]
+    pub device_volumes: Vec<DeviceVolume>,
+    /// Total time allowed for one API request, in seconds.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Time allowed to establish a connection, in seconds.
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// Proxy URL used for all requests.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// PEM bundle of extra trusted root certificates.
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
+    /// Language code for human output, overriding `LANG`.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Hide explicit tracks from listings, as if `--no-explicit` were passed.
+    #[serde(default)]
+    pub no_explicit: Option<bool>,
+    /// Where `open` sends links: `app` (spotify: URI) or `web` (the default).
+    #[serde(default)]
+    pub open_in: Option<String>,
+    /// Log file verbosity: `off` (the default), `error`, or `info`.
+    #[serde(default)]
+    pub log_level: Option<String>,
+    /// Log file size, in KiB, before it is rotated.
+    #[serde(default)]
+    pub log_max_kb: Option<u64>,
+    /// Plays after which a track is saved to the library automatically.
+    #[serde(default)]
+    pub auto_like_plays: Option<u32>,
+    /// User-defined command shortcuts.
+    #[serde(default)]
+    pub aliases: Vec<CommandAlias>,
+    /// Scripts run on playback and playlist events.
+    #[serde(default)]
+    pub hooks: Vec<Hook>,
+}
+
+/// Default volume for a device, matched by name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceVolume {
+    pub device: String,
+    pub volume: u32,
+}
+
+/// Command shortcut expanded before argument parsing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandAlias {
+    pub name: String,
+    pub expansion: String,
+}
+
+/// Shell command run with a JSON payload on stdin when `event` fires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hook {
+    pub event: String,
+    pub command: String,
 }
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Podcast show metadata for info output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Show {
+    pub id: String,
+    pub name: String,
+    pub uri: String,
+    pub publisher: Option<String>,
+    pub description: Option<String>,
+    pub total_episodes: Option<u32>,
+}
+
+/// Podcast show the user follows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedShow {
+    pub show: Show,
+    pub added_at: Option<String>,
+}
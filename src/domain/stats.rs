@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::search::SearchItem;
+
+/// Top items for one Spotify time range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeTop {
+    /// Spotify time range value, e.g. `short_term`.
+    pub range: String,
+    pub tracks: Vec<SearchItem>,
+    pub artists: Vec<SearchItem>,
+}
+
+/// Composite listening summary for `user stats`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListeningStats {
+    pub ranges: Vec<RangeTop>,
+    pub recently_played: Vec<SearchItem>,
+    pub followed_artists: usize,
+}
+
+/// How many playlist tracks share one artist, decade, or genre.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tally {
+    pub name: String,
+    pub count: usize,
+}
+
+/// Aggregate figures for `playlist stats`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistStats {
+    pub id: String,
+    pub name: String,
+    pub tracks: usize,
+    pub duration_ms: u64,
+    /// Most frequent artists, highest count first.
+    pub artists: Vec<Tally>,
+    /// Tracks per release decade (e.g. `1990s`), oldest first.
+    pub decades: Vec<Tally>,
+    /// Mean popularity over tracks that report one.
+    pub average_popularity: Option<f32>,
+    pub explicit: usize,
+    /// Most frequent artist genres, counted once per track.
+    pub genres: Vec<Tally>,
+}
+
+/// One genre's weight among the user's top artists, for `user top genres`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenreShare {
+    pub genre: String,
+    /// Percentage of the total rank weight, so all shares add up to 100.
+    pub percent: f64,
+    /// Top artists tagged with the genre.
+    pub artists: usize,
+}
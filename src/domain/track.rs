@@ -1,5 +1,6 @@
 /// Track metadata used in playback and library actions.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Track {
     pub id: String,
     pub name: String,
@@ -9,3 +10,11 @@ pub struct Track {
     pub album_id: Option<String>,
     pub duration_ms: Option<u32>,
 }
+
+/// Track saved to the user's library.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SavedTrack {
+    pub track: Track,
+    pub added_at: Option<String>,
+}
@@ -1,2 +1,141 @@
+//! Shared error types and the exit-code contract.
+use std::fmt;
+
+use crate::spotify::error::ApiError;
+
 /// Shared error type alias for the application.
 pub type Result<T> = std::result::Result<T, anyhow::Error>;
+
+/// Error category that determines the process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    General,
+    Validation,
+    Auth,
+    NotFound,
+    RateLimited,
+    Network,
+    Unsupported,
+    Timeout,
+    Interrupted,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::General => 1,
+            ErrorKind::Validation => 2,
+            ErrorKind::Auth => 3,
+            ErrorKind::NotFound => 4,
+            ErrorKind::RateLimited => 5,
+            ErrorKind::Network => 6,
+            ErrorKind::Unsupported => 7,
+            // As `timeout(1)` and a shell's SIGINT status.
+            ErrorKind::Timeout => 124,
+            ErrorKind::Interrupted => 130,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::General => "general",
+            ErrorKind::Validation => "validation",
+            ErrorKind::Auth => "auth",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::RateLimited => "rate_limited",
+            ErrorKind::Network => "network",
+            ErrorKind::Unsupported => "unsupported",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Interrupted => "interrupted",
+        }
+    }
+
+    /// Build an error of this kind; use with `bail!`.
+    pub fn error(self, message: impl Into<String>) -> KindError {
+        KindError {
+            kind: self,
+            message: message.into(),
+        }
+    }
+}
+
+/// Error explicitly tagged with an [`ErrorKind`].
+#[derive(Debug)]
+pub struct KindError {
+    pub kind: ErrorKind,
+    message: String,
+}
+
+impl fmt::Display for KindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for KindError {}
+
+/// Classify an error by the first recognizable cause in its chain.
+pub fn classify(err: &anyhow::Error) -> ErrorKind {
+    for cause in err.chain() {
+        if let Some(err) = cause.downcast_ref::<KindError>() {
+            return err.kind;
+        }
+        if let Some(err) = cause.downcast_ref::<ApiError>() {
+            return err.kind();
+        }
+        if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+            if err.is_connect() || err.is_timeout() {
+                return ErrorKind::Network;
+            }
+            if let Some(status) = err.status() {
+                return ErrorKind::from_status(status.as_u16());
+            }
+        }
+    }
+    ErrorKind::General
+}
+
+impl ErrorKind {
+    /// Kind for an unsuccessful HTTP status.
+    pub fn from_status(status: u16) -> ErrorKind {
+        match status {
+            400 => ErrorKind::Validation,
+            401 | 403 => ErrorKind::Auth,
+            404 => ErrorKind::NotFound,
+            429 => ErrorKind::RateLimited,
+            _ => ErrorKind::General,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorKind, classify};
+    use crate::spotify::error::format_api_error;
+    use anyhow::{Context, anyhow};
+    use reqwest::StatusCode;
+
+    #[test]
+    fn classify_uses_tagged_kind_through_context() {
+        let err = anyhow!(ErrorKind::NotFound.error("no device matches 'x'")).context("device set");
+        assert_eq!(classify(&err), ErrorKind::NotFound);
+        assert_eq!(classify(&err).exit_code(), 4);
+    }
+
+    #[test]
+    fn classify_maps_api_status() {
+        let err = anyhow!(format_api_error("op", StatusCode::TOO_MANY_REQUESTS, "{}"));
+        assert_eq!(classify(&err), ErrorKind::RateLimited);
+        let err: anyhow::Result<()> = Err(anyhow!(format_api_error(
+            "op",
+            StatusCode::UNAUTHORIZED,
+            "{}"
+        )));
+        assert_eq!(
+            classify(&err.context("wrapped").unwrap_err()),
+            ErrorKind::Auth
+        );
+        assert_eq!(classify(&anyhow!("plain")), ErrorKind::General);
+    }
+}
@@ -1,5 +1,48 @@
+//! Spotify Web API access from the spotify-cli crate.
+//!
+//! [`SpotifyClient`] is the supported, semver-stable entry point: it handles
+//! login and token refresh and returns the typed values re-exported here.
+//!
+//! ```no_run
+//! use spotify_cli::{SearchType, SpotifyClient};
+//!
+//! let spotify = SpotifyClient::from_cli_login()?;
+//! let results = spotify.search("daft punk", SearchType::Track, 5)?;
+//! if let Some(track) = results.items.first() {
+//!     spotify.queue(&track.uri)?;
+//! }
+//! # Ok::<(), spotify_cli::Error>(())
+//! ```
+//!
+//! Every method returns the crate's own [`Error`]; [`Error::kind`] gives its
+//! [`ErrorKind`], and the cause chain is available through `source()`.
+//!
+//! The re-exported structs and enums are `#[non_exhaustive]`: read their
+//! fields freely, but match with `..` and a wildcard arm so new fields and
+//! variants are not breaking changes.
+//!
+//! The other public modules exist for the integration tests and can change
+//! in any release.
+mod api;
+#[doc(hidden)]
 pub mod cache;
+#[doc(hidden)]
 pub mod domain;
+#[doc(hidden)]
 pub mod error;
+#[doc(hidden)]
 pub mod output;
+#[doc(hidden)]
 pub mod spotify;
+#[doc(hidden)]
+pub mod time;
+
+pub use api::{Error, Result, SpotifyClient};
+pub use domain::auth::AuthStatus;
+pub use domain::device::Device;
+pub use domain::player::{PlaybackContext, PlayerStatus};
+pub use domain::playlist::{Playlist, PlaylistDetail, PlaylistTrack};
+pub use domain::search::{SearchItem, SearchResults, SearchType};
+pub use domain::track::{SavedTrack, Track};
+pub use error::ErrorKind;
+pub use spotify::auth::AuthToken;
@@ -1,46 +1,225 @@
 mod action;
 mod cache;
 mod cli;
+mod constants;
 mod domain;
 mod error;
 mod output;
 mod spotify;
+mod time;
 
 use crate::cache::Cache;
+use crate::domain::oplog::LoggedOperation;
+use crate::domain::settings::Settings;
 use crate::error::Result;
 use crate::output::Output;
 use crate::spotify::auth::AuthService;
 use crate::spotify::client::SpotifyClient;
 use anyhow::Error;
+use std::cell::RefCell;
 use std::sync::OnceLock;
 
+/// Log file size before rotation when `log_max_kb` is unset.
+const DEFAULT_LOG_MAX_KB: u64 = 1024;
+
 /// Shared runtime context for command handlers.
 pub struct AppContext {
     pub cache: Cache,
     pub auth: AuthService,
     pub output: Output,
     pub verbose: bool,
+    pub defer_offline: bool,
+    /// Hide explicit tracks from listings (`--no-explicit` or the `no_explicit` setting).
+    pub no_explicit: bool,
+    /// Plan mutations instead of sending them (`--dry-run`).
+    pub dry_run: bool,
+    /// Answer confirmation prompts with yes (`--yes`).
+    pub assume_yes: bool,
+    /// Mutations collected while `dry_run` is set.
+    pub planned: RefCell<Vec<LoggedOperation>>,
     spotify: OnceLock<Result<SpotifyClient>>,
 }
 
-fn main() -> Result<()> {
+fn main() {
     let parsed = cli::parse();
+    let json = parsed.json;
+    output::set_json_envelope(parsed.json_envelope);
+    spotify::cancel::install(parsed.timeout);
+    let result = run(parsed);
+    // Written even when the command failed; failing requests are the interesting ones.
+    if let Err(err) = spotify::trace::write_har() {
+        eprintln!("warning: {err:#}");
+    }
+    let stop = spotify::cancel::reason();
+    if let Err(mut err) = result {
+        // A request cut short by the stop would otherwise read as a network failure.
+        if let Some(stop) = stop
+            && error::classify(&err) != stop.kind()
+        {
+            err = Error::new(stop.kind().error(stop.message())).context(format!("{err:#}"));
+        }
+        spotify::trace::log_error(&format!("{err:#}"));
+        if json {
+            let _ = output::json::error(&err);
+        } else {
+            eprintln!("{}: {err:?}", output::i18n::t("Error"));
+        }
+        std::process::exit(error::classify(&err).exit_code());
+    }
+    if let Some(stop) = stop {
+        eprintln!("warning: {}; results may be partial", stop.message());
+        std::process::exit(stop.kind().exit_code());
+    }
+}
 
+fn run(parsed: cli::ParsedCli) -> Result<()> {
     let cache = Cache::new()?;
     cache.ensure_dirs()?;
 
     let auth = AuthService::new(cache.metadata_store());
-    let output = Output::new(parsed.json, auth.user_name()?, None, false);
+    let output = Output::new(
+        parsed.json,
+        auth.user_name()?,
+        parsed.max_width,
+        parsed.no_trunc,
+    )
+    .with_dry_run(parsed.dry_run);
+    output::set_json_fields(parsed.fields.clone());
+    output::set_column_widths(parsed.column_widths.clone());
+    if let Some(market) = parsed.market.clone().or(auth.default_market()?) {
+        spotify::base::set_market(market);
+    }
+    let settings = auth.settings()?;
+    output::i18n::set_language(settings.language.as_deref());
+    let no_explicit = parsed.no_explicit || settings.no_explicit.unwrap_or(false);
+    let log_file = file_log(&cache, &settings);
+    spotify::base::set_http_options(spotify::base::HttpOptions {
+        proxy: settings.proxy,
+        ca_bundle: settings.ca_bundle,
+        insecure: parsed.insecure,
+    });
+    spotify::trace::set_trace(spotify::trace::TraceOptions {
+        log: parsed.trace_http,
+        har: parsed.har.clone(),
+        file: log_file,
+    });
 
     let ctx = AppContext {
         cache,
         auth,
         output,
         verbose: false,
+        defer_offline: parsed.defer_offline,
+        no_explicit,
+        dry_run: parsed.dry_run,
+        assume_yes: parsed.yes,
+        planned: RefCell::new(Vec::new()),
         spotify: OnceLock::new(),
     };
 
-    cli::execute(parsed, &ctx)
+    let replay = !parsed.dry_run && !matches!(parsed.command, cli::Command::Pending(_));
+    let pending_before = pending_count(&ctx);
+    let json = parsed.json;
+    if let Err(err) = cli::execute(parsed, &ctx) {
+        if !json && err.to_string().contains("Insufficient client scope") {
+            offer_scope_upgrade(&ctx);
+        }
+        if action::pending::is_offline_error(&err) {
+            return Err(err.context(
+                "network unreachable: could not reach Spotify (offline?); \
+                 mutations can be journaled with --defer-offline",
+            ));
+        }
+        return Err(err);
+    }
+    if ctx.dry_run {
+        let planned = ctx.planned.take();
+        let diffs = action::preview::playlist_diffs(&ctx, &planned);
+        ctx.output.dry_run_plan(planned, diffs)?;
+    }
+    if replay {
+        replay_pending(&ctx, pending_before);
+    }
+    Ok(())
+}
+
+/// Log file in the cache dir, unless `log_level` is unset or `off`.
+fn file_log(cache: &Cache, settings: &Settings) -> Option<spotify::trace::FileLog> {
+    let level = match settings.log_level.as_deref() {
+        Some("error") => spotify::trace::LogLevel::Error,
+        Some("info") => spotify::trace::LogLevel::Info,
+        _ => return None,
+    };
+    Some(spotify::trace::FileLog {
+        path: cache.root().join("spotify-cli.log"),
+        level,
+        max_bytes: settings.log_max_kb.unwrap_or(DEFAULT_LOG_MAX_KB) * 1024,
+    })
+}
+
+fn pending_count(ctx: &AppContext) -> usize {
+    ctx.cache
+        .pending_store()
+        .load()
+        .map(|journal| journal.items.len())
+        .unwrap_or(0)
+}
+
+/// Replay journaled offline mutations once a command has reached Spotify.
+fn replay_pending(ctx: &AppContext, pending_before: usize) {
+    if !matches!(ctx.spotify.get(), Some(Ok(_))) {
+        return;
+    }
+    let pending_after = pending_count(ctx);
+    // Nothing to do, or this command just deferred work because it was offline.
+    if pending_after == 0 || pending_after > pending_before {
+        return;
+    }
+    match action::pending::flush(ctx) {
+        Ok(flushed) if flushed.applied > 0 => {
+            eprintln!(
+                "replayed {} pending operation(s), {} remaining",
+                flushed.applied, flushed.remaining
+            );
+        }
+        Ok(_) => {}
+        Err(err) => eprintln!("pending replay failed: {err}"),
+    }
+}
+
+/// Offer to re-authorize with the scopes missing from the stored token.
+fn offer_scope_upgrade(ctx: &AppContext) {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let Ok(scopes) = ctx.auth.scopes() else {
+        return;
+    };
+    // Without a recorded grant, every scope the CLI uses may be missing.
+    let missing = if scopes.granted.is_some() {
+        scopes.missing
+    } else {
+        scopes.required
+    };
+    if missing.is_empty() || !std::io::stdin().is_terminal() {
+        return;
+    }
+
+    eprint!(
+        "missing scope(s): {}; re-authorize to add them? [y/N] ",
+        missing.join(", ")
+    );
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    if std::io::stdin().lock().read_line(&mut answer).is_err() {
+        return;
+    }
+    if !matches!(answer.trim(), "y" | "Y" | "yes") {
+        return;
+    }
+    match ctx.auth.add_scopes(&missing) {
+        Ok(()) => eprintln!("scopes updated; re-run the command"),
+        Err(err) => eprintln!("re-authorization failed: {err}"),
+    }
 }
 
 impl AppContext {
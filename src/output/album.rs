@@ -0,0 +1,96 @@
+//! Saved album output formatting.
+use serde::Serialize;
+
+use crate::domain::album::SavedAlbum;
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn saved_album_list_human(albums: Vec<SavedAlbum>, table: TableConfig) -> Result<()> {
+    if albums.is_empty() {
+        println!("{}", i18n::t("No saved albums"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = albums
+        .into_iter()
+        .enumerate()
+        .map(|(index, album)| {
+            vec![
+                (index + 1).to_string(),
+                album.name,
+                album.artists.join(", "),
+                album.release_date.unwrap_or_default(),
+                album
+                    .added_at
+                    .as_deref()
+                    .and_then(|added| added.get(..10))
+                    .unwrap_or_default()
+                    .to_string(),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "ALBUM", "ARTIST", "RELEASED", "ADDED"], table);
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct SavedAlbumPayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    release_date: Option<String>,
+    added_at: Option<String>,
+}
+
+describe!(SavedAlbumPayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    release_date: Option<String>,
+    added_at: Option<String>,
+});
+
+pub fn saved_album_list_json(albums: Vec<SavedAlbum>) -> Result<()> {
+    let payload = saved_album_list_payload(albums);
+    write_json(PayloadKind::SavedAlbumList, &payload)
+}
+
+fn saved_album_list_payload(albums: Vec<SavedAlbum>) -> Vec<SavedAlbumPayload> {
+    albums
+        .into_iter()
+        .map(|album| SavedAlbumPayload {
+            id: album.id,
+            name: album.name,
+            uri: album.uri,
+            artists: album.artists,
+            release_date: album.release_date,
+            added_at: album.added_at,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::saved_album_list_payload;
+    use crate::domain::album::SavedAlbum;
+
+    #[test]
+    fn saved_album_list_payload_shape() {
+        let payload = saved_album_list_payload(vec![SavedAlbum {
+            id: "1".to_string(),
+            name: "Geogaddi".to_string(),
+            uri: "spotify:album:1".to_string(),
+            artists: vec!["Boards of Canada".to_string()],
+            release_date: Some("2002-02-18".to_string()),
+            added_at: Some("2024-01-01T00:00:00Z".to_string()),
+        }]);
+        assert_eq!(payload.len(), 1);
+        assert_eq!(payload[0].added_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Blocklist output formatting.
+use serde::Serialize;
+
+use crate::domain::block::BlockEntry;
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn block_list_human(blocks: Vec<BlockEntry>, table: TableConfig) -> Result<()> {
+    if blocks.is_empty() {
+        println!("{}", i18n::t("Blocklist is empty"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = blocks
+        .into_iter()
+        .map(|entry| {
+            vec![
+                entry.kind.as_str().to_string(),
+                entry.name.unwrap_or_default(),
+                entry.id,
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["KIND", "NAME", "ID"], table);
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct BlockPayload {
+    kind: &'static str,
+    id: String,
+    name: Option<String>,
+    uri: String,
+}
+
+describe!(BlockPayload {
+    kind: &'static str,
+    id: String,
+    name: Option<String>,
+    uri: String,
+});
+
+pub fn block_list_json(blocks: Vec<BlockEntry>) -> Result<()> {
+    let payload = block_list_payload(blocks);
+    write_json(PayloadKind::BlockList, &payload)
+}
+
+fn block_list_payload(blocks: Vec<BlockEntry>) -> Vec<BlockPayload> {
+    blocks
+        .into_iter()
+        .map(|entry| BlockPayload {
+            kind: entry.kind.as_str(),
+            uri: format!("spotify:{}:{}", entry.kind.as_str(), entry.id),
+            id: entry.id,
+            name: entry.name,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::block_list_payload;
+    use crate::domain::block::{BlockEntry, BlockKind};
+
+    #[test]
+    fn block_list_payload_shape() {
+        let payload = block_list_payload(vec![BlockEntry {
+            kind: BlockKind::Artist,
+            id: "a1".to_string(),
+            name: Some("Artist".to_string()),
+        }]);
+        assert_eq!(payload.len(), 1);
+        assert_eq!(payload[0].kind, "artist");
+        assert_eq!(payload[0].uri, "spotify:artist:a1");
+    }
+}
@@ -3,6 +3,8 @@ use serde::Serialize;
 
 use crate::domain::cache::CacheStatus;
 use crate::error::Result;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
 
 pub fn status_human(status: CacheStatus) -> Result<()> {
     println!(
@@ -13,16 +15,21 @@ pub fn status_human(status: CacheStatus) -> Result<()> {
 }
 
 #[derive(Serialize)]
-struct CacheStatusPayload {
+pub(super) struct CacheStatusPayload {
     root: String,
     device_count: usize,
     playlist_count: usize,
 }
 
+describe!(CacheStatusPayload {
+    root: String,
+    device_count: usize,
+    playlist_count: usize,
+});
+
 pub fn status_json(status: CacheStatus) -> Result<()> {
     let payload = cache_status_payload(status);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::CacheStatus, &payload)
 }
 
 fn cache_status_payload(status: CacheStatus) -> CacheStatusPayload {
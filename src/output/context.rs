@@ -0,0 +1,131 @@
+//! Recent playback context output formatting.
+use serde::Serialize;
+
+use crate::domain::player::RecentContext;
+use crate::error::Result;
+use crate::output::human::{format_duration, print_table_with_header};
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::{TableConfig, write_json};
+use crate::time::unix_time;
+
+pub fn recent_contexts_human(recent: Vec<RecentContext>, table: TableConfig) -> Result<()> {
+    if recent.is_empty() {
+        println!("{}", i18n::t("No recent contexts"));
+        return Ok(());
+    }
+    let rows = recent_rows(&recent, unix_time());
+    print_table_with_header(
+        &rows,
+        &["#", "NAME", "TYPE", "TRACK", "POSITION", "PLAYED"],
+        table,
+    );
+    Ok(())
+}
+
+fn recent_rows(recent: &[RecentContext], now: u64) -> Vec<Vec<String>> {
+    recent
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            vec![
+                (index + 1).to_string(),
+                item.name.clone().unwrap_or_else(|| item.uri.clone()),
+                item.kind.clone(),
+                item.track_name.clone().unwrap_or_default(),
+                format_duration(item.position_ms as u64),
+                format_age(now.saturating_sub(item.seen_at)),
+            ]
+        })
+        .collect()
+}
+
+/// `5m ago`, `3h ago`, or `2d ago`.
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..3_600 => format!("{}m ago", seconds / 60),
+        3_600..86_400 => format!("{}h ago", seconds / 3_600),
+        _ => format!("{}d ago", seconds / 86_400),
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct RecentContextPayload {
+    uri: String,
+    kind: String,
+    name: Option<String>,
+    track_uri: Option<String>,
+    track_name: Option<String>,
+    position_ms: u32,
+    seen_at: u64,
+}
+
+describe!(RecentContextPayload {
+    uri: String,
+    kind: String,
+    name: Option<String>,
+    track_uri: Option<String>,
+    track_name: Option<String>,
+    position_ms: u32,
+    seen_at: u64,
+});
+
+pub fn recent_contexts_json(recent: Vec<RecentContext>) -> Result<()> {
+    let payload = recent_contexts_payload(recent);
+    write_json(PayloadKind::RecentContexts, &payload)
+}
+
+fn recent_contexts_payload(recent: Vec<RecentContext>) -> Vec<RecentContextPayload> {
+    recent
+        .into_iter()
+        .map(|item| RecentContextPayload {
+            uri: item.uri,
+            kind: item.kind,
+            name: item.name,
+            track_uri: item.track_uri,
+            track_name: item.track_name,
+            position_ms: item.position_ms,
+            seen_at: item.seen_at,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_age, recent_contexts_payload, recent_rows};
+    use crate::domain::player::RecentContext;
+
+    fn recent(name: Option<&str>) -> RecentContext {
+        RecentContext {
+            uri: "spotify:album:a1".to_string(),
+            kind: "album".to_string(),
+            name: name.map(str::to_string),
+            track_uri: Some("spotify:track:t1".to_string()),
+            track_name: Some("Intro".to_string()),
+            position_ms: 83_000,
+            seen_at: 1_000,
+        }
+    }
+
+    #[test]
+    fn recent_rows_fall_back_to_the_uri() {
+        let rows = recent_rows(&[recent(Some("Blue")), recent(None)], 1_000 + 7_200);
+        assert_eq!(rows[0], ["1", "Blue", "album", "Intro", "1:23", "2h ago"]);
+        assert_eq!(rows[1][1], "spotify:album:a1");
+    }
+
+    #[test]
+    fn format_age_picks_the_largest_unit() {
+        assert_eq!(format_age(59), "0m ago");
+        assert_eq!(format_age(3_600), "1h ago");
+        assert_eq!(format_age(3 * 86_400), "3d ago");
+    }
+
+    #[test]
+    fn recent_contexts_payload_shape() {
+        let payload = recent_contexts_payload(vec![recent(None)]);
+        assert_eq!(payload[0].uri, "spotify:album:a1");
+        assert_eq!(payload[0].track_uri.as_deref(), Some("spotify:track:t1"));
+        assert_eq!(payload[0].position_ms, 83_000);
+    }
+}
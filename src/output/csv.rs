@@ -0,0 +1,40 @@
+//! CSV export for list commands.
+use crate::error::Result;
+
+/// Print a header and rows as RFC 4180 CSV.
+pub fn print_rows(header: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    println!("{}", record(header));
+    for row in rows {
+        println!("{}", record(row));
+    }
+    Ok(())
+}
+
+fn record<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|field| escape(field.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::record;
+
+    #[test]
+    fn record_quotes_only_fields_that_need_it() {
+        assert_eq!(
+            record(&["plain", "a, b", "say \"hi\"", "two\nlines"]),
+            "plain,\"a, b\",\"say \"\"hi\"\"\",\"two\nlines\""
+        );
+    }
+}
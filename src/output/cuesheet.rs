@@ -0,0 +1,210 @@
+//! Cue sheet output formatting.
+use serde::Serialize;
+
+use crate::domain::cuesheet::{CueFormat, Cuesheet};
+use crate::error::Result;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+/// The CUE format numbers tracks 01-99.
+const MAX_CUE_TRACKS: usize = 99;
+
+pub fn cuesheet_human(sheet: Cuesheet, format: CueFormat) -> Result<()> {
+    let text = match format {
+        CueFormat::Cue => {
+            if sheet.entries.len() > MAX_CUE_TRACKS {
+                eprintln!(
+                    "warning: a cue sheet holds at most {MAX_CUE_TRACKS} tracks; leaving out the last {}; use --format tracklist for all of them",
+                    sheet.entries.len() - MAX_CUE_TRACKS
+                );
+            }
+            render_cue(&sheet)
+        }
+        CueFormat::Tracklist => render_tracklist(&sheet),
+    };
+    print!("{text}");
+    Ok(())
+}
+
+fn render_cue(sheet: &Cuesheet) -> String {
+    let mut out = String::new();
+    if let Some(performer) = sheet.performer.as_ref() {
+        out.push_str(&format!("PERFORMER {}\n", cue_quote(performer)));
+    }
+    out.push_str(&format!("TITLE {}\n", cue_quote(&sheet.title)));
+    out.push_str(&format!(
+        "FILE {} WAVE\n",
+        cue_quote(&format!("{}.wav", sheet.title))
+    ));
+    for (index, entry) in sheet.entries.iter().take(MAX_CUE_TRACKS).enumerate() {
+        out.push_str(&format!("  TRACK {:02} AUDIO\n", index + 1));
+        out.push_str(&format!("    TITLE {}\n", cue_quote(&entry.title)));
+        if !entry.performer.is_empty() {
+            out.push_str(&format!("    PERFORMER {}\n", cue_quote(&entry.performer)));
+        }
+        out.push_str(&format!("    INDEX 01 {}\n", cue_timestamp(entry.start_ms)));
+    }
+    out
+}
+
+fn render_tracklist(sheet: &Cuesheet) -> String {
+    let long = sheet.duration_ms() >= 3_600_000;
+    let mut out = String::new();
+    for entry in &sheet.entries {
+        let stamp = tracklist_timestamp(entry.start_ms, long);
+        if entry.performer.is_empty() {
+            out.push_str(&format!("{} {}\n", stamp, entry.title));
+        } else {
+            out.push_str(&format!(
+                "{} {} - {}\n",
+                stamp, entry.performer, entry.title
+            ));
+        }
+    }
+    out
+}
+
+/// CUE `INDEX` timestamps are `MM:SS:FF` with 75 frames per second.
+fn cue_timestamp(ms: u64) -> String {
+    let minutes = ms / 60_000;
+    let seconds = (ms / 1000) % 60;
+    let frames = (ms % 1000) * 75 / 1000;
+    format!("{minutes:02}:{seconds:02}:{frames:02}")
+}
+
+fn tracklist_timestamp(ms: u64, long: bool) -> String {
+    let total_seconds = ms / 1000;
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    if long {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+fn cue_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "'"))
+}
+
+#[derive(Serialize)]
+pub(super) struct CuesheetPayload {
+    title: String,
+    performer: Option<String>,
+    duration_ms: u64,
+    entries: Vec<CueEntryPayload>,
+}
+
+describe!(CuesheetPayload {
+    title: String,
+    performer: Option<String>,
+    duration_ms: u64,
+    entries: Vec<CueEntryPayload>,
+});
+
+#[derive(Serialize)]
+struct CueEntryPayload {
+    title: String,
+    performer: String,
+    uri: String,
+    start_ms: u64,
+    duration_ms: u64,
+}
+
+describe!(CueEntryPayload {
+    title: String,
+    performer: String,
+    uri: String,
+    start_ms: u64,
+    duration_ms: u64,
+});
+
+pub fn cuesheet_json(sheet: Cuesheet) -> Result<()> {
+    let payload = cuesheet_payload(sheet);
+    write_json(PayloadKind::Cuesheet, &payload)
+}
+
+fn cuesheet_payload(sheet: Cuesheet) -> CuesheetPayload {
+    let duration_ms = sheet.duration_ms();
+    CuesheetPayload {
+        title: sheet.title,
+        performer: sheet.performer,
+        duration_ms,
+        entries: sheet
+            .entries
+            .into_iter()
+            .map(|entry| CueEntryPayload {
+                title: entry.title,
+                performer: entry.performer,
+                uri: entry.uri,
+                start_ms: entry.start_ms,
+                duration_ms: entry.duration_ms,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_CUE_TRACKS, cue_timestamp, cuesheet_payload, render_cue, render_tracklist};
+    use crate::domain::cuesheet::{CueEntry, Cuesheet};
+
+    fn sheet() -> Cuesheet {
+        Cuesheet {
+            title: "Mix".to_string(),
+            performer: Some("Me".to_string()),
+            entries: vec![
+                CueEntry {
+                    title: "One".to_string(),
+                    performer: "A".to_string(),
+                    uri: "spotify:track:1".to_string(),
+                    start_ms: 0,
+                    duration_ms: 61_500,
+                },
+                CueEntry {
+                    title: "Two".to_string(),
+                    performer: String::new(),
+                    uri: "spotify:track:2".to_string(),
+                    start_ms: 61_500,
+                    duration_ms: 1_000,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn cue_timestamp_uses_frames() {
+        assert_eq!(cue_timestamp(61_500), "01:01:37");
+        assert_eq!(cue_timestamp(3_723_000), "62:03:00");
+    }
+
+    #[test]
+    fn render_cue_lists_tracks() {
+        let text = render_cue(&sheet());
+        assert!(text.contains("  TRACK 02 AUDIO\n    TITLE \"Two\"\n    INDEX 01 01:01:37\n"));
+    }
+
+    #[test]
+    fn render_cue_stops_at_the_track_limit() {
+        let mut sheet = sheet();
+        let entry = sheet.entries[1].clone();
+        sheet.entries.resize(MAX_CUE_TRACKS + 5, entry);
+        let text = render_cue(&sheet);
+        assert_eq!(text.matches("  TRACK ").count(), MAX_CUE_TRACKS);
+        assert!(text.contains("  TRACK 99 AUDIO\n"));
+        assert!(!text.contains("TRACK 100"));
+    }
+
+    #[test]
+    fn render_tracklist_uses_offsets() {
+        assert_eq!(render_tracklist(&sheet()), "00:00 A - One\n01:01 Two\n");
+    }
+
+    #[test]
+    fn cuesheet_payload_shape() {
+        let payload = cuesheet_payload(sheet());
+        assert_eq!(payload.duration_ms, 62_500);
+        assert_eq!(payload.entries.len(), 2);
+    }
+}
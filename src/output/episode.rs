@@ -0,0 +1,223 @@
+//! Episode output formatting.
+use serde::Serialize;
+
+use crate::domain::episode::{Episode, SavedEpisode};
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::csv;
+use crate::output::human::{format_duration, print_table_with_header};
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::show::added_date;
+use crate::output::write_json;
+
+pub fn episode_info_human(episode: Episode) -> Result<()> {
+    match episode.show.as_deref() {
+        Some(show) => println!("{} ({})", episode.name, show),
+        None => println!("{}", episode.name),
+    }
+    if let Some(date) = episode.release_date.as_deref() {
+        println!("released: {}", date);
+    }
+    println!("position: {}", format_progress(&episode));
+    Ok(())
+}
+
+pub fn episode_list_human(episodes: Vec<Episode>, table: TableConfig) -> Result<()> {
+    if episodes.is_empty() {
+        println!("{}", i18n::t("No episodes"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = episodes
+        .iter()
+        .enumerate()
+        .map(|(index, episode)| {
+            vec![
+                (index + 1).to_string(),
+                episode.name.clone(),
+                episode.release_date.clone().unwrap_or_default(),
+                format_progress(episode),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "EPISODE", "RELEASED", "POSITION"], table);
+    Ok(())
+}
+
+pub fn saved_episode_list_human(episodes: Vec<SavedEpisode>, table: TableConfig) -> Result<()> {
+    if episodes.is_empty() {
+        println!("{}", i18n::t("No saved episodes"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = episodes
+        .into_iter()
+        .enumerate()
+        .map(|(index, saved)| {
+            vec![
+                (index + 1).to_string(),
+                saved.episode.name,
+                saved.episode.show.unwrap_or_default(),
+                saved.episode.release_date.unwrap_or_default(),
+                added_date(saved.added_at.as_deref()),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "EPISODE", "SHOW", "RELEASED", "ADDED"], table);
+    Ok(())
+}
+
+pub fn saved_episode_list_csv(episodes: Vec<SavedEpisode>) -> Result<()> {
+    let rows: Vec<Vec<String>> = saved_episode_list_payload(episodes)
+        .into_iter()
+        .map(|episode| {
+            vec![
+                episode.id,
+                episode.name,
+                episode.show.unwrap_or_default(),
+                episode.release_date.unwrap_or_default(),
+                episode.duration_ms.to_string(),
+                episode.added_at.unwrap_or_default(),
+                episode.uri,
+            ]
+        })
+        .collect();
+    csv::print_rows(
+        &[
+            "id",
+            "name",
+            "show",
+            "release_date",
+            "duration_ms",
+            "added_at",
+            "uri",
+        ],
+        &rows,
+    )
+}
+
+fn format_progress(episode: &Episode) -> String {
+    let duration = format_duration(episode.duration_ms as u64);
+    if episode.fully_played {
+        return "played".to_string();
+    }
+    match episode.resume_position_ms {
+        Some(position) if position > 0 => {
+            format!("{} / {}", format_duration(position as u64), duration)
+        }
+        _ => format!("unplayed / {}", duration),
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct EpisodePayload {
+    id: String,
+    name: String,
+    uri: String,
+    show: Option<String>,
+    duration_ms: u32,
+    release_date: Option<String>,
+    resume_position_ms: Option<u32>,
+    fully_played: bool,
+}
+
+describe!(EpisodePayload {
+    id: String,
+    name: String,
+    uri: String,
+    show: Option<String>,
+    duration_ms: u32,
+    release_date: Option<String>,
+    resume_position_ms: Option<u32>,
+    fully_played: bool,
+});
+
+#[derive(Serialize)]
+pub(super) struct SavedEpisodePayload {
+    id: String,
+    name: String,
+    uri: String,
+    show: Option<String>,
+    duration_ms: u32,
+    release_date: Option<String>,
+    added_at: Option<String>,
+}
+
+describe!(SavedEpisodePayload {
+    id: String,
+    name: String,
+    uri: String,
+    show: Option<String>,
+    duration_ms: u32,
+    release_date: Option<String>,
+    added_at: Option<String>,
+});
+
+pub fn episode_info_json(episode: Episode) -> Result<()> {
+    let payload = episode_payload(episode);
+    write_json(PayloadKind::EpisodeInfo, &payload)
+}
+
+pub fn episode_list_json(episodes: Vec<Episode>) -> Result<()> {
+    let payload: Vec<EpisodePayload> = episodes.into_iter().map(episode_payload).collect();
+    write_json(PayloadKind::EpisodeList, &payload)
+}
+
+pub fn saved_episode_list_json(episodes: Vec<SavedEpisode>) -> Result<()> {
+    write_json(
+        PayloadKind::SavedEpisodeList,
+        &saved_episode_list_payload(episodes),
+    )
+}
+
+fn saved_episode_list_payload(episodes: Vec<SavedEpisode>) -> Vec<SavedEpisodePayload> {
+    episodes
+        .into_iter()
+        .map(|saved| SavedEpisodePayload {
+            id: saved.episode.id,
+            name: saved.episode.name,
+            uri: saved.episode.uri,
+            show: saved.episode.show,
+            duration_ms: saved.episode.duration_ms,
+            release_date: saved.episode.release_date,
+            added_at: saved.added_at,
+        })
+        .collect()
+}
+
+fn episode_payload(episode: Episode) -> EpisodePayload {
+    EpisodePayload {
+        id: episode.id,
+        name: episode.name,
+        uri: episode.uri,
+        show: episode.show,
+        duration_ms: episode.duration_ms,
+        release_date: episode.release_date,
+        resume_position_ms: episode.resume_position_ms,
+        fully_played: episode.fully_played,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::episode_payload;
+    use crate::domain::episode::Episode;
+
+    #[test]
+    fn episode_payload_shape() {
+        let payload = episode_payload(Episode {
+            id: "e1".to_string(),
+            name: "Episode".to_string(),
+            uri: "spotify:episode:e1".to_string(),
+            show: Some("Show".to_string()),
+            description: None,
+            duration_ms: 3_600_000,
+            release_date: None,
+            resume_position_ms: Some(60_000),
+            fully_played: false,
+        });
+        assert_eq!(payload.resume_position_ms, Some(60_000));
+        assert!(!payload.fully_played);
+    }
+}
@@ -0,0 +1,122 @@
+//! Followed artist output formatting.
+use serde::Serialize;
+
+use crate::domain::artist::FollowedArtistPage;
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn followed_artists_human(page: FollowedArtistPage, table: TableConfig) -> Result<()> {
+    if page.items.is_empty() {
+        println!("{}", i18n::t("No followed artists"));
+        return Ok(());
+    }
+
+    let shown = page.items.len();
+    let rows: Vec<Vec<String>> = page
+        .items
+        .into_iter()
+        .enumerate()
+        .map(|(index, artist)| {
+            vec![
+                (index + 1).to_string(),
+                artist.name,
+                artist.genres.join(", "),
+                artist
+                    .followers
+                    .map(|followers| followers.to_string())
+                    .unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "ARTIST", "GENRES", "FOLLOWERS"], table);
+    if let Some(after) = page.after {
+        match page.total {
+            Some(total) => println!("Showing {shown} of {total}; use --after {after} for more"),
+            None => println!("Showing {shown}; use --after {after} for more"),
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct FollowedArtistsPayload {
+    items: Vec<FollowedArtistPayload>,
+    total: Option<u32>,
+    after: Option<String>,
+}
+
+describe!(FollowedArtistsPayload {
+    items: Vec<FollowedArtistPayload>,
+    total: Option<u32>,
+    after: Option<String>,
+});
+
+#[derive(Serialize)]
+struct FollowedArtistPayload {
+    id: String,
+    name: String,
+    uri: String,
+    genres: Vec<String>,
+    followers: Option<u64>,
+}
+
+describe!(FollowedArtistPayload {
+    id: String,
+    name: String,
+    uri: String,
+    genres: Vec<String>,
+    followers: Option<u64>,
+});
+
+pub fn followed_artists_json(page: FollowedArtistPage) -> Result<()> {
+    let payload = followed_artists_payload(page);
+    write_json(PayloadKind::FollowedArtists, &payload)
+}
+
+fn followed_artists_payload(page: FollowedArtistPage) -> FollowedArtistsPayload {
+    FollowedArtistsPayload {
+        items: page
+            .items
+            .into_iter()
+            .map(|artist| FollowedArtistPayload {
+                id: artist.id,
+                name: artist.name,
+                uri: artist.uri,
+                genres: artist.genres,
+                followers: artist.followers,
+            })
+            .collect(),
+        total: page.total,
+        after: page.after,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::followed_artists_payload;
+    use crate::domain::artist::{Artist, FollowedArtistPage};
+
+    #[test]
+    fn followed_artists_payload_keeps_cursor() {
+        let payload = followed_artists_payload(FollowedArtistPage {
+            items: vec![Artist {
+                id: "a".to_string(),
+                name: "Artist".to_string(),
+                uri: "spotify:artist:a".to_string(),
+                genres: Vec::new(),
+                followers: Some(3),
+                popularity: None,
+                images: Vec::new(),
+            }],
+            total: Some(120),
+            after: Some("a".to_string()),
+        });
+        assert_eq!(payload.items.len(), 1);
+        assert_eq!(payload.total, Some(120));
+        assert_eq!(payload.after.as_deref(), Some("a"));
+    }
+}
@@ -0,0 +1,69 @@
+//! Prune candidate output formatting.
+use crate::domain::history::PruneCandidate;
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+
+pub fn prune_candidates_human(candidates: &[PruneCandidate], table: TableConfig) -> Result<()> {
+    let (headers, rows) = prune_rows(candidates);
+    print_table_with_header(&rows, &headers, table);
+    Ok(())
+}
+
+/// The ARTIST column only appears for tracks.
+fn prune_rows(candidates: &[PruneCandidate]) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let tracks = candidates.iter().any(|item| !item.artists.is_empty());
+    let mut headers = vec!["#", "NAME"];
+    if tracks {
+        headers.push("ARTIST");
+    }
+    headers.extend(["PLAYED", "ID"]);
+    let rows = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let mut row = vec![(index + 1).to_string(), item.name.clone()];
+            if tracks {
+                row.push(item.artists.join(", "));
+            }
+            let played = match item.last_played.as_deref() {
+                Some(at) => at.get(..10).unwrap_or(at).to_string(),
+                None => i18n::t("never").to_string(),
+            };
+            row.extend([played, item.id.clone()]);
+            row
+        })
+        .collect();
+    (headers, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prune_rows;
+    use crate::domain::history::PruneCandidate;
+
+    fn candidate(id: &str, artists: &[&str], last_played: Option<&str>) -> PruneCandidate {
+        PruneCandidate {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            artists: artists.iter().map(|artist| artist.to_string()).collect(),
+            last_played: last_played.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn prune_rows_show_the_play_date_and_artist_for_tracks() {
+        let (headers, rows) = prune_rows(&[
+            candidate("t1", &["Boards"], Some("2023-02-01T08:00:00Z")),
+            candidate("t2", &["Caribou"], None),
+        ]);
+        assert_eq!(headers, vec!["#", "NAME", "ARTIST", "PLAYED", "ID"]);
+        assert_eq!(rows[0], vec!["1", "T1", "Boards", "2023-02-01", "t1"]);
+        assert_eq!(rows[1][3], "never");
+
+        let (headers, rows) = prune_rows(&[candidate("a1", &[], None)]);
+        assert_eq!(headers, vec!["#", "NAME", "PLAYED", "ID"]);
+        assert_eq!(rows[0].len(), 4);
+    }
+}
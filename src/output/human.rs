@@ -1,28 +1,70 @@
 //! Human-readable output formatting.
 use crate::domain::album::Album;
-use crate::domain::artist::Artist;
+use crate::domain::artist::{Artist, FollowerTrend};
 use crate::domain::auth::{AuthScopes, AuthStatus};
 use crate::domain::device::Device;
 use crate::domain::pin::PinnedPlaylist;
-use crate::domain::player::PlayerStatus;
+use crate::domain::player::{PlaybackContext, PlayerStatus};
 use crate::domain::playlist::{Playlist, PlaylistDetail};
-use crate::domain::search::{SearchItem, SearchResults};
+use crate::domain::search::{SearchItem, SearchResults, SearchTotal};
 use crate::domain::track::Track;
 use crate::error::Result;
-use crate::output::{DEFAULT_MAX_WIDTH, TableConfig};
+use crate::output::{DEFAULT_MAX_WIDTH, TableConfig, column_width, i18n, width};
+use crate::time::unix_time;
 
 pub fn auth_status(status: AuthStatus) -> Result<()> {
-    if status.logged_in {
-        println!("logged_in");
+    if !status.logged_in {
+        println!("logged_out");
+        if let Some(client_id) = status.client_id {
+            println!("client_id: {client_id}");
+        }
         return Ok(());
     }
 
-    println!("logged_out");
+    println!("logged_in");
+    if let Some(profile) = status.profile {
+        match profile.display_name {
+            Some(name) => println!("user: {} ({})", name, profile.id),
+            None => println!("user: {}", profile.id),
+        }
+        if let Some(product) = profile.product {
+            println!("product: {product}");
+        }
+        if let Some(country) = profile.country {
+            println!("country: {country}");
+        }
+    }
+    if let Some(expires_at) = status.expires_at {
+        println!("token: {}", format_expiry(expires_at, unix_time()));
+    }
+    if let Some(scopes) = status.granted_scopes {
+        println!("scopes: {}", scopes.join(" "));
+    }
+    println!("storage: file ({})", status.token_path);
+    if let Some(client_id) = status.client_id {
+        println!("client_id: {client_id}");
+    }
     Ok(())
 }
 
+/// `expires in 42m 10s`, or `expired 3m ago` (it is refreshed on the next request).
+fn format_expiry(expires_at: u64, now: u64) -> String {
+    let countdown = |secs: u64| {
+        if secs >= 3600 {
+            format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+        } else {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        }
+    };
+    if expires_at > now {
+        format!("expires in {}", countdown(expires_at - now))
+    } else {
+        format!("expired {} ago", countdown(now - expires_at))
+    }
+}
+
 pub fn auth_scopes(scopes: AuthScopes) -> Result<()> {
-    println!("Scopes:");
+    println!("{}", i18n::t("Scopes:"));
     for scope in scopes.required {
         let status = if let Some(granted) = scopes.granted.as_ref() {
             if granted.iter().any(|item| item == &scope) {
@@ -45,6 +87,7 @@ pub fn player_status(status: PlayerStatus) -> Result<()> {
         "paused"
     };
     let context = playback_context_line(&status);
+    let source = status.context.as_ref().map(context_source_line);
 
     if let Some(track) = status.track {
         let artists = if track.artists.is_empty() {
@@ -57,11 +100,28 @@ pub fn player_status(status: PlayerStatus) -> Result<()> {
             .as_ref()
             .map(|album| format!(" ({})", album))
             .unwrap_or_default();
-        let progress = format_progress(status.progress_ms, track.duration_ms);
+        let bar =
+            match (status.progress_ms, track.duration_ms) {
+                (Some(progress_ms), Some(duration_ms)) if duration_ms > 0 => Some(
+                    format_progress_bar(progress_ms, duration_ms, progress_bar_width()),
+                ),
+                _ => None,
+            };
+        let progress = if bar.is_some() {
+            String::new()
+        } else {
+            format_progress(status.progress_ms, track.duration_ms)
+        };
         println!("{}: {}{}{}{}", state, track.name, album, artists, progress);
+        if let Some(bar) = bar {
+            println!("{}", bar);
+        }
         if let Some(line) = context {
             println!("{}", line);
         }
+        if let Some(line) = source {
+            println!("{}", line);
+        }
         return Ok(());
     }
 
@@ -69,6 +129,9 @@ pub fn player_status(status: PlayerStatus) -> Result<()> {
     if let Some(line) = context {
         println!("{}", line);
     }
+    if let Some(line) = source {
+        println!("{}", line);
+    }
     Ok(())
 }
 
@@ -92,7 +155,7 @@ pub fn now_playing(status: PlayerStatus) -> Result<()> {
         return Ok(());
     }
 
-    println!("Now Playing: (no active track)");
+    println!("{}", i18n::t("Now Playing: (no active track)"));
     Ok(())
 }
 
@@ -121,6 +184,15 @@ fn playback_context_line(status: &PlayerStatus) -> Option<String> {
     ))
 }
 
+/// `Playing from: playlist Focus`, falling back to the context URI when the name is unknown.
+fn context_source_line(context: &PlaybackContext) -> String {
+    let label = match context.name.as_deref() {
+        Some(name) => format!("{} {}", context.kind, name),
+        None => context.uri.clone(),
+    };
+    format!("{} {}", i18n::t("Playing from:"), label)
+}
+
 pub fn action(message: &str) -> Result<()> {
     println!("{}", message);
     Ok(())
@@ -146,7 +218,7 @@ pub fn album_info(album: Album, table: TableConfig) -> Result<()> {
     for track in album.tracks {
         rows.push(vec![
             format!("{:02}.", track.track_number),
-            track.name,
+            mark_saved(track.name, track.saved),
             format_duration(track.duration_ms as u64),
         ]);
     }
@@ -170,6 +242,26 @@ pub fn artist_info(artist: Artist) -> Result<()> {
     Ok(())
 }
 
+pub fn artist_full(artist: Artist, trend: Option<FollowerTrend>) -> Result<()> {
+    let popularity = artist.popularity;
+    let images = artist.images.clone();
+    artist_info(artist)?;
+    if let Some(popularity) = popularity {
+        println!("popularity: {}", popularity);
+    }
+    if let Some(trend) = trend {
+        let days = unix_time().saturating_sub(trend.since) / 86_400;
+        println!(
+            "followers: {:+} over {}d (was {})",
+            trend.change, days, trend.from
+        );
+    }
+    for url in images {
+        println!("image: {}", url);
+    }
+    Ok(())
+}
+
 pub fn playlist_list(
     playlists: Vec<Playlist>,
     user_name: Option<&str>,
@@ -236,7 +328,7 @@ pub fn playlist_list_with_pins(
 pub fn help() -> Result<()> {
     println!("spotify-cli <object> <verb> [target] [flags]");
     println!(
-        "objects: auth, device, info, search, nowplaying, player, playlist, pin, sync, queue, recentlyplayed"
+        "objects: album, alias, auth, block, config, device, episode, follow, hook, info, log, search, nowplaying, player, playlist, pin, sync, queue, recentlyplayed, releases, session, show, user"
     );
     println!("flags: --json");
     println!("examples:");
@@ -281,10 +373,11 @@ pub fn playlist_info(playlist: PlaylistDetail, user_name: Option<&str>) -> Resul
 pub fn device_list(devices: Vec<Device>, table: TableConfig) -> Result<()> {
     let mut rows = Vec::new();
     for device in devices {
-        let volume = device
-            .volume_percent
-            .map(|v| v.to_string())
-            .unwrap_or_default();
+        let volume = match (device.supports_volume, device.volume_percent) {
+            (Some(false), _) => "fixed".to_string(),
+            (_, Some(v)) => v.to_string(),
+            (_, None) => String::new(),
+        };
         rows.push(vec![device.name, volume]);
     }
     print_table_with_header(&rows, &["NAME", "VOLUME"], table);
@@ -297,7 +390,7 @@ fn format_optional_details(parts: &[Option<String>]) -> String {
 }
 
 #[allow(clippy::collapsible_if)]
-fn display_owner(owner: &str, user_name: Option<&str>) -> String {
+pub(crate) fn display_owner(owner: &str, user_name: Option<&str>) -> String {
     if let Some(user_name) = user_name {
         if user_name.eq_ignore_ascii_case(owner) {
             return "You".to_string();
@@ -321,6 +414,53 @@ fn format_progress(progress_ms: Option<u32>, duration_ms: Option<u32>) -> String
     )
 }
 
+pub fn player_progress(progress_ms: u32, duration_ms: Option<u32>) -> Result<()> {
+    println!("{:.3}", progress_fraction(progress_ms, duration_ms));
+    Ok(())
+}
+
+/// Played fraction clamped to 0..=1; 0 when the duration is unknown.
+pub(crate) fn progress_fraction(progress_ms: u32, duration_ms: Option<u32>) -> f64 {
+    match duration_ms {
+        Some(duration_ms) if duration_ms > 0 => {
+            (progress_ms as f64 / duration_ms as f64).clamp(0.0, 1.0)
+        }
+        _ => 0.0,
+    }
+}
+
+/// Columns taken by the text after the bar, e.g. ` 12:34/56:78 (100%, 44:44 left)`.
+const PROGRESS_TEXT_WIDTH: usize = 32;
+const MIN_BAR_WIDTH: usize = 10;
+const MAX_BAR_WIDTH: usize = 40;
+
+/// Bar cells for the playback progress line, sized to the terminal width.
+fn progress_bar_width() -> usize {
+    let columns = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .unwrap_or(80);
+    columns
+        .saturating_sub(PROGRESS_TEXT_WIDTH)
+        .clamp(MIN_BAR_WIDTH, MAX_BAR_WIDTH)
+}
+
+/// `▮▮▮▯▯ 1:23/3:45 (37%, 2:22 left)`; `duration_ms` must be non-zero.
+fn format_progress_bar(progress_ms: u32, duration_ms: u32, width: usize) -> String {
+    let progress_ms = progress_ms.min(duration_ms);
+    let fraction = progress_ms as f64 / duration_ms as f64;
+    let filled = ((fraction * width as f64) as usize).min(width);
+    format!(
+        "{}{} {}/{} ({:.0}%, {} left)",
+        "\u{25ae}".repeat(filled),
+        "\u{25af}".repeat(width - filled),
+        format_time(progress_ms),
+        format_time(duration_ms),
+        (fraction * 100.0).floor(),
+        format_time(duration_ms - progress_ms)
+    )
+}
+
 fn format_time(ms: u32) -> String {
     let total_seconds = ms / 1000;
     let minutes = total_seconds / 60;
@@ -328,7 +468,7 @@ fn format_time(ms: u32) -> String {
     format!("{minutes}:{seconds:02}")
 }
 
-fn format_duration(ms: u64) -> String {
+pub(crate) fn format_duration(ms: u64) -> String {
     let total_seconds = ms / 1000;
     let minutes = total_seconds / 60;
     let seconds = total_seconds % 60;
@@ -340,7 +480,7 @@ pub fn search_results(results: SearchResults, table: TableConfig) -> Result<()>
     let show_kind = results.kind == crate::domain::search::SearchType::All;
     for (index, item) in results.items.into_iter().enumerate() {
         if show_kind {
-            let name = item.name;
+            let name = mark_saved(mark_explicit(item.name, item.explicit), item.saved);
             let by = if !item.artists.is_empty() {
                 item.artists.join(", ")
             } else {
@@ -372,9 +512,15 @@ pub fn search_results(results: SearchResults, table: TableConfig) -> Result<()>
                     .score
                     .map(|score| format!("{:.2}", score))
                     .unwrap_or_default();
+                let name = match (item.playable, item.linked_from.is_some()) {
+                    (Some(false), _) => format!("{} (unplayable)", item.name),
+                    (_, true) => format!("{} (relinked)", item.name),
+                    _ => item.name,
+                };
+                let name = mark_saved(mark_explicit(name, item.explicit), item.saved);
                 rows.push(vec![
                     (index + 1).to_string(),
-                    item.name,
+                    name,
                     artists,
                     album,
                     duration,
@@ -433,8 +579,78 @@ pub fn search_results(results: SearchResults, table: TableConfig) -> Result<()>
     Ok(())
 }
 
-pub fn queue(items: Vec<Track>, now_playing_id: Option<&str>, table: TableConfig) -> Result<()> {
+/// Per-type match counts with a hint for the next page.
+pub fn search_totals(totals: &[SearchTotal]) {
+    for line in search_total_lines(totals) {
+        println!("{line}");
+    }
+}
+
+fn search_total_lines(totals: &[SearchTotal]) -> Vec<String> {
+    totals
+        .iter()
+        .map(|total| {
+            let label = format_search_kind(total.kind);
+            if total.count == 0 {
+                return format!(
+                    "{label}: none of {} at offset {}",
+                    total.total, total.offset
+                );
+            }
+            let last = total.offset + total.count;
+            let mut line = format!("{label}: {}-{last} of {}", total.offset + 1, total.total);
+            if last < total.total {
+                line.push_str(&format!("; next page: --offset {last}"));
+            }
+            line
+        })
+        .collect()
+}
+
+/// Prefix a track name with a heart when it is saved in the library.
+fn mark_saved(name: String, saved: Option<bool>) -> String {
+    if saved == Some(true) {
+        format!("\u{2665} {}", name)
+    } else {
+        name
+    }
+}
+
+/// Suffix an `[E]` badge to tracks marked explicit.
+pub(crate) fn mark_explicit(name: String, explicit: Option<bool>) -> String {
+    if explicit == Some(true) {
+        format!("{} [E]", name)
+    } else {
+        name
+    }
+}
+
+pub fn queue(
+    items: Vec<Track>,
+    now_playing_id: Option<&str>,
+    context: Option<&PlaybackContext>,
+    saved: &[Option<bool>],
+    starts: &[Option<u64>],
+    table: TableConfig,
+) -> Result<()> {
+    if let Some(context) = context {
+        println!("{}", context_source_line(context));
+    }
+    let total_ms = queued_duration_ms(&items, now_playing_id);
+    let queued = items
+        .iter()
+        .filter(|track| now_playing_id != Some(track.id.as_str()))
+        .count();
+    let now = unix_time();
+    let utc_offset = local_utc_offset();
     let mut rows = Vec::new();
+    // Number queued tracks from 1 so positions line up with `queue play <n>`.
+    let offset = usize::from(
+        now_playing_id.is_none()
+            || items
+                .first()
+                .is_none_or(|track| Some(track.id.as_str()) != now_playing_id),
+    );
     for (index, track) in items.into_iter().enumerate() {
         let Track {
             id,
@@ -444,7 +660,7 @@ pub fn queue(items: Vec<Track>, now_playing_id: Option<&str>, table: TableConfig
             duration_ms,
             ..
         } = track;
-        let mut name = name;
+        let mut name = mark_saved(name, saved.get(index).copied().flatten());
         if now_playing_id.is_some_and(|needle| needle == id) {
             name = format!("* {}", name);
         }
@@ -453,18 +669,91 @@ pub fn queue(items: Vec<Track>, now_playing_id: Option<&str>, table: TableConfig
         let duration = duration_ms
             .map(|ms| format_duration(ms as u64))
             .unwrap_or_default();
+        let starts = match starts.get(index).copied().flatten() {
+            Some(0) => i18n::t("now").to_string(),
+            Some(ms) => format_clock(now + ms / 1000, utc_offset),
+            None => String::new(),
+        };
         rows.push(vec![
-            (index + 1).to_string(),
+            (index + offset).to_string(),
             name,
             artists,
             album,
             duration,
+            starts,
         ]);
     }
-    print_table_with_header(&rows, &["#", "TRACK", "ARTIST", "ALBUM", "DURATION"], table);
+    print_table_with_header(
+        &rows,
+        &["#", "TRACK", "ARTIST", "ALBUM", "DURATION", "STARTS"],
+        table,
+    );
+    if queued > 0 {
+        println!(
+            "{} {queued}, {}",
+            i18n::t("Queued:"),
+            format_total_duration(total_ms)
+        );
+    }
     Ok(())
 }
 
+/// Combined duration of the queued items, excluding the current track.
+pub(crate) fn queued_duration_ms(items: &[Track], now_playing_id: Option<&str>) -> u64 {
+    items
+        .iter()
+        .filter(|track| now_playing_id != Some(track.id.as_str()))
+        .filter_map(|track| track.duration_ms)
+        .map(u64::from)
+        .sum()
+}
+
+/// `1:02:03` past an hour, `2:03` otherwise.
+fn format_total_duration(ms: u64) -> String {
+    let total_seconds = ms / 1000;
+    let hours = total_seconds / 3600;
+    if hours == 0 {
+        return format_duration(ms);
+    }
+    format!(
+        "{hours}:{:02}:{:02}",
+        total_seconds / 60 % 60,
+        total_seconds % 60
+    )
+}
+
+/// Wall-clock `HH:MM` for a unix timestamp shifted by a UTC offset in seconds.
+fn format_clock(unix_secs: u64, utc_offset: i64) -> String {
+    let local = (unix_secs as i64 + utc_offset).rem_euclid(24 * 60 * 60);
+    format!("{:02}:{:02}", local / 3600, local / 60 % 60)
+}
+
+/// Local UTC offset in seconds from `date +%z`; UTC when it cannot be read.
+pub(crate) fn local_utc_offset() -> i64 {
+    std::process::Command::new("date")
+        .arg("+%z")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|offset| parse_utc_offset(offset.trim()))
+        .unwrap_or(0)
+}
+
+/// Parse a `+HHMM` / `-HHMM` offset into seconds.
+fn parse_utc_offset(value: &str) -> Option<i64> {
+    let (sign, digits) = match value.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i64 = digits[..2].parse().ok()?;
+    let minutes: i64 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
 pub fn recently_played(
     items: Vec<SearchItem>,
     now_playing_id: Option<&str>,
@@ -472,7 +761,7 @@ pub fn recently_played(
 ) -> Result<()> {
     let mut rows = Vec::new();
     for (index, item) in items.into_iter().enumerate() {
-        let mut name = item.name;
+        let mut name = mark_explicit(item.name, item.explicit);
         if now_playing_id.is_some_and(|id| id == item.id) {
             name = format!("* {}", name);
         }
@@ -505,19 +794,25 @@ fn format_search_kind(kind: crate::domain::search::SearchType) -> String {
     .to_string()
 }
 
-fn print_table_with_header(rows: &[Vec<String>], headers: &[&str], table: TableConfig) {
+pub(crate) fn print_table_with_header(rows: &[Vec<String>], headers: &[&str], table: TableConfig) {
+    let limits: Vec<Option<usize>> = headers.iter().map(|header| column_width(header)).collect();
     let mut all_rows = Vec::new();
     if !headers.is_empty() {
-        all_rows.push(headers.iter().map(|text| text.to_string()).collect());
+        all_rows.push(
+            headers
+                .iter()
+                .map(|text| i18n::t(text).to_string())
+                .collect(),
+        );
     }
     all_rows.extend_from_slice(rows);
-    print_table(&all_rows, table);
+    for line in render_table(&all_rows, &limits, table) {
+        println!("{line}");
+    }
 }
 
-fn print_table(rows: &[Vec<String>], table: TableConfig) {
-    if rows.is_empty() {
-        return;
-    }
+/// Lay out rows in columns sized by display width, truncating cells over their column limit.
+fn render_table(rows: &[Vec<String>], limits: &[Option<usize>], table: TableConfig) -> Vec<String> {
     let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
     let mut widths = vec![0usize; columns];
     let mut processed = Vec::with_capacity(rows.len());
@@ -527,46 +822,72 @@ fn print_table(rows: &[Vec<String>], table: TableConfig) {
         let mut new_row = Vec::with_capacity(row.len());
         for (index, cell) in row.iter().enumerate() {
             let truncated = if table.truncate {
-                truncate_cell(cell, max_width)
+                let limit = limits.get(index).copied().flatten().unwrap_or(max_width);
+                truncate_cell(cell, limit)
             } else {
                 cell.to_string()
             };
-            widths[index] = widths[index].max(truncated.len());
+            widths[index] = widths[index].max(width::str_width(&truncated));
             new_row.push(truncated);
         }
         processed.push(new_row);
     }
 
-    for row in processed {
-        let mut line = String::new();
-        for (index, cell) in row.iter().enumerate() {
-            if index > 0 {
-                line.push_str("  ");
+    processed
+        .into_iter()
+        .map(|row| {
+            let mut line = String::new();
+            for (index, cell) in row.iter().enumerate() {
+                if index > 0 {
+                    line.push_str("  ");
+                }
+                line.push_str(&width::pad(cell, widths[index]));
             }
-            let width = widths[index];
-            line.push_str(&format!("{:<width$}", cell, width = width));
-        }
-        println!("{}", line.trim_end());
-    }
+            line.trim_end().to_string()
+        })
+        .collect()
 }
 
 pub(crate) fn truncate_cell(text: &str, max: usize) -> String {
-    if text.chars().count() <= max {
-        return text.to_string();
-    }
-    if max <= 3 {
-        return "...".to_string();
-    }
-    let mut truncated: String = text.chars().take(max - 3).collect();
-    truncated.push_str("...");
-    truncated
+    width::truncate(text, max)
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        format_duration, format_optional_details, format_progress, format_time, truncate_cell,
+        format_clock, format_duration, format_expiry, format_optional_details, format_progress,
+        format_progress_bar, format_time, format_total_duration, mark_explicit, mark_saved,
+        parse_utc_offset, progress_fraction, queued_duration_ms, render_table, search_total_lines,
+        truncate_cell,
     };
+    use crate::domain::search::{SearchTotal, SearchType};
+    use crate::domain::track::Track;
+    use crate::output::TableConfig;
+
+    #[test]
+    fn search_total_lines_hint_next_page() {
+        let lines = search_total_lines(&[
+            SearchTotal {
+                kind: SearchType::Track,
+                total: 532,
+                offset: 10,
+                count: 10,
+            },
+            SearchTotal {
+                kind: SearchType::Album,
+                total: 4,
+                offset: 0,
+                count: 4,
+            },
+        ]);
+        assert_eq!(
+            lines,
+            vec![
+                "track: 11-20 of 532; next page: --offset 20",
+                "album: 1-4 of 4"
+            ]
+        );
+    }
 
     #[test]
     fn truncate_cell_keeps_short_values() {
@@ -578,6 +899,85 @@ mod tests {
         assert_eq!(truncate_cell("0123456789", 8), "01234...");
     }
 
+    #[test]
+    fn render_table_aligns_wide_characters() {
+        let rows = vec![
+            vec!["東京".to_string(), "x".to_string()],
+            vec!["ab".to_string(), "y".to_string()],
+        ];
+        let table = TableConfig {
+            max_width: None,
+            truncate: true,
+        };
+        assert_eq!(render_table(&rows, &[], table), vec!["東京  x", "ab    y"]);
+    }
+
+    #[test]
+    fn render_table_applies_column_limits() {
+        let rows = vec![vec!["0123456789".to_string(), "0123456789".to_string()]];
+        let table = TableConfig {
+            max_width: None,
+            truncate: true,
+        };
+        assert_eq!(
+            render_table(&rows, &[Some(6), None], table),
+            vec!["012...  0123456789"]
+        );
+        let table = TableConfig {
+            max_width: None,
+            truncate: false,
+        };
+        assert_eq!(
+            render_table(&rows, &[Some(6), None], table),
+            vec!["0123456789  0123456789"]
+        );
+    }
+
+    #[test]
+    fn format_expiry_counts_down() {
+        assert_eq!(format_expiry(1_000 + 2_530, 1_000), "expires in 42m 10s");
+        assert_eq!(format_expiry(1_000 + 7_260, 1_000), "expires in 2h 01m");
+        assert_eq!(format_expiry(1_000, 1_180), "expired 3m 00s ago");
+    }
+
+    #[test]
+    fn format_clock_applies_offset() {
+        // 2024-01-01T23:30:00Z
+        assert_eq!(format_clock(1_704_151_800, 0), "23:30");
+        assert_eq!(format_clock(1_704_151_800, 3600), "00:30");
+        assert_eq!(format_clock(1_704_151_800, -5 * 3600 - 1800), "18:00");
+    }
+
+    #[test]
+    fn parse_utc_offset_accepts_hhmm() {
+        assert_eq!(parse_utc_offset("+0200"), Some(7200));
+        assert_eq!(parse_utc_offset("-0530"), Some(-19800));
+        assert_eq!(parse_utc_offset("0200"), None);
+        assert_eq!(parse_utc_offset("+2"), None);
+    }
+
+    #[test]
+    fn queued_duration_skips_current_track() {
+        let track = |id: &str, duration_ms| Track {
+            id: id.to_string(),
+            name: id.to_string(),
+            artists: Vec::new(),
+            artist_ids: Vec::new(),
+            album: None,
+            album_id: None,
+            duration_ms,
+        };
+        let items = vec![
+            track("now", Some(100_000)),
+            track("q1", Some(180_000)),
+            track("q2", None),
+            track("q3", Some(3_600_000)),
+        ];
+        assert_eq!(queued_duration_ms(&items, Some("now")), 3_780_000);
+        assert_eq!(format_total_duration(3_780_000), "1:03:00");
+        assert_eq!(format_total_duration(180_000), "3:00");
+    }
+
     #[test]
     fn format_progress_with_duration() {
         assert_eq!(format_progress(Some(61000), Some(120000)), " [1:01 / 2:00]");
@@ -588,6 +988,25 @@ mod tests {
         assert_eq!(format_progress(Some(61000), None), " [1:01]");
     }
 
+    #[test]
+    fn format_progress_bar_fills_by_fraction() {
+        assert_eq!(
+            format_progress_bar(83_000, 225_000, 10),
+            "\u{25ae}\u{25ae}\u{25ae}\u{25af}\u{25af}\u{25af}\u{25af}\u{25af}\u{25af}\u{25af} 1:23/3:45 (36%, 2:22 left)"
+        );
+        assert!(
+            format_progress_bar(300_000, 225_000, 4)
+                .starts_with("\u{25ae}\u{25ae}\u{25ae}\u{25ae} 3:45/3:45 (100%")
+        );
+    }
+
+    #[test]
+    fn progress_fraction_clamps_and_handles_unknown_duration() {
+        assert_eq!(progress_fraction(50_000, Some(200_000)), 0.25);
+        assert_eq!(progress_fraction(250_000, Some(200_000)), 1.0);
+        assert_eq!(progress_fraction(50_000, None), 0.0);
+    }
+
     #[test]
     fn format_time_minutes_seconds() {
         assert_eq!(format_time(61000), "1:01");
@@ -598,6 +1017,20 @@ mod tests {
         assert_eq!(format_duration(125000), "2:05");
     }
 
+    #[test]
+    fn mark_saved_prefixes_only_saved_tracks() {
+        assert_eq!(mark_saved("Song".into(), Some(true)), "\u{2665} Song");
+        assert_eq!(mark_saved("Song".into(), Some(false)), "Song");
+        assert_eq!(mark_saved("Song".into(), None), "Song");
+    }
+
+    #[test]
+    fn mark_explicit_badges_only_explicit_tracks() {
+        assert_eq!(mark_explicit("Song".into(), Some(true)), "Song [E]");
+        assert_eq!(mark_explicit("Song".into(), Some(false)), "Song");
+        assert_eq!(mark_explicit("Song".into(), None), "Song");
+    }
+
     #[test]
     fn format_optional_details_joins() {
         let value =
@@ -0,0 +1,288 @@
+//! Message catalogs for human output.
+//!
+//! Messages are keyed by their English text, so call sites stay readable and an
+//! untranslated message falls back to English. JSON output is never translated.
+//!
+//! The catalogs cover table headers, empty-list messages, dry-run headings, and the
+//! error prefix. Action confirmations, status lines, and warnings stay in English.
+use std::sync::OnceLock;
+
+/// Languages with a message catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    En,
+    Es,
+    De,
+}
+
+impl Language {
+    /// Language for a code such as `es`, `de_DE.UTF-8`, or `en-GB`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "en" | "c" | "posix" => Some(Language::En),
+            "es" => Some(Language::Es),
+            "de" => Some(Language::De),
+            _ => None,
+        }
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::En => &[],
+            Language::Es => ES,
+            Language::De => DE,
+        }
+    }
+}
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Pick the language from the `language` setting, else `LC_ALL`, `LC_MESSAGES`, or `LANG`.
+pub fn set_language(configured: Option<&str>) {
+    let language = configured
+        .and_then(Language::from_code)
+        .or_else(|| {
+            ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .iter()
+                .filter_map(|name| std::env::var(name).ok())
+                .find(|value| !value.is_empty())
+                .and_then(|value| Language::from_code(&value))
+        })
+        .unwrap_or(Language::En);
+    let _ = LANGUAGE.set(language);
+}
+
+/// Translate a message into the selected language.
+pub fn t(message: &str) -> &str {
+    let language = LANGUAGE.get().copied().unwrap_or(Language::En);
+    lookup(language, message)
+}
+
+/// A message in every catalog language, English first.
+pub fn renderings(message: &str) -> Vec<&str> {
+    [Language::En, Language::Es, Language::De]
+        .into_iter()
+        .map(|language| lookup(language, message))
+        .collect()
+}
+
+fn lookup(language: Language, message: &str) -> &str {
+    language
+        .catalog()
+        .iter()
+        .find(|(english, _)| *english == message)
+        .map(|(_, translated)| *translated)
+        .unwrap_or(message)
+}
+
+const ES: &[(&str, &str)] = &[
+    ("#", "#"),
+    ("ADDED", "AÑADIDO"),
+    ("ALBUM", "ÁLBUM"),
+    ("ARTIST", "ARTISTA"),
+    ("ARTISTS", "ARTISTAS"),
+    ("AT", "FECHA"),
+    ("AUDIOBOOK", "AUDIOLIBRO"),
+    ("AUTHOR", "AUTOR"),
+    ("BY", "POR"),
+    ("CODE", "CÓDIGO"),
+    ("COUNTRY", "PAÍS"),
+    ("DATE", "FECHA"),
+    ("DECADE", "DÉCADA"),
+    ("DETAIL", "DETALLE"),
+    ("DURATION", "DURACIÓN"),
+    ("EPISODE", "EPISODIO"),
+    ("EPISODES", "EPISODIOS"),
+    ("FOLLOWERS", "SEGUIDORES"),
+    ("GENRE", "GÉNERO"),
+    ("GENRES", "GÉNEROS"),
+    ("ID", "ID"),
+    ("KIND", "TIPO"),
+    ("MATCH", "COINCIDE"),
+    ("NAME", "NOMBRE"),
+    ("NO", "NÚM"),
+    ("OPERATION", "OPERACIÓN"),
+    ("OWNER", "PROPIETARIO"),
+    ("PINS", "FIJADOS"),
+    ("PLAYED", "REPRODUCIDO"),
+    ("PLAYLIST", "LISTA"),
+    ("POSITION", "POSICIÓN"),
+    ("PUBLISHER", "EDITOR"),
+    ("QUEUED_AT", "EN_COLA"),
+    ("RELEASE", "LANZAMIENTO"),
+    ("RELEASED", "PUBLICADO"),
+    ("SCORE", "PUNTUACIÓN"),
+    ("SHARE", "CUOTA"),
+    ("SHOW", "PROGRAMA"),
+    ("STARTS", "EMPIEZA"),
+    ("STATUS", "ESTADO"),
+    ("TAG", "ETIQUETA"),
+    ("TAGS", "ETIQUETAS"),
+    ("TARGET", "OBJETIVO"),
+    ("TRACK", "CANCIÓN"),
+    ("TRACKS", "CANCIONES"),
+    ("TYPE", "TIPO"),
+    ("VOLUME", "VOLUMEN"),
+    ("Blocklist is empty", "La lista de bloqueo está vacía"),
+    (
+        "Dry run: nothing would change",
+        "Simulación: no cambiaría nada",
+    ),
+    ("Dry run: would apply", "Simulación: se aplicaría"),
+    ("Error", "Error"),
+    ("In Liked Songs", "En Canciones que te gustan"),
+    ("No episodes", "No hay episodios"),
+    ("No followed artists", "No sigues a ningún artista"),
+    ("No genres", "No hay géneros"),
+    ("No logged operations", "No hay operaciones registradas"),
+    ("No markets", "No hay mercados"),
+    ("No new releases", "No hay lanzamientos nuevos"),
+    ("No pending operations", "No hay operaciones pendientes"),
+    ("No playlists", "No hay listas"),
+    ("No recent contexts", "No hay contextos recientes"),
+    ("No releases", "No hay lanzamientos"),
+    ("No saved albums", "No hay álbumes guardados"),
+    ("No saved audiobooks", "No hay audiolibros guardados"),
+    ("No saved episodes", "No hay episodios guardados"),
+    ("No saved shows", "No hay programas guardados"),
+    ("No saved tracks", "No hay canciones guardadas"),
+    ("No tags", "No hay etiquetas"),
+    ("No tracks", "No hay canciones"),
+    ("Not in Liked Songs", "No está en Canciones que te gustan"),
+    (
+        "Now Playing: (no active track)",
+        "Reproduciendo: (ninguna canción activa)",
+    ),
+    ("Playing from:", "Reproduciendo desde:"),
+    ("Queued:", "En cola:"),
+    ("Recently played", "Escuchado recientemente"),
+    ("Scopes:", "Permisos:"),
+    ("never", "nunca"),
+    ("now", "ahora"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("#", "#"),
+    ("ADDED", "HINZUGEFÜGT"),
+    ("ALBUM", "ALBUM"),
+    ("ARTIST", "KÜNSTLER"),
+    ("ARTISTS", "KÜNSTLER"),
+    ("AT", "ZEIT"),
+    ("AUDIOBOOK", "HÖRBUCH"),
+    ("AUTHOR", "AUTOR"),
+    ("BY", "VON"),
+    ("CODE", "CODE"),
+    ("COUNTRY", "LAND"),
+    ("DATE", "DATUM"),
+    ("DECADE", "JAHRZEHNT"),
+    ("DETAIL", "DETAIL"),
+    ("DURATION", "DAUER"),
+    ("EPISODE", "FOLGE"),
+    ("EPISODES", "FOLGEN"),
+    ("FOLLOWERS", "FOLLOWER"),
+    ("GENRE", "GENRE"),
+    ("GENRES", "GENRES"),
+    ("ID", "ID"),
+    ("KIND", "ART"),
+    ("MATCH", "TREFFER"),
+    ("NAME", "NAME"),
+    ("NO", "NR"),
+    ("OPERATION", "VORGANG"),
+    ("OWNER", "BESITZER"),
+    ("PINS", "PINS"),
+    ("PLAYED", "GESPIELT"),
+    ("PLAYLIST", "PLAYLIST"),
+    ("POSITION", "POSITION"),
+    ("PUBLISHER", "VERLAG"),
+    ("QUEUED_AT", "EINGEREIHT"),
+    ("RELEASE", "VERÖFFENTLICHUNG"),
+    ("RELEASED", "ERSCHIENEN"),
+    ("SCORE", "WERTUNG"),
+    ("SHARE", "ANTEIL"),
+    ("SHOW", "SENDUNG"),
+    ("STARTS", "BEGINNT"),
+    ("STATUS", "STATUS"),
+    ("TAG", "TAG"),
+    ("TAGS", "TAGS"),
+    ("TARGET", "ZIEL"),
+    ("TRACK", "TITEL"),
+    ("TRACKS", "TITEL"),
+    ("TYPE", "TYP"),
+    ("VOLUME", "LAUTSTÄRKE"),
+    ("Blocklist is empty", "Die Sperrliste ist leer"),
+    (
+        "Dry run: nothing would change",
+        "Probelauf: nichts würde sich ändern",
+    ),
+    ("Dry run: would apply", "Probelauf: würde ausführen"),
+    ("Error", "Fehler"),
+    ("In Liked Songs", "In Lieblingssongs"),
+    ("No episodes", "Keine Folgen"),
+    ("No followed artists", "Keine gefolgten Künstler"),
+    ("No genres", "Keine Genres"),
+    ("No logged operations", "Keine protokollierten Vorgänge"),
+    ("No markets", "Keine Märkte"),
+    ("No new releases", "Keine neuen Veröffentlichungen"),
+    ("No pending operations", "Keine ausstehenden Vorgänge"),
+    ("No playlists", "Keine Playlists"),
+    ("No recent contexts", "Keine zuletzt gespielten Kontexte"),
+    ("No releases", "Keine Veröffentlichungen"),
+    ("No saved albums", "Keine gespeicherten Alben"),
+    ("No saved audiobooks", "Keine gespeicherten Hörbücher"),
+    ("No saved episodes", "Keine gespeicherten Folgen"),
+    ("No saved shows", "Keine gespeicherten Sendungen"),
+    ("No saved tracks", "Keine gespeicherten Titel"),
+    ("No tags", "Keine Tags"),
+    ("No tracks", "Keine Titel"),
+    ("Not in Liked Songs", "Nicht in Lieblingssongs"),
+    (
+        "Now Playing: (no active track)",
+        "Läuft gerade: (kein aktiver Titel)",
+    ),
+    ("Playing from:", "Wiedergabe aus:"),
+    ("Queued:", "In der Warteschlange:"),
+    ("Recently played", "Zuletzt gespielt"),
+    ("Scopes:", "Berechtigungen:"),
+    ("never", "nie"),
+    ("now", "jetzt"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{DE, ES, Language, lookup, renderings};
+
+    #[test]
+    fn from_code_reads_locale_strings() {
+        assert_eq!(Language::from_code("es_ES.UTF-8"), Some(Language::Es));
+        assert_eq!(Language::from_code("de-AT"), Some(Language::De));
+        assert_eq!(Language::from_code("C"), Some(Language::En));
+        assert_eq!(Language::from_code("fr_FR"), None);
+    }
+
+    #[test]
+    fn lookup_falls_back_to_english() {
+        assert_eq!(lookup(Language::Es, "TRACK"), "CANCIÓN");
+        assert_eq!(lookup(Language::De, "No tracks"), "Keine Titel");
+        assert_eq!(lookup(Language::De, "not in catalog"), "not in catalog");
+        assert_eq!(lookup(Language::En, "TRACK"), "TRACK");
+    }
+
+    #[test]
+    fn renderings_list_every_language() {
+        assert_eq!(renderings("TRACK"), vec!["TRACK", "CANCIÓN", "TITEL"]);
+        assert_eq!(renderings("not in catalog"), vec!["not in catalog"; 3]);
+    }
+
+    #[test]
+    fn catalogs_cover_the_same_messages() {
+        let keys = |catalog: &[(&str, &str)]| -> Vec<String> {
+            catalog.iter().map(|(key, _)| key.to_string()).collect()
+        };
+        assert_eq!(keys(ES), keys(DE));
+    }
+}
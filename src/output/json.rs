@@ -1,46 +1,106 @@
 //! JSON output formatting for machine-readable responses.
 use serde::Serialize;
+use serde_json::Value;
 
 use crate::domain::album::Album;
-use crate::domain::artist::Artist;
+use crate::domain::artist::{Artist, FollowerTrend};
 use crate::domain::auth::{AuthScopes, AuthStatus};
 use crate::domain::device::Device;
 use crate::domain::pin::PinnedPlaylist;
-use crate::domain::player::PlayerStatus;
+use crate::domain::player::{PlaybackContext, PlayerStatus};
 use crate::domain::playlist::{Playlist, PlaylistDetail};
-use crate::domain::search::{SearchItem, SearchResults, SearchType};
-use crate::error::Result;
+use crate::domain::search::{SearchItem, SearchResults, SearchTotal, SearchType};
+use crate::error::{Result, classify};
+use crate::output::human::progress_fraction;
+use crate::output::schema::{Describe, PayloadKind, describe, envelope};
+use crate::output::{json_envelope, write_json};
+use crate::spotify::error::ApiError;
 
 #[derive(Serialize)]
-struct AuthStatusPayload {
+pub(super) struct AuthStatusPayload {
     logged_in: bool,
     expires_at: Option<u64>,
+    client_id: Option<String>,
+    granted_scopes: Option<Vec<String>>,
+    storage: TokenStoragePayload,
+    profile: Option<AccountProfilePayload>,
 }
 
+describe!(AuthStatusPayload {
+    logged_in: bool,
+    expires_at: Option<u64>,
+    client_id: Option<String>,
+    granted_scopes: Option<Vec<String>>,
+    storage: TokenStoragePayload,
+    profile: Option<AccountProfilePayload>,
+});
+
+#[derive(Serialize)]
+struct TokenStoragePayload {
+    backend: &'static str,
+    path: String,
+}
+
+describe!(TokenStoragePayload {
+    backend: &'static str,
+    path: String,
+});
+
+#[derive(Serialize)]
+struct AccountProfilePayload {
+    id: String,
+    display_name: Option<String>,
+    product: Option<String>,
+    country: Option<String>,
+}
+
+describe!(AccountProfilePayload {
+    id: String,
+    display_name: Option<String>,
+    product: Option<String>,
+    country: Option<String>,
+});
+
 pub fn auth_status(status: AuthStatus) -> Result<()> {
     let payload = auth_status_payload(status);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::AuthStatus, &payload)
 }
 
 fn auth_status_payload(status: AuthStatus) -> AuthStatusPayload {
     AuthStatusPayload {
         logged_in: status.logged_in,
         expires_at: status.expires_at,
+        client_id: status.client_id,
+        granted_scopes: status.granted_scopes,
+        storage: TokenStoragePayload {
+            backend: "file",
+            path: status.token_path,
+        },
+        profile: status.profile.map(|profile| AccountProfilePayload {
+            id: profile.id,
+            display_name: profile.display_name,
+            product: profile.product,
+            country: profile.country,
+        }),
     }
 }
 
 #[derive(Serialize)]
-struct AuthScopesPayload {
+pub(super) struct AuthScopesPayload {
     required: Vec<String>,
     granted: Option<Vec<String>>,
     missing: Vec<String>,
 }
 
+describe!(AuthScopesPayload {
+    required: Vec<String>,
+    granted: Option<Vec<String>>,
+    missing: Vec<String>,
+});
+
 pub fn auth_scopes(scopes: AuthScopes) -> Result<()> {
     let payload = auth_scopes_payload(scopes);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::AuthScopes, &payload)
 }
 
 fn auth_scopes_payload(scopes: AuthScopes) -> AuthScopesPayload {
@@ -52,7 +112,7 @@ fn auth_scopes_payload(scopes: AuthScopes) -> AuthScopesPayload {
 }
 
 #[derive(Serialize)]
-struct PlayerStatusPayload {
+pub(super) struct PlayerStatusPayload {
     is_playing: bool,
     track: Option<TrackPayload>,
     device: Option<DevicePayload>,
@@ -62,6 +122,16 @@ struct PlayerStatusPayload {
     shuffle_state: Option<bool>,
 }
 
+describe!(PlayerStatusPayload {
+    is_playing: bool,
+    track: Option<TrackPayload>,
+    device: Option<DevicePayload>,
+    context: Option<PlaybackContextPayload>,
+    progress_ms: Option<u32>,
+    repeat_state: Option<String>,
+    shuffle_state: Option<bool>,
+});
+
 #[derive(Serialize)]
 struct TrackPayload {
     id: String,
@@ -72,25 +142,37 @@ struct TrackPayload {
     duration_ms: Option<u32>,
 }
 
+describe!(TrackPayload {
+    id: String,
+    name: String,
+    artists: Vec<String>,
+    album: Option<String>,
+    album_id: Option<String>,
+    duration_ms: Option<u32>,
+});
+
 #[derive(Serialize)]
 struct PlaybackContextPayload {
     kind: String,
     uri: String,
+    name: Option<String>,
 }
 
+describe!(PlaybackContextPayload {
+    kind: String,
+    uri: String,
+    name: Option<String>,
+});
+
 pub fn player_status(status: PlayerStatus) -> Result<()> {
     let payload = player_status_payload(status);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::PlayerStatus, &payload)
 }
 
 fn player_status_payload(status: PlayerStatus) -> PlayerStatusPayload {
     let track = status.track.map(track_payload);
     let device = status.device.map(device_payload);
-    let context = status.context.map(|context| PlaybackContextPayload {
-        kind: context.kind,
-        uri: context.uri,
-    });
+    let context = status.context.map(playback_context_payload);
 
     PlayerStatusPayload {
         is_playing: status.is_playing,
@@ -104,24 +186,49 @@ fn player_status_payload(status: PlayerStatus) -> PlayerStatusPayload {
 }
 
 #[derive(Serialize)]
-struct NowPlayingPayload {
+pub(super) struct ProgressPayload {
+    progress_ms: u32,
+    duration_ms: Option<u32>,
+    fraction: f64,
+}
+
+describe!(ProgressPayload {
+    progress_ms: u32,
+    duration_ms: Option<u32>,
+    fraction: f64,
+});
+
+pub fn player_progress(progress_ms: u32, duration_ms: Option<u32>) -> Result<()> {
+    write_json(
+        PayloadKind::PlayerProgress,
+        &ProgressPayload {
+            progress_ms,
+            duration_ms,
+            fraction: progress_fraction(progress_ms, duration_ms),
+        },
+    )
+}
+
+#[derive(Serialize)]
+pub(super) struct NowPlayingPayload {
     event: &'static str,
     status: PlayerStatusPayload,
 }
 
+describe!(NowPlayingPayload {
+    event: &'static str,
+    status: PlayerStatusPayload,
+});
+
 pub fn now_playing(status: PlayerStatus) -> Result<()> {
     let payload = now_playing_payload(status);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::NowPlaying, &payload)
 }
 
 fn now_playing_payload(status: PlayerStatus) -> NowPlayingPayload {
     let track = status.track.map(track_payload);
     let device = status.device.map(device_payload);
-    let context = status.context.map(|context| PlaybackContextPayload {
-        kind: context.kind,
-        uri: context.uri,
-    });
+    let context = status.context.map(playback_context_payload);
 
     let status_payload = PlayerStatusPayload {
         is_playing: status.is_playing,
@@ -140,22 +247,89 @@ fn now_playing_payload(status: PlayerStatus) -> NowPlayingPayload {
 }
 
 #[derive(Serialize)]
-struct DevicePayload {
+pub(super) struct DevicePayload {
     id: String,
     name: String,
     volume_percent: Option<u32>,
+    supports_volume: Option<bool>,
+}
+
+describe!(DevicePayload {
+    id: String,
+    name: String,
+    volume_percent: Option<u32>,
+    supports_volume: Option<bool>,
+});
+
+#[derive(Serialize)]
+pub(super) struct ErrorPayload {
+    code: i32,
+    kind: &'static str,
+    message: String,
+    details: ErrorDetails,
+}
+
+describe!(ErrorPayload {
+    code: i32,
+    kind: &'static str,
+    message: String,
+    details: ErrorDetails,
+});
+
+#[derive(Serialize)]
+struct ErrorDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    causes: Vec<String>,
+}
+
+describe!(ErrorDetails {
+    #[optional] status: Option<u16>,
+    causes: Vec<String>,
+});
+
+/// Print the error envelope; `--fields` is not applied so the contract fields stay present.
+pub fn error(err: &anyhow::Error) -> Result<()> {
+    let payload = serde_json::to_value(error_payload(err))?;
+    let payload = match json_envelope() {
+        true => envelope(PayloadKind::Error, payload),
+        false => payload,
+    };
+    println!("{}", serde_json::to_string(&payload)?);
+    Ok(())
+}
+
+fn error_payload(err: &anyhow::Error) -> ErrorPayload {
+    let kind = classify(err);
+    let status = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<ApiError>())
+        .map(|api| api.status.as_u16());
+    ErrorPayload {
+        code: kind.exit_code(),
+        kind: kind.as_str(),
+        message: err.to_string(),
+        details: ErrorDetails {
+            status,
+            causes: err.chain().skip(1).map(|cause| cause.to_string()).collect(),
+        },
+    }
 }
 
 #[derive(Serialize)]
-struct ActionPayload<'a> {
+pub(super) struct ActionPayload<'a> {
     event: &'a str,
     message: &'a str,
 }
 
+describe!(ActionPayload<'_> {
+    event: &str,
+    message: &str,
+});
+
 pub fn action(event: &str, message: &str) -> Result<()> {
     let payload = action_payload(event, message);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::Action, &payload)
 }
 
 fn action_payload<'a>(event: &'a str, message: &'a str) -> ActionPayload<'a> {
@@ -163,7 +337,7 @@ fn action_payload<'a>(event: &'a str, message: &'a str) -> ActionPayload<'a> {
 }
 
 #[derive(Serialize)]
-struct AlbumPayload {
+pub(super) struct AlbumPayload {
     id: String,
     name: String,
     uri: String,
@@ -174,10 +348,20 @@ struct AlbumPayload {
     tracks: Vec<AlbumTrackPayload>,
 }
 
+describe!(AlbumPayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    release_date: Option<String>,
+    total_tracks: Option<u32>,
+    duration_ms: Option<u64>,
+    tracks: Vec<AlbumTrackPayload>,
+});
+
 pub fn album_info(album: Album) -> Result<()> {
     let payload = album_info_payload(album);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::AlbumInfo, &payload)
 }
 
 fn album_info_payload(album: Album) -> AlbumPayload {
@@ -196,6 +380,7 @@ fn album_info_payload(album: Album) -> AlbumPayload {
                 name: track.name,
                 duration_ms: track.duration_ms,
                 track_number: track.track_number,
+                saved: track.saved,
             })
             .collect(),
     }
@@ -206,35 +391,61 @@ struct AlbumTrackPayload {
     name: String,
     duration_ms: u32,
     track_number: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saved: Option<bool>,
 }
 
+describe!(AlbumTrackPayload {
+    name: String,
+    duration_ms: u32,
+    track_number: u32,
+    #[optional] saved: Option<bool>,
+});
+
 #[derive(Serialize)]
-struct ArtistPayload {
+pub(super) struct ArtistPayload {
     id: String,
     name: String,
     uri: String,
     genres: Vec<String>,
     followers: Option<u64>,
+    popularity: Option<u32>,
+    images: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    follower_trend: Option<FollowerTrend>,
 }
 
-pub fn artist_info(artist: Artist) -> Result<()> {
-    let payload = artist_info_payload(artist);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+describe!(ArtistPayload {
+    id: String,
+    name: String,
+    uri: String,
+    genres: Vec<String>,
+    followers: Option<u64>,
+    popularity: Option<u32>,
+    images: Vec<String>,
+    #[optional] follower_trend: Option<FollowerTrend>,
+});
+
+pub fn artist_info(artist: Artist, trend: Option<FollowerTrend>) -> Result<()> {
+    let payload = artist_info_payload(artist, trend);
+    write_json(PayloadKind::ArtistInfo, &payload)
 }
 
-fn artist_info_payload(artist: Artist) -> ArtistPayload {
+fn artist_info_payload(artist: Artist, trend: Option<FollowerTrend>) -> ArtistPayload {
     ArtistPayload {
         id: artist.id,
         name: artist.name,
         uri: artist.uri,
         genres: artist.genres,
         followers: artist.followers,
+        popularity: artist.popularity,
+        images: artist.images,
+        follower_trend: trend,
     }
 }
 
 #[derive(Serialize)]
-struct PlaylistPayload {
+pub(super) struct PlaylistPayload {
     id: String,
     name: String,
     owner: Option<String>,
@@ -242,10 +453,17 @@ struct PlaylistPayload {
     public: Option<bool>,
 }
 
+describe!(PlaylistPayload {
+    id: String,
+    name: String,
+    owner: Option<String>,
+    collaborative: bool,
+    public: Option<bool>,
+});
+
 pub fn playlist_list(playlists: Vec<Playlist>) -> Result<()> {
     let payload = playlist_list_payload(playlists);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::PlaylistList, &payload)
 }
 
 fn playlist_list_payload(playlists: Vec<Playlist>) -> Vec<PlaylistPayload> {
@@ -253,21 +471,30 @@ fn playlist_list_payload(playlists: Vec<Playlist>) -> Vec<PlaylistPayload> {
 }
 
 #[derive(Serialize)]
-struct PlaylistListPayload {
+pub(super) struct PlaylistListPayload {
     playlists: Vec<PlaylistPayload>,
     pinned: Vec<PinPayload>,
 }
 
+describe!(PlaylistListPayload {
+    playlists: Vec<PlaylistPayload>,
+    pinned: Vec<PinPayload>,
+});
+
 #[derive(Serialize)]
-struct PinPayload {
+pub(super) struct PinPayload {
     name: String,
     url: String,
 }
 
+describe!(PinPayload {
+    name: String,
+    url: String,
+});
+
 pub fn playlist_list_with_pins(playlists: Vec<Playlist>, pins: Vec<PinnedPlaylist>) -> Result<()> {
     let payload = playlist_list_with_pins_payload(playlists, pins);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::PlaylistListWithPins, &payload)
 }
 
 #[cfg(test)]
@@ -275,7 +502,7 @@ mod tests {
     use super::*;
     use crate::domain::album::AlbumTrack;
     use crate::domain::artist::Artist;
-    use crate::domain::auth::{AuthScopes, AuthStatus};
+    use crate::domain::auth::{AccountProfile, AuthScopes, AuthStatus};
     use crate::domain::device::Device;
     use crate::domain::player::PlayerStatus;
     use crate::domain::playlist::{Playlist, PlaylistDetail};
@@ -286,9 +513,27 @@ mod tests {
         let payload = auth_status_payload(AuthStatus {
             logged_in: true,
             expires_at: Some(1),
+            client_id: Some("client".to_string()),
+            token_path: "/tmp/metadata.json".to_string(),
+            profile: Some(AccountProfile {
+                id: "user".to_string(),
+                display_name: Some("User".to_string()),
+                product: Some("premium".to_string()),
+                country: Some("DE".to_string()),
+            }),
+            ..AuthStatus::default()
         });
         assert!(payload.logged_in);
         assert_eq!(payload.expires_at, Some(1));
+        assert_eq!(payload.client_id.as_deref(), Some("client"));
+        assert_eq!(payload.storage.backend, "file");
+        assert_eq!(
+            payload
+                .profile
+                .and_then(|profile| profile.product)
+                .as_deref(),
+            Some("premium")
+        );
     }
 
     #[test]
@@ -329,6 +574,22 @@ mod tests {
         assert_eq!(payload.event, "now_playing");
     }
 
+    #[test]
+    fn error_payload_shape() {
+        let err = anyhow::anyhow!(crate::spotify::error::format_api_error(
+            "spotify playlist add failed",
+            reqwest::StatusCode::NOT_FOUND,
+            "{}"
+        ))
+        .context("playlist add");
+        let payload = error_payload(&err);
+        assert_eq!(payload.code, 4);
+        assert_eq!(payload.kind, "not_found");
+        assert_eq!(payload.message, "playlist add");
+        assert_eq!(payload.details.status, Some(404));
+        assert_eq!(payload.details.causes.len(), 1);
+    }
+
     #[test]
     fn action_payload_shape() {
         let payload = action_payload("event", "message");
@@ -347,8 +608,10 @@ mod tests {
             total_tracks: Some(1),
             tracks: vec![AlbumTrack {
                 name: "Track".into(),
+                uri: "uri".into(),
                 duration_ms: 1000,
                 track_number: 1,
+                saved: None,
             }],
             duration_ms: Some(1000),
         });
@@ -357,14 +620,20 @@ mod tests {
 
     #[test]
     fn artist_info_payload_shape() {
-        let payload = artist_info_payload(Artist {
-            id: "1".into(),
-            name: "Artist".into(),
-            uri: "uri".into(),
-            genres: vec![],
-            followers: Some(10),
-        });
+        let payload = artist_info_payload(
+            Artist {
+                id: "1".into(),
+                name: "Artist".into(),
+                uri: "uri".into(),
+                genres: vec![],
+                followers: Some(10),
+                popularity: Some(50),
+                images: vec!["https://i.scdn.co/image/1".into()],
+            },
+            None,
+        );
         assert_eq!(payload.followers, Some(10));
+        assert_eq!(payload.images.len(), 1);
     }
 
     #[test]
@@ -375,6 +644,7 @@ mod tests {
             owner: None,
             collaborative: false,
             public: Some(true),
+            description: None,
         }]);
         assert_eq!(payload.len(), 1);
     }
@@ -388,10 +658,12 @@ mod tests {
                 owner: None,
                 collaborative: false,
                 public: Some(true),
+                description: None,
             }],
             vec![PinnedPlaylist {
                 name: "Pin".into(),
                 url: "url".into(),
+                tags: Vec::new(),
             }],
         );
         assert_eq!(payload.pinned.len(), 1);
@@ -417,6 +689,7 @@ mod tests {
             id: "1".into(),
             name: "Device".into(),
             volume_percent: Some(10),
+            supports_volume: None,
         }]);
         assert_eq!(payload.len(), 1);
     }
@@ -435,6 +708,11 @@ mod tests {
                 duration_ms: Some(1000),
                 owner: None,
                 score: None,
+                playable: None,
+                linked_from: None,
+                saved: None,
+                explicit: None,
+                popularity: None,
             }],
         });
         assert_eq!(payload.kind, "all");
@@ -460,25 +738,38 @@ fn playlist_list_with_pins_payload(
 }
 
 #[derive(Serialize)]
-struct HelpPayload {
+pub(super) struct HelpPayload {
     usage: &'static str,
     objects: Vec<&'static str>,
     examples: Vec<&'static str>,
 }
 
+describe!(HelpPayload {
+    usage: &'static str,
+    objects: Vec<&'static str>,
+    examples: Vec<&'static str>,
+});
+
 pub fn help() -> Result<()> {
     let payload = help_payload();
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::Help, &payload)
 }
 
 fn help_payload() -> HelpPayload {
     HelpPayload {
         usage: "spotify-cli <object> <verb> [target] [flags]",
         objects: vec![
+            "album",
+            "alias",
             "auth",
+            "block",
+            "config",
             "device",
+            "episode",
+            "follow",
+            "hook",
             "info",
+            "log",
             "search",
             "nowplaying",
             "player",
@@ -487,6 +778,10 @@ fn help_payload() -> HelpPayload {
             "sync",
             "queue",
             "recentlyplayed",
+            "releases",
+            "session",
+            "show",
+            "user",
         ],
         examples: vec![
             "spotify-cli auth status",
@@ -503,7 +798,7 @@ fn help_payload() -> HelpPayload {
 }
 
 #[derive(Serialize)]
-struct PlaylistDetailPayload {
+pub(super) struct PlaylistDetailPayload {
     id: String,
     name: String,
     uri: String,
@@ -513,10 +808,19 @@ struct PlaylistDetailPayload {
     public: Option<bool>,
 }
 
+describe!(PlaylistDetailPayload {
+    id: String,
+    name: String,
+    uri: String,
+    owner: Option<String>,
+    tracks_total: Option<u32>,
+    collaborative: bool,
+    public: Option<bool>,
+});
+
 pub fn playlist_info(playlist: PlaylistDetail) -> Result<()> {
     let payload = playlist_info_payload(playlist);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::PlaylistInfo, &payload)
 }
 
 fn playlist_info_payload(playlist: PlaylistDetail) -> PlaylistDetailPayload {
@@ -533,8 +837,7 @@ fn playlist_info_payload(playlist: PlaylistDetail) -> PlaylistDetailPayload {
 
 pub fn device_list(devices: Vec<Device>) -> Result<()> {
     let payload = device_list_payload(devices);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::DeviceList, &payload)
 }
 
 fn device_list_payload(devices: Vec<Device>) -> Vec<DevicePayload> {
@@ -542,11 +845,34 @@ fn device_list_payload(devices: Vec<Device>) -> Vec<DevicePayload> {
 }
 
 #[derive(Serialize)]
-struct SearchResultsPayload {
+pub(super) struct SearchResultsPayload {
+    kind: &'static str,
+    items: Vec<SearchItemPayload>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    totals: Vec<SearchTotalPayload>,
+}
+
+describe!(SearchResultsPayload {
     kind: &'static str,
     items: Vec<SearchItemPayload>,
+    #[optional] totals: Vec<SearchTotalPayload>,
+});
+
+#[derive(Serialize)]
+struct SearchTotalPayload {
+    kind: &'static str,
+    total: u32,
+    offset: u32,
+    count: u32,
 }
 
+describe!(SearchTotalPayload {
+    kind: &'static str,
+    total: u32,
+    offset: u32,
+    count: u32,
+});
+
 #[derive(Serialize)]
 struct SearchItemPayload {
     id: String,
@@ -559,13 +885,40 @@ struct SearchItemPayload {
     owner: Option<String>,
     score: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    is_playable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    linked_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saved: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explicit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    popularity: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     now_playing: Option<bool>,
 }
 
+describe!(SearchItemPayload {
+    id: String,
+    name: String,
+    uri: String,
+    kind: &'static str,
+    artists: Vec<String>,
+    album: Option<String>,
+    duration_ms: Option<u32>,
+    owner: Option<String>,
+    score: Option<f32>,
+    #[optional] is_playable: Option<bool>,
+    #[optional] linked_from: Option<String>,
+    #[optional] saved: Option<bool>,
+    #[optional] explicit: Option<bool>,
+    #[optional] popularity: Option<u32>,
+    #[optional] now_playing: Option<bool>,
+});
+
 pub fn search_results(results: SearchResults) -> Result<()> {
     let payload = search_results_payload(results);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::SearchResults, &payload)
 }
 
 fn search_results_payload(results: SearchResults) -> SearchResultsPayload {
@@ -574,19 +927,85 @@ fn search_results_payload(results: SearchResults) -> SearchResultsPayload {
     SearchResultsPayload {
         kind: search_type_label(results.kind),
         items,
+        totals: Vec::new(),
     }
 }
 
-pub fn queue(now_playing_id: Option<&str>, items: Vec<SearchItem>) -> Result<()> {
-    let payload = search_results_payload_with_now(
+pub fn search_results_paged(results: SearchResults, totals: Vec<SearchTotal>) -> Result<()> {
+    let payload = search_results_paged_payload(results, totals);
+    write_json(PayloadKind::SearchResults, &payload)
+}
+
+fn search_results_paged_payload(
+    results: SearchResults,
+    totals: Vec<SearchTotal>,
+) -> SearchResultsPayload {
+    let mut payload = search_results_payload(results);
+    payload.totals = totals
+        .into_iter()
+        .map(|total| SearchTotalPayload {
+            kind: search_type_label(total.kind),
+            total: total.total,
+            offset: total.offset,
+            count: total.count,
+        })
+        .collect();
+    payload
+}
+
+#[derive(Serialize)]
+pub(super) struct QueuePayload {
+    context: Option<PlaybackContextPayload>,
+    #[serde(flatten)]
+    results: SearchResultsPayload,
+    /// Milliseconds from now until each item starts, aligned with `items`.
+    starts_in_ms: Vec<Option<u64>>,
+    total_duration_ms: u64,
+}
+
+impl Describe for QueuePayload {
+    fn schema() -> Value {
+        // `results` is flattened into the queue object.
+        let mut schema = SearchResultsPayload::schema();
+        schema["properties"]["context"] = Option::<PlaybackContextPayload>::schema();
+        schema["properties"]["starts_in_ms"] = Vec::<Option<u64>>::schema();
+        schema["properties"]["total_duration_ms"] = u64::schema();
+        if let Some(required) = schema["required"].as_array_mut() {
+            required.extend(["context", "starts_in_ms", "total_duration_ms"].map(Value::from));
+        }
+        schema
+    }
+}
+
+pub fn queue(
+    now_playing_id: Option<&str>,
+    context: Option<PlaybackContext>,
+    items: Vec<SearchItem>,
+    starts_in_ms: Vec<Option<u64>>,
+    total_duration_ms: u64,
+) -> Result<()> {
+    let mut results = search_results_payload_with_now(
         SearchResults {
             kind: SearchType::Track,
             items,
         },
         now_playing_id,
     );
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    // Podcast episodes share the queue with tracks; their URI tells them apart.
+    for item in &mut results.items {
+        if item.uri.starts_with("spotify:episode:") {
+            item.kind = "episode";
+        }
+    }
+    write_json(
+        PayloadKind::Queue,
+        &QueuePayload {
+            context: context.map(playback_context_payload),
+            results,
+            starts_in_ms,
+            total_duration_ms,
+        },
+    )
 }
 
 pub fn recently_played(now_playing_id: Option<&str>, items: Vec<SearchItem>) -> Result<()> {
@@ -597,8 +1016,7 @@ pub fn recently_played(now_playing_id: Option<&str>, items: Vec<SearchItem>) ->
         },
         now_playing_id,
     );
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::RecentlyPlayed, &payload)
 }
 
 fn search_results_payload_with_now(
@@ -614,6 +1032,7 @@ fn search_results_payload_with_now(
     SearchResultsPayload {
         kind: search_type_label(results.kind),
         items,
+        totals: Vec::new(),
     }
 }
 
@@ -628,11 +1047,20 @@ fn track_payload(track: crate::domain::track::Track) -> TrackPayload {
     }
 }
 
+fn playback_context_payload(context: PlaybackContext) -> PlaybackContextPayload {
+    PlaybackContextPayload {
+        kind: context.kind,
+        uri: context.uri,
+        name: context.name,
+    }
+}
+
 fn device_payload(device: Device) -> DevicePayload {
     DevicePayload {
         id: device.id,
         name: device.name,
         volume_percent: device.volume_percent,
+        supports_volume: device.supports_volume,
     }
 }
 
@@ -664,6 +1092,11 @@ fn search_item_payload(item: crate::domain::search::SearchItem) -> SearchItemPay
         duration_ms: item.duration_ms,
         owner: item.owner,
         score: item.score,
+        is_playable: item.playable,
+        linked_from: item.linked_from,
+        saved: item.saved,
+        explicit: item.explicit,
+        popularity: item.popularity,
         now_playing: None,
     }
 }
@@ -683,6 +1116,11 @@ fn search_item_payload_with_now(
         duration_ms: item.duration_ms,
         owner: item.owner,
         score: item.score,
+        is_playable: item.playable,
+        linked_from: item.linked_from,
+        saved: item.saved,
+        explicit: item.explicit,
+        popularity: item.popularity,
         now_playing: if is_now_playing { Some(true) } else { None },
     }
 }
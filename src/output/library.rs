@@ -0,0 +1,113 @@
+//! Saved track output formatting.
+use serde::Serialize;
+
+use crate::domain::track::SavedTrack;
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::{format_duration, print_table_with_header};
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn saved_track_list_human(tracks: Vec<SavedTrack>, table: TableConfig) -> Result<()> {
+    if tracks.is_empty() {
+        println!("{}", i18n::t("No saved tracks"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = tracks
+        .into_iter()
+        .enumerate()
+        .map(|(index, saved)| {
+            let added = saved
+                .added_at
+                .as_deref()
+                .and_then(|added| added.get(..10))
+                .unwrap_or_default()
+                .to_string();
+            let track = saved.track;
+            vec![
+                (index + 1).to_string(),
+                track.name,
+                track.artists.join(", "),
+                track.album.unwrap_or_default(),
+                track
+                    .duration_ms
+                    .map(|ms| format_duration(ms as u64))
+                    .unwrap_or_default(),
+                added,
+            ]
+        })
+        .collect();
+    print_table_with_header(
+        &rows,
+        &["#", "TRACK", "ARTIST", "ALBUM", "DURATION", "ADDED"],
+        table,
+    );
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct SavedTrackPayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    album: Option<String>,
+    duration_ms: Option<u32>,
+    added_at: Option<String>,
+}
+
+describe!(SavedTrackPayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    album: Option<String>,
+    duration_ms: Option<u32>,
+    added_at: Option<String>,
+});
+
+pub fn saved_track_list_json(tracks: Vec<SavedTrack>) -> Result<()> {
+    let payload = saved_track_list_payload(tracks);
+    write_json(PayloadKind::SavedTrackList, &payload)
+}
+
+fn saved_track_list_payload(tracks: Vec<SavedTrack>) -> Vec<SavedTrackPayload> {
+    tracks
+        .into_iter()
+        .map(|saved| SavedTrackPayload {
+            uri: format!("spotify:track:{}", saved.track.id),
+            id: saved.track.id,
+            name: saved.track.name,
+            artists: saved.track.artists,
+            album: saved.track.album,
+            duration_ms: saved.track.duration_ms,
+            added_at: saved.added_at,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::saved_track_list_payload;
+    use crate::domain::track::{SavedTrack, Track};
+
+    #[test]
+    fn saved_track_list_payload_shape() {
+        let payload = saved_track_list_payload(vec![SavedTrack {
+            track: Track {
+                id: "t1".to_string(),
+                name: "Dayvan Cowboy".to_string(),
+                artists: vec!["Boards of Canada".to_string()],
+                artist_ids: Vec::new(),
+                album: Some("The Campfire Headphase".to_string()),
+                album_id: None,
+                duration_ms: Some(300_000),
+            },
+            added_at: Some("2024-01-01T00:00:00Z".to_string()),
+        }]);
+        assert_eq!(payload[0].uri, "spotify:track:t1");
+        assert_eq!(payload[0].added_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+    }
+}
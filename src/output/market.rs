@@ -0,0 +1,136 @@
+//! Market output formatting.
+use serde::Serialize;
+
+use crate::domain::market::{Market, MarketAvailability};
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn market_list_human(markets: Vec<Market>, table: TableConfig) -> Result<()> {
+    if markets.is_empty() {
+        println!("{}", i18n::t("No markets"));
+        return Ok(());
+    }
+    print_market_table(markets, table);
+    Ok(())
+}
+
+pub fn market_availability_human(
+    availability: MarketAvailability,
+    table: TableConfig,
+) -> Result<()> {
+    println!(
+        "{} ({}): playable in {} market(s)",
+        availability.name,
+        availability.uri,
+        availability.markets.len()
+    );
+    if let Some((code, playable)) = &availability.current {
+        let verdict = if *playable {
+            "playable"
+        } else {
+            "not playable"
+        };
+        println!("{code}: {verdict}");
+    }
+    if !availability.markets.is_empty() {
+        print_market_table(availability.markets, table);
+    }
+    Ok(())
+}
+
+fn print_market_table(markets: Vec<Market>, table: TableConfig) {
+    let rows: Vec<Vec<String>> = markets
+        .into_iter()
+        .map(|market| vec![market.code, market.name.unwrap_or_default()])
+        .collect();
+    print_table_with_header(&rows, &["CODE", "COUNTRY"], table);
+}
+
+#[derive(Serialize)]
+pub(super) struct MarketPayload {
+    code: String,
+    name: Option<String>,
+}
+
+describe!(MarketPayload {
+    code: String,
+    name: Option<String>,
+});
+
+#[derive(Serialize)]
+pub(super) struct MarketAvailabilityPayload {
+    uri: String,
+    name: String,
+    markets: Vec<MarketPayload>,
+    market: Option<String>,
+    playable: Option<bool>,
+}
+
+describe!(MarketAvailabilityPayload {
+    uri: String,
+    name: String,
+    markets: Vec<MarketPayload>,
+    market: Option<String>,
+    playable: Option<bool>,
+});
+
+pub fn market_list_json(markets: Vec<Market>) -> Result<()> {
+    write_json(PayloadKind::MarketList, &market_list_payload(markets))
+}
+
+pub fn market_availability_json(availability: MarketAvailability) -> Result<()> {
+    write_json(
+        PayloadKind::MarketAvailability,
+        &market_availability_payload(availability),
+    )
+}
+
+fn market_list_payload(markets: Vec<Market>) -> Vec<MarketPayload> {
+    markets
+        .into_iter()
+        .map(|market| MarketPayload {
+            code: market.code,
+            name: market.name,
+        })
+        .collect()
+}
+
+fn market_availability_payload(availability: MarketAvailability) -> MarketAvailabilityPayload {
+    let (market, playable) = match availability.current {
+        Some((code, playable)) => (Some(code), Some(playable)),
+        None => (None, None),
+    };
+    MarketAvailabilityPayload {
+        uri: availability.uri,
+        name: availability.name,
+        markets: market_list_payload(availability.markets),
+        market,
+        playable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::market_availability_payload;
+    use crate::domain::market::{Market, MarketAvailability};
+
+    #[test]
+    fn market_availability_payload_shape() {
+        let payload = market_availability_payload(MarketAvailability {
+            uri: "spotify:track:1".to_string(),
+            name: "Song".to_string(),
+            markets: vec![Market {
+                code: "SE".to_string(),
+                name: Some("Sweden".to_string()),
+            }],
+            current: Some(("US".to_string(), false)),
+        });
+        assert_eq!(payload.markets.len(), 1);
+        assert_eq!(payload.market.as_deref(), Some("US"));
+        assert_eq!(payload.playable, Some(false));
+    }
+}
@@ -1,22 +1,63 @@
 //! Output formatting for human and JSON modes.
-use crate::domain::album::Album;
-use crate::domain::artist::Artist;
+use crate::domain::album::{Album, SavedAlbum};
+use crate::domain::artist::{Artist, FollowedArtistPage, FollowerTrend};
+use crate::domain::audiobook::{Audiobook, SavedAudiobook};
 use crate::domain::auth::{AuthScopes, AuthStatus};
+use crate::domain::block::BlockEntry;
 use crate::domain::cache::CacheStatus;
+use crate::domain::cuesheet::{CueFormat, Cuesheet};
 use crate::domain::device::Device;
+use crate::domain::episode::{Episode, SavedEpisode};
+use crate::domain::history::PruneCandidate;
+use crate::domain::market::{Market, MarketAvailability};
+use crate::domain::oplog::{LoggedOperation, OpLogEntry, PlaylistDiff};
+use crate::domain::pending::PendingEntry;
 use crate::domain::pin::PinnedPlaylist;
-use crate::domain::player::PlayerStatus;
-use crate::domain::playlist::{Playlist, PlaylistDetail};
-use crate::domain::search::{SearchItem, SearchResults};
+use crate::domain::player::{PlaybackContext, PlayerStatus, RecentContext};
+use crate::domain::playlist::{
+    Playlist, PlaylistAudit, PlaylistDetail, PlaylistMatch, PlaylistTrackPage, TrackLocation,
+    UserPlaylistPage,
+};
+use crate::domain::queue::QueueItem;
+use crate::domain::release::{Release, ReleaseGroup};
+use crate::domain::search::{SearchItem, SearchResults, SearchTotal};
+use crate::domain::session::SessionReport;
 use crate::domain::settings::Settings;
-use crate::domain::track::Track;
+use crate::domain::show::{SavedShow, Show};
+use crate::domain::stats::{GenreShare, ListeningStats, PlaylistStats, Tally};
+use crate::domain::track::{SavedTrack, Track};
 use crate::error::Result;
+use crate::output::schema::PayloadKind;
+use serde::Serialize;
+use std::sync::OnceLock;
 
+pub mod album;
+pub mod block;
 pub mod cache;
+pub mod context;
+pub mod csv;
+pub mod cuesheet;
+pub mod episode;
+pub mod follow;
+pub mod history;
 pub mod human;
+pub mod i18n;
 pub mod json;
+pub mod library;
+pub mod market;
+pub mod oplog;
+pub mod pending;
 pub mod pin;
+pub mod playlist;
+pub mod progress;
+pub mod projection;
+pub mod release;
+pub mod schema;
+pub mod session;
 pub mod settings;
+pub mod show;
+pub mod stats;
+pub mod width;
 
 /// Output mode for CLI responses.
 #[derive(Debug, Clone, Copy)]
@@ -27,6 +68,68 @@ pub enum OutputMode {
 
 pub const DEFAULT_MAX_WIDTH: usize = 48;
 
+static JSON_FIELDS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Restrict JSON payloads to the given dotted field paths for this process.
+pub fn set_json_fields(fields: Vec<String>) {
+    if !fields.is_empty() {
+        let _ = JSON_FIELDS.set(fields);
+    }
+}
+
+static COLUMN_WIDTHS: OnceLock<Vec<(String, usize)>> = OnceLock::new();
+
+/// Override the truncation width of named table columns (matched against headers in any
+/// catalog language) for this process.
+pub fn set_column_widths(widths: Vec<(String, usize)>) {
+    if !widths.is_empty() {
+        let _ = COLUMN_WIDTHS.set(widths);
+    }
+}
+
+pub(crate) fn column_width(header: &str) -> Option<usize> {
+    let widths = COLUMN_WIDTHS.get()?;
+    matching_width(widths, header)
+}
+
+/// Width for `header`, whichever language the user named the column in.
+fn matching_width(widths: &[(String, usize)], header: &str) -> Option<usize> {
+    let names: Vec<String> = i18n::renderings(header)
+        .into_iter()
+        .map(str::to_lowercase)
+        .collect();
+    widths
+        .iter()
+        .find(|(name, _)| names.contains(&name.to_lowercase()))
+        .map(|(_, width)| *width)
+}
+
+static JSON_ENVELOPE: OnceLock<bool> = OnceLock::new();
+
+/// Wrap JSON payloads in the versioned envelope for this process.
+pub fn set_json_envelope(enabled: bool) {
+    let _ = JSON_ENVELOPE.set(enabled);
+}
+
+pub(crate) fn json_envelope() -> bool {
+    JSON_ENVELOPE.get().copied().unwrap_or(false)
+}
+
+/// Serialize a JSON payload to stdout, applying any `--fields` projection.
+pub(crate) fn write_json<T: Serialize>(kind: PayloadKind, payload: &T) -> Result<()> {
+    let value = serde_json::to_value(payload)?;
+    let value = match JSON_FIELDS.get() {
+        Some(fields) => projection::project(value, fields),
+        None => value,
+    };
+    let value = match json_envelope() {
+        true => schema::envelope(kind, value),
+        false => value,
+    };
+    println!("{}", serde_json::to_string(&value)?);
+    Ok(())
+}
+
 /// Table rendering configuration for human output.
 #[derive(Debug, Clone, Copy)]
 pub struct TableConfig {
@@ -40,6 +143,7 @@ pub struct Output {
     mode: OutputMode,
     user_name: Option<String>,
     table: TableConfig,
+    dry_run: bool,
 }
 
 impl Output {
@@ -62,9 +166,25 @@ impl Output {
             mode,
             user_name,
             table,
+            dry_run: false,
         }
     }
 
+    /// Under `--dry-run`, action confirmations are replaced by the plan.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Progress line for a bulk operation; hidden in JSON mode or off a terminal.
+    pub fn progress(&self, label: &str, total: Option<u64>) -> progress::Progress {
+        use std::io::IsTerminal;
+        let enabled = matches!(self.mode, OutputMode::Human)
+            && std::io::stdout().is_terminal()
+            && std::io::stderr().is_terminal();
+        progress::Progress::new(label, total, enabled)
+    }
+
     pub fn auth_status(&self, status: AuthStatus) -> Result<()> {
         match self.mode {
             OutputMode::Human => human::auth_status(status),
@@ -79,6 +199,14 @@ impl Output {
         }
     }
 
+    /// Played fraction of the current item, for `player status --progress-only`.
+    pub fn player_progress(&self, progress_ms: u32, duration_ms: Option<u32>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => human::player_progress(progress_ms, duration_ms),
+            OutputMode::Json => json::player_progress(progress_ms, duration_ms),
+        }
+    }
+
     pub fn player_status(&self, status: PlayerStatus) -> Result<()> {
         match self.mode {
             OutputMode::Human => human::player_status(status),
@@ -86,6 +214,13 @@ impl Output {
         }
     }
 
+    pub fn recent_contexts(&self, recent: Vec<RecentContext>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => context::recent_contexts_human(recent, self.table),
+            OutputMode::Json => context::recent_contexts_json(recent),
+        }
+    }
+
     pub fn now_playing(&self, status: PlayerStatus) -> Result<()> {
         match self.mode {
             OutputMode::Human => human::now_playing(status),
@@ -100,28 +235,66 @@ impl Output {
         }
     }
 
-    pub fn queue(&self, now_playing_id: Option<&str>, items: Vec<Track>) -> Result<()> {
+    pub fn search_results_paged(
+        &self,
+        results: SearchResults,
+        totals: Vec<SearchTotal>,
+    ) -> Result<()> {
         match self.mode {
-            OutputMode::Human => human::queue(items, now_playing_id, self.table),
+            OutputMode::Human => {
+                human::search_results(results, self.table)?;
+                human::search_totals(&totals);
+                Ok(())
+            }
+            OutputMode::Json => json::search_results_paged(results, totals),
+        }
+    }
+
+    /// `saved` holds each track's library state in order; it may be empty when unchecked.
+    pub fn queue(
+        &self,
+        now_playing_id: Option<&str>,
+        context: Option<PlaybackContext>,
+        items: Vec<QueueItem>,
+        saved: Vec<Option<bool>>,
+        starts: Vec<Option<u64>>,
+    ) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => human::queue(
+                items.into_iter().map(|item| item.track).collect(),
+                now_playing_id,
+                context.as_ref(),
+                &saved,
+                &starts,
+                self.table,
+            ),
             OutputMode::Json => {
+                let tracks: Vec<Track> = items.iter().map(|item| item.track.clone()).collect();
+                let total_duration_ms = human::queued_duration_ms(&tracks, now_playing_id);
                 let items = items
                     .into_iter()
-                    .map(|track| {
-                        let id = track.id;
+                    .enumerate()
+                    .map(|(index, item)| {
+                        let track = item.track;
                         crate::domain::search::SearchItem {
-                            id: id.clone(),
+                            id: track.id,
                             name: track.name,
-                            uri: format!("spotify:track:{}", id),
+                            uri: item.uri,
                             kind: crate::domain::search::SearchType::Track,
                             artists: track.artists,
                             album: track.album,
                             duration_ms: track.duration_ms,
                             owner: None,
                             score: None,
+                            playable: None,
+                            linked_from: None,
+                            saved: saved.get(index).copied().flatten(),
+                            explicit: None,
+                            popularity: None,
                         }
                     })
                     .collect();
-                json::queue(now_playing_id, items)
+                json::queue(now_playing_id, context, items, starts, total_duration_ms)
             }
         }
     }
@@ -145,6 +318,9 @@ impl Output {
     }
 
     pub fn action(&self, event: &str, message: &str) -> Result<()> {
+        if self.dry_run {
+            return Ok(());
+        }
         match self.mode {
             OutputMode::Human => human::action(message),
             OutputMode::Json => json::action(event, message),
@@ -161,7 +337,49 @@ impl Output {
     pub fn artist_info(&self, artist: Artist) -> Result<()> {
         match self.mode {
             OutputMode::Human => human::artist_info(artist),
-            OutputMode::Json => json::artist_info(artist),
+            OutputMode::Json => json::artist_info(artist, None),
+        }
+    }
+
+    pub fn episode_info(&self, episode: Episode) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => episode::episode_info_human(episode),
+            OutputMode::Json => episode::episode_info_json(episode),
+        }
+    }
+
+    pub fn show_info(&self, info: Show) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => show::show_info_human(info),
+            OutputMode::Json => show::show_info_json(info),
+        }
+    }
+
+    pub fn audiobook_info(&self, audiobook: Audiobook) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => show::audiobook_info_human(audiobook),
+            OutputMode::Json => show::audiobook_info_json(audiobook),
+        }
+    }
+
+    pub fn episode_list(&self, episodes: Vec<Episode>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => episode::episode_list_human(episodes, self.table),
+            OutputMode::Json => episode::episode_list_json(episodes),
+        }
+    }
+
+    pub fn artist_releases(&self, artist: Artist, groups: Vec<ReleaseGroup>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => release::artist_releases_human(artist, groups, self.table),
+            OutputMode::Json => release::artist_releases_json(artist, groups),
+        }
+    }
+
+    pub fn artist_full(&self, artist: Artist, trend: Option<FollowerTrend>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => human::artist_full(artist, trend),
+            OutputMode::Json => json::artist_info(artist, trend),
         }
     }
 
@@ -197,6 +415,13 @@ impl Output {
         }
     }
 
+    pub fn cuesheet(&self, sheet: Cuesheet, format: CueFormat) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => cuesheet::cuesheet_human(sheet, format),
+            OutputMode::Json => cuesheet::cuesheet_json(sheet),
+        }
+    }
+
     pub fn device_list(&self, devices: Vec<Device>) -> Result<()> {
         match self.mode {
             OutputMode::Human => human::device_list(devices, self.table),
@@ -218,6 +443,204 @@ impl Output {
         }
     }
 
+    pub fn pin_tags(&self, tags: Vec<Tally>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => pin::pin_tags_human(tags, self.table),
+            OutputMode::Json => pin::pin_tags_json(tags),
+        }
+    }
+
+    pub fn block_list(&self, blocks: Vec<BlockEntry>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => block::block_list_human(blocks, self.table),
+            OutputMode::Json => block::block_list_json(blocks),
+        }
+    }
+
+    pub fn session_report(&self, report: SessionReport) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => session::session_report_human(report, self.table),
+            OutputMode::Json => session::session_report_json(report),
+        }
+    }
+
+    pub fn saved_album_list(&self, albums: Vec<SavedAlbum>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => album::saved_album_list_human(albums, self.table),
+            OutputMode::Json => album::saved_album_list_json(albums),
+        }
+    }
+
+    /// Saved shows as a table, JSON, or with `csv` set, CSV in either mode.
+    pub fn saved_show_list(&self, shows: Vec<SavedShow>, csv: bool) -> Result<()> {
+        match self.mode {
+            _ if csv => show::saved_show_list_csv(shows),
+            OutputMode::Human => show::saved_show_list_human(shows, self.table),
+            OutputMode::Json => show::saved_show_list_json(shows),
+        }
+    }
+
+    /// Saved episodes as a table, JSON, or with `csv` set, CSV in either mode.
+    pub fn saved_episode_list(&self, episodes: Vec<SavedEpisode>, csv: bool) -> Result<()> {
+        match self.mode {
+            _ if csv => episode::saved_episode_list_csv(episodes),
+            OutputMode::Human => episode::saved_episode_list_human(episodes, self.table),
+            OutputMode::Json => episode::saved_episode_list_json(episodes),
+        }
+    }
+
+    /// Saved audiobooks as a table, JSON, or with `csv` set, CSV in either mode.
+    pub fn saved_audiobook_list(&self, audiobooks: Vec<SavedAudiobook>, csv: bool) -> Result<()> {
+        match self.mode {
+            _ if csv => show::saved_audiobook_list_csv(audiobooks),
+            OutputMode::Human => show::saved_audiobook_list_human(audiobooks, self.table),
+            OutputMode::Json => show::saved_audiobook_list_json(audiobooks),
+        }
+    }
+
+    pub fn playlist_tracks(&self, page: PlaylistTrackPage, offset: u32) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => playlist::playlist_tracks_human(page, offset, self.table),
+            OutputMode::Json => playlist::playlist_tracks_json(page, offset),
+        }
+    }
+
+    pub fn playlist_matches(&self, matches: Vec<PlaylistMatch>, ids_only: bool) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => playlist::playlist_matches_human(
+                matches,
+                ids_only,
+                self.user_name.as_deref(),
+                self.table,
+            ),
+            OutputMode::Json => playlist::playlist_matches_json(matches),
+        }
+    }
+
+    pub fn playlist_audit(&self, audit: PlaylistAudit) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => playlist::playlist_audit_human(audit, self.table),
+            OutputMode::Json => playlist::playlist_audit_json(audit),
+        }
+    }
+
+    pub fn track_location(&self, location: TrackLocation) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => {
+                playlist::track_location_human(location, self.user_name.as_deref(), self.table)
+            }
+            OutputMode::Json => playlist::track_location_json(location),
+        }
+    }
+
+    /// Human mode lists what `prune` would remove; JSON callers get the resulting action.
+    pub fn prune_candidates(&self, candidates: &[PruneCandidate]) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => history::prune_candidates_human(candidates, self.table),
+            OutputMode::Json => Ok(()),
+        }
+    }
+
+    pub fn user_playlists(&self, page: UserPlaylistPage, next_offset: Option<u32>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => playlist::user_playlists_human(page, next_offset, self.table),
+            OutputMode::Json => playlist::user_playlists_json(page, next_offset),
+        }
+    }
+
+    pub fn playlist_contains(
+        &self,
+        playlist_id: &str,
+        uri: &str,
+        positions: Vec<usize>,
+    ) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => playlist::playlist_contains_human(playlist_id, uri, positions),
+            OutputMode::Json => playlist::playlist_contains_json(playlist_id, uri, positions),
+        }
+    }
+
+    pub fn saved_track_list(&self, tracks: Vec<SavedTrack>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => library::saved_track_list_human(tracks, self.table),
+            OutputMode::Json => library::saved_track_list_json(tracks),
+        }
+    }
+
+    pub fn listening_stats(&self, stats: ListeningStats) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => stats::listening_stats_human(stats),
+            OutputMode::Json => stats::listening_stats_json(stats),
+        }
+    }
+
+    pub fn playlist_stats(&self, stats: PlaylistStats) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => stats::playlist_stats_human(stats, self.table),
+            OutputMode::Json => stats::playlist_stats_json(stats),
+        }
+    }
+
+    pub fn top_genres(&self, genres: Vec<GenreShare>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => stats::top_genres_human(genres, self.table),
+            OutputMode::Json => stats::top_genres_json(genres),
+        }
+    }
+
+    pub fn followed_artists(&self, page: FollowedArtistPage) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => follow::followed_artists_human(page, self.table),
+            OutputMode::Json => follow::followed_artists_json(page),
+        }
+    }
+
+    pub fn release_list(&self, releases: Vec<Release>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => release::release_list_human(releases, self.table),
+            OutputMode::Json => release::release_list_json(releases),
+        }
+    }
+
+    pub fn market_list(&self, markets: Vec<Market>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => market::market_list_human(markets, self.table),
+            OutputMode::Json => market::market_list_json(markets),
+        }
+    }
+
+    pub fn market_availability(&self, availability: MarketAvailability) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => market::market_availability_human(availability, self.table),
+            OutputMode::Json => market::market_availability_json(availability),
+        }
+    }
+
+    pub fn pending_list(&self, entries: Vec<PendingEntry>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => pending::pending_list_human(entries, self.table),
+            OutputMode::Json => pending::pending_list_json(entries),
+        }
+    }
+
+    pub fn oplog_list(&self, entries: Vec<OpLogEntry>) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => oplog::oplog_list_human(entries, self.table),
+            OutputMode::Json => oplog::oplog_list_json(entries),
+        }
+    }
+
+    pub fn dry_run_plan(
+        &self,
+        operations: Vec<LoggedOperation>,
+        diffs: Vec<PlaylistDiff>,
+    ) -> Result<()> {
+        match self.mode {
+            OutputMode::Human => oplog::dry_run_human(operations, diffs, self.table),
+            OutputMode::Json => oplog::dry_run_json(operations, diffs),
+        }
+    }
+
     pub fn help(&self) -> Result<()> {
         match self.mode {
             OutputMode::Human => human::help(),
@@ -225,3 +648,16 @@ impl Output {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::matching_width;
+
+    #[test]
+    fn matching_width_accepts_translated_header_names() {
+        let widths = vec![("titel".to_string(), 30), ("ARTISTA".to_string(), 20)];
+        assert_eq!(matching_width(&widths, "TRACK"), Some(30));
+        assert_eq!(matching_width(&widths, "ARTIST"), Some(20));
+        assert_eq!(matching_width(&widths, "ALBUM"), None);
+    }
+}
@@ -0,0 +1,299 @@
+//! Operations log output formatting.
+use serde::Serialize;
+
+use crate::domain::oplog::{DiffChange, LoggedOperation, OpLogEntry, PlaylistDiff};
+use crate::error::Result;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::{TableConfig, write_json};
+
+pub fn oplog_list_human(entries: Vec<OpLogEntry>, table: TableConfig) -> Result<()> {
+    if entries.is_empty() {
+        println!("{}", i18n::t("No logged operations"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|entry| {
+            let mut detail = entry.operation.detail();
+            if let Some(undoes) = entry.undoes {
+                if !detail.is_empty() {
+                    detail.push_str("; ");
+                }
+                detail.push_str(&format!("undoes #{undoes}"));
+            }
+            vec![
+                entry.id.to_string(),
+                entry.at.to_string(),
+                entry.operation.label().to_string(),
+                entry.operation.target(),
+                detail,
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["ID", "AT", "OPERATION", "TARGET", "DETAIL"], table);
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct OpLogPayload {
+    id: u64,
+    at: u64,
+    operation: &'static str,
+    target: String,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    undoes: Option<u64>,
+}
+
+describe!(OpLogPayload {
+    id: u64,
+    at: u64,
+    operation: &'static str,
+    target: String,
+    detail: String,
+    #[optional] snapshot_id: Option<String>,
+    #[optional] undoes: Option<u64>,
+});
+
+pub fn oplog_list_json(entries: Vec<OpLogEntry>) -> Result<()> {
+    let payload = oplog_list_payload(entries);
+    write_json(PayloadKind::OpLogList, &payload)
+}
+
+fn oplog_list_payload(entries: Vec<OpLogEntry>) -> Vec<OpLogPayload> {
+    entries
+        .into_iter()
+        .map(|entry| OpLogPayload {
+            id: entry.id,
+            at: entry.at,
+            operation: entry.operation.label(),
+            target: entry.operation.target(),
+            detail: entry.operation.detail(),
+            snapshot_id: entry.snapshot_id,
+            undoes: entry.undoes,
+        })
+        .collect()
+}
+
+pub fn dry_run_human(
+    operations: Vec<LoggedOperation>,
+    diffs: Vec<PlaylistDiff>,
+    table: TableConfig,
+) -> Result<()> {
+    if operations.is_empty() {
+        println!("{}", i18n::t("Dry run: nothing would change"));
+        return Ok(());
+    }
+
+    println!("{}", i18n::t("Dry run: would apply"));
+    let rows: Vec<Vec<String>> = operations
+        .iter()
+        .map(|operation| {
+            vec![
+                operation.label().to_string(),
+                operation.target(),
+                operation.detail(),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["OPERATION", "TARGET", "DETAIL"], table);
+    for diff in diffs {
+        print_diff(diff);
+    }
+    Ok(())
+}
+
+fn print_diff(diff: PlaylistDiff) {
+    let (added, removed, duplicates) = diff_counts(&diff);
+    let name = diff.name.as_deref().unwrap_or(&diff.playlist_id);
+    println!();
+    println!("{name}: +{added} -{removed}");
+    for line in &diff.lines {
+        let label = line.label.as_deref().unwrap_or(&line.uri);
+        let marker = if line.duplicate { "  (duplicate)" } else { "" };
+        println!("{} {}{}", line.change.sign(), label, marker);
+    }
+    if duplicates > 0 {
+        println!("warning: {duplicates} tracks would be duplicated in {name}");
+    }
+}
+
+/// Added, removed, and duplicate-add line counts.
+fn diff_counts(diff: &PlaylistDiff) -> (usize, usize, usize) {
+    let added = diff
+        .lines
+        .iter()
+        .filter(|line| line.change == DiffChange::Added)
+        .count();
+    let duplicates = diff.lines.iter().filter(|line| line.duplicate).count();
+    (added, diff.lines.len() - added, duplicates)
+}
+
+#[derive(Serialize)]
+pub(super) struct DryRunPayload {
+    dry_run: bool,
+    operations: Vec<PlannedPayload>,
+    diffs: Vec<DiffPayload>,
+}
+
+describe!(DryRunPayload {
+    dry_run: bool,
+    operations: Vec<PlannedPayload>,
+    diffs: Vec<DiffPayload>,
+});
+
+#[derive(Serialize)]
+struct DiffPayload {
+    playlist_id: String,
+    name: Option<String>,
+    added: usize,
+    removed: usize,
+    duplicates: usize,
+    lines: Vec<DiffLinePayload>,
+}
+
+describe!(DiffPayload {
+    playlist_id: String,
+    name: Option<String>,
+    added: usize,
+    removed: usize,
+    duplicates: usize,
+    lines: Vec<DiffLinePayload>,
+});
+
+#[derive(Serialize)]
+struct DiffLinePayload {
+    change: &'static str,
+    uri: String,
+    label: Option<String>,
+    duplicate: bool,
+}
+
+describe!(DiffLinePayload {
+    change: &'static str,
+    uri: String,
+    label: Option<String>,
+    duplicate: bool,
+});
+
+#[derive(Serialize)]
+struct PlannedPayload {
+    operation: &'static str,
+    target: String,
+    detail: String,
+}
+
+describe!(PlannedPayload {
+    operation: &'static str,
+    target: String,
+    detail: String,
+});
+
+pub fn dry_run_json(operations: Vec<LoggedOperation>, diffs: Vec<PlaylistDiff>) -> Result<()> {
+    let payload = dry_run_payload(operations, diffs);
+    write_json(PayloadKind::DryRun, &payload)
+}
+
+fn dry_run_payload(operations: Vec<LoggedOperation>, diffs: Vec<PlaylistDiff>) -> DryRunPayload {
+    DryRunPayload {
+        dry_run: true,
+        operations: operations
+            .iter()
+            .map(|operation| PlannedPayload {
+                operation: operation.label(),
+                target: operation.target(),
+                detail: operation.detail(),
+            })
+            .collect(),
+        diffs: diffs.into_iter().map(diff_payload).collect(),
+    }
+}
+
+fn diff_payload(diff: PlaylistDiff) -> DiffPayload {
+    let (added, removed, duplicates) = diff_counts(&diff);
+    DiffPayload {
+        playlist_id: diff.playlist_id,
+        name: diff.name,
+        added,
+        removed,
+        duplicates,
+        lines: diff
+            .lines
+            .into_iter()
+            .map(|line| DiffLinePayload {
+                change: line.change.sign(),
+                uri: line.uri,
+                label: line.label,
+                duplicate: line.duplicate,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dry_run_payload, oplog_list_payload};
+    use crate::domain::oplog::{DiffChange, DiffLine, LoggedOperation, OpLogEntry, PlaylistDiff};
+
+    #[test]
+    fn dry_run_payload_shape() {
+        let payload = dry_run_payload(
+            vec![LoggedOperation::QueueAdd {
+                uri: "spotify:track:1".to_string(),
+            }],
+            Vec::new(),
+        );
+        assert!(payload.dry_run);
+        assert_eq!(payload.operations[0].operation, "queue add");
+        assert_eq!(payload.operations[0].target, "spotify:track:1");
+        assert!(payload.diffs.is_empty());
+    }
+
+    #[test]
+    fn dry_run_payload_counts_diff_lines() {
+        let line = |change, uri: &str, duplicate| DiffLine {
+            change,
+            uri: uri.to_string(),
+            label: None,
+            duplicate,
+        };
+        let payload = dry_run_payload(
+            Vec::new(),
+            vec![PlaylistDiff {
+                playlist_id: "p".to_string(),
+                name: Some("Mix".to_string()),
+                lines: vec![
+                    line(DiffChange::Added, "spotify:track:1", false),
+                    line(DiffChange::Added, "spotify:track:2", true),
+                    line(DiffChange::Removed, "spotify:track:3", false),
+                ],
+            }],
+        );
+        let diff = &payload.diffs[0];
+        assert_eq!((diff.added, diff.removed, diff.duplicates), (2, 1, 1));
+        assert_eq!(diff.lines[2].change, "-");
+    }
+
+    #[test]
+    fn oplog_list_payload_shape() {
+        let payload = oplog_list_payload(vec![OpLogEntry {
+            id: 4,
+            at: 3,
+            operation: LoggedOperation::PlaylistAdd {
+                playlist_id: "p".to_string(),
+                uris: vec!["spotify:track:1".to_string()],
+            },
+            snapshot_id: Some("snap".to_string()),
+            undoes: None,
+        }]);
+        assert_eq!(payload[0].operation, "playlist add");
+        assert_eq!(payload[0].target, "p");
+        assert_eq!(payload[0].detail, "1 tracks");
+        assert_eq!(payload[0].snapshot_id.as_deref(), Some("snap"));
+    }
+}
@@ -0,0 +1,78 @@
+//! Pending operation output formatting.
+use serde::Serialize;
+
+use crate::domain::pending::PendingEntry;
+use crate::error::Result;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::{TableConfig, write_json};
+
+pub fn pending_list_human(entries: Vec<PendingEntry>, table: TableConfig) -> Result<()> {
+    if entries.is_empty() {
+        println!("{}", i18n::t("No pending operations"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            vec![
+                (index + 1).to_string(),
+                entry.operation.label().to_string(),
+                entry.operation.target().to_string(),
+                entry.queued_at.to_string(),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "OPERATION", "TARGET", "QUEUED_AT"], table);
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct PendingPayload {
+    operation: &'static str,
+    target: String,
+    queued_at: u64,
+}
+
+describe!(PendingPayload {
+    operation: &'static str,
+    target: String,
+    queued_at: u64,
+});
+
+pub fn pending_list_json(entries: Vec<PendingEntry>) -> Result<()> {
+    let payload = pending_list_payload(entries);
+    write_json(PayloadKind::PendingList, &payload)
+}
+
+fn pending_list_payload(entries: Vec<PendingEntry>) -> Vec<PendingPayload> {
+    entries
+        .into_iter()
+        .map(|entry| PendingPayload {
+            operation: entry.operation.label(),
+            target: entry.operation.target().to_string(),
+            queued_at: entry.queued_at,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pending_list_payload;
+    use crate::domain::pending::{PendingEntry, PendingOperation};
+
+    #[test]
+    fn pending_list_payload_shape() {
+        let payload = pending_list_payload(vec![PendingEntry {
+            operation: PendingOperation::QueueTrack {
+                uri: "spotify:track:1".to_string(),
+            },
+            queued_at: 3,
+        }]);
+        assert_eq!(payload[0].operation, "queue add");
+        assert_eq!(payload[0].target, "spotify:track:1");
+    }
+}
@@ -2,62 +2,43 @@
 use serde::Serialize;
 
 use crate::domain::pin::PinnedPlaylist;
+use crate::domain::stats::Tally;
 use crate::error::Result;
-use crate::output::human::truncate_cell;
-use crate::output::{DEFAULT_MAX_WIDTH, TableConfig};
+use crate::output::TableConfig;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
 
 pub fn pin_list_human(pins: Vec<PinnedPlaylist>, table: TableConfig) -> Result<()> {
     if pins.is_empty() {
         return Ok(());
     }
 
-    let max_width = table.max_width.unwrap_or(DEFAULT_MAX_WIDTH);
-    let mut names: Vec<String> = pins
-        .iter()
-        .map(|pin| {
-            if table.truncate {
-                truncate_cell(&pin.name, max_width)
-            } else {
-                pin.name.clone()
-            }
-        })
-        .collect();
-    let mut urls: Vec<String> = pins
-        .iter()
-        .map(|pin| {
-            if table.truncate {
-                truncate_cell(&pin.url, max_width)
-            } else {
-                pin.url.clone()
-            }
-        })
+    let rows: Vec<Vec<String>> = pins
+        .into_iter()
+        .map(|pin| vec![pin.name, pin.url, pin.tags.join(", ")])
         .collect();
-
-    let name_width = names
-        .iter()
-        .map(|name| name.len())
-        .max()
-        .unwrap_or(0)
-        .max("NAME".len());
-
-    println!("{:<width$}  URL", "NAME", width = name_width);
-
-    for (name, url) in names.drain(..).zip(urls.drain(..)) {
-        println!("{:<width$}  {}", name, url, width = name_width);
-    }
+    print_table_with_header(&rows, &["NAME", "URL", "TAGS"], table);
     Ok(())
 }
 
 #[derive(Serialize)]
-struct PinPayload {
+pub(super) struct PinPayload {
     name: String,
     url: String,
+    tags: Vec<String>,
 }
 
+describe!(PinPayload {
+    name: String,
+    url: String,
+    tags: Vec<String>,
+});
+
 pub fn pin_list_json(pins: Vec<PinnedPlaylist>) -> Result<()> {
     let payload = pin_list_payload(pins);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::PinList, &payload)
 }
 
 fn pin_list_payload(pins: Vec<PinnedPlaylist>) -> Vec<PinPayload> {
@@ -65,22 +46,75 @@ fn pin_list_payload(pins: Vec<PinnedPlaylist>) -> Vec<PinPayload> {
         .map(|pin| PinPayload {
             name: pin.name,
             url: pin.url,
+            tags: pin.tags,
+        })
+        .collect()
+}
+
+pub fn pin_tags_human(tags: Vec<Tally>, table: TableConfig) -> Result<()> {
+    if tags.is_empty() {
+        println!("{}", i18n::t("No tags"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = tags
+        .into_iter()
+        .map(|tally| vec![tally.name, tally.count.to_string()])
+        .collect();
+    print_table_with_header(&rows, &["TAG", "PINS"], table);
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct PinTagPayload {
+    tag: String,
+    pins: usize,
+}
+
+describe!(PinTagPayload {
+    tag: String,
+    pins: usize,
+});
+
+pub fn pin_tags_json(tags: Vec<Tally>) -> Result<()> {
+    let payload = pin_tags_payload(tags);
+    write_json(PayloadKind::PinTags, &payload)
+}
+
+fn pin_tags_payload(tags: Vec<Tally>) -> Vec<PinTagPayload> {
+    tags.into_iter()
+        .map(|tally| PinTagPayload {
+            tag: tally.name,
+            pins: tally.count,
         })
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::pin_list_payload;
+    use super::{pin_list_payload, pin_tags_payload};
     use crate::domain::pin::PinnedPlaylist;
+    use crate::domain::stats::Tally;
 
     #[test]
     fn pin_list_payload_shape() {
         let payload = pin_list_payload(vec![PinnedPlaylist {
             name: "Release Radar".to_string(),
             url: "url".to_string(),
+            tags: vec!["weekly".to_string()],
         }]);
         assert_eq!(payload.len(), 1);
         assert_eq!(payload[0].name, "Release Radar");
+        assert_eq!(payload[0].tags, vec!["weekly"]);
+    }
+
+    #[test]
+    fn pin_tags_payload_names_the_count() {
+        let payload = pin_tags_payload(vec![Tally {
+            name: "workout".to_string(),
+            count: 3,
+        }]);
+        assert_eq!(payload[0].tag, "workout");
+        assert_eq!(payload[0].pins, 3);
     }
 }
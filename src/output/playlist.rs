@@ -0,0 +1,762 @@
+//! Playlist track and user playlist output formatting.
+use serde::Serialize;
+
+use crate::domain::playlist::{
+    AuditEntry, AuditStatus, MatchField, PlaylistAudit, PlaylistMatch, PlaylistTrackPage,
+    TrackLocation, UserPlaylistPage,
+};
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::{display_owner, format_duration, print_table_with_header};
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn playlist_tracks_human(
+    page: PlaylistTrackPage,
+    offset: u32,
+    table: TableConfig,
+) -> Result<()> {
+    if page.items.is_empty() {
+        println!("{}", i18n::t("No tracks"));
+        return Ok(());
+    }
+
+    let shown = page.items.len();
+    let rows: Vec<Vec<String>> = page
+        .items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let added = item
+                .added_at
+                .as_deref()
+                .and_then(|added| added.get(..10))
+                .unwrap_or_default()
+                .to_string();
+            let track = item.track;
+            vec![
+                (offset as usize + index + 1).to_string(),
+                track.name,
+                track.artists.join(", "),
+                track
+                    .duration_ms
+                    .map(|ms| format_duration(ms as u64))
+                    .unwrap_or_default(),
+                added,
+                item.added_by.unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table_with_header(
+        &rows,
+        &["#", "TRACK", "ARTIST", "DURATION", "ADDED", "BY"],
+        table,
+    );
+    if offset as usize + shown < page.total as usize {
+        println!(
+            "Showing {}-{} of {}; use --offset {} for more",
+            offset + 1,
+            offset as usize + shown,
+            page.total,
+            offset as usize + shown
+        );
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct PlaylistTracksPayload {
+    total: u32,
+    offset: u32,
+    items: Vec<PlaylistTrackPayload>,
+}
+
+describe!(PlaylistTracksPayload {
+    total: u32,
+    offset: u32,
+    items: Vec<PlaylistTrackPayload>,
+});
+
+#[derive(Serialize)]
+struct PlaylistTrackPayload {
+    position: usize,
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    album: Option<String>,
+    duration_ms: Option<u32>,
+    added_at: Option<String>,
+    added_by: Option<String>,
+}
+
+describe!(PlaylistTrackPayload {
+    position: usize,
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    album: Option<String>,
+    duration_ms: Option<u32>,
+    added_at: Option<String>,
+    added_by: Option<String>,
+});
+
+pub fn playlist_tracks_json(page: PlaylistTrackPage, offset: u32) -> Result<()> {
+    let payload = playlist_tracks_payload(page, offset);
+    write_json(PayloadKind::PlaylistTracks, &payload)
+}
+
+fn playlist_tracks_payload(page: PlaylistTrackPage, offset: u32) -> PlaylistTracksPayload {
+    PlaylistTracksPayload {
+        total: page.total,
+        offset,
+        items: page
+            .items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| PlaylistTrackPayload {
+                position: offset as usize + index + 1,
+                uri: format!("spotify:track:{}", item.track.id),
+                id: item.track.id,
+                name: item.track.name,
+                artists: item.track.artists,
+                album: item.track.album,
+                duration_ms: item.track.duration_ms,
+                added_at: item.added_at,
+                added_by: item.added_by,
+            })
+            .collect(),
+    }
+}
+
+pub fn playlist_contains_human(playlist_id: &str, uri: &str, positions: Vec<usize>) -> Result<()> {
+    if positions.is_empty() {
+        println!("No: {uri} is not in playlist {playlist_id}");
+    } else {
+        let positions: Vec<String> = positions.iter().map(|p| p.to_string()).collect();
+        println!(
+            "Yes: {uri} is in playlist {playlist_id} (position {})",
+            positions.join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct PlaylistContainsPayload<'a> {
+    playlist_id: &'a str,
+    uri: &'a str,
+    contains: bool,
+    positions: Vec<usize>,
+}
+
+describe!(PlaylistContainsPayload<'_> {
+    playlist_id: &str,
+    uri: &str,
+    contains: bool,
+    positions: Vec<usize>,
+});
+
+pub fn playlist_contains_json(playlist_id: &str, uri: &str, positions: Vec<usize>) -> Result<()> {
+    write_json(
+        PayloadKind::PlaylistContains,
+        &PlaylistContainsPayload {
+            playlist_id,
+            uri,
+            contains: !positions.is_empty(),
+            positions,
+        },
+    )
+}
+
+pub fn user_playlists_human(
+    page: UserPlaylistPage,
+    next_offset: Option<u32>,
+    table: TableConfig,
+) -> Result<()> {
+    if page.items.is_empty() {
+        println!("{}", i18n::t("No playlists"));
+    } else {
+        let rows: Vec<Vec<String>> = page
+            .items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                vec![
+                    (index + 1).to_string(),
+                    item.playlist.name,
+                    item.playlist.owner.or(item.owner_id).unwrap_or_default(),
+                    item.tracks_total
+                        .map(|total| total.to_string())
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table_with_header(&rows, &["#", "NAME", "OWNER", "TRACKS"], table);
+    }
+    if let Some(next) = next_offset {
+        println!(
+            "{} playlists in total; use --offset {} for more",
+            page.total, next
+        );
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct UserPlaylistsPayload {
+    total: u32,
+    next_offset: Option<u32>,
+    items: Vec<UserPlaylistPayload>,
+}
+
+describe!(UserPlaylistsPayload {
+    total: u32,
+    next_offset: Option<u32>,
+    items: Vec<UserPlaylistPayload>,
+});
+
+#[derive(Serialize)]
+struct UserPlaylistPayload {
+    index: usize,
+    id: String,
+    name: String,
+    uri: String,
+    owner: Option<String>,
+    owner_id: Option<String>,
+    tracks_total: Option<u32>,
+    collaborative: bool,
+    public: Option<bool>,
+}
+
+describe!(UserPlaylistPayload {
+    index: usize,
+    id: String,
+    name: String,
+    uri: String,
+    owner: Option<String>,
+    owner_id: Option<String>,
+    tracks_total: Option<u32>,
+    collaborative: bool,
+    public: Option<bool>,
+});
+
+pub fn user_playlists_json(page: UserPlaylistPage, next_offset: Option<u32>) -> Result<()> {
+    let payload = user_playlists_payload(page, next_offset);
+    write_json(PayloadKind::UserPlaylists, &payload)
+}
+
+fn user_playlists_payload(
+    page: UserPlaylistPage,
+    next_offset: Option<u32>,
+) -> UserPlaylistsPayload {
+    UserPlaylistsPayload {
+        total: page.total,
+        next_offset,
+        items: page
+            .items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| UserPlaylistPayload {
+                index: index + 1,
+                uri: format!("spotify:playlist:{}", item.playlist.id),
+                id: item.playlist.id,
+                name: item.playlist.name,
+                owner: item.playlist.owner,
+                owner_id: item.owner_id,
+                tracks_total: item.tracks_total,
+                collaborative: item.playlist.collaborative,
+                public: item.playlist.public,
+            })
+            .collect(),
+    }
+}
+
+pub fn playlist_matches_human(
+    matches: Vec<PlaylistMatch>,
+    ids_only: bool,
+    user_name: Option<&str>,
+    table: TableConfig,
+) -> Result<()> {
+    if ids_only {
+        for item in matches {
+            println!("{}", item.playlist.id);
+        }
+        return Ok(());
+    }
+    if matches.is_empty() {
+        println!("{}", i18n::t("No playlists"));
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = matches
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            vec![
+                (index + 1).to_string(),
+                item.playlist.name,
+                item.playlist
+                    .owner
+                    .map(|owner| display_owner(&owner, user_name))
+                    .unwrap_or_default(),
+                match_field_label(item.field).to_string(),
+                item.playlist.id,
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "NAME", "OWNER", "MATCH", "ID"], table);
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct PlaylistMatchPayload {
+    id: String,
+    uri: String,
+    name: String,
+    owner: Option<String>,
+    description: Option<String>,
+    matched: &'static str,
+    score: f32,
+}
+
+describe!(PlaylistMatchPayload {
+    id: String,
+    uri: String,
+    name: String,
+    owner: Option<String>,
+    description: Option<String>,
+    matched: &'static str,
+    score: f32,
+});
+
+pub fn playlist_matches_json(matches: Vec<PlaylistMatch>) -> Result<()> {
+    write_json(
+        PayloadKind::PlaylistMatches,
+        &playlist_matches_payload(matches),
+    )
+}
+
+fn playlist_matches_payload(matches: Vec<PlaylistMatch>) -> Vec<PlaylistMatchPayload> {
+    matches
+        .into_iter()
+        .map(|item| PlaylistMatchPayload {
+            uri: format!("spotify:playlist:{}", item.playlist.id),
+            id: item.playlist.id,
+            name: item.playlist.name,
+            owner: item.playlist.owner,
+            description: item.playlist.description,
+            matched: match_field_label(item.field),
+            score: item.score,
+        })
+        .collect()
+}
+
+pub fn playlist_audit_human(audit: PlaylistAudit, table: TableConfig) -> Result<()> {
+    let unavailable = audit
+        .entries
+        .iter()
+        .filter(|entry| matches!(entry.status, AuditStatus::Unavailable { .. }))
+        .count();
+    println!(
+        "{}: {} tracks checked, {} unavailable, {} relinked",
+        audit.name,
+        audit.checked,
+        unavailable,
+        audit.entries.len() - unavailable
+    );
+    if audit.entries.is_empty() {
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = audit.entries.iter().map(audit_row).collect();
+    print_table_with_header(&rows, &["#", "TRACK", "ARTIST", "STATUS", "DETAIL"], table);
+    Ok(())
+}
+
+fn audit_row(entry: &AuditEntry) -> Vec<String> {
+    let (status, detail) = match &entry.status {
+        AuditStatus::Relinked { from } => ("relinked", format!("saved as {from}")),
+        AuditStatus::Unavailable {
+            reason,
+            replacement,
+            replaced,
+        } => {
+            let mut parts = Vec::new();
+            if let Some(reason) = reason {
+                parts.push(format!("restricted: {reason}"));
+            }
+            match replacement {
+                Some(uri) if *replaced => parts.push(format!("replaced with {uri}")),
+                Some(uri) => parts.push(format!("same recording: {uri}")),
+                None => parts.push("no playable version found".to_string()),
+            }
+            ("unavailable", parts.join("; "))
+        }
+    };
+    vec![
+        entry.position.to_string(),
+        entry.track.name.clone(),
+        entry.track.artists.join(", "),
+        status.to_string(),
+        detail,
+    ]
+}
+
+#[derive(Serialize)]
+pub(super) struct PlaylistAuditPayload {
+    playlist_id: String,
+    name: String,
+    checked: usize,
+    entries: Vec<AuditEntryPayload>,
+}
+
+describe!(PlaylistAuditPayload {
+    playlist_id: String,
+    name: String,
+    checked: usize,
+    entries: Vec<AuditEntryPayload>,
+});
+
+#[derive(Serialize)]
+pub(super) struct AuditEntryPayload {
+    position: usize,
+    uri: String,
+    name: String,
+    artists: Vec<String>,
+    status: &'static str,
+    relinked_from: Option<String>,
+    reason: Option<String>,
+    replacement: Option<String>,
+    replaced: bool,
+}
+
+describe!(AuditEntryPayload {
+    position: usize,
+    uri: String,
+    name: String,
+    artists: Vec<String>,
+    status: &'static str,
+    relinked_from: Option<String>,
+    reason: Option<String>,
+    replacement: Option<String>,
+    replaced: bool,
+});
+
+pub fn playlist_audit_json(audit: PlaylistAudit) -> Result<()> {
+    let payload = playlist_audit_payload(audit);
+    write_json(PayloadKind::PlaylistAudit, &payload)
+}
+
+fn playlist_audit_payload(audit: PlaylistAudit) -> PlaylistAuditPayload {
+    PlaylistAuditPayload {
+        playlist_id: audit.playlist_id,
+        name: audit.name,
+        checked: audit.checked,
+        entries: audit
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let (status, relinked_from, reason, replacement, replaced) = match entry.status {
+                    AuditStatus::Relinked { from } => ("relinked", Some(from), None, None, false),
+                    AuditStatus::Unavailable {
+                        reason,
+                        replacement,
+                        replaced,
+                    } => ("unavailable", None, reason, replacement, replaced),
+                };
+                AuditEntryPayload {
+                    position: entry.position,
+                    uri: entry.uri,
+                    name: entry.track.name,
+                    artists: entry.track.artists,
+                    status,
+                    relinked_from,
+                    reason,
+                    replacement,
+                    replaced,
+                }
+            })
+            .collect(),
+    }
+}
+
+pub fn track_location_human(
+    location: TrackLocation,
+    user_name: Option<&str>,
+    table: TableConfig,
+) -> Result<()> {
+    println!("{}", location.uri);
+    if location.liked {
+        println!("{}", i18n::t("In Liked Songs"));
+    } else {
+        println!("{}", i18n::t("Not in Liked Songs"));
+    }
+    if location.playlists.is_empty() {
+        println!("{}", i18n::t("No playlists"));
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = location
+        .playlists
+        .into_iter()
+        .enumerate()
+        .map(|(index, hit)| {
+            let positions: Vec<String> = hit.positions.iter().map(|p| p.to_string()).collect();
+            vec![
+                (index + 1).to_string(),
+                hit.playlist.name,
+                hit.playlist
+                    .owner
+                    .map(|owner| display_owner(&owner, user_name))
+                    .unwrap_or_default(),
+                positions.join(", "),
+                hit.playlist.id,
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "PLAYLIST", "OWNER", "POSITION", "ID"], table);
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct TrackLocationPayload {
+    uri: String,
+    liked: bool,
+    playlists: Vec<PlaylistHitPayload>,
+    skipped: usize,
+}
+
+describe!(TrackLocationPayload {
+    uri: String,
+    liked: bool,
+    playlists: Vec<PlaylistHitPayload>,
+    skipped: usize,
+});
+
+#[derive(Serialize)]
+struct PlaylistHitPayload {
+    id: String,
+    uri: String,
+    name: String,
+    owner: Option<String>,
+    positions: Vec<usize>,
+}
+
+describe!(PlaylistHitPayload {
+    id: String,
+    uri: String,
+    name: String,
+    owner: Option<String>,
+    positions: Vec<usize>,
+});
+
+pub fn track_location_json(location: TrackLocation) -> Result<()> {
+    write_json(
+        PayloadKind::TrackLocation,
+        &track_location_payload(location),
+    )
+}
+
+fn track_location_payload(location: TrackLocation) -> TrackLocationPayload {
+    TrackLocationPayload {
+        uri: location.uri,
+        liked: location.liked,
+        playlists: location
+            .playlists
+            .into_iter()
+            .map(|hit| PlaylistHitPayload {
+                uri: format!("spotify:playlist:{}", hit.playlist.id),
+                id: hit.playlist.id,
+                name: hit.playlist.name,
+                owner: hit.playlist.owner,
+                positions: hit.positions,
+            })
+            .collect(),
+        skipped: location.skipped,
+    }
+}
+
+fn match_field_label(field: MatchField) -> &'static str {
+    match field {
+        MatchField::Name => "name",
+        MatchField::Description => "description",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        audit_row, playlist_audit_payload, playlist_matches_payload, playlist_tracks_payload,
+        track_location_payload, user_playlists_payload,
+    };
+    use crate::domain::playlist::{
+        AuditEntry, AuditStatus, MatchField, Playlist, PlaylistAudit, PlaylistHit, PlaylistMatch,
+        PlaylistTrack, PlaylistTrackPage, TrackLocation, UserPlaylist, UserPlaylistPage,
+    };
+    use crate::domain::track::Track;
+
+    #[test]
+    fn playlist_tracks_payload_numbers_from_offset() {
+        let payload = playlist_tracks_payload(
+            PlaylistTrackPage {
+                items: vec![PlaylistTrack {
+                    track: Track {
+                        id: "1".to_string(),
+                        name: "Roygbiv".to_string(),
+                        artists: vec!["Boards of Canada".to_string()],
+                        artist_ids: Vec::new(),
+                        album: None,
+                        album_id: None,
+                        duration_ms: None,
+                    },
+                    added_at: Some("2024-01-01T00:00:00Z".to_string()),
+                    added_by: Some("alice".to_string()),
+                    popularity: None,
+                    explicit: None,
+                    release_date: None,
+                }],
+                total: 120,
+            },
+            50,
+        );
+        assert_eq!(payload.total, 120);
+        assert_eq!(payload.items[0].position, 51);
+        assert_eq!(payload.items[0].uri, "spotify:track:1");
+        assert_eq!(payload.items[0].added_by.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn user_playlists_payload_indexes_the_listing() {
+        let payload = user_playlists_payload(
+            UserPlaylistPage {
+                items: vec![UserPlaylist {
+                    playlist: Playlist {
+                        id: "pl1".to_string(),
+                        name: "Road Trip".to_string(),
+                        owner: Some("Alice".to_string()),
+                        collaborative: false,
+                        public: Some(true),
+                        description: None,
+                    },
+                    owner_id: Some("alice".to_string()),
+                    tracks_total: Some(42),
+                }],
+                total: 75,
+            },
+            Some(20),
+        );
+        assert_eq!(payload.total, 75);
+        assert_eq!(payload.next_offset, Some(20));
+        assert_eq!(payload.items[0].index, 1);
+        assert_eq!(payload.items[0].uri, "spotify:playlist:pl1");
+        assert_eq!(payload.items[0].tracks_total, Some(42));
+    }
+
+    #[test]
+    fn playlist_matches_payload_reports_the_matched_field() {
+        let payload = playlist_matches_payload(vec![PlaylistMatch {
+            playlist: Playlist {
+                id: "p1".to_string(),
+                name: "Morning".to_string(),
+                owner: Some("alice".to_string()),
+                collaborative: false,
+                public: None,
+                description: Some("deep focus".to_string()),
+            },
+            score: 0.72,
+            field: MatchField::Description,
+        }]);
+        assert_eq!(payload[0].uri, "spotify:playlist:p1");
+        assert_eq!(payload[0].matched, "description");
+        assert_eq!(payload[0].description.as_deref(), Some("deep focus"));
+    }
+
+    #[test]
+    fn track_location_payload_lists_positions() {
+        let payload = track_location_payload(TrackLocation {
+            uri: "spotify:track:t1".to_string(),
+            liked: true,
+            playlists: vec![PlaylistHit {
+                playlist: Playlist {
+                    id: "p1".to_string(),
+                    name: "Morning".to_string(),
+                    owner: Some("alice".to_string()),
+                    collaborative: false,
+                    public: None,
+                    description: None,
+                },
+                positions: vec![2, 9],
+            }],
+            skipped: 1,
+        });
+        assert!(payload.liked);
+        assert_eq!(payload.playlists[0].uri, "spotify:playlist:p1");
+        assert_eq!(payload.playlists[0].positions, vec![2, 9]);
+        assert_eq!(payload.skipped, 1);
+    }
+
+    fn audited(position: usize, status: AuditStatus) -> AuditEntry {
+        AuditEntry {
+            position,
+            uri: format!("spotify:track:t{position}"),
+            track: Track {
+                id: format!("t{position}"),
+                name: "Roygbiv".to_string(),
+                artists: vec!["Boards of Canada".to_string()],
+                artist_ids: Vec::new(),
+                album: None,
+                album_id: None,
+                duration_ms: None,
+            },
+            status,
+        }
+    }
+
+    #[test]
+    fn playlist_audit_payload_flattens_statuses() {
+        let payload = playlist_audit_payload(PlaylistAudit {
+            playlist_id: "pl1".to_string(),
+            name: "Mix".to_string(),
+            checked: 40,
+            entries: vec![
+                audited(
+                    3,
+                    AuditStatus::Relinked {
+                        from: "spotify:track:old".to_string(),
+                    },
+                ),
+                audited(
+                    7,
+                    AuditStatus::Unavailable {
+                        reason: Some("market".to_string()),
+                        replacement: None,
+                        replaced: false,
+                    },
+                ),
+            ],
+        });
+        assert_eq!(payload.checked, 40);
+        assert_eq!(payload.entries[0].status, "relinked");
+        assert_eq!(
+            payload.entries[0].relinked_from.as_deref(),
+            Some("spotify:track:old")
+        );
+        assert_eq!(payload.entries[1].status, "unavailable");
+        assert_eq!(payload.entries[1].reason.as_deref(), Some("market"));
+        assert_eq!(payload.entries[1].replacement, None);
+    }
+
+    #[test]
+    fn audit_row_explains_missing_replacements() {
+        let row = audit_row(&audited(
+            7,
+            AuditStatus::Unavailable {
+                reason: Some("market".to_string()),
+                replacement: None,
+                replaced: false,
+            },
+        ));
+        assert_eq!(row[3], "unavailable");
+        assert_eq!(row[4], "restricted: market; no playable version found");
+    }
+}
@@ -0,0 +1,117 @@
+//! Progress reporting for long-running bulk operations.
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+const BAR_WIDTH: usize = 24;
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Progress line drawn on stderr; a no-op when disabled.
+#[derive(Debug)]
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    done: u64,
+    started: Instant,
+    last_draw: Option<Instant>,
+    enabled: bool,
+}
+
+impl Progress {
+    pub fn new(label: &str, total: Option<u64>, enabled: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            total,
+            done: 0,
+            started: Instant::now(),
+            last_draw: None,
+            enabled,
+        }
+    }
+
+    pub fn inc(&mut self, amount: u64) {
+        self.update(self.done + amount, None);
+    }
+
+    /// Record progress from a paginated fetch; a known total replaces the current one.
+    pub fn update(&mut self, done: u64, total: Option<u64>) {
+        self.done = done;
+        if total.is_some() {
+            self.total = total;
+        }
+        if !self.enabled {
+            return;
+        }
+        let now = Instant::now();
+        if self
+            .last_draw
+            .is_some_and(|last| now.duration_since(last) < REDRAW_INTERVAL)
+        {
+            return;
+        }
+        self.last_draw = Some(now);
+        let line = render(&self.label, self.done, self.total, self.started.elapsed());
+        let mut stderr = std::io::stderr();
+        let _ = write!(stderr, "\r\x1b[2K{line}");
+        let _ = stderr.flush();
+    }
+
+    /// Clear the progress line.
+    pub fn finish(self) {
+        drop(self);
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.enabled && self.last_draw.is_some() {
+            let mut stderr = std::io::stderr();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+}
+
+fn render(label: &str, done: u64, total: Option<u64>, elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    let rate = if secs > 0.0 { done as f64 / secs } else { 0.0 };
+    let Some(total) = total.filter(|total| *total > 0) else {
+        return format!("{label} {done} ({rate:.0}/s)");
+    };
+    let done = done.min(total);
+    let filled = (done as usize * BAR_WIDTH) / total as usize;
+    let bar = format!("{}{}", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled));
+    let eta = if rate > 0.0 {
+        format_eta(((total - done) as f64 / rate).round() as u64)
+    } else {
+        "--:--".to_string()
+    };
+    format!(
+        "{label} [{bar}] {done}/{total} {}% ({rate:.0}/s, ETA {eta})",
+        done * 100 / total
+    )
+}
+
+fn format_eta(secs: u64) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use std::time::Duration;
+
+    #[test]
+    fn render_shows_bar_rate_and_eta() {
+        let line = render("Fetching", 50, Some(100), Duration::from_secs(10));
+        assert_eq!(
+            line,
+            "Fetching [############------------] 50/100 50% (5/s, ETA 0:10)"
+        );
+    }
+
+    #[test]
+    fn render_without_total_shows_count() {
+        let line = render("Fetching", 30, None, Duration::from_secs(3));
+        assert_eq!(line, "Fetching 30 (10/s)");
+    }
+}
@@ -0,0 +1,97 @@
+//! Field projection for `--json --fields` output.
+use serde_json::{Map, Value};
+
+/// Keep only the requested dotted field paths, mapping over arrays.
+pub fn project(value: Value, fields: &[String]) -> Value {
+    let paths: Vec<Vec<&str>> = fields
+        .iter()
+        .map(|field| field.trim())
+        .filter(|field| !field.is_empty())
+        .map(|field| field.split('.').collect())
+        .collect();
+    if paths.is_empty() {
+        return value;
+    }
+    project_paths(value, &paths).unwrap_or(Value::Null)
+}
+
+fn project_paths(value: Value, paths: &[Vec<&str>]) -> Option<Value> {
+    if paths.iter().any(|path| path.is_empty()) {
+        return Some(value);
+    }
+
+    match value {
+        Value::Array(items) => Some(Value::Array(
+            items
+                .into_iter()
+                .filter_map(|item| project_paths(item, paths))
+                .collect(),
+        )),
+        Value::Object(mut object) => {
+            let mut projected = Map::new();
+            for path in paths {
+                let key = path[0];
+                if projected.contains_key(key) {
+                    continue;
+                }
+                let Some(child) = object.remove(key) else {
+                    continue;
+                };
+                let rest: Vec<Vec<&str>> = paths
+                    .iter()
+                    .filter(|other| other[0] == key)
+                    .map(|other| other[1..].to_vec())
+                    .collect();
+                if let Some(child) = project_paths(child, &rest) {
+                    projected.insert(key.to_string(), child);
+                }
+            }
+            Some(Value::Object(projected))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::project;
+    use serde_json::json;
+
+    fn fields(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn project_keeps_top_level_fields() {
+        let value = json!({ "id": "1", "name": "A", "uri": "u" });
+        let projected = project(value, &fields(&["id", "name"]));
+        assert_eq!(projected, json!({ "id": "1", "name": "A" }));
+    }
+
+    #[test]
+    fn project_maps_over_arrays() {
+        let value = json!([{ "id": "1", "name": "A" }, { "id": "2", "name": "B" }]);
+        let projected = project(value, &fields(&["name"]));
+        assert_eq!(projected, json!([{ "name": "A" }, { "name": "B" }]));
+    }
+
+    #[test]
+    fn project_follows_dotted_paths() {
+        let value = json!({
+            "kind": "track",
+            "items": [{ "id": "1", "album": { "id": "a", "name": "Album" } }]
+        });
+        let projected = project(value, &fields(&["items.id", "items.album.name"]));
+        assert_eq!(
+            projected,
+            json!({ "items": [{ "id": "1", "album": { "name": "Album" } }] })
+        );
+    }
+
+    #[test]
+    fn project_skips_missing_fields() {
+        let value = json!({ "id": "1" });
+        let projected = project(value, &fields(&["id", "missing", "id.deeper"]));
+        assert_eq!(projected, json!({ "id": "1" }));
+    }
+}
@@ -0,0 +1,186 @@
+//! Release output formatting.
+use serde::Serialize;
+
+use crate::domain::artist::Artist;
+use crate::domain::release::{Release, ReleaseGroup};
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn release_list_human(releases: Vec<Release>, table: TableConfig) -> Result<()> {
+    if releases.is_empty() {
+        println!("{}", i18n::t("No new releases"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = releases
+        .into_iter()
+        .enumerate()
+        .map(|(index, release)| {
+            vec![
+                (index + 1).to_string(),
+                release.name,
+                release.artists.join(", "),
+                release.album_type.unwrap_or_default(),
+                release.release_date.unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "RELEASE", "ARTIST", "TYPE", "DATE"], table);
+    Ok(())
+}
+
+pub fn artist_releases_human(
+    artist: Artist,
+    groups: Vec<ReleaseGroup>,
+    table: TableConfig,
+) -> Result<()> {
+    println!("{}", artist.name);
+    if groups.is_empty() {
+        println!("{}", i18n::t("No releases"));
+        return Ok(());
+    }
+    for group in groups {
+        println!();
+        println!("{} ({})", group_label(&group.group), group.releases.len());
+        let rows: Vec<Vec<String>> = group
+            .releases
+            .into_iter()
+            .enumerate()
+            .map(|(index, release)| {
+                vec![
+                    (index + 1).to_string(),
+                    release.name,
+                    release.artists.join(", "),
+                    release.release_date.unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table_with_header(&rows, &["#", "RELEASE", "ARTIST", "DATE"], table);
+    }
+    Ok(())
+}
+
+fn group_label(group: &str) -> &str {
+    match group {
+        "album" => "Albums",
+        "single" => "Singles",
+        "compilation" => "Compilations",
+        "appears_on" => "Appears on",
+        other => other,
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct ArtistReleasesPayload {
+    id: String,
+    name: String,
+    uri: String,
+    groups: Vec<ReleaseGroupPayload>,
+}
+
+describe!(ArtistReleasesPayload {
+    id: String,
+    name: String,
+    uri: String,
+    groups: Vec<ReleaseGroupPayload>,
+});
+
+#[derive(Serialize)]
+struct ReleaseGroupPayload {
+    group: String,
+    releases: Vec<ReleasePayload>,
+}
+
+describe!(ReleaseGroupPayload {
+    group: String,
+    releases: Vec<ReleasePayload>,
+});
+
+pub fn artist_releases_json(artist: Artist, groups: Vec<ReleaseGroup>) -> Result<()> {
+    write_json(
+        PayloadKind::ArtistReleases,
+        &artist_releases_payload(artist, groups),
+    )
+}
+
+fn artist_releases_payload(artist: Artist, groups: Vec<ReleaseGroup>) -> ArtistReleasesPayload {
+    ArtistReleasesPayload {
+        id: artist.id,
+        name: artist.name,
+        uri: artist.uri,
+        groups: groups
+            .into_iter()
+            .map(|group| ReleaseGroupPayload {
+                group: group.group,
+                releases: release_list_payload(group.releases),
+            })
+            .collect(),
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct ReleasePayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    album_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    album_group: Option<String>,
+    release_date: Option<String>,
+}
+
+describe!(ReleasePayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    album_type: Option<String>,
+    #[optional] album_group: Option<String>,
+    release_date: Option<String>,
+});
+
+pub fn release_list_json(releases: Vec<Release>) -> Result<()> {
+    let payload = release_list_payload(releases);
+    write_json(PayloadKind::ReleaseList, &payload)
+}
+
+fn release_list_payload(releases: Vec<Release>) -> Vec<ReleasePayload> {
+    releases
+        .into_iter()
+        .map(|release| ReleasePayload {
+            id: release.id,
+            name: release.name,
+            uri: release.uri,
+            artists: release.artists,
+            album_type: release.album_type,
+            album_group: release.album_group,
+            release_date: release.release_date,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::release_list_payload;
+    use crate::domain::release::Release;
+
+    #[test]
+    fn release_list_payload_shape() {
+        let payload = release_list_payload(vec![Release {
+            id: "1".to_string(),
+            name: "New Album".to_string(),
+            uri: "spotify:album:1".to_string(),
+            artists: vec!["Artist".to_string()],
+            album_type: Some("album".to_string()),
+            album_group: None,
+            release_date: Some("2024-05-01".to_string()),
+        }]);
+        assert_eq!(payload.len(), 1);
+        assert_eq!(payload[0].release_date.as_deref(), Some("2024-05-01"));
+    }
+}
@@ -0,0 +1,271 @@
+//! Versioned envelope and JSON Schema for `--json` payloads.
+//!
+//! With `--json-envelope` every payload is printed as
+//! `{"schema_version": N, "kind": "<kind>", "data": <payload>}`. Adding a field
+//! keeps the version; removing, renaming, or retyping one bumps it.
+use serde_json::{Map, Value, json};
+
+use crate::domain::artist::FollowerTrend;
+use crate::domain::stats::{GenreShare, Tally};
+use crate::error::Result;
+use crate::output::{
+    album, block, cache, context, cuesheet, episode, follow, json, library, market, oplog, pending,
+    pin, playlist, release, session, settings, show, stats,
+};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// JSON Schema of a serialized payload type.
+pub(crate) trait Describe {
+    fn schema() -> Value;
+}
+
+macro_rules! describe_scalar {
+    ($($ty:ty => $name:literal),* $(,)?) => {
+        $(impl Describe for $ty {
+            fn schema() -> Value {
+                json!({ "type": $name })
+            }
+        })*
+    };
+}
+
+describe_scalar!(
+    str => "string",
+    String => "string",
+    bool => "boolean",
+    u16 => "integer",
+    u32 => "integer",
+    u64 => "integer",
+    usize => "integer",
+    i32 => "integer",
+    i64 => "integer",
+    f32 => "number",
+    f64 => "number",
+);
+
+impl<T: Describe + ?Sized> Describe for &T {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: Describe> Describe for Option<T> {
+    fn schema() -> Value {
+        json!({ "anyOf": [T::schema(), { "type": "null" }] })
+    }
+}
+
+impl<T: Describe> Describe for Vec<T> {
+    fn schema() -> Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+}
+
+/// Object schema; fields not in `required` may be left out of the payload.
+pub(crate) fn object(properties: Vec<(&str, Value)>, required: Vec<&str>) -> Value {
+    let properties: Map<String, Value> = properties
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect();
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// Implement [`Describe`] for a payload struct from its field list.
+///
+/// Mark fields serialized with `skip_serializing_if` as `#[optional]`. The field
+/// list is checked against the struct, so it cannot drift silently.
+macro_rules! describe {
+    ($name:ident $(<$lt:lifetime>)? { $($(#[$optional:ident])? $field:ident: $ty:ty),* $(,)? }) => {
+        impl $crate::output::schema::Describe for $name $(<$lt>)? {
+            fn schema() -> serde_json::Value {
+                let _ = |payload: &$name $(<$lt>)?| {
+                    let $name { $($field),* } = payload;
+                    $(let _: &$ty = $field;)*
+                };
+                let mut required = Vec::new();
+                $(if !$crate::output::schema::describe!(@optional $($optional)?) {
+                    required.push(stringify!($field));
+                })*
+                $crate::output::schema::object(
+                    vec![$((
+                        stringify!($field),
+                        <$ty as $crate::output::schema::Describe>::schema(),
+                    )),*],
+                    required,
+                )
+            }
+        }
+    };
+    (@optional optional) => { true };
+    (@optional) => { false };
+}
+pub(crate) use describe;
+
+describe!(FollowerTrend {
+    since: u64,
+    from: u64,
+    change: i64,
+});
+describe!(GenreShare {
+    genre: String,
+    percent: f64,
+    artists: usize,
+});
+describe!(Tally {
+    name: String,
+    count: usize,
+});
+
+macro_rules! payload_kinds {
+    ($($variant:ident => $name:literal: $ty:ty),* $(,)?) => {
+        /// Every payload shape `--json` can print, named by the envelope's `kind`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum PayloadKind {
+            $($variant),*
+        }
+
+        impl PayloadKind {
+            pub const ALL: &'static [PayloadKind] = &[$(PayloadKind::$variant),*];
+
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(PayloadKind::$variant => $name),*
+                }
+            }
+
+            /// JSON Schema of the payload, which is the envelope's `data`.
+            pub fn schema(self) -> Value {
+                match self {
+                    $(PayloadKind::$variant => <$ty as Describe>::schema()),*
+                }
+            }
+        }
+    };
+}
+
+payload_kinds! {
+    Action => "action": json::ActionPayload<'_>,
+    AlbumInfo => "album_info": json::AlbumPayload,
+    ArtistInfo => "artist_info": json::ArtistPayload,
+    ArtistReleases => "artist_releases": release::ArtistReleasesPayload,
+    AudiobookInfo => "audiobook_info": show::AudiobookPayload,
+    AuthScopes => "auth_scopes": json::AuthScopesPayload,
+    AuthStatus => "auth_status": json::AuthStatusPayload,
+    BlockList => "block_list": Vec<block::BlockPayload>,
+    CacheStatus => "cache_status": cache::CacheStatusPayload,
+    Cuesheet => "cuesheet": cuesheet::CuesheetPayload,
+    DeviceList => "device_list": Vec<json::DevicePayload>,
+    DryRun => "dry_run": oplog::DryRunPayload,
+    EpisodeInfo => "episode_info": episode::EpisodePayload,
+    EpisodeList => "episode_list": Vec<episode::EpisodePayload>,
+    Error => "error": json::ErrorPayload,
+    FollowedArtists => "followed_artists": follow::FollowedArtistsPayload,
+    Help => "help": json::HelpPayload,
+    ListeningStats => "listening_stats": stats::StatsPayload,
+    MarketAvailability => "market_availability": market::MarketAvailabilityPayload,
+    MarketList => "market_list": Vec<market::MarketPayload>,
+    NowPlaying => "now_playing": json::NowPlayingPayload,
+    OpLogList => "oplog_list": Vec<oplog::OpLogPayload>,
+    PendingList => "pending_list": Vec<pending::PendingPayload>,
+    PinList => "pin_list": Vec<pin::PinPayload>,
+    PinTags => "pin_tags": Vec<pin::PinTagPayload>,
+    PlayerProgress => "player_progress": json::ProgressPayload,
+    PlayerStatus => "player_status": json::PlayerStatusPayload,
+    PlaylistAudit => "playlist_audit": playlist::PlaylistAuditPayload,
+    PlaylistContains => "playlist_contains": playlist::PlaylistContainsPayload<'_>,
+    PlaylistInfo => "playlist_info": json::PlaylistDetailPayload,
+    PlaylistList => "playlist_list": Vec<json::PlaylistPayload>,
+    PlaylistListWithPins => "playlist_list_with_pins": json::PlaylistListPayload,
+    PlaylistMatches => "playlist_matches": Vec<playlist::PlaylistMatchPayload>,
+    PlaylistStats => "playlist_stats": stats::PlaylistStatsPayload,
+    PlaylistTracks => "playlist_tracks": playlist::PlaylistTracksPayload,
+    Queue => "queue": json::QueuePayload,
+    RecentContexts => "recent_contexts": Vec<context::RecentContextPayload>,
+    RecentlyPlayed => "recently_played": json::SearchResultsPayload,
+    ReleaseList => "release_list": Vec<release::ReleasePayload>,
+    SavedAlbumList => "saved_album_list": Vec<album::SavedAlbumPayload>,
+    SavedAudiobookList => "saved_audiobook_list": Vec<show::SavedAudiobookPayload>,
+    SavedEpisodeList => "saved_episode_list": Vec<episode::SavedEpisodePayload>,
+    SavedShowList => "saved_show_list": Vec<show::SavedShowPayload>,
+    SavedTrackList => "saved_track_list": Vec<library::SavedTrackPayload>,
+    SearchResults => "search_results": json::SearchResultsPayload,
+    SessionReport => "session_report": session::SessionPayload,
+    Settings => "settings": settings::SettingsPayload,
+    ShowInfo => "show_info": show::ShowPayload,
+    TopGenres => "top_genres": Vec<GenreShare>,
+    TrackLocation => "track_location": playlist::TrackLocationPayload,
+    UserPlaylists => "user_playlists": playlist::UserPlaylistsPayload,
+}
+
+/// Wrap a payload in the versioned envelope.
+pub fn envelope(kind: PayloadKind, data: Value) -> Value {
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "kind": kind.as_str(),
+        "data": data,
+    })
+}
+
+/// One JSON Schema document covering the envelope of every payload kind.
+pub fn document() -> Value {
+    let mut defs = Map::new();
+    let mut variants = Vec::new();
+    for kind in PayloadKind::ALL {
+        defs.insert(kind.as_str().to_string(), kind.schema());
+        variants.push(json!({
+            "type": "object",
+            "properties": {
+                "schema_version": { "const": SCHEMA_VERSION },
+                "kind": { "const": kind.as_str() },
+                "data": { "$ref": format!("#/$defs/{}", kind.as_str()) },
+            },
+            "required": ["schema_version", "kind", "data"],
+        }));
+    }
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "spotify-cli --json-envelope output",
+        "schema_version": SCHEMA_VERSION,
+        "oneOf": variants,
+        "$defs": defs,
+    })
+}
+
+pub fn print_document() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&document())?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PayloadKind, SCHEMA_VERSION, document, envelope};
+    use serde_json::json;
+
+    #[test]
+    fn envelope_wraps_payload_with_version_and_kind() {
+        let value = envelope(PayloadKind::Action, json!({ "event": "x" }));
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["kind"], "action");
+        assert_eq!(value["data"]["event"], "x");
+    }
+
+    #[test]
+    fn schemas_mark_skipped_fields_optional() {
+        let schema = PayloadKind::SearchResults.schema();
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&json!("items")));
+        assert!(!required.contains(&json!("totals")));
+        let item = &schema["properties"]["items"]["items"];
+        assert_eq!(item["properties"]["name"]["type"], "string");
+        assert_eq!(item["properties"]["album"]["anyOf"][1]["type"], "null");
+    }
+
+    #[test]
+    fn document_defines_every_kind() {
+        let doc = document();
+        let defs = doc["$defs"].as_object().unwrap();
+        assert_eq!(defs.len(), PayloadKind::ALL.len());
+        assert_eq!(doc["oneOf"].as_array().unwrap().len(), defs.len());
+    }
+}
@@ -0,0 +1,163 @@
+//! Listening session output formatting.
+use serde::Serialize;
+
+use crate::domain::session::SessionReport;
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::{format_duration, print_table_with_header};
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn session_report_human(report: SessionReport, table: TableConfig) -> Result<()> {
+    let stopped_at = report.stopped_at.as_deref().unwrap_or("now (running)");
+    println!("Session: {} - {}", report.started_at, stopped_at);
+    println!(
+        "Tracks: {}, time {}",
+        report.tracks.len(),
+        format_duration(report.total_ms)
+    );
+    if !report.top_artists.is_empty() {
+        let artists: Vec<String> = report
+            .top_artists
+            .iter()
+            .map(|artist| format!("{} ({})", artist.name, artist.plays))
+            .collect();
+        println!("Top artists: {}", artists.join(", "));
+    }
+    if report.truncated {
+        println!("Spotify returned its page limit; earlier plays in the session may be missing");
+    }
+    if report.tracks.is_empty() {
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = report
+        .tracks
+        .into_iter()
+        .enumerate()
+        .map(|(index, play)| {
+            let played_at = play.played_at.get(11..16).unwrap_or_default().to_string();
+            vec![
+                (index + 1).to_string(),
+                play.item.name,
+                play.item.artists.join(", "),
+                played_at,
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "TRACK", "ARTIST", "PLAYED"], table);
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub(super) struct SessionPayload {
+    started_at: String,
+    stopped_at: Option<String>,
+    running: bool,
+    track_count: usize,
+    total_ms: u64,
+    truncated: bool,
+    top_artists: Vec<ArtistCountPayload>,
+    tracks: Vec<PlayedTrackPayload>,
+}
+
+describe!(SessionPayload {
+    started_at: String,
+    stopped_at: Option<String>,
+    running: bool,
+    track_count: usize,
+    total_ms: u64,
+    truncated: bool,
+    top_artists: Vec<ArtistCountPayload>,
+    tracks: Vec<PlayedTrackPayload>,
+});
+
+#[derive(Serialize)]
+struct ArtistCountPayload {
+    name: String,
+    plays: usize,
+}
+
+describe!(ArtistCountPayload {
+    name: String,
+    plays: usize,
+});
+
+#[derive(Serialize)]
+struct PlayedTrackPayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    duration_ms: Option<u32>,
+    played_at: String,
+}
+
+describe!(PlayedTrackPayload {
+    id: String,
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+    duration_ms: Option<u32>,
+    played_at: String,
+});
+
+pub fn session_report_json(report: SessionReport) -> Result<()> {
+    let payload = session_report_payload(report);
+    write_json(PayloadKind::SessionReport, &payload)
+}
+
+fn session_report_payload(report: SessionReport) -> SessionPayload {
+    SessionPayload {
+        running: report.stopped_at.is_none(),
+        started_at: report.started_at,
+        stopped_at: report.stopped_at,
+        track_count: report.tracks.len(),
+        total_ms: report.total_ms,
+        truncated: report.truncated,
+        top_artists: report
+            .top_artists
+            .into_iter()
+            .map(|artist| ArtistCountPayload {
+                name: artist.name,
+                plays: artist.plays,
+            })
+            .collect(),
+        tracks: report
+            .tracks
+            .into_iter()
+            .map(|play| PlayedTrackPayload {
+                id: play.item.id,
+                name: play.item.name,
+                uri: play.item.uri,
+                artists: play.item.artists,
+                duration_ms: play.item.duration_ms,
+                played_at: play.played_at,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::session_report_payload;
+    use crate::domain::session::{ArtistCount, SessionReport};
+
+    #[test]
+    fn session_report_payload_marks_running_sessions() {
+        let payload = session_report_payload(SessionReport {
+            started_at: "2024-01-01T10:00:00".to_string(),
+            stopped_at: None,
+            tracks: Vec::new(),
+            total_ms: 0,
+            top_artists: vec![ArtistCount {
+                name: "Artist".to_string(),
+                plays: 2,
+            }],
+            truncated: false,
+        });
+        assert!(payload.running);
+        assert_eq!(payload.track_count, 0);
+        assert_eq!(payload.top_artists[0].plays, 2);
+    }
+}
@@ -3,6 +3,8 @@ use serde::Serialize;
 
 use crate::domain::settings::Settings;
 use crate::error::Result;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
 
 pub fn settings_human(settings: Settings) -> Result<()> {
     if let Some(country) = settings.country {
@@ -11,40 +13,209 @@ pub fn settings_human(settings: Settings) -> Result<()> {
     if let Some(user_name) = settings.user_name {
         println!("user_name={}", user_name);
     }
+    if let Some(market) = settings.default_market {
+        println!("default_market={}", market);
+    }
+    if let Some(max_volume) = settings.max_volume {
+        println!("max_volume={}", max_volume);
+    }
+    if let Some(timeout) = settings.timeout_secs {
+        println!("timeout_secs={}", timeout);
+    }
+    if let Some(timeout) = settings.connect_timeout_secs {
+        println!("connect_timeout_secs={}", timeout);
+    }
+    if let Some(proxy) = settings.proxy {
+        println!("proxy={}", proxy);
+    }
+    if let Some(ca_bundle) = settings.ca_bundle {
+        println!("ca_bundle={}", ca_bundle);
+    }
+    if let Some(language) = settings.language {
+        println!("language={}", language);
+    }
+    if let Some(no_explicit) = settings.no_explicit {
+        println!("no_explicit={}", no_explicit);
+    }
+    if let Some(open_in) = settings.open_in {
+        println!("open_in={}", open_in);
+    }
+    if let Some(log_level) = settings.log_level {
+        println!("log_level={}", log_level);
+    }
+    if let Some(log_max_kb) = settings.log_max_kb {
+        println!("log_max_kb={}", log_max_kb);
+    }
+    if let Some(plays) = settings.auto_like_plays {
+        println!("auto_like_plays={}", plays);
+    }
+    for entry in settings.device_volumes {
+        println!("device_volume.{}={}", entry.device, entry.volume);
+    }
+    for alias in settings.aliases {
+        println!("alias.{}={}", alias.name, alias.expansion);
+    }
+    for hook in settings.hooks {
+        println!("hook.{}={}", hook.event, hook.command);
+    }
     Ok(())
 }
 
 #[derive(Serialize)]
-struct SettingsPayload {
+pub(super) struct SettingsPayload {
     country: Option<String>,
     user_name: Option<String>,
+    default_market: Option<String>,
+    max_volume: Option<u32>,
+    device_volumes: Vec<DeviceVolumePayload>,
+    timeout_secs: Option<u64>,
+    connect_timeout_secs: Option<u64>,
+    proxy: Option<String>,
+    ca_bundle: Option<String>,
+    language: Option<String>,
+    no_explicit: Option<bool>,
+    open_in: Option<String>,
+    log_level: Option<String>,
+    log_max_kb: Option<u64>,
+    auto_like_plays: Option<u32>,
+    aliases: Vec<AliasPayload>,
+    hooks: Vec<HookPayload>,
 }
 
+describe!(SettingsPayload {
+    country: Option<String>,
+    user_name: Option<String>,
+    default_market: Option<String>,
+    max_volume: Option<u32>,
+    device_volumes: Vec<DeviceVolumePayload>,
+    timeout_secs: Option<u64>,
+    connect_timeout_secs: Option<u64>,
+    proxy: Option<String>,
+    ca_bundle: Option<String>,
+    language: Option<String>,
+    no_explicit: Option<bool>,
+    open_in: Option<String>,
+    log_level: Option<String>,
+    log_max_kb: Option<u64>,
+    auto_like_plays: Option<u32>,
+    aliases: Vec<AliasPayload>,
+    hooks: Vec<HookPayload>,
+});
+
+#[derive(Serialize)]
+struct HookPayload {
+    event: String,
+    command: String,
+}
+
+describe!(HookPayload {
+    event: String,
+    command: String,
+});
+
+#[derive(Serialize)]
+struct AliasPayload {
+    name: String,
+    expansion: String,
+}
+
+describe!(AliasPayload {
+    name: String,
+    expansion: String,
+});
+
+#[derive(Serialize)]
+struct DeviceVolumePayload {
+    device: String,
+    volume: u32,
+}
+
+describe!(DeviceVolumePayload {
+    device: String,
+    volume: u32,
+});
+
 pub fn settings_json(settings: Settings) -> Result<()> {
     let payload = settings_payload(settings);
-    println!("{}", serde_json::to_string(&payload)?);
-    Ok(())
+    write_json(PayloadKind::Settings, &payload)
 }
 
 fn settings_payload(settings: Settings) -> SettingsPayload {
     SettingsPayload {
         country: settings.country,
         user_name: settings.user_name,
+        default_market: settings.default_market,
+        max_volume: settings.max_volume,
+        device_volumes: settings
+            .device_volumes
+            .into_iter()
+            .map(|entry| DeviceVolumePayload {
+                device: entry.device,
+                volume: entry.volume,
+            })
+            .collect(),
+        timeout_secs: settings.timeout_secs,
+        connect_timeout_secs: settings.connect_timeout_secs,
+        proxy: settings.proxy,
+        ca_bundle: settings.ca_bundle,
+        language: settings.language,
+        no_explicit: settings.no_explicit,
+        open_in: settings.open_in,
+        log_level: settings.log_level,
+        log_max_kb: settings.log_max_kb,
+        auto_like_plays: settings.auto_like_plays,
+        aliases: settings
+            .aliases
+            .into_iter()
+            .map(|alias| AliasPayload {
+                name: alias.name,
+                expansion: alias.expansion,
+            })
+            .collect(),
+        hooks: settings
+            .hooks
+            .into_iter()
+            .map(|hook| HookPayload {
+                event: hook.event,
+                command: hook.command,
+            })
+            .collect(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::settings_payload;
-    use crate::domain::settings::Settings;
+    use crate::domain::settings::{DeviceVolume, Settings};
 
     #[test]
     fn settings_payload_shape() {
         let payload = settings_payload(Settings {
             country: Some("AU".to_string()),
             user_name: None,
+            default_market: None,
+            max_volume: Some(70),
+            device_volumes: vec![DeviceVolume {
+                device: "Kitchen".to_string(),
+                volume: 30,
+            }],
+            timeout_secs: Some(10),
+            connect_timeout_secs: None,
+            proxy: None,
+            ca_bundle: None,
+            language: None,
+            no_explicit: None,
+            open_in: None,
+            log_level: None,
+            log_max_kb: None,
+            auto_like_plays: None,
+            aliases: Vec::new(),
+            hooks: Vec::new(),
         });
         assert_eq!(payload.country.as_deref(), Some("AU"));
         assert!(payload.user_name.is_none());
+        assert_eq!(payload.max_volume, Some(70));
+        assert_eq!(payload.device_volumes[0].volume, 30);
+        assert_eq!(payload.timeout_secs, Some(10));
     }
 }
@@ -0,0 +1,366 @@
+//! Show and audiobook output formatting.
+use serde::Serialize;
+
+use crate::domain::audiobook::{Audiobook, SavedAudiobook};
+use crate::domain::show::{SavedShow, Show};
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::csv;
+use crate::output::human::print_table_with_header;
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn show_info_human(show: Show) -> Result<()> {
+    match show.publisher.as_deref() {
+        Some(publisher) => println!("{} ({})", show.name, publisher),
+        None => println!("{}", show.name),
+    }
+    if let Some(total) = show.total_episodes {
+        println!("episodes: {total}");
+    }
+    if let Some(description) = show.description.as_deref().filter(|text| !text.is_empty()) {
+        println!("{description}");
+    }
+    Ok(())
+}
+
+pub fn audiobook_info_human(audiobook: Audiobook) -> Result<()> {
+    if audiobook.authors.is_empty() {
+        println!("{}", audiobook.name);
+    } else {
+        println!("{} - {}", audiobook.name, audiobook.authors.join(", "));
+    }
+    if !audiobook.narrators.is_empty() {
+        println!("narrated by: {}", audiobook.narrators.join(", "));
+    }
+    if let Some(publisher) = audiobook.publisher.as_deref() {
+        println!("publisher: {publisher}");
+    }
+    if let Some(total) = audiobook.total_chapters {
+        println!("chapters: {total}");
+    }
+    if let Some(description) = audiobook
+        .description
+        .as_deref()
+        .filter(|text| !text.is_empty())
+    {
+        println!("{description}");
+    }
+    Ok(())
+}
+
+pub fn saved_show_list_human(shows: Vec<SavedShow>, table: TableConfig) -> Result<()> {
+    if shows.is_empty() {
+        println!("{}", i18n::t("No saved shows"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = shows
+        .into_iter()
+        .enumerate()
+        .map(|(index, saved)| {
+            vec![
+                (index + 1).to_string(),
+                saved.show.name,
+                saved.show.publisher.unwrap_or_default(),
+                saved
+                    .show
+                    .total_episodes
+                    .map(|total| total.to_string())
+                    .unwrap_or_default(),
+                added_date(saved.added_at.as_deref()),
+            ]
+        })
+        .collect();
+    print_table_with_header(
+        &rows,
+        &["#", "SHOW", "PUBLISHER", "EPISODES", "ADDED"],
+        table,
+    );
+    Ok(())
+}
+
+pub fn saved_audiobook_list_human(
+    audiobooks: Vec<SavedAudiobook>,
+    table: TableConfig,
+) -> Result<()> {
+    if audiobooks.is_empty() {
+        println!("{}", i18n::t("No saved audiobooks"));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = audiobooks
+        .into_iter()
+        .enumerate()
+        .map(|(index, saved)| {
+            vec![
+                (index + 1).to_string(),
+                saved.audiobook.name,
+                saved.audiobook.authors.join(", "),
+                added_date(saved.added_at.as_deref()),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "AUDIOBOOK", "AUTHOR", "ADDED"], table);
+    Ok(())
+}
+
+/// Day part of an ISO 8601 timestamp.
+pub(super) fn added_date(added_at: Option<&str>) -> String {
+    added_at
+        .and_then(|added| added.get(..10))
+        .unwrap_or_default()
+        .to_string()
+}
+
+pub fn saved_show_list_csv(shows: Vec<SavedShow>) -> Result<()> {
+    let rows: Vec<Vec<String>> = saved_show_list_payload(shows)
+        .into_iter()
+        .map(|show| {
+            vec![
+                show.id,
+                show.name,
+                show.publisher.unwrap_or_default(),
+                show.total_episodes
+                    .map(|total| total.to_string())
+                    .unwrap_or_default(),
+                show.added_at.unwrap_or_default(),
+                show.uri,
+            ]
+        })
+        .collect();
+    csv::print_rows(
+        &[
+            "id",
+            "name",
+            "publisher",
+            "total_episodes",
+            "added_at",
+            "uri",
+        ],
+        &rows,
+    )
+}
+
+pub fn saved_audiobook_list_csv(audiobooks: Vec<SavedAudiobook>) -> Result<()> {
+    let rows: Vec<Vec<String>> = saved_audiobook_list_payload(audiobooks)
+        .into_iter()
+        .map(|audiobook| {
+            vec![
+                audiobook.id,
+                audiobook.name,
+                audiobook.authors.join("; "),
+                audiobook.narrators.join("; "),
+                audiobook.publisher.unwrap_or_default(),
+                audiobook.added_at.unwrap_or_default(),
+                audiobook.uri,
+            ]
+        })
+        .collect();
+    csv::print_rows(
+        &[
+            "id",
+            "name",
+            "authors",
+            "narrators",
+            "publisher",
+            "added_at",
+            "uri",
+        ],
+        &rows,
+    )
+}
+
+#[derive(Serialize)]
+pub(super) struct ShowPayload {
+    id: String,
+    name: String,
+    uri: String,
+    publisher: Option<String>,
+    description: Option<String>,
+    total_episodes: Option<u32>,
+}
+
+describe!(ShowPayload {
+    id: String,
+    name: String,
+    uri: String,
+    publisher: Option<String>,
+    description: Option<String>,
+    total_episodes: Option<u32>,
+});
+
+#[derive(Serialize)]
+pub(super) struct AudiobookPayload {
+    id: String,
+    name: String,
+    uri: String,
+    authors: Vec<String>,
+    narrators: Vec<String>,
+    publisher: Option<String>,
+    description: Option<String>,
+    total_chapters: Option<u32>,
+}
+
+describe!(AudiobookPayload {
+    id: String,
+    name: String,
+    uri: String,
+    authors: Vec<String>,
+    narrators: Vec<String>,
+    publisher: Option<String>,
+    description: Option<String>,
+    total_chapters: Option<u32>,
+});
+
+#[derive(Serialize)]
+pub(super) struct SavedShowPayload {
+    id: String,
+    name: String,
+    uri: String,
+    publisher: Option<String>,
+    total_episodes: Option<u32>,
+    added_at: Option<String>,
+}
+
+describe!(SavedShowPayload {
+    id: String,
+    name: String,
+    uri: String,
+    publisher: Option<String>,
+    total_episodes: Option<u32>,
+    added_at: Option<String>,
+});
+
+#[derive(Serialize)]
+pub(super) struct SavedAudiobookPayload {
+    id: String,
+    name: String,
+    uri: String,
+    authors: Vec<String>,
+    narrators: Vec<String>,
+    publisher: Option<String>,
+    added_at: Option<String>,
+}
+
+describe!(SavedAudiobookPayload {
+    id: String,
+    name: String,
+    uri: String,
+    authors: Vec<String>,
+    narrators: Vec<String>,
+    publisher: Option<String>,
+    added_at: Option<String>,
+});
+
+pub fn show_info_json(show: Show) -> Result<()> {
+    write_json(
+        PayloadKind::ShowInfo,
+        &ShowPayload {
+            id: show.id,
+            name: show.name,
+            uri: show.uri,
+            publisher: show.publisher,
+            description: show.description,
+            total_episodes: show.total_episodes,
+        },
+    )
+}
+
+pub fn audiobook_info_json(audiobook: Audiobook) -> Result<()> {
+    write_json(PayloadKind::AudiobookInfo, &audiobook_payload(audiobook))
+}
+
+pub fn saved_show_list_json(shows: Vec<SavedShow>) -> Result<()> {
+    write_json(PayloadKind::SavedShowList, &saved_show_list_payload(shows))
+}
+
+pub fn saved_audiobook_list_json(audiobooks: Vec<SavedAudiobook>) -> Result<()> {
+    write_json(
+        PayloadKind::SavedAudiobookList,
+        &saved_audiobook_list_payload(audiobooks),
+    )
+}
+
+fn saved_show_list_payload(shows: Vec<SavedShow>) -> Vec<SavedShowPayload> {
+    shows
+        .into_iter()
+        .map(|saved| SavedShowPayload {
+            id: saved.show.id,
+            name: saved.show.name,
+            uri: saved.show.uri,
+            publisher: saved.show.publisher,
+            total_episodes: saved.show.total_episodes,
+            added_at: saved.added_at,
+        })
+        .collect()
+}
+
+fn saved_audiobook_list_payload(audiobooks: Vec<SavedAudiobook>) -> Vec<SavedAudiobookPayload> {
+    audiobooks
+        .into_iter()
+        .map(|saved| SavedAudiobookPayload {
+            id: saved.audiobook.id,
+            name: saved.audiobook.name,
+            uri: saved.audiobook.uri,
+            authors: saved.audiobook.authors,
+            narrators: saved.audiobook.narrators,
+            publisher: saved.audiobook.publisher,
+            added_at: saved.added_at,
+        })
+        .collect()
+}
+
+fn audiobook_payload(audiobook: Audiobook) -> AudiobookPayload {
+    AudiobookPayload {
+        id: audiobook.id,
+        name: audiobook.name,
+        uri: audiobook.uri,
+        authors: audiobook.authors,
+        narrators: audiobook.narrators,
+        publisher: audiobook.publisher,
+        description: audiobook.description,
+        total_chapters: audiobook.total_chapters,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{audiobook_payload, saved_show_list_payload};
+    use crate::domain::audiobook::Audiobook;
+    use crate::domain::show::{SavedShow, Show};
+
+    #[test]
+    fn audiobook_payload_shape() {
+        let payload = audiobook_payload(Audiobook {
+            id: "b1".to_string(),
+            name: "Book".to_string(),
+            uri: "spotify:audiobook:b1".to_string(),
+            authors: vec!["Author".to_string()],
+            narrators: vec!["Narrator".to_string()],
+            publisher: None,
+            description: None,
+            total_chapters: Some(12),
+        });
+        assert_eq!(payload.authors, vec!["Author".to_string()]);
+        assert_eq!(payload.total_chapters, Some(12));
+    }
+
+    #[test]
+    fn saved_show_list_payload_keeps_added_at() {
+        let payload = saved_show_list_payload(vec![SavedShow {
+            show: Show {
+                id: "s1".to_string(),
+                name: "Show".to_string(),
+                uri: "spotify:show:s1".to_string(),
+                publisher: Some("Publisher".to_string()),
+                description: Some("Long text".to_string()),
+                total_episodes: Some(120),
+            },
+            added_at: Some("2024-01-01T00:00:00Z".to_string()),
+        }]);
+        assert_eq!(payload[0].total_episodes, Some(120));
+        assert_eq!(payload[0].added_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+    }
+}
@@ -0,0 +1,307 @@
+//! Listening stats output formatting.
+use serde::Serialize;
+
+use crate::domain::search::SearchItem;
+use crate::domain::stats::{GenreShare, ListeningStats, PlaylistStats, Tally};
+use crate::error::Result;
+use crate::output::TableConfig;
+use crate::output::human::{mark_explicit, print_table_with_header};
+use crate::output::i18n;
+use crate::output::schema::{PayloadKind, describe};
+use crate::output::write_json;
+
+pub fn listening_stats_human(stats: ListeningStats) -> Result<()> {
+    println!("Followed artists: {}", stats.followed_artists);
+    for range in stats.ranges {
+        println!();
+        println!("Top tracks ({})", range_label(&range.range));
+        print_items(&range.tracks);
+        println!("Top artists ({})", range_label(&range.range));
+        print_items(&range.artists);
+    }
+    println!();
+    println!("{}", i18n::t("Recently played"));
+    print_items(&stats.recently_played);
+    Ok(())
+}
+
+fn print_items(items: &[SearchItem]) {
+    if items.is_empty() {
+        println!("  (none)");
+        return;
+    }
+    for (index, item) in items.iter().enumerate() {
+        let name = mark_explicit(item.name.clone(), item.explicit);
+        if item.artists.is_empty() {
+            println!("  {}. {}", index + 1, name);
+        } else {
+            println!("  {}. {} - {}", index + 1, name, item.artists.join(", "));
+        }
+    }
+}
+
+fn range_label(range: &str) -> &str {
+    match range {
+        "short_term" => "4 weeks",
+        "medium_term" => "6 months",
+        "long_term" => "all time",
+        other => other,
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct StatsPayload {
+    followed_artists: usize,
+    ranges: Vec<RangePayload>,
+    recently_played: Vec<ItemPayload>,
+}
+
+describe!(StatsPayload {
+    followed_artists: usize,
+    ranges: Vec<RangePayload>,
+    recently_played: Vec<ItemPayload>,
+});
+
+#[derive(Serialize)]
+struct RangePayload {
+    range: String,
+    tracks: Vec<ItemPayload>,
+    artists: Vec<ItemPayload>,
+}
+
+describe!(RangePayload {
+    range: String,
+    tracks: Vec<ItemPayload>,
+    artists: Vec<ItemPayload>,
+});
+
+#[derive(Serialize)]
+struct ItemPayload {
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+}
+
+describe!(ItemPayload {
+    name: String,
+    uri: String,
+    artists: Vec<String>,
+});
+
+pub fn listening_stats_json(stats: ListeningStats) -> Result<()> {
+    let payload = listening_stats_payload(stats);
+    write_json(PayloadKind::ListeningStats, &payload)
+}
+
+fn listening_stats_payload(stats: ListeningStats) -> StatsPayload {
+    StatsPayload {
+        followed_artists: stats.followed_artists,
+        ranges: stats
+            .ranges
+            .into_iter()
+            .map(|range| RangePayload {
+                range: range.range,
+                tracks: range.tracks.into_iter().map(item_payload).collect(),
+                artists: range.artists.into_iter().map(item_payload).collect(),
+            })
+            .collect(),
+        recently_played: stats
+            .recently_played
+            .into_iter()
+            .map(item_payload)
+            .collect(),
+    }
+}
+
+fn item_payload(item: SearchItem) -> ItemPayload {
+    ItemPayload {
+        name: item.name,
+        uri: item.uri,
+        artists: item.artists,
+    }
+}
+
+pub fn playlist_stats_human(stats: PlaylistStats, table: TableConfig) -> Result<()> {
+    println!(
+        "{} ({} tracks, {})",
+        stats.name,
+        stats.tracks,
+        format_total(stats.duration_ms)
+    );
+    if let Some(popularity) = stats.average_popularity {
+        println!("Average popularity: {popularity:.1}");
+    }
+    println!(
+        "Explicit: {} of {} ({:.0}%)",
+        stats.explicit,
+        stats.tracks,
+        explicit_ratio(stats.explicit, stats.tracks) * 100.0
+    );
+    print_tallies(&stats.artists, "ARTIST", table);
+    print_tallies(&stats.decades, "DECADE", table);
+    print_tallies(&stats.genres, "GENRE", table);
+    Ok(())
+}
+
+pub fn top_genres_human(genres: Vec<GenreShare>, table: TableConfig) -> Result<()> {
+    if genres.is_empty() {
+        println!("{}", i18n::t("No genres"));
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = genres
+        .into_iter()
+        .enumerate()
+        .map(|(index, share)| {
+            vec![
+                (index + 1).to_string(),
+                share.genre,
+                format!("{:.1}%", share.percent),
+                share.artists.to_string(),
+            ]
+        })
+        .collect();
+    print_table_with_header(&rows, &["#", "GENRE", "SHARE", "ARTISTS"], table);
+    Ok(())
+}
+
+pub fn top_genres_json(genres: Vec<GenreShare>) -> Result<()> {
+    write_json(PayloadKind::TopGenres, &genres)
+}
+
+fn print_tallies(tallies: &[Tally], header: &str, table: TableConfig) {
+    if tallies.is_empty() {
+        return;
+    }
+    println!();
+    let rows: Vec<Vec<String>> = tallies
+        .iter()
+        .map(|tally| vec![tally.name.clone(), tally.count.to_string()])
+        .collect();
+    print_table_with_header(&rows, &[header, "TRACKS"], table);
+}
+
+/// Playlist length as `2h 05m`, or `42m` under an hour.
+fn format_total(ms: u64) -> String {
+    let minutes = ms / 60_000;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+fn explicit_ratio(explicit: usize, tracks: usize) -> f32 {
+    if tracks == 0 {
+        0.0
+    } else {
+        explicit as f32 / tracks as f32
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct PlaylistStatsPayload {
+    id: String,
+    name: String,
+    tracks: usize,
+    duration_ms: u64,
+    average_popularity: Option<f32>,
+    explicit: usize,
+    explicit_ratio: f32,
+    artists: Vec<Tally>,
+    decades: Vec<Tally>,
+    genres: Vec<Tally>,
+}
+
+describe!(PlaylistStatsPayload {
+    id: String,
+    name: String,
+    tracks: usize,
+    duration_ms: u64,
+    average_popularity: Option<f32>,
+    explicit: usize,
+    explicit_ratio: f32,
+    artists: Vec<Tally>,
+    decades: Vec<Tally>,
+    genres: Vec<Tally>,
+});
+
+pub fn playlist_stats_json(stats: PlaylistStats) -> Result<()> {
+    write_json(PayloadKind::PlaylistStats, &playlist_stats_payload(stats))
+}
+
+fn playlist_stats_payload(stats: PlaylistStats) -> PlaylistStatsPayload {
+    PlaylistStatsPayload {
+        explicit_ratio: explicit_ratio(stats.explicit, stats.tracks),
+        id: stats.id,
+        name: stats.name,
+        tracks: stats.tracks,
+        duration_ms: stats.duration_ms,
+        average_popularity: stats.average_popularity,
+        explicit: stats.explicit,
+        artists: stats.artists,
+        decades: stats.decades,
+        genres: stats.genres,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_total, listening_stats_payload, playlist_stats_payload};
+    use crate::domain::search::{SearchItem, SearchType};
+    use crate::domain::stats::{ListeningStats, PlaylistStats, RangeTop};
+
+    #[test]
+    fn listening_stats_payload_shape() {
+        let track = SearchItem {
+            id: "t1".to_string(),
+            name: "Track".to_string(),
+            uri: "spotify:track:t1".to_string(),
+            kind: SearchType::Track,
+            artists: vec!["Artist".to_string()],
+            album: None,
+            duration_ms: None,
+            owner: None,
+            score: None,
+            playable: None,
+            linked_from: None,
+            saved: None,
+            explicit: None,
+            popularity: None,
+        };
+        let payload = listening_stats_payload(ListeningStats {
+            ranges: vec![RangeTop {
+                range: "short_term".to_string(),
+                tracks: vec![track.clone()],
+                artists: Vec::new(),
+            }],
+            recently_played: vec![track],
+            followed_artists: 3,
+        });
+        assert_eq!(payload.followed_artists, 3);
+        assert_eq!(payload.ranges[0].range, "short_term");
+        assert_eq!(payload.recently_played[0].uri, "spotify:track:t1");
+    }
+
+    #[test]
+    fn format_total_switches_to_hours() {
+        assert_eq!(format_total(42 * 60_000 + 30_000), "42m");
+        assert_eq!(format_total(125 * 60_000), "2h 05m");
+    }
+
+    #[test]
+    fn playlist_stats_payload_adds_explicit_ratio() {
+        let payload = playlist_stats_payload(PlaylistStats {
+            id: "p1".to_string(),
+            name: "Mix".to_string(),
+            tracks: 4,
+            duration_ms: 0,
+            artists: Vec::new(),
+            decades: Vec::new(),
+            average_popularity: None,
+            explicit: 1,
+            genres: Vec::new(),
+        });
+        assert_eq!(payload.explicit_ratio, 0.25);
+        assert_eq!(payload.name, "Mix");
+    }
+}
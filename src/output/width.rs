@@ -0,0 +1,166 @@
+//! Terminal display width for table cells.
+//!
+//! East Asian wide/fullwidth characters and most emoji take two columns; combining marks,
+//! zero-width joiners, and variation selectors take none.
+
+/// Columns a character occupies in a terminal.
+pub fn char_width(c: char) -> usize {
+    let code = c as u32;
+    if is_zero_width(code) {
+        0
+    } else if is_wide(code) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Columns a string occupies in a terminal.
+pub fn str_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Truncate to at most `max` columns, ending with `...` when shortened.
+pub fn truncate(text: &str, max: usize) -> String {
+    if str_width(text) <= max {
+        return text.to_string();
+    }
+    if max <= 3 {
+        return "...".to_string();
+    }
+    let budget = max - 3;
+    let mut used = 0;
+    let mut truncated = String::new();
+    for c in text.chars() {
+        let width = char_width(c);
+        if used + width > budget {
+            break;
+        }
+        used += width;
+        truncated.push(c);
+    }
+    truncated.push_str("...");
+    truncated
+}
+
+/// Left-align `text` in a field `width` columns wide.
+pub fn pad(text: &str, width: usize) -> String {
+    let mut padded = text.to_string();
+    padded.extend(std::iter::repeat_n(
+        ' ',
+        width.saturating_sub(str_width(text)),
+    ));
+    padded
+}
+
+fn is_zero_width(code: u32) -> bool {
+    matches!(
+        code,
+        0x0300..=0x036F
+            | 0x0483..=0x0489
+            | 0x0591..=0x05BD
+            | 0x0610..=0x061A
+            | 0x064B..=0x065F
+            | 0x0E31
+            | 0x0E34..=0x0E3A
+            | 0x0E47..=0x0E4E
+            | 0x1AB0..=0x1AFF
+            | 0x1DC0..=0x1DFF
+            | 0x200B..=0x200F
+            | 0x20D0..=0x20FF
+            | 0xFE00..=0xFE0F
+            | 0xFE20..=0xFE2F
+            | 0xFEFF
+            | 0xE0100..=0xE01EF
+    )
+}
+
+fn is_wide(code: u32) -> bool {
+    matches!(
+        code,
+        0x1100..=0x115F
+            | 0x231A..=0x231B
+            | 0x23E9..=0x23EC
+            | 0x23F0
+            | 0x23F3
+            | 0x25FD..=0x25FE
+            | 0x2614..=0x2615
+            | 0x2648..=0x2653
+            | 0x267F
+            | 0x2693
+            | 0x26A1
+            | 0x26AA..=0x26AB
+            | 0x26BD..=0x26BE
+            | 0x26C4..=0x26C5
+            | 0x26CE
+            | 0x26D4
+            | 0x26EA
+            | 0x26F2..=0x26F3
+            | 0x26F5
+            | 0x26FA
+            | 0x26FD
+            | 0x2705
+            | 0x270A..=0x270B
+            | 0x2728
+            | 0x274C
+            | 0x274E
+            | 0x2753..=0x2755
+            | 0x2757
+            | 0x2795..=0x2797
+            | 0x27B0
+            | 0x27BF
+            | 0x2B1B..=0x2B1C
+            | 0x2B50
+            | 0x2B55
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xA960..=0xA97F
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE10..=0xFE19
+            | 0xFE30..=0xFE6F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x1F004
+            | 0x1F0CF
+            | 0x1F18E
+            | 0x1F191..=0x1F19A
+            | 0x1F200..=0x1F251
+            | 0x1F300..=0x1F64F
+            | 0x1F680..=0x1F6FF
+            | 0x1F7E0..=0x1F7EB
+            | 0x1F90C..=0x1F9FF
+            | 0x1FA70..=0x1FAFF
+            | 0x20000..=0x2FFFD
+            | 0x30000..=0x3FFFD
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pad, str_width, truncate};
+
+    #[test]
+    fn str_width_counts_wide_and_zero_width() {
+        assert_eq!(str_width("abc"), 3);
+        assert_eq!(str_width("東京"), 4);
+        assert_eq!(str_width("🎵"), 2);
+        assert_eq!(str_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn truncate_respects_columns() {
+        assert_eq!(truncate("0123456789", 8), "01234...");
+        assert_eq!(truncate("東京事変の歌", 8), "東京...");
+        assert_eq!(truncate("東京", 4), "東京");
+    }
+
+    #[test]
+    fn pad_fills_to_display_width() {
+        assert_eq!(pad("東京", 6), "東京  ");
+        assert_eq!(pad("abc", 2), "abc");
+    }
+}
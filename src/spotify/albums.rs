@@ -2,11 +2,13 @@ use anyhow::bail;
 use reqwest::blocking::Client as HttpClient;
 use serde::Deserialize;
 
-use crate::domain::album::{Album, AlbumTrack};
+use crate::domain::album::{Album, AlbumTrack, SavedAlbum};
 use crate::error::Result;
 use crate::spotify::auth::AuthService;
-use crate::spotify::base::api_base;
+use crate::spotify::base::{api_base, push_market};
+use crate::spotify::cancel;
 use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
 
 /// Spotify album API client.
 #[derive(Debug, Clone)]
@@ -22,14 +24,15 @@ impl AlbumsClient {
 
     pub fn get(&self, album_id: &str) -> Result<Album> {
         let token = self.auth.token()?;
-        let url = format!("{}/albums/{album_id}", api_base());
+        let mut url = format!("{}/albums/{album_id}", api_base());
+        push_market(&mut url, false);
 
         let access_token = token.access_token.clone();
         let response = self
             .http
             .get(url)
             .bearer_auth(access_token.clone())
-            .send()?;
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -63,12 +66,68 @@ impl AlbumsClient {
         })
     }
 
+    pub fn saved(&self) -> Result<Vec<SavedAlbum>> {
+        let token = self.auth.token()?;
+        let mut albums = Vec::new();
+        let mut url = format!("{}/me/albums?limit=50", api_base());
+
+        loop {
+            cancel::check()?;
+            let response = self
+                .http
+                .get(&url)
+                .bearer_auth(token.access_token.clone())
+                .send_traced()?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+                bail!(format_api_error(
+                    "spotify saved albums failed",
+                    status,
+                    &body
+                ));
+            }
+
+            let payload: SavedAlbumsResponse = response.json()?;
+            albums.extend(payload.items.into_iter().map(|item| {
+                SavedAlbum {
+                    id: item.album.id,
+                    name: item.album.name,
+                    uri: item.album.uri,
+                    artists: item
+                        .album
+                        .artists
+                        .into_iter()
+                        .map(|artist| artist.name)
+                        .collect(),
+                    release_date: item.album.release_date,
+                    added_at: item.added_at,
+                }
+            }));
+
+            if let Some(next) = payload.next {
+                url = next;
+            } else {
+                break;
+            }
+        }
+
+        Ok(albums)
+    }
+
     fn fetch_tracks(&self, album_id: &str, access_token: &str) -> Result<Vec<AlbumTrack>> {
         let mut tracks = Vec::new();
         let mut url = format!("{}/albums/{album_id}/tracks?limit=50", api_base());
+        push_market(&mut url, false);
 
         loop {
-            let response = self.http.get(&url).bearer_auth(access_token).send()?;
+            cancel::check()?;
+            let response = self
+                .http
+                .get(&url)
+                .bearer_auth(access_token)
+                .send_traced()?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -83,8 +142,10 @@ impl AlbumsClient {
             let payload: AlbumTracksResponse = response.json()?;
             tracks.extend(payload.items.into_iter().map(|item| AlbumTrack {
                 name: item.name,
+                uri: item.uri,
                 duration_ms: item.duration_ms,
                 track_number: item.track_number,
+                saved: None,
             }));
 
             if let Some(next) = payload.next {
@@ -108,6 +169,18 @@ struct SpotifyAlbum {
     artists: Vec<SpotifyArtistRef>,
 }
 
+#[derive(Debug, Deserialize)]
+struct SavedAlbumsResponse {
+    items: Vec<SavedAlbumItem>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedAlbumItem {
+    added_at: Option<String>,
+    album: SpotifyAlbum,
+}
+
 #[derive(Debug, Deserialize)]
 struct SpotifyArtistRef {
     name: String,
@@ -122,6 +195,8 @@ struct AlbumTracksResponse {
 #[derive(Debug, Deserialize)]
 struct SpotifyAlbumTrack {
     name: String,
+    #[serde(default)]
+    uri: String,
     duration_ms: u32,
     track_number: u32,
 }
@@ -1,12 +1,17 @@
-use anyhow::bail;
+use anyhow::{Context, bail};
+use reqwest::Method;
 use reqwest::blocking::Client as HttpClient;
 use serde::Deserialize;
 
-use crate::domain::artist::Artist;
+use crate::domain::artist::{Artist, FollowedArtistPage};
+use crate::domain::release::Release;
 use crate::error::Result;
 use crate::spotify::auth::AuthService;
 use crate::spotify::base::api_base;
+use crate::spotify::cancel;
+use crate::spotify::concurrent::{MAX_IN_FLIGHT, fetch_all};
 use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
 
 /// Spotify artist API client.
 #[derive(Debug, Clone)]
@@ -24,7 +29,11 @@ impl ArtistsClient {
         let token = self.auth.token()?;
         let url = format!("{}/artists/{artist_id}", api_base());
 
-        let response = self.http.get(url).bearer_auth(token.access_token).send()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -37,14 +46,227 @@ impl ArtistsClient {
         }
 
         let payload: SpotifyArtist = response.json()?;
-        Ok(Artist {
-            id: payload.id,
-            name: payload.name,
-            uri: payload.uri,
-            genres: payload.genres,
-            followers: payload.followers.map(|followers| followers.total),
+        Ok(map_artist(payload))
+    }
+
+    /// Look up artists by id in batches of 50; unknown ids are skipped.
+    pub fn several(&self, ids: &[String]) -> Result<Vec<Artist>> {
+        let token = self.auth.token()?;
+        let chunks: Vec<&[String]> = ids.chunks(SEVERAL_BATCH).collect();
+        let pages = fetch_all(
+            &chunks,
+            MAX_IN_FLIGHT,
+            |chunk| self.several_request(chunk, &token.access_token),
+            |_| {},
+        )?;
+        Ok(pages.into_iter().flatten().collect())
+    }
+
+    fn several_request(&self, ids: &[String], access_token: &str) -> Result<Vec<Artist>> {
+        let url = format!("{}/artists?ids={}", api_base(), ids.join(","));
+        let response = self.http.get(url).bearer_auth(access_token).send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify artists request failed",
+                status,
+                &body
+            ));
+        }
+
+        let payload: SeveralArtistsResponse = response.json()?;
+        Ok(payload
+            .artists
+            .into_iter()
+            .flatten()
+            .map(map_artist)
+            .collect())
+    }
+
+    pub fn followed(&self) -> Result<Vec<Artist>> {
+        self.followed_from(None)
+    }
+
+    /// Every followed artist after an optional cursor, following each page's `next` link.
+    pub fn followed_from(&self, after: Option<&str>) -> Result<Vec<Artist>> {
+        let token = self.auth.token()?;
+        let mut url = followed_url(FOLLOWED_PAGE, after);
+        let mut artists = Vec::new();
+
+        loop {
+            cancel::check()?;
+            let page = self.followed_request(&url, &token.access_token)?;
+            artists.extend(page.items.into_iter().map(map_artist));
+
+            if let Some(next) = page.next {
+                url = next;
+            } else {
+                break;
+            }
+        }
+
+        Ok(artists)
+    }
+
+    /// One page of followed artists; Spotify pages these by cursor rather than offset.
+    pub fn followed_page(&self, limit: u32, after: Option<&str>) -> Result<FollowedArtistPage> {
+        let token = self.auth.token()?;
+        let page = self.followed_request(&followed_url(limit, after), &token.access_token)?;
+        // The cursor is the last artist id; only hand it out while another page exists.
+        let after = page.next.as_ref().and_then(|_| {
+            page.cursors
+                .as_ref()
+                .and_then(|cursors| cursors.after.clone())
+                .or_else(|| page.items.last().map(|artist| artist.id.clone()))
+        });
+        Ok(FollowedArtistPage {
+            items: page.items.into_iter().map(map_artist).collect(),
+            total: page.total,
+            after,
         })
     }
+
+    pub fn follow(&self, ids: &[String]) -> Result<()> {
+        self.set_following(Method::PUT, ids)
+    }
+
+    pub fn unfollow(&self, ids: &[String]) -> Result<()> {
+        self.set_following(Method::DELETE, ids)
+    }
+
+    fn set_following(&self, method: Method, ids: &[String]) -> Result<()> {
+        let token = self.auth.token()?;
+        for chunk in ids.chunks(FOLLOW_BATCH) {
+            let url = format!(
+                "{}/me/following?type=artist&ids={}",
+                api_base(),
+                chunk.join(",")
+            );
+            let response = self
+                .http
+                .request(method.clone(), url)
+                .bearer_auth(&token.access_token)
+                .body(Vec::new())
+                .send_traced()
+                .context("spotify request failed")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+                bail!(format_api_error(
+                    "spotify follow request failed",
+                    status,
+                    &body
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn followed_request(&self, url: &str, access_token: &str) -> Result<FollowedArtistsPage> {
+        let response = self.http.get(url).bearer_auth(access_token).send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify followed artists request failed",
+                status,
+                &body
+            ));
+        }
+
+        let payload: FollowedArtistsResponse = response.json()?;
+        Ok(payload.artists)
+    }
+
+    /// Newest albums and newest singles, up to `limit` of each.
+    pub fn albums(&self, artist_id: &str, limit: u32) -> Result<Vec<Release>> {
+        let token = self.auth.token()?;
+        // Results come grouped albums first, so a shared page could hide every single.
+        let mut releases = Vec::new();
+        for group in ["album", "single"] {
+            let url = albums_url(artist_id, &[group], limit);
+            let page = self.albums_request(&url, &token.access_token)?;
+            releases.extend(page.items.into_iter().map(map_release));
+        }
+        Ok(releases)
+    }
+
+    /// Every release in the given album groups (all groups when empty), following `next`.
+    pub fn albums_all(&self, artist_id: &str, groups: &[&str]) -> Result<Vec<Release>> {
+        let token = self.auth.token()?;
+        let mut url = albums_url(artist_id, groups, ALBUMS_PAGE);
+        let mut releases = Vec::new();
+        loop {
+            cancel::check()?;
+            let page = self.albums_request(&url, &token.access_token)?;
+            releases.extend(page.items.into_iter().map(map_release));
+            match page.next {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+        Ok(releases)
+    }
+
+    fn albums_request(&self, url: &str, access_token: &str) -> Result<ArtistAlbumsResponse> {
+        let response = self.http.get(url).bearer_auth(access_token).send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify artist albums request failed",
+                status,
+                &body
+            ));
+        }
+
+        Ok(response.json()?)
+    }
+}
+
+fn albums_url(artist_id: &str, groups: &[&str], limit: u32) -> String {
+    let mut url = format!("{}/artists/{artist_id}/albums?limit={limit}", api_base());
+    if !groups.is_empty() {
+        url.push_str(&format!("&include_groups={}", groups.join(",")));
+    }
+    url
+}
+
+fn map_release(item: SpotifyArtistAlbum) -> Release {
+    Release {
+        id: item.id,
+        name: item.name,
+        uri: item.uri,
+        artists: item.artists.into_iter().map(|artist| artist.name).collect(),
+        album_type: item.album_type,
+        album_group: item.album_group,
+        release_date: item.release_date,
+    }
+}
+
+fn followed_url(limit: u32, after: Option<&str>) -> String {
+    let mut url = format!("{}/me/following?type=artist&limit={limit}", api_base());
+    if let Some(after) = after {
+        url.push_str(&format!("&after={after}"));
+    }
+    url
+}
+
+fn map_artist(payload: SpotifyArtist) -> Artist {
+    Artist {
+        id: payload.id,
+        name: payload.name,
+        uri: payload.uri,
+        genres: payload.genres,
+        followers: payload.followers.map(|followers| followers.total),
+        popularity: payload.popularity,
+        images: payload.images.into_iter().map(|image| image.url).collect(),
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -55,9 +277,75 @@ struct SpotifyArtist {
     #[serde(default)]
     genres: Vec<String>,
     followers: Option<SpotifyFollowers>,
+    popularity: Option<u32>,
+    #[serde(default)]
+    images: Vec<SpotifyImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyImage {
+    url: String,
 }
 
 #[derive(Debug, Deserialize)]
 struct SpotifyFollowers {
     total: u64,
 }
+
+#[derive(Debug, Deserialize)]
+struct FollowedArtistsResponse {
+    artists: FollowedArtistsPage,
+}
+
+#[derive(Debug, Deserialize)]
+struct FollowedArtistsPage {
+    items: Vec<SpotifyArtist>,
+    next: Option<String>,
+    total: Option<u32>,
+    cursors: Option<FollowedCursors>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FollowedCursors {
+    after: Option<String>,
+}
+
+/// Spotify caps followed-artist pages at 50.
+const FOLLOWED_PAGE: u32 = 50;
+/// Spotify caps several-artist lookups at 50 ids.
+const SEVERAL_BATCH: usize = 50;
+/// Spotify caps follow and unfollow requests at 50 ids.
+const FOLLOW_BATCH: usize = 50;
+
+#[derive(Debug, Deserialize)]
+struct SeveralArtistsResponse {
+    #[serde(default)]
+    artists: Vec<Option<SpotifyArtist>>,
+}
+
+/// Spotify caps artist album pages at 50.
+const ALBUMS_PAGE: u32 = 50;
+
+#[derive(Debug, Deserialize)]
+struct ArtistAlbumsResponse {
+    items: Vec<SpotifyArtistAlbum>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyArtistAlbum {
+    id: String,
+    name: String,
+    uri: String,
+    album_type: Option<String>,
+    /// The artist's relation to the release: album, single, compilation, or appears_on.
+    album_group: Option<String>,
+    release_date: Option<String>,
+    #[serde(default)]
+    artists: Vec<SpotifyArtistRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyArtistRef {
+    name: String,
+}
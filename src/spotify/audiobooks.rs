@@ -0,0 +1,193 @@
+use anyhow::{Context, bail};
+use reqwest::Method;
+use reqwest::blocking::Client as HttpClient;
+use serde::Deserialize;
+
+use crate::domain::audiobook::{Audiobook, SavedAudiobook};
+use crate::error::Result;
+use crate::spotify::auth::AuthService;
+use crate::spotify::base::{api_base, push_market};
+use crate::spotify::cancel;
+use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
+
+/// Spotify audiobook API client.
+#[derive(Debug, Clone)]
+pub struct AudiobooksClient {
+    http: HttpClient,
+    auth: AuthService,
+}
+
+impl AudiobooksClient {
+    pub fn new(http: HttpClient, auth: AuthService) -> Self {
+        Self { http, auth }
+    }
+
+    pub fn get(&self, audiobook_id: &str) -> Result<Audiobook> {
+        let token = self.auth.token()?;
+        let mut url = format!("{}/audiobooks/{audiobook_id}", api_base());
+        push_market(&mut url, false);
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify audiobook request failed",
+                status,
+                &body
+            ));
+        }
+
+        let payload: SpotifyAudiobook = response.json()?;
+        Ok(map_audiobook(payload))
+    }
+
+    /// Saved audiobooks, most recently saved first; `None` fetches every page, otherwise up to
+    /// `limit`.
+    pub fn saved(&self, limit: Option<u32>) -> Result<Vec<SavedAudiobook>> {
+        let token = self.auth.token()?;
+        let page = limit.map_or(SAVED_PAGE, |limit| limit.clamp(1, SAVED_PAGE));
+        let mut url = format!("{}/me/audiobooks?limit={page}", api_base());
+        push_market(&mut url, false);
+        let mut saved = Vec::new();
+
+        loop {
+            cancel::check()?;
+            let response = self
+                .http
+                .get(&url)
+                .bearer_auth(token.access_token.clone())
+                .send_traced()?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+                bail!(format_api_error(
+                    "spotify saved audiobooks failed",
+                    status,
+                    &body
+                ));
+            }
+
+            let payload: SavedAudiobooksResponse = response.json()?;
+            saved.extend(
+                payload
+                    .items
+                    .into_iter()
+                    .flatten()
+                    .map(|item| SavedAudiobook {
+                        audiobook: map_audiobook(item.audiobook),
+                        added_at: item.added_at,
+                    }),
+            );
+
+            match (payload.next, limit) {
+                (Some(_), Some(limit)) if saved.len() >= limit as usize => break,
+                (Some(next), _) => url = next,
+                (None, _) => break,
+            }
+        }
+
+        if let Some(limit) = limit {
+            saved.truncate(limit as usize);
+        }
+        Ok(saved)
+    }
+
+    pub fn save(&self, ids: &[String]) -> Result<()> {
+        self.set_saved(Method::PUT, ids)
+    }
+
+    pub fn remove(&self, ids: &[String]) -> Result<()> {
+        self.set_saved(Method::DELETE, ids)
+    }
+
+    fn set_saved(&self, method: Method, ids: &[String]) -> Result<()> {
+        let token = self.auth.token()?;
+        for chunk in ids.chunks(SAVED_PAGE as usize) {
+            let url = format!("{}/me/audiobooks?ids={}", api_base(), chunk.join(","));
+            let response = self
+                .http
+                .request(method.clone(), url)
+                .bearer_auth(&token.access_token)
+                .body(Vec::new())
+                .send_traced()
+                .context("spotify request failed")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+                bail!(format_api_error(
+                    "spotify saved audiobooks request failed",
+                    status,
+                    &body
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Spotify caps saved item pages at 50.
+const SAVED_PAGE: u32 = 50;
+
+fn map_audiobook(payload: SpotifyAudiobook) -> Audiobook {
+    Audiobook {
+        id: payload.id,
+        name: payload.name,
+        uri: payload.uri,
+        authors: payload
+            .authors
+            .into_iter()
+            .map(|author| author.name)
+            .collect(),
+        narrators: payload
+            .narrators
+            .into_iter()
+            .map(|narrator| narrator.name)
+            .collect(),
+        publisher: payload.publisher,
+        description: payload.description,
+        total_chapters: payload.total_chapters,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedAudiobooksResponse {
+    items: Vec<Option<SavedAudiobookItem>>,
+    next: Option<String>,
+}
+
+/// Saved audiobooks come back as bare audiobook objects, without a save date.
+#[derive(Debug, Deserialize)]
+struct SavedAudiobookItem {
+    #[serde(default)]
+    added_at: Option<String>,
+    #[serde(flatten)]
+    audiobook: SpotifyAudiobook,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyAudiobook {
+    id: String,
+    name: String,
+    uri: String,
+    #[serde(default)]
+    authors: Vec<SpotifyPerson>,
+    #[serde(default)]
+    narrators: Vec<SpotifyPerson>,
+    publisher: Option<String>,
+    description: Option<String>,
+    total_chapters: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyPerson {
+    name: String,
+}
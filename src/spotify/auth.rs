@@ -1,21 +1,23 @@
 use std::io::{Read, Write};
 use std::net::TcpListener;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, bail};
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use rand::RngCore;
-use reqwest::blocking::Client as HttpClient;
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
 use url::Url;
 
 use crate::cache::metadata::MetadataStore;
 use crate::cache::metadata::{AuthTokenCache, ClientIdentity, Metadata};
-use crate::domain::auth::{AuthScopes, AuthStatus};
-use crate::domain::settings::Settings;
-use crate::error::Result;
+use crate::domain::auth::{AccountProfile, AuthScopes, AuthStatus};
+use crate::domain::settings::{DeviceVolume, Settings};
+use crate::error::{ErrorKind, Result};
+use crate::spotify::base::{api_base, http_builder};
+use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
+use crate::time::unix_time;
 
 const ACCOUNTS_BASE: &str = "https://accounts.spotify.com";
 const API_BASE: &str = "https://api.spotify.com/v1";
@@ -48,6 +50,17 @@ pub struct AuthToken {
     pub scopes: Option<Vec<String>>,
 }
 
+/// How the authorization code is collected during login.
+#[derive(Debug, Clone, Default)]
+pub struct LoginOptions {
+    /// Read the redirect URL or code from stdin instead of a local listener.
+    pub manual: bool,
+    /// Print the authorization URL without trying to open a browser.
+    pub no_browser: bool,
+    /// Scopes to request instead of the full default set.
+    pub scopes: Option<Vec<String>>,
+}
+
 /// OAuth login and token refresh service.
 #[derive(Debug, Clone)]
 pub struct AuthService {
@@ -64,15 +77,39 @@ impl AuthService {
     }
 
     pub fn login_oauth_with_redirect(&self, client_id: String, redirect_uri: &str) -> Result<()> {
+        self.login_oauth_with_options(client_id, redirect_uri, &LoginOptions::default())
+    }
+
+    pub fn login_oauth_with_options(
+        &self,
+        client_id: String,
+        redirect_uri: &str,
+        options: &LoginOptions,
+    ) -> Result<()> {
         let code_verifier = pkce_verifier();
         let code_challenge = pkce_challenge(&code_verifier);
         let state = oauth_state();
-        let authorize_url = build_authorize_url(&client_id, redirect_uri, &state, &code_challenge)?;
+        let scopes = match options.scopes.as_ref() {
+            Some(scopes) if scopes.is_empty() => {
+                bail!(ErrorKind::Validation.error("--scopes needs at least one scope"))
+            }
+            Some(scopes) => scopes.clone(),
+            None => SCOPES.iter().map(|scope| scope.to_string()).collect(),
+        };
+        let authorize_url =
+            build_authorize_url(&client_id, redirect_uri, &state, &code_challenge, &scopes)?;
 
         println!("Open this URL to authorize: {}", authorize_url);
-        println!("Waiting for Spotify authorization...");
+        if !options.no_browser && !options.manual {
+            open_browser(&authorize_url);
+        }
 
-        let code = wait_for_code(redirect_uri, &state)?;
+        let code = if options.manual {
+            read_manual_code(&state)?
+        } else {
+            println!("Waiting for Spotify authorization...");
+            wait_for_code(redirect_uri, &state)?
+        };
         let token = exchange_code(&client_id, redirect_uri, &code, &code_verifier)?;
 
         let user_name = if should_fetch_profile() {
@@ -80,6 +117,8 @@ impl AuthService {
         } else {
             None
         };
+        let _lock = self.store.lock()?;
+        let previous = self.store.load()?.settings;
         let metadata = Metadata {
             auth: Some(AuthTokenCache {
                 access_token: token.access_token,
@@ -88,9 +127,11 @@ impl AuthService {
                 granted_scopes: token.scopes,
             }),
             client: Some(ClientIdentity { client_id }),
+            // The profile (name, country) belongs to the new account; preferences carry over.
             settings: Settings {
                 user_name,
-                ..Settings::default()
+                country: None,
+                ..previous
             },
         };
 
@@ -99,37 +140,76 @@ impl AuthService {
     }
 
     pub fn login(&self, token: AuthToken) -> Result<()> {
-        let mut metadata = self.store.load()?;
         let user_name = if should_fetch_profile() {
             fetch_user_name(&token.access_token).ok()
         } else {
             None
         };
-        metadata.auth = Some(AuthTokenCache {
-            access_token: token.access_token,
-            refresh_token: token.refresh_token,
-            expires_at: token.expires_at,
-            granted_scopes: token.scopes.clone(),
-        });
-        if user_name.is_some() {
-            metadata.settings.user_name = user_name;
-        }
-        self.store.save(&metadata)?;
-        Ok(())
+        self.update(|metadata| {
+            metadata.auth = Some(AuthTokenCache {
+                access_token: token.access_token,
+                refresh_token: token.refresh_token,
+                expires_at: token.expires_at,
+                granted_scopes: token.scopes.clone(),
+            });
+            if user_name.is_some() {
+                metadata.settings.user_name = user_name;
+            }
+        })
+    }
+
+    /// Load, change, and save the metadata under its lock, so concurrent runs keep each
+    /// other's writes (a refreshed token in particular).
+    fn update(&self, change: impl FnOnce(&mut Metadata)) -> Result<()> {
+        let _lock = self.store.lock()?;
+        let mut metadata = self.store.load()?;
+        change(&mut metadata);
+        self.store.save(&metadata)
     }
 
     pub fn status(&self) -> Result<AuthStatus> {
         let metadata = self.store.load()?;
+        let token_path = self.store.path().display().to_string();
+        let client_id = metadata.client.map(|client| client.client_id);
         let Some(auth) = metadata.auth else {
             return Ok(AuthStatus {
-                logged_in: false,
-                expires_at: None,
+                client_id,
+                token_path,
+                ..AuthStatus::default()
             });
         };
 
         Ok(AuthStatus {
             logged_in: !auth.access_token.is_empty(),
             expires_at: auth.expires_at,
+            client_id,
+            granted_scopes: auth.granted_scopes,
+            token_path,
+            profile: None,
+        })
+    }
+
+    /// The logged-in account, refreshing the token first if needed.
+    pub fn profile(&self) -> Result<AccountProfile> {
+        let token = self.token()?;
+        let client = http_builder()?.build()?;
+        let url = format!("{}/me", api_base());
+        let response = client
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()
+            .context("spotify profile request failed")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error("spotify profile failed", status, &body));
+        }
+        let payload: UserProfile = response.json()?;
+        Ok(AccountProfile {
+            id: payload.id.unwrap_or_default(),
+            display_name: payload.display_name,
+            product: payload.product,
+            country: payload.country,
         })
     }
 
@@ -160,11 +240,40 @@ impl AuthService {
         })
     }
 
+    /// Re-run the login flow requesting the granted scopes plus `extra`.
+    pub fn add_scopes(&self, extra: &[String]) -> Result<()> {
+        let metadata = self.store.load()?;
+        let Some(client) = metadata.client else {
+            bail!(ErrorKind::Auth.error("not logged in; run `spotify auth login`"));
+        };
+        let granted = metadata
+            .auth
+            .and_then(|auth| auth.granted_scopes)
+            .unwrap_or_default();
+        let options = LoginOptions {
+            scopes: Some(merge_scopes(&granted, extra)),
+            ..LoginOptions::default()
+        };
+        self.login_oauth_with_options(client.client_id, REDIRECT_URI_DEFAULT, &options)
+    }
+
     #[allow(clippy::collapsible_if)]
     pub fn token(&self) -> Result<AuthToken> {
-        let metadata = self.store.load()?;
+        let mut metadata = self.store.load()?;
+        // Another invocation may be refreshing; wait for it and reuse its token.
+        let _lock = if metadata
+            .auth
+            .as_ref()
+            .is_some_and(|auth| token_needs_refresh(auth.expires_at))
+        {
+            let lock = self.store.lock()?;
+            metadata = self.store.load()?;
+            Some(lock)
+        } else {
+            None
+        };
         let Some(mut auth) = metadata.auth else {
-            bail!("not logged in; run `spotify auth login`");
+            bail!(ErrorKind::Auth.error("not logged in; run `spotify auth login`"));
         };
 
         if token_needs_refresh(auth.expires_at) {
@@ -188,7 +297,7 @@ impl AuthService {
         }
 
         if token_needs_refresh(auth.expires_at) {
-            bail!("token expired; run `spotify auth login`");
+            bail!(ErrorKind::Auth.error("token expired; run `spotify auth login`"));
         }
 
         Ok(AuthToken {
@@ -199,14 +308,13 @@ impl AuthService {
         })
     }
 
+    /// Forget the login; user configuration stays, only the account's name goes with it.
     pub fn clear(&self) -> Result<()> {
-        let metadata = Metadata {
-            auth: None,
-            client: None,
-            settings: Settings::default(),
-        };
-        self.store.save(&metadata)?;
-        Ok(())
+        self.update(|metadata| {
+            metadata.auth = None;
+            metadata.client = None;
+            metadata.settings.user_name = None;
+        })
     }
 
     pub fn country(&self) -> Result<Option<String>> {
@@ -215,10 +323,7 @@ impl AuthService {
     }
 
     pub fn set_country(&self, country: Option<String>) -> Result<()> {
-        let mut metadata = self.store.load()?;
-        metadata.settings.country = country;
-        self.store.save(&metadata)?;
-        Ok(())
+        self.update(|metadata| metadata.settings.country = country)
     }
 
     pub fn user_name(&self) -> Result<Option<String>> {
@@ -227,15 +332,61 @@ impl AuthService {
     }
 
     pub fn set_user_name(&self, user_name: Option<String>) -> Result<()> {
-        let mut metadata = self.store.load()?;
-        metadata.settings.user_name = user_name;
-        self.store.save(&metadata)?;
-        Ok(())
+        self.update(|metadata| metadata.settings.user_name = user_name)
+    }
+
+    pub fn default_market(&self) -> Result<Option<String>> {
+        let metadata = self.store.load()?;
+        Ok(metadata.settings.default_market)
+    }
+
+    pub fn settings(&self) -> Result<Settings> {
+        let metadata = self.store.load()?;
+        Ok(metadata.settings)
+    }
+
+    /// Request and connect timeouts configured for API calls.
+    pub fn timeouts(&self) -> Result<(Option<u64>, Option<u64>)> {
+        let metadata = self.store.load()?;
+        let settings = metadata.settings;
+        Ok((settings.timeout_secs, settings.connect_timeout_secs))
+    }
+
+    pub fn max_volume(&self) -> Result<Option<u32>> {
+        let metadata = self.store.load()?;
+        Ok(metadata.settings.max_volume)
+    }
+
+    pub fn set_max_volume(&self, max_volume: Option<u32>) -> Result<()> {
+        self.update(|metadata| metadata.settings.max_volume = max_volume)
+    }
+
+    pub fn device_volume(&self, device: &str) -> Result<Option<u32>> {
+        let metadata = self.store.load()?;
+        Ok(metadata
+            .settings
+            .device_volumes
+            .iter()
+            .find(|entry| entry.device.eq_ignore_ascii_case(device))
+            .map(|entry| entry.volume))
+    }
+
+    pub fn set_device_volume(&self, device: &str, volume: Option<u32>) -> Result<()> {
+        self.update(|metadata| {
+            let volumes = &mut metadata.settings.device_volumes;
+            volumes.retain(|entry| !entry.device.eq_ignore_ascii_case(device));
+            if let Some(volume) = volume {
+                volumes.push(DeviceVolume {
+                    device: device.to_string(),
+                    volume,
+                });
+            }
+        })
     }
 
     #[allow(clippy::collapsible_if)]
     pub fn ensure_user_name(&self) -> Result<Option<String>> {
-        let mut metadata = self.store.load()?;
+        let metadata = self.store.load()?;
         if metadata.settings.user_name.is_some() {
             return Ok(metadata.settings.user_name);
         }
@@ -246,8 +397,7 @@ impl AuthService {
 
         if let Some(auth) = metadata.auth.as_ref() {
             if let Ok(user_name) = fetch_user_name(&auth.access_token) {
-                metadata.settings.user_name = Some(user_name.clone());
-                self.store.save(&metadata)?;
+                self.set_user_name(Some(user_name.clone()))?;
                 return Ok(Some(user_name));
             }
         }
@@ -268,8 +418,9 @@ fn build_authorize_url(
     redirect_uri: &str,
     state: &str,
     code_challenge: &str,
+    scopes: &[String],
 ) -> Result<String> {
-    let scope = SCOPES.join(" ");
+    let scope = scopes.join(" ");
     let encoded_scope = urlencoding::encode(&scope);
     let encoded_redirect = urlencoding::encode(redirect_uri);
 
@@ -278,10 +429,74 @@ fn build_authorize_url(
     ))
 }
 
+/// Union of two scope lists, keeping the order of first appearance.
+pub fn merge_scopes(granted: &[String], extra: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    for scope in granted.iter().chain(extra) {
+        if !merged.contains(scope) {
+            merged.push(scope.clone());
+        }
+    }
+    merged
+}
+
+/// Redirect URI on the loopback host for a custom callback port.
+pub fn redirect_uri_for_port(port: u16) -> String {
+    format!("http://127.0.0.1:{port}/callback")
+}
+
+fn open_browser(url: &str) {
+    use std::process::{Command, Stdio};
+
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let _ = Command::new(opener)
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+}
+
+fn read_manual_code(expected_state: &str) -> Result<String> {
+    print!("Paste the redirect URL (or code) here: ");
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    parse_manual_code(&input, expected_state)
+}
+
+/// Extract the authorization code from a pasted redirect URL or bare code.
+fn parse_manual_code(input: &str, expected_state: &str) -> Result<String> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!(ErrorKind::Validation.error("no redirect URL or code provided"));
+    }
+    let Some((_, query)) = input.split_once('?') else {
+        return Ok(input.to_string());
+    };
+
+    let params = parse_query(query);
+    if let Some(error) = params.get("error") {
+        bail!(ErrorKind::Auth.error(format!("authorization failed: {error}")));
+    }
+    match params.get("state") {
+        Some(state) if state == expected_state => {}
+        Some(_) => bail!(ErrorKind::Auth.error("state mismatch during login")),
+        None => bail!(ErrorKind::Auth.error("missing state in redirect")),
+    }
+    let Some(code) = params.get("code") else {
+        bail!(ErrorKind::Auth.error("missing code in redirect"));
+    };
+    Ok(code.to_string())
+}
+
 fn wait_for_code(redirect_uri: &str, expected_state: &str) -> Result<String> {
     let url = Url::parse(redirect_uri)?;
     if url.scheme() != "http" {
-        bail!("redirect URI must use http");
+        bail!(ErrorKind::Validation.error("redirect URI must use http"));
     }
 
     let host = url.host_str().unwrap_or("127.0.0.1");
@@ -291,7 +506,7 @@ fn wait_for_code(redirect_uri: &str, expected_state: &str) -> Result<String> {
         host
     };
     if !matches!(host, "127.0.0.1" | "::1") {
-        bail!("redirect URI must use a loopback host");
+        bail!(ErrorKind::Validation.error("redirect URI must use a loopback host"));
     }
     let port = url.port_or_known_default().unwrap_or(8888);
     let path = url.path().to_string();
@@ -311,7 +526,7 @@ fn wait_for_code(redirect_uri: &str, expected_state: &str) -> Result<String> {
     let target = parts.next().unwrap_or("");
 
     if method != "GET" {
-        bail!("unexpected redirect method: {method}");
+        bail!(ErrorKind::Auth.error(format!("unexpected redirect method: {method}")));
     }
 
     let (request_path, query) = match target.split_once('?') {
@@ -320,20 +535,20 @@ fn wait_for_code(redirect_uri: &str, expected_state: &str) -> Result<String> {
     };
 
     if request_path != path {
-        bail!("unexpected redirect path: {request_path}");
+        bail!(ErrorKind::Auth.error(format!("unexpected redirect path: {request_path}")));
     }
 
     let params = parse_query(query);
     let Some(state) = params.get("state") else {
-        bail!("missing state in redirect");
+        bail!(ErrorKind::Auth.error("missing state in redirect"));
     };
 
     if state != expected_state {
-        bail!("state mismatch during login");
+        bail!(ErrorKind::Auth.error("state mismatch during login"));
     }
 
     let Some(code) = params.get("code") else {
-        bail!("missing code in redirect");
+        bail!(ErrorKind::Auth.error("missing code in redirect"));
     };
 
     let response = "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\nYou can close this window.";
@@ -366,7 +581,7 @@ fn exchange_code(
     code: &str,
     code_verifier: &str,
 ) -> Result<AuthToken> {
-    let client = HttpClient::builder().build()?;
+    let client = http_builder()?.build()?;
     let url = format!("{ACCOUNTS_BASE}/api/token");
 
     let response = client
@@ -378,11 +593,14 @@ fn exchange_code(
             ("client_id", client_id),
             ("code_verifier", code_verifier),
         ])
-        .send()
+        .send_traced()
         .context("spotify token exchange failed")?;
 
     if !response.status().is_success() {
-        bail!("spotify token exchange failed: {}", response.status());
+        bail!(ErrorKind::Auth.error(format!(
+            "spotify token exchange failed: {}",
+            response.status()
+        )));
     }
 
     let payload: TokenResponse = response.json()?;
@@ -395,7 +613,7 @@ fn exchange_code(
 }
 
 fn refresh_token(client_id: &str, refresh_token: &str) -> Result<AuthToken> {
-    let client = HttpClient::builder().build()?;
+    let client = http_builder()?.build()?;
     let url = format!("{ACCOUNTS_BASE}/api/token");
 
     let response = client
@@ -405,11 +623,14 @@ fn refresh_token(client_id: &str, refresh_token: &str) -> Result<AuthToken> {
             ("refresh_token", refresh_token),
             ("client_id", client_id),
         ])
-        .send()
+        .send_traced()
         .context("spotify token refresh failed")?;
 
     if !response.status().is_success() {
-        bail!("spotify token refresh failed: {}", response.status());
+        bail!(ErrorKind::Auth.error(format!(
+            "spotify token refresh failed: {}",
+            response.status()
+        )));
     }
 
     let payload: TokenResponse = response.json()?;
@@ -431,13 +652,6 @@ struct TokenResponse {
     scope: Option<String>,
 }
 
-fn unix_time() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("time")
-        .as_secs()
-}
-
 fn token_needs_refresh(expires_at: Option<u64>) -> bool {
     let Some(expires_at) = expires_at else {
         return false;
@@ -450,11 +664,14 @@ fn should_fetch_profile() -> bool {
 }
 
 fn fetch_user_name(access_token: &str) -> Result<String> {
-    let client = HttpClient::builder().build()?;
+    let client = http_builder()?.build()?;
     let url = format!("{API_BASE}/me");
-    let response = client.get(url).bearer_auth(access_token).send()?;
+    let response = client.get(url).bearer_auth(access_token).send_traced()?;
     if !response.status().is_success() {
-        bail!("spotify profile request failed: {}", response.status());
+        bail!(ErrorKind::General.error(format!(
+            "spotify profile request failed: {}",
+            response.status()
+        )));
     }
     let payload: UserProfile = response.json()?;
     Ok(payload
@@ -475,6 +692,10 @@ fn parse_scopes(scope: String) -> Vec<String> {
 struct UserProfile {
     display_name: Option<String>,
     id: Option<String>,
+    #[serde(default)]
+    product: Option<String>,
+    #[serde(default)]
+    country: Option<String>,
 }
 
 fn pkce_verifier() -> String {
@@ -487,3 +708,33 @@ fn pkce_challenge(verifier: &str) -> String {
     let digest = Sha256::digest(verifier.as_bytes());
     URL_SAFE_NO_PAD.encode(digest)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_scopes, parse_manual_code, redirect_uri_for_port};
+
+    #[test]
+    fn parse_manual_code_accepts_url_or_code() {
+        let url = "http://127.0.0.1:8888/callback?code=abc%2B1&state=xyz";
+        assert_eq!(parse_manual_code(url, "xyz").unwrap(), "abc+1");
+        assert_eq!(parse_manual_code("  rawcode\n", "xyz").unwrap(), "rawcode");
+        assert!(parse_manual_code(url, "other").is_err());
+        assert!(parse_manual_code("http://x/callback?error=access_denied", "xyz").is_err());
+        assert!(parse_manual_code("", "xyz").is_err());
+    }
+
+    #[test]
+    fn merge_scopes_adds_only_new_scopes() {
+        let granted = vec!["a".to_string(), "b".to_string()];
+        let extra = vec!["b".to_string(), "c".to_string()];
+        assert_eq!(merge_scopes(&granted, &extra), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn redirect_uri_for_port_uses_loopback() {
+        assert_eq!(
+            redirect_uri_for_port(9000),
+            "http://127.0.0.1:9000/callback"
+        );
+    }
+}
@@ -1,4 +1,11 @@
-//! Shared Spotify API base URL configuration.
+//! Shared Spotify API base URL, market and HTTP transport configuration.
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use reqwest::blocking::ClientBuilder;
+use reqwest::{Certificate, Proxy};
+
+use crate::error::Result;
 
 pub const DEFAULT_API_BASE: &str = "https://api.spotify.com/v1";
 
@@ -7,3 +14,118 @@ pub fn api_base() -> String {
         std::env::var("SPOTIFY_CLI_API_BASE").unwrap_or_else(|_| DEFAULT_API_BASE.to_string());
     base.trim_end_matches('/').to_string()
 }
+
+static MARKET: OnceLock<String> = OnceLock::new();
+
+/// Set the market passed to endpoints that support relinking.
+pub fn set_market(market: String) {
+    let _ = MARKET.set(market.to_uppercase());
+}
+
+pub fn market() -> Option<&'static str> {
+    MARKET.get().map(String::as_str)
+}
+
+/// Append a `market` query parameter to `url` when one applies.
+///
+/// An explicit market wins; otherwise `from_token` uses the account's country.
+pub fn push_market(url: &mut String, from_token: bool) {
+    let market = match market() {
+        Some(market) => market,
+        None if from_token => "from_token",
+        None => return,
+    };
+    let separator = if url.contains('?') { '&' } else { '?' };
+    url.push(separator);
+    url.push_str("market=");
+    url.push_str(market);
+}
+
+/// Proxy and TLS options applied to every HTTP client.
+///
+/// `http_proxy`/`https_proxy` from the environment are honored by reqwest;
+/// an explicit `proxy` takes precedence over them.
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+    pub proxy: Option<String>,
+    /// PEM file with extra root certificates.
+    pub ca_bundle: Option<String>,
+    /// Skip certificate verification.
+    pub insecure: bool,
+}
+
+static HTTP_OPTIONS: OnceLock<HttpOptions> = OnceLock::new();
+
+pub fn set_http_options(options: HttpOptions) {
+    let _ = HTTP_OPTIONS.set(options);
+}
+
+/// Client builder with the configured proxy and TLS options applied.
+pub fn http_builder() -> Result<ClientBuilder> {
+    let mut options = HTTP_OPTIONS.get().cloned().unwrap_or_default();
+    if let Ok(path) = std::env::var("SPOTIFY_CLI_CA_BUNDLE") {
+        options.ca_bundle = Some(path);
+    }
+    http_builder_with(&options)
+}
+
+fn http_builder_with(options: &HttpOptions) -> Result<ClientBuilder> {
+    let mut builder = ClientBuilder::new();
+    if let Some(proxy) = options.proxy.as_deref() {
+        let proxy = Proxy::all(proxy).with_context(|| format!("invalid proxy: {proxy}"))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(path) = options.ca_bundle.as_deref() {
+        let pem =
+            std::fs::read(path).with_context(|| format!("unable to read CA bundle {path}"))?;
+        for cert in Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("invalid CA bundle {path}"))?
+        {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    if options.insecure {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    Ok(builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HttpOptions, http_builder_with, push_market};
+
+    #[test]
+    fn push_market_uses_token_market_without_override() {
+        let mut url = "https://api.spotify.com/v1/search?q=x".to_string();
+        push_market(&mut url, true);
+        assert_eq!(
+            url,
+            "https://api.spotify.com/v1/search?q=x&market=from_token"
+        );
+
+        let mut url = "https://api.spotify.com/v1/albums/1".to_string();
+        push_market(&mut url, false);
+        assert_eq!(url, "https://api.spotify.com/v1/albums/1");
+    }
+
+    #[test]
+    fn http_builder_rejects_bad_proxy_and_missing_bundle() {
+        let options = HttpOptions {
+            proxy: Some("http://proxy.local:3128".to_string()),
+            ..HttpOptions::default()
+        };
+        assert!(http_builder_with(&options).is_ok());
+
+        let options = HttpOptions {
+            proxy: Some("not a url".to_string()),
+            ..HttpOptions::default()
+        };
+        assert!(http_builder_with(&options).is_err());
+
+        let options = HttpOptions {
+            ca_bundle: Some("/nonexistent/ca.pem".to_string()),
+            ..HttpOptions::default()
+        };
+        assert!(http_builder_with(&options).is_err());
+    }
+}
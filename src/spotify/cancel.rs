@@ -0,0 +1,149 @@
+//! Ctrl-C and `--timeout` handling.
+//!
+//! Both record a process-wide stop reason instead of killing the process. Paginated and
+//! batched fetches check it before each request and fail with an `interrupted` or `timeout`
+//! error, so no command acts on a half-read list; read-only listings that page in the command
+//! stop early and print what they have. `main` then exits 130 or 124. A second Ctrl-C quits at
+//! once.
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::bail;
+
+use crate::error::{ErrorKind, Result};
+
+/// How long a timed-out command gets to wind down before the process exits anyway.
+const TIMEOUT_GRACE: Duration = Duration::from_secs(2);
+/// Waits are sliced so a stop is noticed promptly.
+const SLEEP_SLICE: Duration = Duration::from_millis(100);
+
+const RUNNING: u8 = 0;
+const INTERRUPTED: u8 = 1;
+const TIMED_OUT: u8 = 2;
+
+static STOP: AtomicU8 = AtomicU8::new(RUNNING);
+static DEADLINE: OnceLock<Instant> = OnceLock::new();
+
+/// Why the command is stopping early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Interrupted,
+    TimedOut,
+}
+
+impl Stop {
+    pub fn kind(self) -> ErrorKind {
+        match self {
+            Stop::Interrupted => ErrorKind::Interrupted,
+            Stop::TimedOut => ErrorKind::Timeout,
+        }
+    }
+
+    pub fn message(self) -> &'static str {
+        match self {
+            Stop::Interrupted => "interrupted",
+            Stop::TimedOut => "timed out",
+        }
+    }
+}
+
+/// Trap Ctrl-C and start the `--timeout` clock.
+pub fn install(timeout: Option<Duration>) {
+    #[cfg(unix)]
+    // SAFETY: the handler only touches an atomic and calls async-signal-safe functions.
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            on_interrupt as *const () as libc::sighandler_t,
+        );
+    }
+    if let Some(timeout) = timeout {
+        let _ = DEADLINE.set(Instant::now() + timeout);
+        std::thread::spawn(move || {
+            std::thread::sleep(timeout);
+            if STOP
+                .compare_exchange(RUNNING, TIMED_OUT, Ordering::SeqCst, Ordering::SeqCst)
+                .is_err()
+            {
+                return;
+            }
+            eprintln!("warning: timed out after {}s; stopping", timeout.as_secs());
+            std::thread::sleep(TIMEOUT_GRACE);
+            eprintln!("error: timed out; a request did not finish in time");
+            std::process::exit(ErrorKind::Timeout.exit_code());
+        });
+    }
+}
+
+#[cfg(unix)]
+extern "C" fn on_interrupt(_: libc::c_int) {
+    if STOP.swap(INTERRUPTED, Ordering::SeqCst) == INTERRUPTED {
+        // SAFETY: `_exit` is async-signal-safe.
+        unsafe { libc::_exit(ErrorKind::Interrupted.exit_code()) };
+    }
+    let message = b"\ninterrupted; stopping (press Ctrl-C again to quit now)\n";
+    // SAFETY: `write` is async-signal-safe and the buffer outlives the call.
+    unsafe {
+        libc::write(libc::STDERR_FILENO, message.as_ptr().cast(), message.len());
+    }
+}
+
+/// The stop reason, once Ctrl-C was pressed or the timeout passed.
+pub fn reason() -> Option<Stop> {
+    match STOP.load(Ordering::SeqCst) {
+        INTERRUPTED => Some(Stop::Interrupted),
+        TIMED_OUT => Some(Stop::TimedOut),
+        _ => None,
+    }
+}
+
+/// Fail with the stop reason; call before each page of a long fetch.
+pub fn check() -> Result<()> {
+    if let Some(stop) = reason() {
+        bail!(stop.kind().error(stop.message()));
+    }
+    Ok(())
+}
+
+/// Time left before `--timeout`, if one was given.
+pub fn remaining() -> Option<Duration> {
+    DEADLINE
+        .get()
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Sleep for `duration`, returning early (and false) when the command is stopping.
+pub fn sleep(duration: Duration) -> bool {
+    let until = Instant::now() + duration;
+    loop {
+        if reason().is_some() {
+            return false;
+        }
+        let left = until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return true;
+        }
+        std::thread::sleep(left.min(SLEEP_SLICE));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Stop, check, reason, sleep};
+    use std::time::Duration;
+
+    #[test]
+    fn stops_map_to_conventional_exit_codes() {
+        assert_eq!(Stop::Interrupted.kind().exit_code(), 130);
+        assert_eq!(Stop::TimedOut.kind().exit_code(), 124);
+        assert_eq!(Stop::TimedOut.kind().as_str(), "timeout");
+    }
+
+    #[test]
+    fn running_commands_are_not_stopped() {
+        assert_eq!(reason(), None);
+        assert!(check().is_ok());
+        assert!(sleep(Duration::from_millis(1)));
+    }
+}
@@ -1,15 +1,28 @@
+use std::time::Duration;
+
 use reqwest::blocking::Client as HttpClient;
 
+use crate::spotify::base::http_builder;
+use crate::spotify::cancel;
+
 use crate::error::Result;
 use crate::spotify::albums::AlbumsClient;
 use crate::spotify::artists::ArtistsClient;
+use crate::spotify::audiobooks::AudiobooksClient;
 use crate::spotify::auth::AuthService;
 use crate::spotify::devices::DevicesClient;
+use crate::spotify::episodes::EpisodesClient;
+use crate::spotify::markets::MarketsClient;
 use crate::spotify::playback::PlaybackClient;
 use crate::spotify::playlists::PlaylistsClient;
 use crate::spotify::search::SearchClient;
+use crate::spotify::shows::ShowsClient;
 use crate::spotify::track::TrackClient;
 
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+/// Short enough that an unreachable network fails fast.
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+
 /// Top-level Spotify API client factory.
 #[derive(Debug, Clone)]
 pub struct SpotifyClient {
@@ -19,7 +32,18 @@ pub struct SpotifyClient {
 
 impl SpotifyClient {
     pub fn new(auth: AuthService) -> Result<Self> {
-        let http = HttpClient::builder().build()?;
+        let (timeout, connect_timeout) = auth.timeouts()?;
+        let mut timeout = Duration::from_secs(timeout.unwrap_or(DEFAULT_TIMEOUT_SECS));
+        // No single request may outlast `--timeout`.
+        if let Some(remaining) = cancel::remaining() {
+            timeout = timeout.min(remaining);
+        }
+        let http = http_builder()?
+            .timeout(timeout)
+            .connect_timeout(Duration::from_secs(
+                connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS),
+            ))
+            .build()?;
         Ok(Self { http, auth })
     }
 
@@ -35,10 +59,22 @@ impl SpotifyClient {
         ArtistsClient::new(self.http.clone(), self.auth.clone())
     }
 
+    pub fn audiobooks(&self) -> AudiobooksClient {
+        AudiobooksClient::new(self.http.clone(), self.auth.clone())
+    }
+
     pub fn devices(&self) -> DevicesClient {
         DevicesClient::new(self.http.clone(), self.auth.clone())
     }
 
+    pub fn episodes(&self) -> EpisodesClient {
+        EpisodesClient::new(self.http.clone(), self.auth.clone())
+    }
+
+    pub fn markets(&self) -> MarketsClient {
+        MarketsClient::new(self.http.clone(), self.auth.clone())
+    }
+
     pub fn playlists(&self) -> PlaylistsClient {
         PlaylistsClient::new(self.http.clone(), self.auth.clone())
     }
@@ -47,6 +83,10 @@ impl SpotifyClient {
         SearchClient::new(self.http.clone(), self.auth.clone())
     }
 
+    pub fn shows(&self) -> ShowsClient {
+        ShowsClient::new(self.http.clone(), self.auth.clone())
+    }
+
     pub fn track(&self) -> TrackClient {
         TrackClient::new(self.http.clone(), self.auth.clone())
     }
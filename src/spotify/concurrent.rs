@@ -0,0 +1,129 @@
+//! Bounded-concurrency fetching for batched and paginated requests.
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use crate::error::Result;
+use crate::spotify::cancel;
+
+/// Requests kept in flight at once; low enough to stay clear of Spotify's rate limit.
+pub const MAX_IN_FLIGHT: usize = 4;
+
+/// Run `fetch` over `inputs` with at most `limit` calls in flight, returning results in input
+/// order.
+///
+/// `on_result` is called on the calling thread as each result arrives. The first error (a 429
+/// or a Ctrl-C included) stops workers from starting new requests and is returned once in-flight
+/// ones finish.
+pub fn fetch_all<T, R, F>(
+    inputs: &[T],
+    limit: usize,
+    fetch: F,
+    mut on_result: impl FnMut(&R),
+) -> Result<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let stopped = AtomicBool::new(false);
+    let mut results: Vec<Option<R>> = inputs.iter().map(|_| None).collect();
+    let mut first_error = None;
+
+    std::thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..limit.clamp(1, inputs.len().max(1)) {
+            let sender = sender.clone();
+            let (next, stopped, fetch) = (&next, &stopped, &fetch);
+            scope.spawn(move || {
+                loop {
+                    if stopped.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(input) = inputs.get(index) else {
+                        break;
+                    };
+                    let result = cancel::check().and_then(|()| fetch(input));
+                    if sender.send((index, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        for (index, result) in receiver {
+            match result {
+                Ok(value) => {
+                    on_result(&value);
+                    results[index] = Some(value);
+                }
+                Err(err) => {
+                    stopped.store(true, Ordering::Relaxed);
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+    });
+
+    if let Some(err) = first_error {
+        return Err(err);
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Offsets of the pages after the first, given the page size and total item count.
+pub fn remaining_offsets(page_size: u32, total: u32) -> Vec<u32> {
+    (1..total.div_ceil(page_size.max(1)))
+        .map(|page| page * page_size)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fetch_all, remaining_offsets};
+    use anyhow::bail;
+
+    #[test]
+    fn fetch_all_preserves_input_order() {
+        let inputs: Vec<u64> = (0..20).collect();
+        let mut seen = 0;
+        let results = fetch_all(
+            &inputs,
+            4,
+            |value| {
+                std::thread::sleep(std::time::Duration::from_millis(20 - value));
+                Ok(value * 2)
+            },
+            |_| seen += 1,
+        )
+        .unwrap();
+        assert_eq!(results, inputs.iter().map(|v| v * 2).collect::<Vec<_>>());
+        assert_eq!(seen, 20);
+    }
+
+    #[test]
+    fn fetch_all_returns_first_error() {
+        let inputs = vec![1, 2, 3];
+        let result = fetch_all(
+            &inputs,
+            2,
+            |value| {
+                if *value == 2 {
+                    bail!("boom");
+                }
+                Ok(*value)
+            },
+            |_| {},
+        );
+        assert_eq!(result.unwrap_err().to_string(), "boom");
+    }
+
+    #[test]
+    fn remaining_offsets_skip_first_page() {
+        assert_eq!(remaining_offsets(100, 250), vec![100, 200]);
+        assert_eq!(remaining_offsets(100, 100), Vec::<u32>::new());
+        assert_eq!(remaining_offsets(50, 0), Vec::<u32>::new());
+    }
+}
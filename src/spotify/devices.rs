@@ -8,6 +8,7 @@ use crate::error::Result;
 use crate::spotify::auth::AuthService;
 use crate::spotify::base::api_base;
 use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
 
 /// Spotify devices API client.
 #[derive(Debug, Clone)]
@@ -22,10 +23,27 @@ impl DevicesClient {
     }
 
     pub fn list(&self) -> Result<Vec<Device>> {
+        Ok(self.fetch()?.into_iter().map(Device::from).collect())
+    }
+
+    /// The device currently playing, if any.
+    pub fn active(&self) -> Result<Option<Device>> {
+        Ok(self
+            .fetch()?
+            .into_iter()
+            .find(|device| device.is_active)
+            .map(Device::from))
+    }
+
+    fn fetch(&self) -> Result<Vec<SpotifyDevice>> {
         let token = self.auth.token()?;
         let url = format!("{}/me/player/devices", api_base());
 
-        let response = self.http.get(url).bearer_auth(token.access_token).send()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -38,15 +56,7 @@ impl DevicesClient {
         }
 
         let payload: DevicesResponse = response.json()?;
-        Ok(payload
-            .devices
-            .into_iter()
-            .map(|device| Device {
-                id: device.id,
-                name: device.name,
-                volume_percent: device.volume_percent,
-            })
-            .collect())
+        Ok(payload.devices)
     }
 
     pub fn set_active(&self, device_id: &str) -> Result<()> {
@@ -59,7 +69,7 @@ impl DevicesClient {
             .put(url)
             .bearer_auth(token.access_token)
             .json(&body)
-            .send()?;
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -84,4 +94,18 @@ struct SpotifyDevice {
     id: String,
     name: String,
     volume_percent: Option<u32>,
+    #[serde(default)]
+    is_active: bool,
+    supports_volume: Option<bool>,
+}
+
+impl From<SpotifyDevice> for Device {
+    fn from(device: SpotifyDevice) -> Self {
+        Device {
+            id: device.id,
+            name: device.name,
+            volume_percent: device.volume_percent,
+            supports_volume: device.supports_volume,
+        }
+    }
 }
@@ -0,0 +1,308 @@
+use anyhow::{Context, bail};
+use reqwest::Method;
+use reqwest::blocking::Client as HttpClient;
+use serde::Deserialize;
+
+use crate::domain::episode::{Episode, SavedEpisode};
+use crate::error::Result;
+use crate::spotify::auth::AuthService;
+use crate::spotify::base::{api_base, push_market};
+use crate::spotify::cancel;
+use crate::spotify::concurrent::{MAX_IN_FLIGHT, fetch_all, remaining_offsets};
+use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
+
+/// Spotify podcast episode API client.
+#[derive(Debug, Clone)]
+pub struct EpisodesClient {
+    http: HttpClient,
+    auth: AuthService,
+}
+
+impl EpisodesClient {
+    pub fn new(http: HttpClient, auth: AuthService) -> Self {
+        Self { http, auth }
+    }
+
+    pub fn get(&self, episode_id: &str) -> Result<Episode> {
+        let token = self.auth.token()?;
+        let mut url = format!("{}/episodes/{episode_id}", api_base());
+        push_market(&mut url, false);
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify episode request failed",
+                status,
+                &body
+            ));
+        }
+
+        let payload: SpotifyEpisode = response.json()?;
+        Ok(map_episode(payload))
+    }
+
+    /// All episodes of a show, newest first.
+    pub fn for_show(&self, show_id: &str) -> Result<Vec<Episode>> {
+        let token = self.auth.token()?;
+        let access_token = token.access_token.as_str();
+        let first = self.show_page(&show_episodes_url(show_id, 0), access_token)?;
+        let mut next = first.next.clone();
+        let total = first.total;
+        let mut episodes: Vec<Episode> =
+            first.items.into_iter().flatten().map(map_episode).collect();
+
+        // With a known total the remaining pages can be requested side by side.
+        if let (Some(_), Some(total)) = (&next, total) {
+            let offsets = remaining_offsets(SHOW_PAGE, total);
+            let pages = fetch_all(
+                &offsets,
+                MAX_IN_FLIGHT,
+                |offset| self.show_page(&show_episodes_url(show_id, *offset), access_token),
+                |_| {},
+            )?;
+            for page in pages {
+                episodes.extend(page.items.into_iter().flatten().map(map_episode));
+            }
+            return Ok(episodes);
+        }
+
+        while let Some(url) = next {
+            cancel::check()?;
+            let page = self.show_page(&url, access_token)?;
+            episodes.extend(page.items.into_iter().flatten().map(map_episode));
+            next = page.next;
+        }
+
+        Ok(episodes)
+    }
+
+    /// Newest episode of a show.
+    pub fn latest(&self, show_id: &str) -> Result<Option<Episode>> {
+        let token = self.auth.token()?;
+        let mut url = format!("{}/shows/{show_id}/episodes?limit=1", api_base());
+        push_market(&mut url, false);
+        let page = self.show_page(&url, &token.access_token)?;
+        Ok(page.items.into_iter().flatten().next().map(map_episode))
+    }
+
+    /// Episodes matching a search query, in Spotify's order.
+    pub fn search(&self, query: &str, limit: u32) -> Result<Vec<Episode>> {
+        let token = self.auth.token()?;
+        let mut url = format!(
+            "{}/search?q={}&type=episode&limit={limit}",
+            api_base(),
+            urlencoding::encode(query)
+        );
+        push_market(&mut url, false);
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error("spotify search failed", status, &body));
+        }
+
+        let payload: SearchEpisodesResponse = response.json()?;
+        Ok(payload
+            .episodes
+            .map(|page| page.items.into_iter().flatten().map(map_episode).collect())
+            .unwrap_or_default())
+    }
+
+    /// Saved episodes, most recently saved first; `None` fetches every page, otherwise up to
+    /// `limit`.
+    pub fn saved(&self, limit: Option<u32>) -> Result<Vec<SavedEpisode>> {
+        let token = self.auth.token()?;
+        let page = limit.map_or(SAVED_PAGE, |limit| limit.clamp(1, SAVED_PAGE));
+        let mut url = format!("{}/me/episodes?limit={page}", api_base());
+        push_market(&mut url, false);
+        let mut saved = Vec::new();
+
+        loop {
+            cancel::check()?;
+            let response = self
+                .http
+                .get(&url)
+                .bearer_auth(token.access_token.clone())
+                .send_traced()?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+                bail!(format_api_error(
+                    "spotify saved episodes failed",
+                    status,
+                    &body
+                ));
+            }
+
+            let payload: SavedEpisodesResponse = response.json()?;
+            saved.extend(
+                payload
+                    .items
+                    .into_iter()
+                    .flatten()
+                    .map(|item| SavedEpisode {
+                        episode: map_episode(item.episode),
+                        added_at: item.added_at,
+                    }),
+            );
+
+            match (payload.next, limit) {
+                (Some(_), Some(limit)) if saved.len() >= limit as usize => break,
+                (Some(next), _) => url = next,
+                (None, _) => break,
+            }
+        }
+
+        if let Some(limit) = limit {
+            saved.truncate(limit as usize);
+        }
+        Ok(saved)
+    }
+
+    fn show_page(&self, url: &str, access_token: &str) -> Result<ShowEpisodesResponse> {
+        let response = self.http.get(url).bearer_auth(access_token).send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify show episodes failed",
+                status,
+                &body
+            ));
+        }
+
+        Ok(response.json()?)
+    }
+
+    pub fn save(&self, ids: &[String]) -> Result<()> {
+        self.set_saved(Method::PUT, ids)
+    }
+
+    pub fn remove(&self, ids: &[String]) -> Result<()> {
+        self.set_saved(Method::DELETE, ids)
+    }
+
+    fn set_saved(&self, method: Method, ids: &[String]) -> Result<()> {
+        let token = self.auth.token()?;
+        for chunk in ids.chunks(SAVED_PAGE as usize) {
+            let url = format!("{}/me/episodes?ids={}", api_base(), chunk.join(","));
+            let response = self
+                .http
+                .request(method.clone(), url)
+                .bearer_auth(&token.access_token)
+                .body(Vec::new())
+                .send_traced()
+                .context("spotify request failed")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+                bail!(format_api_error(
+                    "spotify saved episodes request failed",
+                    status,
+                    &body
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Spotify caps show episode pages at 50.
+const SHOW_PAGE: u32 = 50;
+/// Spotify caps saved item pages at 50.
+const SAVED_PAGE: u32 = 50;
+
+fn show_episodes_url(show_id: &str, offset: u32) -> String {
+    let mut url = format!(
+        "{}/shows/{show_id}/episodes?limit={SHOW_PAGE}&offset={offset}",
+        api_base()
+    );
+    push_market(&mut url, false);
+    url
+}
+
+fn map_episode(payload: SpotifyEpisode) -> Episode {
+    let resume_point = payload.resume_point;
+    Episode {
+        id: payload.id,
+        name: payload.name,
+        uri: payload.uri,
+        show: payload.show.map(|show| show.name),
+        description: payload.description,
+        duration_ms: payload.duration_ms,
+        release_date: payload.release_date,
+        resume_position_ms: resume_point.as_ref().map(|point| point.resume_position_ms),
+        fully_played: resume_point.is_some_and(|point| point.fully_played),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ShowEpisodesResponse {
+    items: Vec<Option<SpotifyEpisode>>,
+    next: Option<String>,
+    total: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedEpisodesResponse {
+    items: Vec<Option<SavedEpisodeItem>>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedEpisodeItem {
+    added_at: Option<String>,
+    episode: SpotifyEpisode,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchEpisodesResponse {
+    episodes: Option<SearchEpisodesPage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchEpisodesPage {
+    items: Vec<Option<SpotifyEpisode>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyEpisode {
+    id: String,
+    name: String,
+    uri: String,
+    duration_ms: u32,
+    release_date: Option<String>,
+    description: Option<String>,
+    show: Option<SpotifyShowRef>,
+    resume_point: Option<SpotifyResumePoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyShowRef {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyResumePoint {
+    #[serde(default)]
+    fully_played: bool,
+    #[serde(default)]
+    resume_position_ms: u32,
+}
@@ -1,6 +1,31 @@
+use std::fmt;
+
 use reqwest::StatusCode;
 
-pub fn format_api_error(operation: &str, status: StatusCode, body: &str) -> String {
+use crate::error::ErrorKind;
+
+/// Unsuccessful Spotify API response, keeping the status for exit codes.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::from_status(self.status.as_u16())
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+pub fn format_api_error(operation: &str, status: StatusCode, body: &str) -> ApiError {
     let mut message = format!("{operation}: {} {}", status, body);
 
     if body.contains("Insufficient client scope") {
@@ -11,7 +36,7 @@ pub fn format_api_error(operation: &str, status: StatusCode, body: &str) -> Stri
         message.push_str("; hint: playlist may be read-only or missing modify scope, re-run `spotify auth login`");
     }
 
-    message
+    ApiError { status, message }
 }
 
 #[cfg(test)]
@@ -26,12 +51,12 @@ mod tests {
             StatusCode::FORBIDDEN,
             r#"{"error":{"message":"Insufficient client scope"}}"#,
         );
-        assert!(message.contains("missing scope"));
+        assert!(message.to_string().contains("missing scope"));
     }
 
     #[test]
     fn adds_unauthorized_hint() {
         let message = format_api_error("spotify request failed", StatusCode::UNAUTHORIZED, "{}");
-        assert!(message.contains("token expired"));
+        assert!(message.to_string().contains("token expired"));
     }
 }
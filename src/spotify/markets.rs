@@ -0,0 +1,70 @@
+use anyhow::{Context, bail};
+use reqwest::blocking::Client as HttpClient;
+use serde::Deserialize;
+
+use crate::error::Result;
+use crate::spotify::auth::AuthService;
+use crate::spotify::base::api_base;
+use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
+
+/// Spotify markets API client.
+#[derive(Debug, Clone)]
+pub struct MarketsClient {
+    http: HttpClient,
+    auth: AuthService,
+}
+
+impl MarketsClient {
+    pub fn new(http: HttpClient, auth: AuthService) -> Self {
+        Self { http, auth }
+    }
+
+    /// Market codes where Spotify is available.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let url = format!("{}/markets", api_base());
+        let payload: SpotifyMarkets = self.get(&url, "spotify markets request failed")?;
+        Ok(payload.markets)
+    }
+
+    /// Name and `available_markets` of a track or album.
+    ///
+    /// No `market` is sent: Spotify omits `available_markets` when one is given.
+    pub fn availability(&self, kind: &str, id: &str) -> Result<(String, Vec<String>)> {
+        let url = format!("{}/{kind}s/{id}", api_base());
+        let payload: SpotifyAvailability =
+            self.get(&url, &format!("spotify {kind} request failed"))?;
+        Ok((payload.name, payload.available_markets))
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, url: &str, failure: &str) -> Result<T> {
+        let token = self.auth.token()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()
+            .context("spotify request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(failure, status, &body));
+        }
+
+        Ok(response.json()?)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyMarkets {
+    #[serde(default)]
+    markets: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyAvailability {
+    name: String,
+    #[serde(default)]
+    available_markets: Vec<String>,
+}
@@ -1,12 +1,19 @@
 //! Spotify API clients (auth, playback, search, playlists, etc).
 pub mod albums;
 pub mod artists;
+pub mod audiobooks;
 pub mod auth;
 pub mod base;
+pub mod cancel;
 pub mod client;
+pub mod concurrent;
 pub mod devices;
+pub mod episodes;
 pub mod error;
+pub mod markets;
 pub mod playback;
 pub mod playlists;
 pub mod search;
+pub mod shows;
+pub mod trace;
 pub mod track;
@@ -5,12 +5,14 @@ use serde::Deserialize;
 use serde_json::json;
 
 use crate::domain::device::Device;
-use crate::domain::player::{PlaybackContext, PlayerStatus};
+use crate::domain::player::{PlaybackContext, PlayerStatus, PlayingEpisode, PlayingItem};
+use crate::domain::queue::{QueueItem, QueueItemKind};
 use crate::domain::track::Track;
 use crate::error::Result;
 use crate::spotify::auth::AuthService;
 use crate::spotify::base::api_base;
 use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
 
 /// Spotify playback API client.
 #[derive(Debug, Clone)]
@@ -21,8 +23,8 @@ pub struct PlaybackClient {
 
 #[derive(Debug)]
 pub struct QueueState {
-    pub now_playing: Option<Track>,
-    pub queue: Vec<Track>,
+    pub now_playing: Option<QueueItem>,
+    pub queue: Vec<QueueItem>,
 }
 
 impl PlaybackClient {
@@ -51,11 +53,37 @@ impl PlaybackClient {
         self.send(Method::PUT, "/me/player/play", Some(body))
     }
 
+    /// Play a playlist or album from its item at `position` (0-based).
+    pub fn play_context_from(&self, uri: &str, position: u32) -> Result<()> {
+        let body = json!({ "context_uri": uri, "offset": { "position": position } });
+        self.send(Method::PUT, "/me/player/play", Some(body))
+    }
+
     pub fn play_track(&self, uri: &str) -> Result<()> {
         let body = json!({ "uris": [uri] });
         self.send(Method::PUT, "/me/player/play", Some(body))
     }
 
+    pub fn play_uris(&self, uris: &[String]) -> Result<()> {
+        let body = json!({ "uris": uris });
+        self.send(Method::PUT, "/me/player/play", Some(body))
+    }
+
+    pub fn play_track_at(&self, uri: &str, position_ms: u32) -> Result<()> {
+        let body = json!({ "uris": [uri], "position_ms": position_ms });
+        self.send(Method::PUT, "/me/player/play", Some(body))
+    }
+
+    /// Play a playlist or album from `track_uri` at `position_ms`.
+    pub fn play_context_at(&self, uri: &str, track_uri: &str, position_ms: u32) -> Result<()> {
+        let body = json!({
+            "context_uri": uri,
+            "offset": { "uri": track_uri },
+            "position_ms": position_ms
+        });
+        self.send(Method::PUT, "/me/player/play", Some(body))
+    }
+
     pub fn status(&self) -> Result<PlayerStatus> {
         let token = self.auth.token()?;
         let url = format!("{}/me/player", api_base());
@@ -64,7 +92,7 @@ impl PlaybackClient {
             .http
             .get(url)
             .bearer_auth(token.access_token)
-            .send()
+            .send_traced()
             .context("spotify status request failed")?;
 
         if response.status() == reqwest::StatusCode::NO_CONTENT {
@@ -89,6 +117,11 @@ impl PlaybackClient {
         Ok(payload.into())
     }
 
+    pub fn seek(&self, position_ms: u32) -> Result<()> {
+        let path = format!("/me/player/seek?position_ms={}", position_ms);
+        self.send(Method::PUT, &path, None)
+    }
+
     pub fn shuffle(&self, state: bool) -> Result<()> {
         let path = format!("/me/player/shuffle?state={}", state);
         self.send(Method::PUT, &path, None)
@@ -104,6 +137,11 @@ impl PlaybackClient {
         self.send(Method::PUT, &path, None)
     }
 
+    pub fn add_to_queue(&self, uri: &str) -> Result<()> {
+        let path = format!("/me/player/queue?uri={}", urlencoding::encode(uri));
+        self.send(Method::POST, &path, None)
+    }
+
     pub fn queue(&self, limit: u32) -> Result<QueueState> {
         let token = self.auth.token()?;
         let url = format!("{}/me/player/queue", api_base());
@@ -112,7 +150,7 @@ impl PlaybackClient {
             .http
             .get(url)
             .bearer_auth(token.access_token)
-            .send()
+            .send_traced()
             .context("spotify queue request failed")?;
 
         if !response.status().is_success() {
@@ -122,11 +160,11 @@ impl PlaybackClient {
         }
 
         let payload: SpotifyQueueResponse = response.json()?;
-        let now_playing = payload.currently_playing.and_then(map_track);
+        let now_playing = payload.currently_playing.and_then(map_queue_item);
         let mut queue = Vec::new();
-        for track in payload.queue {
-            if let Some(track) = map_track(track) {
-                queue.push(track);
+        for item in payload.queue {
+            if let Some(item) = map_queue_item(item) {
+                queue.push(item);
             }
             if queue.len() >= limit as usize {
                 break;
@@ -135,6 +173,76 @@ impl PlaybackClient {
         Ok(QueueState { now_playing, queue })
     }
 
+    /// The episode being played, if any; `status` only reports tracks.
+    pub fn current_episode(&self) -> Result<Option<PlayingEpisode>> {
+        let Some(payload) = self.item_status()? else {
+            return Ok(None);
+        };
+        if payload.currently_playing_type.as_deref() != Some("episode") {
+            return Ok(None);
+        }
+        Ok(payload.item.and_then(|item| {
+            Some(PlayingEpisode {
+                id: item.id?,
+                uri: item.uri?,
+                show_id: item.show.map(|show| show.id),
+            })
+        }))
+    }
+
+    /// The item being played, whether a track, a podcast episode, or an audiobook chapter.
+    pub fn current_item(&self) -> Result<Option<PlayingItem>> {
+        Ok(self.item_status()?.and_then(playing_item))
+    }
+
+    fn item_status(&self) -> Result<Option<SpotifyItemStatus>> {
+        let token = self.auth.token()?;
+        let url = format!("{}/me/player?additional_types=episode", api_base());
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()
+            .context("spotify status request failed")?;
+
+        if response.status() == reqwest::StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error("spotify status failed", status, &body));
+        }
+
+        Ok(Some(response.json()?))
+    }
+
+    /// Display name of a playback context (playlist, album, artist, show, or audiobook).
+    pub fn context_name(&self, kind: &str, id: &str) -> Result<String> {
+        let token = self.auth.token()?;
+        let mut url = format!("{}/{kind}s/{id}", api_base());
+        if kind == "playlist" {
+            url.push_str("?fields=name");
+        }
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()
+            .context("spotify context request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error("spotify context failed", status, &body));
+        }
+
+        let payload: SpotifyNamed = response.json()?;
+        Ok(payload.name)
+    }
+
     fn send(&self, method: Method, path: &str, body: Option<serde_json::Value>) -> Result<()> {
         let token = self.auth.token()?;
         let url = format!("{}{}", api_base(), path);
@@ -149,7 +257,7 @@ impl PlaybackClient {
             request = request.body(Vec::new());
         }
 
-        let response = request.send().context("spotify request failed")?;
+        let response = request.send_traced().context("spotify request failed")?;
 
         if response.status().is_success() {
             return Ok(());
@@ -179,6 +287,8 @@ struct SpotifyTrack {
     name: String,
     duration_ms: Option<u32>,
     album: Option<SpotifyAlbum>,
+    // Episodes in the same slot have no artists.
+    #[serde(default)]
     artists: Vec<SpotifyArtist>,
 }
 
@@ -193,6 +303,7 @@ struct SpotifyDevice {
     id: String,
     name: String,
     volume_percent: Option<u32>,
+    supports_volume: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -208,11 +319,48 @@ struct SpotifyContext {
     uri: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct SpotifyItemStatus {
+    currently_playing_type: Option<String>,
+    item: Option<SpotifyPlayingItem>,
+    context: Option<SpotifyContext>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyPlayingItem {
+    // Optional so a local-file track in the same slot still parses.
+    id: Option<String>,
+    uri: Option<String>,
+    show: Option<SpotifyItemRef>,
+    audiobook: Option<SpotifyItemRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyItemRef {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyNamed {
+    name: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct SpotifyQueueResponse {
-    currently_playing: Option<SpotifyTrack>,
+    currently_playing: Option<SpotifyQueueItem>,
     #[serde(default)]
-    queue: Vec<SpotifyTrack>,
+    queue: Vec<SpotifyQueueItem>,
+}
+
+/// A track or podcast episode in the queue.
+#[derive(Debug, Deserialize)]
+struct SpotifyQueueItem {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    uri: Option<String>,
+    #[serde(flatten)]
+    track: SpotifyTrack,
+    show: Option<SpotifyNamed>,
 }
 
 impl From<SpotifyPlayerStatus> for PlayerStatus {
@@ -240,12 +388,17 @@ impl From<SpotifyPlayerStatus> for PlayerStatus {
             id: device.id,
             name: device.name,
             volume_percent: device.volume_percent,
+            supports_volume: device.supports_volume,
         });
 
         let context = value.context.and_then(|context| {
             let kind = context.kind?;
             let uri = context.uri?;
-            Some(PlaybackContext { kind, uri })
+            Some(PlaybackContext {
+                kind,
+                uri,
+                name: None,
+            })
         });
 
         PlayerStatus {
@@ -260,6 +413,47 @@ impl From<SpotifyPlayerStatus> for PlayerStatus {
     }
 }
 
+/// Audiobook chapters come through as episodes; the audiobook they belong to tells them apart.
+fn playing_item(payload: SpotifyItemStatus) -> Option<PlayingItem> {
+    let item = payload.item?;
+    let audiobook_id = item.audiobook.map(|audiobook| audiobook.id).or_else(|| {
+        let context = payload.context?;
+        let uri = context.uri?;
+        (context.kind.as_deref() == Some("audiobook"))
+            .then(|| uri.rsplit(':').next().unwrap_or_default().to_string())
+    });
+    if let Some(audiobook_id) = audiobook_id {
+        return Some(PlayingItem::Chapter { audiobook_id });
+    }
+    let id = item.id?;
+    match payload.currently_playing_type.as_deref() {
+        Some("track") => Some(PlayingItem::Track { id }),
+        Some("episode") => Some(PlayingItem::Episode(PlayingEpisode {
+            id,
+            uri: item.uri?,
+            show_id: item.show.map(|show| show.id),
+        })),
+        _ => None,
+    }
+}
+
+fn map_queue_item(item: SpotifyQueueItem) -> Option<QueueItem> {
+    let kind = match item.kind.as_deref() {
+        Some("episode") => QueueItemKind::Episode,
+        _ => QueueItemKind::Track,
+    };
+    let show = item.show.map(|show| show.name);
+    let mut track = map_track(item.track)?;
+    if let Some(show) = show {
+        // The show stands in for the artist column.
+        track.artists = vec![show];
+    }
+    let uri = item
+        .uri
+        .unwrap_or_else(|| format!("spotify:{}:{}", kind.as_str(), track.id));
+    Some(QueueItem { kind, uri, track })
+}
+
 fn map_track(item: SpotifyTrack) -> Option<Track> {
     item.id.map(|id| {
         let (album, album_id) = match item.album {
@@ -2,11 +2,19 @@ use anyhow::bail;
 use reqwest::blocking::Client as HttpClient;
 use serde::Deserialize;
 
-use crate::domain::playlist::{Playlist, PlaylistDetail};
+use crate::domain::cuesheet::CueItem;
+use crate::domain::playlist::{
+    Playlist, PlaylistDetail, PlaylistTrack, PlaylistTrackPage, TrackAvailability, UserPlaylist,
+    UserPlaylistPage,
+};
+use crate::domain::track::Track;
 use crate::error::Result;
 use crate::spotify::auth::AuthService;
-use crate::spotify::base::api_base;
+use crate::spotify::base::{api_base, push_market};
+use crate::spotify::cancel;
+use crate::spotify::concurrent::{MAX_IN_FLIGHT, fetch_all, remaining_offsets};
 use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
 
 /// Spotify playlists API client.
 #[derive(Debug, Clone)]
@@ -26,11 +34,12 @@ impl PlaylistsClient {
         let mut playlists = Vec::new();
 
         loop {
+            cancel::check()?;
             let response = self
                 .http
                 .get(&url)
                 .bearer_auth(token.access_token.clone())
-                .send()?;
+                .send_traced()?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -49,6 +58,7 @@ impl PlaylistsClient {
                 owner: item.owner.and_then(|owner| owner.display_name),
                 collaborative: item.collaborative,
                 public: item.public,
+                description: item.description,
             }));
 
             if let Some(next) = payload.next {
@@ -61,11 +71,61 @@ impl PlaylistsClient {
         Ok(playlists)
     }
 
+    /// Fetch one page of a user's public playlists.
+    pub fn user_page(&self, user_id: &str, limit: u32, offset: u32) -> Result<UserPlaylistPage> {
+        let payload = self.user_request(&user_playlists_url(user_id, limit, offset))?;
+        Ok(UserPlaylistPage {
+            total: payload.total.unwrap_or(0),
+            items: payload.items.into_iter().map(map_user_playlist).collect(),
+        })
+    }
+
+    /// Fetch every public playlist of a user, following `next` links.
+    pub fn user_all(&self, user_id: &str) -> Result<Vec<UserPlaylist>> {
+        let mut url = user_playlists_url(user_id, 50, 0);
+        let mut playlists = Vec::new();
+        loop {
+            cancel::check()?;
+            let payload = self.user_request(&url)?;
+            playlists.extend(payload.items.into_iter().map(map_user_playlist));
+            match payload.next {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+        Ok(playlists)
+    }
+
+    fn user_request(&self, url: &str) -> Result<PlaylistsResponse> {
+        let token = self.auth.token()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify user playlists request failed",
+                status,
+                &body
+            ));
+        }
+
+        Ok(response.json()?)
+    }
+
     pub fn get(&self, playlist_id: &str) -> Result<PlaylistDetail> {
         let token = self.auth.token()?;
         let url = format!("{}/playlists/{playlist_id}", api_base());
 
-        let response = self.http.get(url).bearer_auth(token.access_token).send()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -89,6 +149,122 @@ impl PlaylistsClient {
         })
     }
 
+    pub fn tracks(&self, playlist_id: &str) -> Result<Vec<Track>> {
+        let items = self.items_all(playlist_id)?;
+        Ok(items.into_iter().map(|item| item.track).collect())
+    }
+
+    /// Fetch every playlist entry, following pagination.
+    pub fn items_all(&self, playlist_id: &str) -> Result<Vec<PlaylistTrack>> {
+        self.items_all_with(playlist_id, |_, _| {})
+    }
+
+    /// Like `items_all`, reporting entries fetched so far and the total after each page.
+    pub fn items_all_with(
+        &self,
+        playlist_id: &str,
+        mut on_page: impl FnMut(u64, Option<u64>),
+    ) -> Result<Vec<PlaylistTrack>> {
+        let first = self.items_request(&items_url(playlist_id, ITEMS_PAGE, 0, false))?;
+        let total = first.total;
+        let mut fetched = first.items.len() as u64;
+        on_page(fetched, total.map(u64::from));
+        let mut items: Vec<PlaylistTrack> = first.items.into_iter().filter_map(map_item).collect();
+
+        // With a known total the remaining pages can be requested side by side.
+        if let (Some(_), Some(total)) = (&first.next, total) {
+            let offsets = remaining_offsets(ITEMS_PAGE, total);
+            let pages = fetch_all(
+                &offsets,
+                MAX_IN_FLIGHT,
+                |offset| self.items_request(&items_url(playlist_id, ITEMS_PAGE, *offset, false)),
+                |page| {
+                    fetched += page.items.len() as u64;
+                    on_page(fetched, Some(u64::from(total)));
+                },
+            )?;
+            for page in pages {
+                items.extend(page.items.into_iter().filter_map(map_item));
+            }
+            return Ok(items);
+        }
+
+        let mut next = first.next;
+        while let Some(url) = next {
+            cancel::check()?;
+            let payload = self.items_request(&url)?;
+            fetched += payload.items.len() as u64;
+            on_page(fetched, payload.total.map(u64::from));
+            items.extend(payload.items.into_iter().filter_map(map_item));
+            next = payload.next;
+        }
+
+        Ok(items)
+    }
+
+    /// Every track entry as the user's market sees it, and the number of entries.
+    ///
+    /// `market=from_token` makes Spotify report `is_playable` and swap in relinked
+    /// versions of tracks that do not play as saved.
+    pub fn availability(&self, playlist_id: &str) -> Result<(Vec<TrackAvailability>, usize)> {
+        let mut tracks = Vec::new();
+        let mut position = 0;
+        let mut next = Some(items_url(playlist_id, ITEMS_PAGE, 0, true));
+        while let Some(url) = next {
+            cancel::check()?;
+            let payload = self.items_request(&url)?;
+            for item in payload.items {
+                position += 1;
+                tracks.extend(map_availability(position, item));
+            }
+            next = payload.next;
+        }
+        Ok((tracks, position))
+    }
+
+    /// Every playlist item in order for a cue sheet, including local files and unavailable items.
+    pub fn cue_items(&self, playlist_id: &str) -> Result<Vec<CueItem>> {
+        let mut items = Vec::new();
+        let mut next = Some(items_url(playlist_id, ITEMS_PAGE, 0, false));
+        while let Some(url) = next {
+            cancel::check()?;
+            let payload = self.items_request(&url)?;
+            items.extend(payload.items.into_iter().map(map_cue_item));
+            next = payload.next;
+        }
+        Ok(items)
+    }
+
+    /// Fetch a single page of playlist entries.
+    pub fn items(&self, playlist_id: &str, limit: u32, offset: u32) -> Result<PlaylistTrackPage> {
+        let payload = self.items_request(&items_url(playlist_id, limit, offset, false))?;
+        Ok(PlaylistTrackPage {
+            total: payload.total.unwrap_or(0),
+            items: payload.items.into_iter().filter_map(map_item).collect(),
+        })
+    }
+
+    fn items_request(&self, url: &str) -> Result<PlaylistTracksResponse> {
+        let token = self.auth.token()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify playlist tracks request failed",
+                status,
+                &body
+            ));
+        }
+
+        Ok(response.json()?)
+    }
+
     pub fn create(&self, name: &str, public: Option<bool>) -> Result<PlaylistDetail> {
         let token = self.auth.token()?;
         let user_id = self.current_user_id(&token.access_token)?;
@@ -104,7 +280,7 @@ impl PlaylistsClient {
             .post(url)
             .bearer_auth(token.access_token)
             .json(&body)
-            .send()?;
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -138,7 +314,7 @@ impl PlaylistsClient {
             .put(url)
             .bearer_auth(token.access_token)
             .json(&body)
-            .send()?;
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -152,6 +328,39 @@ impl PlaylistsClient {
         Ok(())
     }
 
+    /// Set the public and collaborative flags; `public: None` leaves it unchanged.
+    pub fn set_visibility(
+        &self,
+        playlist_id: &str,
+        public: Option<bool>,
+        collaborative: bool,
+    ) -> Result<()> {
+        let token = self.auth.token()?;
+        let url = format!("{}/playlists/{playlist_id}", api_base());
+        let mut body = serde_json::json!({ "collaborative": collaborative });
+        if let Some(public) = public {
+            body["public"] = serde_json::Value::Bool(public);
+        }
+
+        let response = self
+            .http
+            .put(url)
+            .bearer_auth(token.access_token)
+            .json(&body)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify playlist edit failed",
+                status,
+                &body
+            ));
+        }
+        Ok(())
+    }
+
     pub fn delete(&self, playlist_id: &str) -> Result<()> {
         self.unfollow(playlist_id)
     }
@@ -165,7 +374,7 @@ impl PlaylistsClient {
             .put(url)
             .bearer_auth(token.access_token)
             .body(Vec::new())
-            .send()?;
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -188,7 +397,7 @@ impl PlaylistsClient {
             .delete(url)
             .bearer_auth(token.access_token)
             .body(Vec::new())
-            .send()?;
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -202,7 +411,8 @@ impl PlaylistsClient {
         Ok(())
     }
 
-    pub fn add_tracks(&self, playlist_id: &str, uris: &[String]) -> Result<()> {
+    /// Add tracks, returning the playlist's new snapshot id.
+    pub fn add_tracks(&self, playlist_id: &str, uris: &[String]) -> Result<Option<String>> {
         let token = self.auth.token()?;
         let url = format!("{}/playlists/{playlist_id}/tracks", api_base());
 
@@ -211,7 +421,7 @@ impl PlaylistsClient {
             .post(url)
             .bearer_auth(token.access_token)
             .json(&serde_json::json!({ "uris": uris }))
-            .send()?;
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -222,12 +432,75 @@ impl PlaylistsClient {
                 &body
             ));
         }
-        Ok(())
+        let payload: SnapshotResponse = response.json().unwrap_or_default();
+        Ok(payload.snapshot_id)
+    }
+
+    /// Remove tracks, returning the playlist's new snapshot id.
+    pub fn remove_tracks(&self, playlist_id: &str, uris: &[String]) -> Result<Option<String>> {
+        let token = self.auth.token()?;
+        let url = format!("{}/playlists/{playlist_id}/tracks", api_base());
+        let tracks: Vec<serde_json::Value> = uris
+            .iter()
+            .map(|uri| serde_json::json!({ "uri": uri }))
+            .collect();
+
+        let response = self
+            .http
+            .delete(url)
+            .bearer_auth(token.access_token)
+            .json(&serde_json::json!({ "tracks": tracks }))
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify playlist remove failed",
+                status,
+                &body
+            ));
+        }
+        let payload: SnapshotResponse = response.json().unwrap_or_default();
+        Ok(payload.snapshot_id)
+    }
+
+    /// Move the track at `range_start` before `insert_before`, returning the new snapshot id.
+    pub fn reorder(
+        &self,
+        playlist_id: &str,
+        range_start: u32,
+        insert_before: u32,
+    ) -> Result<Option<String>> {
+        let token = self.auth.token()?;
+        let url = format!("{}/playlists/{playlist_id}/tracks", api_base());
+
+        let response = self
+            .http
+            .put(url)
+            .bearer_auth(token.access_token)
+            .json(&serde_json::json!({
+                "range_start": range_start,
+                "insert_before": insert_before,
+            }))
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify playlist reorder failed",
+                status,
+                &body
+            ));
+        }
+        let payload: SnapshotResponse = response.json().unwrap_or_default();
+        Ok(payload.snapshot_id)
     }
 
     fn current_user_id(&self, access_token: &str) -> Result<String> {
         let url = format!("{}/me", api_base());
-        let response = self.http.get(url).bearer_auth(access_token).send()?;
+        let response = self.http.get(url).bearer_auth(access_token).send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -244,10 +517,17 @@ impl PlaylistsClient {
     }
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct SnapshotResponse {
+    snapshot_id: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct PlaylistsResponse {
     items: Vec<SpotifyPlaylist>,
     next: Option<String>,
+    #[serde(default)]
+    total: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -258,10 +538,16 @@ struct SpotifyPlaylist {
     #[serde(default)]
     collaborative: bool,
     public: Option<bool>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    tracks: Option<SpotifyTracks>,
 }
 
 #[derive(Debug, Deserialize)]
 struct SpotifyOwner {
+    #[serde(default)]
+    id: Option<String>,
     display_name: Option<String>,
 }
 
@@ -286,3 +572,196 @@ struct PlaylistDetailResponse {
 struct SpotifyTracks {
     total: u32,
 }
+
+#[derive(Debug, Deserialize)]
+struct PlaylistTracksResponse {
+    items: Vec<PlaylistTrackItem>,
+    next: Option<String>,
+    total: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlaylistTrackItem {
+    track: Option<SpotifyTrack>,
+    added_at: Option<String>,
+    added_by: Option<SpotifyAddedBy>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyAddedBy {
+    id: Option<String>,
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyTrack {
+    id: Option<String>,
+    name: String,
+    duration_ms: Option<u32>,
+    album: Option<SpotifyAlbumRef>,
+    #[serde(default)]
+    artists: Vec<SpotifyArtistRef>,
+    popularity: Option<u32>,
+    explicit: Option<bool>,
+    // Read by `availability` and `cue_items` only.
+    uri: Option<String>,
+    is_playable: Option<bool>,
+    linked_from: Option<SpotifyLinkedFrom>,
+    restrictions: Option<SpotifyRestrictions>,
+    external_ids: Option<SpotifyExternalIds>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyLinkedFrom {
+    uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyRestrictions {
+    reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyExternalIds {
+    isrc: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyAlbumRef {
+    id: Option<String>,
+    name: String,
+    release_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyArtistRef {
+    id: Option<String>,
+    name: String,
+}
+
+/// Spotify caps playlist item pages at 100.
+const ITEMS_PAGE: u32 = 100;
+
+fn items_url(playlist_id: &str, limit: u32, offset: u32, market_from_token: bool) -> String {
+    let mut url = format!(
+        "{}/playlists/{playlist_id}/tracks?limit={limit}&offset={offset}",
+        api_base()
+    );
+    push_market(&mut url, market_from_token);
+    url
+}
+
+fn map_cue_item(item: PlaylistTrackItem) -> CueItem {
+    let Some(track) = item.track else {
+        return CueItem {
+            title: "(unavailable)".to_string(),
+            performer: String::new(),
+            uri: String::new(),
+            duration_ms: 0,
+        };
+    };
+    let uri = track
+        .uri
+        .or_else(|| track.id.map(|id| format!("spotify:track:{id}")))
+        .unwrap_or_default();
+    CueItem {
+        title: track.name,
+        performer: track
+            .artists
+            .into_iter()
+            .map(|artist| artist.name)
+            .collect::<Vec<_>>()
+            .join(", "),
+        uri,
+        duration_ms: track.duration_ms.unwrap_or(0) as u64,
+    }
+}
+
+fn map_availability(position: usize, item: PlaylistTrackItem) -> Option<TrackAvailability> {
+    let mut track = item.track?;
+    let uri = track.uri.take()?;
+    let playable = track.is_playable.unwrap_or(true);
+    let linked_from = track.linked_from.take().map(|linked| linked.uri);
+    let restriction = track
+        .restrictions
+        .take()
+        .map(|restrictions| restrictions.reason);
+    let isrc = track.external_ids.take().and_then(|ids| ids.isrc);
+    Some(TrackAvailability {
+        position,
+        uri,
+        track: map_track(track)?,
+        playable,
+        linked_from,
+        restriction,
+        isrc,
+    })
+}
+
+fn map_item(item: PlaylistTrackItem) -> Option<PlaylistTrack> {
+    let track = item.track?;
+    let popularity = track.popularity;
+    let explicit = track.explicit;
+    let release_date = track
+        .album
+        .as_ref()
+        .and_then(|album| album.release_date.clone());
+    let track = map_track(track)?;
+    Some(PlaylistTrack {
+        track,
+        popularity,
+        explicit,
+        release_date,
+        added_at: item.added_at,
+        added_by: item
+            .added_by
+            .and_then(|user| user.display_name.or(user.id))
+            .filter(|name| !name.is_empty()),
+    })
+}
+
+fn map_track(item: SpotifyTrack) -> Option<Track> {
+    item.id.map(|id| {
+        let (album, album_id) = match item.album {
+            Some(album) => (Some(album.name), album.id),
+            None => (None, None),
+        };
+
+        Track {
+            id,
+            name: item.name,
+            album,
+            album_id,
+            artists: item.artists.iter().map(|a| a.name.clone()).collect(),
+            artist_ids: item.artists.into_iter().filter_map(|a| a.id).collect(),
+            duration_ms: item.duration_ms,
+        }
+    })
+}
+
+fn user_playlists_url(user_id: &str, limit: u32, offset: u32) -> String {
+    format!(
+        "{}/users/{}/playlists?limit={limit}&offset={offset}",
+        api_base(),
+        urlencoding::encode(user_id)
+    )
+}
+
+fn map_user_playlist(item: SpotifyPlaylist) -> UserPlaylist {
+    let (owner, owner_id) = match item.owner {
+        Some(owner) => (owner.display_name, owner.id),
+        None => (None, None),
+    };
+    UserPlaylist {
+        playlist: Playlist {
+            id: item.id,
+            name: item.name,
+            owner,
+            collaborative: item.collaborative,
+            public: item.public,
+            description: item.description,
+        },
+        owner_id,
+        tracks_total: item.tracks.map(|tracks| tracks.total),
+    }
+}
@@ -3,10 +3,13 @@ use reqwest::blocking::Client as HttpClient;
 use serde::Deserialize;
 
 use crate::domain::search::{SearchItem, SearchResults, SearchType};
-use crate::error::Result;
+use crate::domain::session::PlayedTrack;
+use crate::error::{ErrorKind, Result};
 use crate::spotify::auth::AuthService;
-use crate::spotify::base::api_base;
+use crate::spotify::base::{api_base, push_market};
+use crate::spotify::concurrent::{MAX_IN_FLIGHT, fetch_all};
 use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
 
 /// Spotify search API client.
 #[derive(Debug, Clone)]
@@ -15,6 +18,30 @@ pub struct SearchClient {
     auth: AuthService,
 }
 
+/// One type's slice of a multi-type search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeQuery {
+    pub kind: SearchType,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+/// Results for one [`TypeQuery`], with Spotify's total match count for the type.
+#[derive(Debug)]
+pub struct TypePage {
+    pub kind: SearchType,
+    pub items: Vec<SearchItem>,
+    pub total: u32,
+}
+
+/// A page of the recently played history.
+#[derive(Debug)]
+pub struct PlayedPage {
+    pub plays: Vec<PlayedTrack>,
+    /// Cursor for the next, older page; `None` at the end of the history.
+    pub before: Option<u64>,
+}
+
 impl SearchClient {
     pub fn new(http: HttpClient, auth: AuthService) -> Self {
         Self { http, auth }
@@ -28,38 +55,84 @@ impl SearchClient {
         market_from_token: bool,
     ) -> Result<SearchResults> {
         if kind == SearchType::All {
-            let mut items = Vec::new();
-            let kinds = [
-                SearchType::Track,
-                SearchType::Album,
-                SearchType::Artist,
-                SearchType::Playlist,
-            ];
-            for kind in kinds {
-                let results = self.search(query, kind, limit, market_from_token)?;
-                items.extend(results.items);
-            }
+            let queries = SearchType::EACH.map(|kind| TypeQuery {
+                kind,
+                limit,
+                offset: 0,
+            });
+            let pages = self.search_types(query, &queries, market_from_token)?;
             return Ok(SearchResults {
                 kind: SearchType::All,
-                items,
+                items: pages.into_iter().flat_map(|page| page.items).collect(),
             });
         }
 
+        let (items, _) = self.search_page(query, kind, limit, 0, market_from_token)?;
+        Ok(SearchResults { kind, items })
+    }
+
+    /// Search several types at once, one request per type, returning pages in query order.
+    ///
+    /// Spotify applies one limit and offset to every type of a combined request and ranks
+    /// worse across them, so each type gets its own call, run with bounded concurrency.
+    pub fn search_types(
+        &self,
+        query: &str,
+        queries: &[TypeQuery],
+        market_from_token: bool,
+    ) -> Result<Vec<TypePage>> {
+        fetch_all(
+            queries,
+            MAX_IN_FLIGHT,
+            |request| {
+                let (items, total) = self.search_page(
+                    query,
+                    request.kind,
+                    request.limit,
+                    request.offset,
+                    market_from_token,
+                )?;
+                Ok(TypePage {
+                    kind: request.kind,
+                    items,
+                    total,
+                })
+            },
+            |_| {},
+        )
+    }
+
+    /// One page of results for a single type, with Spotify's total match count.
+    pub fn search_page(
+        &self,
+        query: &str,
+        kind: SearchType,
+        limit: u32,
+        offset: u32,
+        market_from_token: bool,
+    ) -> Result<(Vec<SearchItem>, u32)> {
+        if kind == SearchType::All {
+            bail!(ErrorKind::Validation.error("search pages are per type"));
+        }
+
         let token = self.auth.token()?;
         let kind_param = search_type_param(kind);
         let mut url = format!(
-            "{}/search?q={}&type={}&limit={}",
+            "{}/search?q={}&type={}&limit={}&offset={}",
             api_base(),
             urlencoding::encode(query),
             kind_param,
-            limit
+            limit,
+            offset
         );
 
-        if market_from_token {
-            url.push_str("&market=from_token");
-        }
+        push_market(&mut url, market_from_token);
 
-        let response = self.http.get(url).bearer_auth(token.access_token).send()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -68,6 +141,13 @@ impl SearchClient {
         }
 
         let payload: SearchResponse = response.json()?;
+        let total = match kind {
+            SearchType::Track => payload.tracks.as_ref().and_then(|list| list.total),
+            SearchType::Album => payload.albums.as_ref().and_then(|list| list.total),
+            SearchType::Artist => payload.artists.as_ref().and_then(|list| list.total),
+            SearchType::Playlist => payload.playlists.as_ref().and_then(|list| list.total),
+            SearchType::All => None,
+        };
         let items = match kind {
             SearchType::Track => payload
                 .tracks
@@ -75,17 +155,7 @@ impl SearchClient {
                     list.items
                         .into_iter()
                         .flatten()
-                        .map(|item| SearchItem {
-                            id: item.id,
-                            name: item.name,
-                            uri: item.uri,
-                            kind: SearchType::Track,
-                            artists: item.artists.into_iter().map(|artist| artist.name).collect(),
-                            album: item.album.map(|album| album.name),
-                            duration_ms: item.duration_ms,
-                            owner: None,
-                            score: None,
-                        })
+                        .map(map_track)
                         .collect::<Vec<_>>()
                 })
                 .unwrap_or_default(),
@@ -95,17 +165,7 @@ impl SearchClient {
                     list.items
                         .into_iter()
                         .flatten()
-                        .map(|item| SearchItem {
-                            id: item.id,
-                            name: item.name,
-                            uri: item.uri,
-                            kind: SearchType::Album,
-                            artists: item.artists.into_iter().map(|artist| artist.name).collect(),
-                            album: None,
-                            duration_ms: None,
-                            owner: None,
-                            score: None,
-                        })
+                        .map(map_album)
                         .collect::<Vec<_>>()
                 })
                 .unwrap_or_default(),
@@ -115,17 +175,7 @@ impl SearchClient {
                     list.items
                         .into_iter()
                         .flatten()
-                        .map(|item| SearchItem {
-                            id: item.id,
-                            name: item.name,
-                            uri: item.uri,
-                            kind: SearchType::Artist,
-                            artists: Vec::new(),
-                            album: None,
-                            duration_ms: None,
-                            owner: None,
-                            score: None,
-                        })
+                        .map(map_artist)
                         .collect::<Vec<_>>()
                 })
                 .unwrap_or_default(),
@@ -145,6 +195,11 @@ impl SearchClient {
                             duration_ms: None,
                             owner: item.owner.and_then(|owner| owner.display_name),
                             score: None,
+                            playable: None,
+                            linked_from: None,
+                            saved: None,
+                            explicit: None,
+                            popularity: None,
                         })
                         .collect::<Vec<_>>()
                 })
@@ -152,36 +207,258 @@ impl SearchClient {
             SearchType::All => Vec::new(),
         };
 
-        Ok(SearchResults { kind, items })
+        let total = total.unwrap_or(items.len() as u32);
+        Ok((items, total))
     }
 
-    pub fn recently_played(&self, limit: u32) -> Result<Vec<SearchItem>> {
+    /// Fetch one page of the user's top tracks or artists.
+    pub fn top(
+        &self,
+        kind: SearchType,
+        time_range: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<SearchItem>> {
         let token = self.auth.token()?;
-        let url = format!("{}/me/player/recently-played?limit={}", api_base(), limit);
+        let path = match kind {
+            SearchType::Artist => "artists",
+            SearchType::Track => "tracks",
+            _ => bail!(
+                ErrorKind::Unsupported.error("top items are only available for tracks and artists")
+            ),
+        };
+        let url = format!(
+            "{}/me/top/{path}?time_range={time_range}&limit={limit}&offset={offset}",
+            api_base()
+        );
 
-        let response = self.http.get(url).bearer_auth(token.access_token).send()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error("spotify top items failed", status, &body));
+        }
+
+        let items = if kind == SearchType::Artist {
+            let payload: TopResponse<SpotifyArtist> = response.json()?;
+            payload.items.into_iter().map(map_artist).collect()
+        } else {
+            let payload: TopResponse<SpotifyTrack> = response.json()?;
+            payload.items.into_iter().map(map_track).collect()
+        };
+        Ok(items)
+    }
+
+    /// Tracks similar to a seed track or artist.
+    pub fn recommendations(
+        &self,
+        seed: SearchType,
+        seed_id: &str,
+        limit: u32,
+    ) -> Result<Vec<SearchItem>> {
+        let token = self.auth.token()?;
+        let param = match seed {
+            SearchType::Track => "seed_tracks",
+            SearchType::Artist => "seed_artists",
+            _ => bail!(
+                ErrorKind::Unsupported
+                    .error("recommendations can only be seeded by a track or artist")
+            ),
+        };
+        let mut url = format!(
+            "{}/recommendations?{param}={seed_id}&limit={limit}",
+            api_base()
+        );
+        push_market(&mut url, false);
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
 
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
             bail!(format_api_error(
-                "spotify recently played failed",
+                "spotify recommendations failed",
                 status,
                 &body
             ));
         }
 
-        let payload: RecentlyPlayedResponse = response.json()?;
+        let payload: RecommendationsResponse = response.json()?;
+        Ok(payload.tracks.into_iter().map(map_track).collect())
+    }
+
+    /// Look up tracks, albums, or artists by id, preserving input order.
+    ///
+    /// Unknown ids come back as `None`.
+    pub fn lookup(&self, kind: SearchType, ids: &[String]) -> Result<Vec<Option<SearchItem>>> {
+        let (path, batch) = match kind {
+            SearchType::Track => ("tracks", 50),
+            SearchType::Album => ("albums", 20),
+            SearchType::Artist => ("artists", 50),
+            _ => bail!(
+                ErrorKind::Unsupported
+                    .error("lookup is only available for tracks, albums, and artists")
+            ),
+        };
+        let token = self.auth.token()?;
+        let chunks: Vec<&[String]> = ids.chunks(batch).collect();
+        let pages = fetch_all(
+            &chunks,
+            MAX_IN_FLIGHT,
+            |chunk| self.lookup_page(kind, path, chunk, &token.access_token),
+            |_| {},
+        )?;
+        Ok(pages.into_iter().flatten().collect())
+    }
+
+    fn lookup_page(
+        &self,
+        kind: SearchType,
+        path: &str,
+        ids: &[String],
+        access_token: &str,
+    ) -> Result<Vec<Option<SearchItem>>> {
+        let mut url = format!("{}/{path}?ids={}", api_base(), ids.join(","));
+        if kind != SearchType::Artist {
+            push_market(&mut url, false);
+        }
+
+        let response = self
+            .http
+            .get(&url)
+            .bearer_auth(access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error("spotify lookup failed", status, &body));
+        }
+
+        let payload: SeveralResponse = response.json()?;
+        let items = match kind {
+            SearchType::Track => payload
+                .tracks
+                .into_iter()
+                .map(|item| item.map(map_track))
+                .collect(),
+            SearchType::Album => payload
+                .albums
+                .into_iter()
+                .map(|item| item.map(map_album))
+                .collect(),
+            _ => payload
+                .artists
+                .into_iter()
+                .map(|item| item.map(map_artist))
+                .collect(),
+        };
+        Ok(items)
+    }
+
+    pub fn recently_played(&self, limit: u32) -> Result<Vec<SearchItem>> {
+        let url = format!("{}/me/player/recently-played?limit={}", api_base(), limit);
+        let payload = self.recently_played_request(&url)?;
         Ok(payload
             .items
             .into_iter()
             .filter_map(|item| item.track.map(map_track))
             .collect())
     }
+
+    /// Tracks played after `after_ms` (unix milliseconds), newest first.
+    pub fn played_after(&self, after_ms: u64, limit: u32) -> Result<Vec<PlayedTrack>> {
+        let url = format!(
+            "{}/me/player/recently-played?limit={}&after={}",
+            api_base(),
+            limit,
+            after_ms
+        );
+        let payload = self.recently_played_request(&url)?;
+        Ok(payload
+            .items
+            .into_iter()
+            .filter_map(|item| {
+                let played_at = item.played_at?;
+                item.track.map(|track| PlayedTrack {
+                    artist_ids: track
+                        .artists
+                        .iter()
+                        .filter_map(|artist| artist.id.clone())
+                        .collect(),
+                    item: map_track(track),
+                    played_at,
+                })
+            })
+            .collect())
+    }
+
+    /// One page of plays before `before_ms` (unix milliseconds), newest first.
+    ///
+    /// The returned cursor points at the oldest play, for requesting the next page.
+    pub fn played_before(&self, before_ms: Option<u64>, limit: u32) -> Result<PlayedPage> {
+        let mut url = format!("{}/me/player/recently-played?limit={}", api_base(), limit);
+        if let Some(before_ms) = before_ms {
+            url.push_str(&format!("&before={before_ms}"));
+        }
+        let payload = self.recently_played_request(&url)?;
+        let before = payload
+            .cursors
+            .and_then(|cursors| cursors.before)
+            .and_then(|before| before.parse().ok());
+        let plays = payload
+            .items
+            .into_iter()
+            .filter_map(|item| {
+                let played_at = item.played_at?;
+                item.track.map(|track| PlayedTrack {
+                    artist_ids: track
+                        .artists
+                        .iter()
+                        .filter_map(|artist| artist.id.clone())
+                        .collect(),
+                    item: map_track(track),
+                    played_at,
+                })
+            })
+            .collect();
+        Ok(PlayedPage { plays, before })
+    }
+
+    fn recently_played_request(&self, url: &str) -> Result<RecentlyPlayedResponse> {
+        let token = self.auth.token()?;
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify recently played failed",
+                status,
+                &body
+            ));
+        }
+
+        Ok(response.json()?)
+    }
 }
 
 fn search_type_param(kind: SearchType) -> &'static str {
     match kind {
+        // Never sent: `search_page` rejects it and `search` splits it per type.
         SearchType::All => "track,album,artist,playlist",
         SearchType::Track => "track",
         SearchType::Album => "album",
@@ -198,9 +475,20 @@ struct SearchResponse {
     playlists: Option<ItemList<SpotifyPlaylist>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct SeveralResponse {
+    #[serde(default)]
+    tracks: Vec<Option<SpotifyTrack>>,
+    #[serde(default)]
+    albums: Vec<Option<SpotifyAlbum>>,
+    #[serde(default)]
+    artists: Vec<Option<SpotifyArtist>>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ItemList<T> {
     items: Vec<Option<T>>,
+    total: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -211,6 +499,17 @@ struct SpotifyTrack {
     artists: Vec<SpotifyArtistRef>,
     album: Option<SpotifyAlbumRef>,
     duration_ms: Option<u32>,
+    is_playable: Option<bool>,
+    linked_from: Option<SpotifyLinkedFrom>,
+    #[serde(default)]
+    explicit: Option<bool>,
+    #[serde(default)]
+    popularity: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyLinkedFrom {
+    id: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -221,14 +520,33 @@ struct SpotifyAlbum {
     artists: Vec<SpotifyArtistRef>,
 }
 
+#[derive(Debug, Deserialize)]
+struct TopResponse<T> {
+    items: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecommendationsResponse {
+    tracks: Vec<SpotifyTrack>,
+}
+
 #[derive(Debug, Deserialize)]
 struct RecentlyPlayedResponse {
     items: Vec<RecentlyPlayedItem>,
+    #[serde(default)]
+    cursors: Option<RecentlyPlayedCursors>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecentlyPlayedCursors {
+    before: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct RecentlyPlayedItem {
     track: Option<SpotifyTrack>,
+    #[serde(default)]
+    played_at: Option<String>,
 }
 
 fn map_track(item: SpotifyTrack) -> SearchItem {
@@ -242,6 +560,49 @@ fn map_track(item: SpotifyTrack) -> SearchItem {
         duration_ms: item.duration_ms,
         owner: None,
         score: None,
+        playable: item.is_playable,
+        linked_from: item.linked_from.map(|linked| linked.id),
+        saved: None,
+        explicit: item.explicit,
+        popularity: item.popularity,
+    }
+}
+
+fn map_album(item: SpotifyAlbum) -> SearchItem {
+    SearchItem {
+        id: item.id,
+        name: item.name,
+        uri: item.uri,
+        kind: SearchType::Album,
+        artists: item.artists.into_iter().map(|artist| artist.name).collect(),
+        album: None,
+        duration_ms: None,
+        owner: None,
+        score: None,
+        playable: None,
+        linked_from: None,
+        saved: None,
+        explicit: None,
+        popularity: None,
+    }
+}
+
+fn map_artist(item: SpotifyArtist) -> SearchItem {
+    SearchItem {
+        id: item.id,
+        name: item.name,
+        uri: item.uri,
+        kind: SearchType::Artist,
+        artists: Vec::new(),
+        album: None,
+        duration_ms: None,
+        owner: None,
+        score: None,
+        playable: None,
+        linked_from: None,
+        saved: None,
+        explicit: None,
+        popularity: item.popularity,
     }
 }
 
@@ -255,6 +616,8 @@ struct SpotifyArtist {
     id: String,
     name: String,
     uri: String,
+    #[serde(default)]
+    popularity: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -267,6 +630,9 @@ struct SpotifyPlaylist {
 
 #[derive(Debug, Deserialize)]
 struct SpotifyArtistRef {
+    // Local files have artists without ids.
+    #[serde(default)]
+    id: Option<String>,
     name: String,
 }
 
@@ -0,0 +1,164 @@
+use anyhow::{Context, bail};
+use reqwest::Method;
+use reqwest::blocking::Client as HttpClient;
+use serde::Deserialize;
+
+use crate::domain::show::{SavedShow, Show};
+use crate::error::Result;
+use crate::spotify::auth::AuthService;
+use crate::spotify::base::{api_base, push_market};
+use crate::spotify::cancel;
+use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
+
+/// Spotify podcast show API client.
+#[derive(Debug, Clone)]
+pub struct ShowsClient {
+    http: HttpClient,
+    auth: AuthService,
+}
+
+impl ShowsClient {
+    pub fn new(http: HttpClient, auth: AuthService) -> Self {
+        Self { http, auth }
+    }
+
+    pub fn get(&self, show_id: &str) -> Result<Show> {
+        let token = self.auth.token()?;
+        let mut url = format!("{}/shows/{show_id}", api_base());
+        push_market(&mut url, false);
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(token.access_token)
+            .send_traced()?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify show request failed",
+                status,
+                &body
+            ));
+        }
+
+        let payload: SpotifyShow = response.json()?;
+        Ok(map_show(payload))
+    }
+
+    /// Saved shows, most recently saved first; `None` fetches every page, otherwise up to
+    /// `limit`.
+    pub fn saved(&self, limit: Option<u32>) -> Result<Vec<SavedShow>> {
+        let token = self.auth.token()?;
+        let page = limit.map_or(SAVED_PAGE, |limit| limit.clamp(1, SAVED_PAGE));
+        let mut url = format!("{}/me/shows?limit={page}", api_base());
+        let mut saved = Vec::new();
+
+        loop {
+            cancel::check()?;
+            let response = self
+                .http
+                .get(&url)
+                .bearer_auth(token.access_token.clone())
+                .send_traced()?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+                bail!(format_api_error(
+                    "spotify saved shows failed",
+                    status,
+                    &body
+                ));
+            }
+
+            let payload: SavedShowsResponse = response.json()?;
+            saved.extend(payload.items.into_iter().flatten().map(|item| SavedShow {
+                show: map_show(item.show),
+                added_at: item.added_at,
+            }));
+
+            match (payload.next, limit) {
+                (Some(_), Some(limit)) if saved.len() >= limit as usize => break,
+                (Some(next), _) => url = next,
+                (None, _) => break,
+            }
+        }
+
+        if let Some(limit) = limit {
+            saved.truncate(limit as usize);
+        }
+        Ok(saved)
+    }
+
+    pub fn save(&self, ids: &[String]) -> Result<()> {
+        self.set_saved(Method::PUT, ids)
+    }
+
+    pub fn remove(&self, ids: &[String]) -> Result<()> {
+        self.set_saved(Method::DELETE, ids)
+    }
+
+    fn set_saved(&self, method: Method, ids: &[String]) -> Result<()> {
+        let token = self.auth.token()?;
+        for chunk in ids.chunks(SAVED_PAGE as usize) {
+            let url = format!("{}/me/shows?ids={}", api_base(), chunk.join(","));
+            let response = self
+                .http
+                .request(method.clone(), url)
+                .bearer_auth(&token.access_token)
+                .body(Vec::new())
+                .send_traced()
+                .context("spotify request failed")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+                bail!(format_api_error(
+                    "spotify saved shows request failed",
+                    status,
+                    &body
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Spotify caps saved item pages at 50.
+const SAVED_PAGE: u32 = 50;
+
+fn map_show(payload: SpotifyShow) -> Show {
+    Show {
+        id: payload.id,
+        name: payload.name,
+        uri: payload.uri,
+        publisher: payload.publisher,
+        description: payload.description,
+        total_episodes: payload.total_episodes,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedShowsResponse {
+    items: Vec<Option<SavedShowItem>>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedShowItem {
+    added_at: Option<String>,
+    show: SpotifyShow,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyShow {
+    id: String,
+    name: String,
+    uri: String,
+    publisher: Option<String>,
+    description: Option<String>,
+    total_episodes: Option<u32>,
+}
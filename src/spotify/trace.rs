@@ -0,0 +1,538 @@
+//! Request tracing for `--trace-http`, `--har`, and the `log_level` log file.
+//!
+//! Tracing wraps `send` rather than the client, so every Spotify and auth request
+//! goes through `send_traced`. Authorization and cookie headers, and token-bearing
+//! query parameters, are redacted; request and response bodies are never captured.
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use reqwest::Url;
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::time::civil_from_days;
+
+const REDACTED: &str = "[redacted]";
+const SECRET_HEADERS: &[&str] = &["authorization", "cookie", "set-cookie"];
+const SECRET_PARAMS: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "code",
+    "code_verifier",
+    "client_secret",
+];
+
+/// Where traced requests are reported.
+#[derive(Debug, Clone, Default)]
+pub struct TraceOptions {
+    /// Log one line per request to stderr.
+    pub log: bool,
+    /// Collect requests for a HAR file written by `write_har`.
+    pub har: Option<PathBuf>,
+    /// Append JSON lines to a log file, independent of `log`.
+    pub file: Option<FileLog>,
+}
+
+/// Severity of a log file record; `Info` includes `Error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Info,
+}
+
+impl LogLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Info => "info",
+        }
+    }
+}
+
+/// Size-rotated JSON-lines log; the previous file is kept with a `.1` suffix.
+#[derive(Debug, Clone)]
+pub struct FileLog {
+    pub path: PathBuf,
+    pub level: LogLevel,
+    pub max_bytes: u64,
+}
+
+static TRACE: OnceLock<TraceOptions> = OnceLock::new();
+static HAR_ENTRIES: Mutex<Vec<HarEntry>> = Mutex::new(Vec::new());
+
+pub fn set_trace(options: TraceOptions) {
+    let _ = TRACE.set(options);
+}
+
+fn active() -> Option<&'static TraceOptions> {
+    TRACE
+        .get()
+        .filter(|options| options.log || options.har.is_some() || options.file.is_some())
+}
+
+/// `send` that reports the request when tracing is enabled.
+pub trait TracedSend {
+    fn send_traced(self) -> reqwest::Result<Response>;
+}
+
+impl TracedSend for RequestBuilder {
+    fn send_traced(self) -> reqwest::Result<Response> {
+        let Some(options) = active() else {
+            return self.send();
+        };
+        let (client, request) = self.build_split();
+        let request = request?;
+        let method = request.method().to_string();
+        let url = redact_url(request.url());
+        let request_headers = redacted_headers(request.headers());
+        let started = SystemTime::now();
+        let timer = Instant::now();
+        let result = client.execute(request);
+        let elapsed_ms = timer.elapsed().as_secs_f64() * 1000.0;
+
+        let (status, response_headers) = match &result {
+            Ok(response) => (
+                response.status().as_u16(),
+                redacted_headers(response.headers()),
+            ),
+            Err(_) => (0, Vec::new()),
+        };
+        if options.log {
+            let outcome = match &result {
+                Ok(_) => status.to_string(),
+                Err(err) => format!("error ({err})"),
+            };
+            let limits = rate_limit_summary(&response_headers);
+            eprintln!("http: {method} {url} -> {outcome} in {elapsed_ms:.0}ms{limits}");
+        }
+        if let Some(file) = &options.file {
+            let level = if status == 0 || status >= 400 {
+                LogLevel::Error
+            } else {
+                LogLevel::Info
+            };
+            let record = LogRecord {
+                ts: iso_time(started),
+                level: level.as_str(),
+                event: "http",
+                method: Some(method.clone()),
+                url: Some(url.clone()),
+                status: (status != 0).then_some(status),
+                elapsed_ms: Some(elapsed_ms.round() as u64),
+                message: result.as_ref().err().map(|err| err.to_string()),
+            };
+            append_log(file, level, &record);
+        }
+        if options.har.is_some() {
+            let entry = HarEntry::new(
+                started,
+                elapsed_ms,
+                method,
+                url,
+                request_headers,
+                status,
+                response_headers,
+            );
+            if let Ok(mut entries) = HAR_ENTRIES.lock() {
+                entries.push(entry);
+            }
+        }
+        result
+    }
+}
+
+/// Record a failed command in the log file, if one is configured.
+pub fn log_error(message: &str) {
+    let Some(file) = TRACE.get().and_then(|options| options.file.as_ref()) else {
+        return;
+    };
+    let record = LogRecord {
+        ts: iso_time(SystemTime::now()),
+        level: LogLevel::Error.as_str(),
+        event: "command",
+        method: None,
+        url: None,
+        status: None,
+        elapsed_ms: None,
+        message: Some(message.to_string()),
+    };
+    append_log(file, LogLevel::Error, &record);
+}
+
+#[derive(Serialize)]
+struct LogRecord {
+    ts: String,
+    level: &'static str,
+    event: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elapsed_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+/// Logging is best effort: a log that cannot be written never fails the command.
+fn append_log(file: &FileLog, level: LogLevel, record: &LogRecord) {
+    if level > file.level {
+        return;
+    }
+    let Ok(line) = serde_json::to_string(record) else {
+        return;
+    };
+    rotate_log(&file.path, file.max_bytes);
+    if let Ok(mut handle) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&file.path)
+    {
+        let _ = writeln!(handle, "{line}");
+    }
+}
+
+/// Move the log aside once it reaches `max_bytes`, replacing the older rotation.
+fn rotate_log(path: &Path, max_bytes: u64) {
+    let size = std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+    if size >= max_bytes {
+        let _ = std::fs::rename(path, rotated_path(path));
+    }
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".1");
+    PathBuf::from(name)
+}
+
+/// Write collected requests to the `--har` path, if one was given.
+pub fn write_har() -> Result<()> {
+    let Some(path) = TRACE.get().and_then(|options| options.har.as_deref()) else {
+        return Ok(());
+    };
+    let entries = HAR_ENTRIES
+        .lock()
+        .map(|mut entries| std::mem::take(&mut *entries))
+        .unwrap_or_default();
+    write_har_to(path, entries)
+}
+
+fn write_har_to(path: &Path, entries: Vec<HarEntry>) -> Result<()> {
+    let har = Har {
+        log: HarLog {
+            version: "1.2",
+            creator: HarCreator {
+                name: "spotify-cli",
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            entries,
+        },
+    };
+    let payload = serde_json::to_string_pretty(&har)?;
+    std::fs::write(path, payload)
+        .with_context(|| format!("unable to write HAR file {}", path.display()))?;
+    Ok(())
+}
+
+fn redact_url(url: &Url) -> String {
+    if url.query().is_none() {
+        return url.to_string();
+    }
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if SECRET_PARAMS.contains(&key.as_ref()) {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+    let mut url = url.clone();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    url.to_string()
+}
+
+fn redacted_headers(headers: &HeaderMap) -> Vec<HarHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str().to_string();
+            let value = if SECRET_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            HarHeader { name, value }
+        })
+        .collect()
+}
+
+/// `Retry-After` and `X-RateLimit-*` headers, formatted for the log line.
+fn rate_limit_summary(headers: &[HarHeader]) -> String {
+    let limits: Vec<String> = headers
+        .iter()
+        .filter(|header| header.name == "retry-after" || header.name.starts_with("x-ratelimit"))
+        .map(|header| format!("{}={}", header.name, header.value))
+        .collect();
+    if limits.is_empty() {
+        return String::new();
+    }
+    format!(" [{}]", limits.join(", "))
+}
+
+#[derive(Serialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Serialize)]
+struct HarLog {
+    version: &'static str,
+    creator: HarCreator,
+    entries: Vec<HarEntry>,
+}
+
+#[derive(Serialize)]
+struct HarCreator {
+    name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HarEntry {
+    started_date_time: String,
+    time: f64,
+    request: HarRequest,
+    response: HarResponse,
+    cache: HarCache,
+    timings: HarTimings,
+}
+
+impl HarEntry {
+    fn new(
+        started: SystemTime,
+        elapsed_ms: f64,
+        method: String,
+        url: String,
+        request_headers: Vec<HarHeader>,
+        status: u16,
+        response_headers: Vec<HarHeader>,
+    ) -> Self {
+        let status_text = reqwest::StatusCode::from_u16(status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default()
+            .to_string();
+        let mime_type = response_headers
+            .iter()
+            .find(|header| header.name == "content-type")
+            .map(|header| header.value.clone())
+            .unwrap_or_default();
+        Self {
+            started_date_time: iso_time(started),
+            time: elapsed_ms,
+            request: HarRequest {
+                method,
+                url,
+                http_version: "HTTP/1.1",
+                headers: request_headers,
+                query_string: Vec::new(),
+                cookies: Vec::new(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            response: HarResponse {
+                status,
+                status_text,
+                http_version: "HTTP/1.1",
+                headers: response_headers,
+                cookies: Vec::new(),
+                content: HarContent {
+                    size: -1,
+                    mime_type,
+                },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            cache: HarCache {},
+            timings: HarTimings {
+                send: 0.0,
+                wait: elapsed_ms,
+                receive: 0.0,
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct HarHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    http_version: &'static str,
+    headers: Vec<HarHeader>,
+    query_string: Vec<HarHeader>,
+    cookies: Vec<HarHeader>,
+    headers_size: i64,
+    body_size: i64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HarResponse {
+    status: u16,
+    status_text: String,
+    http_version: &'static str,
+    headers: Vec<HarHeader>,
+    cookies: Vec<HarHeader>,
+    content: HarContent,
+    #[serde(rename = "redirectURL")]
+    redirect_url: String,
+    headers_size: i64,
+    body_size: i64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HarContent {
+    size: i64,
+    mime_type: String,
+}
+
+#[derive(Serialize)]
+struct HarCache {}
+
+#[derive(Serialize)]
+struct HarTimings {
+    send: f64,
+    wait: f64,
+    receive: f64,
+}
+
+/// Format a time as an ISO 8601 UTC timestamp with milliseconds.
+fn iso_time(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    let of_day = seconds % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        of_day / 3600,
+        of_day % 3600 / 60,
+        of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FileLog, HarHeader, LogLevel, LogRecord, append_log, iso_time, rate_limit_summary,
+        redact_url, redacted_headers, rotated_path,
+    };
+    use reqwest::Url;
+    use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue, RETRY_AFTER};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn redact_url_hides_token_params_only() {
+        let url = Url::parse("https://accounts.example/api/token?code=abc&state=xyz").unwrap();
+        assert_eq!(
+            redact_url(&url),
+            "https://accounts.example/api/token?code=%5Bredacted%5D&state=xyz"
+        );
+        let url = Url::parse("https://api.spotify.com/v1/me/player").unwrap();
+        assert_eq!(redact_url(&url), "https://api.spotify.com/v1/me/player");
+    }
+
+    #[test]
+    fn redacted_headers_hide_authorization() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        let headers = redacted_headers(&headers);
+        let auth = headers
+            .iter()
+            .find(|header| header.name == "authorization")
+            .unwrap();
+        assert_eq!(auth.value, "[redacted]");
+        assert_eq!(rate_limit_summary(&headers), " [retry-after=3]");
+    }
+
+    #[test]
+    fn rate_limit_summary_is_empty_without_limits() {
+        let headers = vec![HarHeader {
+            name: "content-type".to_string(),
+            value: "application/json".to_string(),
+        }];
+        assert_eq!(rate_limit_summary(&headers), "");
+    }
+
+    #[test]
+    fn iso_time_formats_utc_millis() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        assert_eq!(iso_time(time), "2023-11-14T22:13:20.123Z");
+    }
+
+    fn record(level: LogLevel) -> LogRecord {
+        LogRecord {
+            ts: "2024-01-01T00:00:00.000Z".to_string(),
+            level: level.as_str(),
+            event: "command",
+            method: None,
+            url: None,
+            status: None,
+            elapsed_ms: None,
+            message: Some("boom".to_string()),
+        }
+    }
+
+    #[test]
+    fn append_log_filters_by_level_and_rotates() {
+        let stamp = std::time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("spotify-cli-log-{stamp}.log"));
+        let file = FileLog {
+            path: path.clone(),
+            level: LogLevel::Error,
+            max_bytes: 50,
+        };
+
+        append_log(&file, LogLevel::Info, &record(LogLevel::Info));
+        assert!(!path.exists());
+        append_log(&file, LogLevel::Error, &record(LogLevel::Error));
+        let line = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            line,
+            "{\"ts\":\"2024-01-01T00:00:00.000Z\",\"level\":\"error\",\"event\":\"command\",\"message\":\"boom\"}\n"
+        );
+
+        append_log(&file, LogLevel::Error, &record(LogLevel::Error));
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+        assert!(rotated_path(&path).exists());
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(rotated_path(&path));
+    }
+}
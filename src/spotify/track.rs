@@ -1,11 +1,17 @@
 use anyhow::{Context, bail};
 use reqwest::Method;
 use reqwest::blocking::Client as HttpClient;
+use serde::Deserialize;
+
+use crate::domain::track::{SavedTrack, Track};
 
 use crate::error::Result;
 use crate::spotify::auth::AuthService;
-use crate::spotify::base::api_base;
+use crate::spotify::base::{api_base, push_market};
+use crate::spotify::cancel;
+use crate::spotify::concurrent::{MAX_IN_FLIGHT, fetch_all, remaining_offsets};
 use crate::spotify::error::format_api_error;
+use crate::spotify::trace::TracedSend;
 
 /// Spotify saved tracks (library) API client.
 #[derive(Debug, Clone)]
@@ -29,6 +35,108 @@ impl TrackClient {
         self.send(Method::DELETE, &path)
     }
 
+    /// Whether each track is saved in the library, in input order.
+    pub fn contains(&self, ids: &[String]) -> Result<Vec<bool>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let token = self.auth.token()?;
+        let chunks: Vec<&[String]> = ids.chunks(SAVED_PAGE as usize).collect();
+        let pages = fetch_all(
+            &chunks,
+            MAX_IN_FLIGHT,
+            |chunk| self.contains_page(chunk, &token.access_token),
+            |_| {},
+        )?;
+        Ok(pages.into_iter().flatten().collect())
+    }
+
+    fn contains_page(&self, ids: &[String], access_token: &str) -> Result<Vec<bool>> {
+        let url = format!("{}/me/tracks/contains?ids={}", api_base(), ids.join(","));
+        let response = self
+            .http
+            .get(&url)
+            .bearer_auth(access_token)
+            .send_traced()
+            .context("spotify request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify saved tracks check failed",
+                status,
+                &body
+            ));
+        }
+
+        Ok(response.json()?)
+    }
+
+    pub fn saved(&self) -> Result<Vec<SavedTrack>> {
+        self.saved_with(|_, _| {})
+    }
+
+    /// Like `saved`, reporting tracks fetched so far and the total after each page.
+    pub fn saved_with(&self, mut on_page: impl FnMut(u64, Option<u64>)) -> Result<Vec<SavedTrack>> {
+        let token = self.auth.token()?;
+        let access_token = token.access_token.as_str();
+        let first = self.saved_page(&saved_url(0), access_token)?;
+        let total = first.total;
+        let mut fetched = first.items.len() as u64;
+        on_page(fetched, total.map(u64::from));
+        let mut next = first.next.clone();
+        let mut tracks = map_saved(first);
+
+        // With a known total the remaining pages can be requested side by side.
+        if let (Some(_), Some(total)) = (&next, total) {
+            let offsets = remaining_offsets(SAVED_PAGE, total);
+            let pages = fetch_all(
+                &offsets,
+                MAX_IN_FLIGHT,
+                |offset| self.saved_page(&saved_url(*offset), access_token),
+                |page| {
+                    fetched += page.items.len() as u64;
+                    on_page(fetched, Some(u64::from(total)));
+                },
+            )?;
+            tracks.extend(pages.into_iter().flat_map(map_saved));
+            return Ok(tracks);
+        }
+
+        while let Some(url) = next {
+            cancel::check()?;
+            let payload = self.saved_page(&url, access_token)?;
+            fetched += payload.items.len() as u64;
+            on_page(fetched, payload.total.map(u64::from));
+            next = payload.next.clone();
+            tracks.extend(map_saved(payload));
+        }
+
+        Ok(tracks)
+    }
+
+    fn saved_page(&self, url: &str, access_token: &str) -> Result<SavedTracksResponse> {
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(access_token)
+            .send_traced()
+            .context("spotify request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_else(|_| "<no body>".to_string());
+            bail!(format_api_error(
+                "spotify saved tracks request failed",
+                status,
+                &body
+            ));
+        }
+
+        Ok(response.json()?)
+    }
+
     fn send(&self, method: Method, path: &str) -> Result<()> {
         let token = self.auth.token()?;
         let url = format!("{}{}", api_base(), path);
@@ -38,7 +146,7 @@ impl TrackClient {
             .request(method, url)
             .bearer_auth(token.access_token)
             .body(Vec::new())
-            .send()
+            .send_traced()
             .context("spotify request failed")?;
 
         if response.status().is_success() {
@@ -50,3 +158,82 @@ impl TrackClient {
         bail!(format_api_error("spotify request failed", status, &body))
     }
 }
+
+#[derive(Debug, Deserialize)]
+struct SavedTracksResponse {
+    items: Vec<SavedTrackItem>,
+    next: Option<String>,
+    total: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SavedTrackItem {
+    added_at: Option<String>,
+    track: SpotifyTrack,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyTrack {
+    id: Option<String>,
+    name: String,
+    duration_ms: Option<u32>,
+    album: Option<SpotifyAlbumRef>,
+    #[serde(default)]
+    artists: Vec<SpotifyArtistRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyAlbumRef {
+    id: Option<String>,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyArtistRef {
+    id: Option<String>,
+    name: String,
+}
+
+/// Spotify caps saved-track pages at 50.
+const SAVED_PAGE: u32 = 50;
+
+fn saved_url(offset: u32) -> String {
+    let mut url = format!(
+        "{}/me/tracks?limit={SAVED_PAGE}&offset={offset}",
+        api_base()
+    );
+    push_market(&mut url, false);
+    url
+}
+
+fn map_saved(payload: SavedTracksResponse) -> Vec<SavedTrack> {
+    payload
+        .items
+        .into_iter()
+        .filter_map(|item| {
+            map_track(item.track).map(|track| SavedTrack {
+                track,
+                added_at: item.added_at,
+            })
+        })
+        .collect()
+}
+
+fn map_track(item: SpotifyTrack) -> Option<Track> {
+    item.id.map(|id| {
+        let (album, album_id) = match item.album {
+            Some(album) => (Some(album.name), album.id),
+            None => (None, None),
+        };
+
+        Track {
+            id,
+            name: item.name,
+            album,
+            album_id,
+            artists: item.artists.iter().map(|a| a.name.clone()).collect(),
+            artist_ids: item.artists.into_iter().filter_map(|a| a.id).collect(),
+            duration_ms: item.duration_ms,
+        }
+    })
+}
@@ -65,3 +65,37 @@ fn auth_scopes_reports_missing() {
     assert!(scopes.required.contains(&"user-read-private".to_string()));
     assert!(scopes.missing.len() + scopes.granted.unwrap_or_default().len() >= 1);
 }
+
+#[test]
+fn auth_clear_keeps_user_settings() {
+    let dir = temp_path("auth-clear");
+    fs::create_dir_all(&dir).expect("create dir");
+    let path = dir.join("metadata.json");
+    let store = MetadataStore::new(path.clone());
+
+    let mut metadata = Metadata {
+        auth: Some(AuthTokenCache {
+            access_token: "token".to_string(),
+            refresh_token: None,
+            expires_at: None,
+            granted_scopes: None,
+        }),
+        client: None,
+        settings: Default::default(),
+    };
+    metadata.settings.user_name = Some("listener".to_string());
+    metadata.settings.max_volume = Some(60);
+    metadata.settings.proxy = Some("http://proxy:3128".to_string());
+    store.save(&metadata).expect("save metadata");
+
+    AuthService::new(store).clear().expect("clear");
+
+    let metadata = MetadataStore::new(path).load().expect("load");
+    assert!(metadata.auth.is_none());
+    assert_eq!(metadata.settings.user_name, None);
+    assert_eq!(metadata.settings.max_volume, Some(60));
+    assert_eq!(
+        metadata.settings.proxy.as_deref(),
+        Some("http://proxy:3128")
+    );
+}
@@ -61,3 +61,18 @@ fn pin_list_smoke() {
         .expect("run pin list");
     assert!(output.status.success());
 }
+
+#[test]
+fn json_envelope_wraps_pin_list() {
+    let cache_dir = temp_dir("cache");
+    let output = Command::new(bin())
+        .args(["--json", "--json-envelope", "pin", "list"])
+        .env("SPOTIFY_CLI_CACHE_DIR", &cache_dir)
+        .output()
+        .expect("run pin list");
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["kind"], "pin_list");
+    assert_eq!(value["schema_version"], 1);
+    assert!(value["data"].is_array());
+}
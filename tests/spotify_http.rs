@@ -3,6 +3,8 @@
 use httpmock::Method::{DELETE, GET, POST, PUT};
 use httpmock::MockServer;
 use spotify_cli::cache::metadata::MetadataStore;
+use spotify_cli::domain::player::PlayingItem;
+use spotify_cli::domain::queue::QueueItemKind;
 use spotify_cli::spotify::auth::{AuthService, AuthToken};
 use spotify_cli::spotify::client::SpotifyClient;
 use std::fs;
@@ -42,6 +44,42 @@ fn teardown_env() {
     }
 }
 
+#[test]
+fn facade_plays_contexts_and_tracks() {
+    let server = MockServer::start();
+    let context = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/me/player/play")
+            .json_body(serde_json::json!({ "context_uri": "spotify:album:a1" }));
+        then.status(204);
+    });
+    let track = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/me/player/play")
+            .json_body(serde_json::json!({ "uris": ["spotify:track:t1"] }));
+        then.status(204);
+    });
+    let dir = temp_dir("facade");
+    let spotify =
+        spotify_cli::SpotifyClient::with_metadata_path(dir.join("metadata.json")).unwrap();
+    spotify
+        .login(AuthToken {
+            access_token: "token".to_string(),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+        })
+        .unwrap();
+    unsafe {
+        std::env::set_var("SPOTIFY_CLI_API_BASE", server.base_url());
+    }
+    spotify.play_uri("spotify:album:a1").unwrap();
+    spotify.play_uri("spotify:track:t1").unwrap();
+    context.assert();
+    track.assert();
+    teardown_env();
+}
+
 #[test]
 fn search_tracks_parses_items() {
     let server = MockServer::start();
@@ -72,6 +110,104 @@ fn search_tracks_parses_items() {
     teardown_env();
 }
 
+#[test]
+fn search_types_requests_each_type_and_keeps_query_order() {
+    use spotify_cli::domain::search::SearchType;
+    use spotify_cli::spotify::search::TypeQuery;
+
+    let server = MockServer::start();
+    let tracks = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search")
+            .query_param("type", "track")
+            .query_param("limit", "2")
+            .query_param("offset", "0");
+        then.status(200).json_body(serde_json::json!({
+            "tracks": {
+                "items": [ { "id": "t1", "name": "Track", "uri": "spotify:track:t1", "artists": [] } ],
+                "total": 7
+            }
+        }));
+    });
+    let artists = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search")
+            .query_param("type", "artist")
+            .query_param("limit", "5")
+            .query_param("offset", "10");
+        then.status(200).json_body(serde_json::json!({
+            "artists": {
+                "items": [ { "id": "r1", "name": "Artist", "uri": "spotify:artist:r1" } ],
+                "total": 30
+            }
+        }));
+    });
+
+    let client = client_with_token(&server);
+    let pages = client
+        .search()
+        .search_types(
+            "boards",
+            &[
+                TypeQuery {
+                    kind: SearchType::Track,
+                    limit: 2,
+                    offset: 0,
+                },
+                TypeQuery {
+                    kind: SearchType::Artist,
+                    limit: 5,
+                    offset: 10,
+                },
+            ],
+            false,
+        )
+        .unwrap();
+    tracks.assert();
+    artists.assert();
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[0].kind, SearchType::Track);
+    assert_eq!(pages[0].items[0].id, "t1");
+    assert_eq!(pages[0].total, 7);
+    assert_eq!(pages[1].kind, SearchType::Artist);
+    assert_eq!(pages[1].total, 30);
+    teardown_env();
+}
+
+#[test]
+fn search_page_passes_offset_and_reads_total() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search")
+            .query_param("type", "album")
+            .query_param("limit", "5")
+            .query_param("offset", "10");
+        then.status(200).json_body(serde_json::json!({
+            "albums": {
+                "items": [ { "id": "a1", "name": "Album", "uri": "spotify:album:a1", "artists": [] } ],
+                "total": 42
+            }
+        }));
+    });
+
+    let client = client_with_token(&server);
+    let (items, total) = client
+        .search()
+        .search_page(
+            "boards",
+            spotify_cli::domain::search::SearchType::Album,
+            5,
+            10,
+            false,
+        )
+        .unwrap();
+    mock.assert();
+    assert_eq!(items.len(), 1);
+    assert_eq!(total, 42);
+    teardown_env();
+}
+
 #[test]
 fn playlists_list_parses_items() {
     let server = MockServer::start();
@@ -114,6 +250,165 @@ fn playlist_get_parses_detail() {
     teardown_env();
 }
 
+#[test]
+fn playlist_items_passes_offset_and_maps_added_by() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/playlists/abc/tracks")
+            .query_param("limit", "2")
+            .query_param("offset", "4");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "added_at": "2024-01-01T00:00:00Z",
+                    "added_by": { "id": "alice" },
+                    "track": { "id": "t1", "name": "Track", "artists": [ { "name": "Artist" } ] }
+                },
+                { "added_at": null, "added_by": null, "track": null }
+            ],
+            "next": null,
+            "total": 6
+        }));
+    });
+
+    let client = client_with_token(&server);
+    let page = client.playlists().items("abc", 2, 4).unwrap();
+    mock.assert();
+    assert_eq!(page.total, 6);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].added_by.as_deref(), Some("alice"));
+    teardown_env();
+}
+
+#[test]
+fn playlist_items_all_fetches_remaining_pages_by_offset() {
+    let server = MockServer::start();
+    let item = |id: &str| serde_json::json!({ "track": { "id": id, "name": id, "artists": [] } });
+    let pages: Vec<_> = [("0", "t1"), ("100", "t2"), ("200", "t3")]
+        .into_iter()
+        .map(|(offset, id)| {
+            let next = (offset != "200").then(|| format!("{}/next", server.base_url()));
+            let body = serde_json::json!({ "items": [item(id)], "next": next, "total": 250 });
+            server.mock(move |when, then| {
+                when.method(GET)
+                    .path("/playlists/abc/tracks")
+                    .query_param("offset", offset);
+                then.status(200).json_body(body);
+            })
+        })
+        .collect();
+
+    let client = client_with_token(&server);
+    let items = client.playlists().items_all("abc").unwrap();
+    for page in &pages {
+        page.assert();
+    }
+    let ids: Vec<&str> = items.iter().map(|item| item.track.id.as_str()).collect();
+    assert_eq!(ids, vec!["t1", "t2", "t3"]);
+    teardown_env();
+}
+
+#[test]
+fn playlist_availability_requests_token_market_and_reads_relinking() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/playlists/p1/tracks")
+            .query_param("market", "from_token");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                { "track": {
+                    "id": "new", "uri": "spotify:track:new", "name": "One", "artists": [],
+                    "is_playable": true,
+                    "linked_from": { "id": "old", "uri": "spotify:track:old" }
+                } },
+                { "track": null },
+                { "track": {
+                    "id": "gone", "uri": "spotify:track:gone", "name": "Two", "artists": [],
+                    "is_playable": false,
+                    "restrictions": { "reason": "market" },
+                    "external_ids": { "isrc": "USRC17607839" }
+                } }
+            ],
+            "next": null,
+            "total": 3
+        }));
+    });
+
+    let client = client_with_token(&server);
+    let (tracks, length) = client.playlists().availability("p1").unwrap();
+    mock.assert();
+    assert_eq!(length, 3);
+    assert_eq!(tracks.len(), 2);
+    assert_eq!(tracks[0].linked_from.as_deref(), Some("spotify:track:old"));
+    assert!(tracks[0].playable);
+    assert_eq!(tracks[1].position, 3);
+    assert!(!tracks[1].playable);
+    assert_eq!(tracks[1].restriction.as_deref(), Some("market"));
+    assert_eq!(tracks[1].isrc.as_deref(), Some("USRC17607839"));
+    teardown_env();
+}
+
+#[test]
+fn playlist_cue_items_keep_local_files_and_missing_items() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/playlists/p1/tracks");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "track": {
+                        "id": "t1",
+                        "uri": "spotify:track:t1",
+                        "name": "One",
+                        "duration_ms": 1000,
+                        "artists": [{ "id": "a1", "name": "Artist" }]
+                    }
+                },
+                {
+                    "track": {
+                        "id": null,
+                        "uri": "spotify:local:Band:Demo:Two:2",
+                        "name": "Two",
+                        "duration_ms": 2000,
+                        "artists": [{ "id": null, "name": "Band" }]
+                    }
+                },
+                { "track": null },
+                {
+                    "track": {
+                        "id": "e1",
+                        "uri": "spotify:episode:e1",
+                        "name": "Three",
+                        "duration_ms": 3000
+                    }
+                }
+            ],
+            "next": null,
+            "total": 4
+        }));
+    });
+
+    let client = client_with_token(&server);
+    let items = client.playlists().cue_items("p1").unwrap();
+    mock.assert();
+    let uris: Vec<&str> = items.iter().map(|item| item.uri.as_str()).collect();
+    assert_eq!(
+        uris,
+        vec![
+            "spotify:track:t1",
+            "spotify:local:Band:Demo:Two:2",
+            "",
+            "spotify:episode:e1"
+        ]
+    );
+    let durations: Vec<u64> = items.iter().map(|item| item.duration_ms).collect();
+    assert_eq!(durations, vec![1000, 2000, 0, 3000]);
+    assert_eq!(items[1].performer, "Band");
+    teardown_env();
+}
+
 #[test]
 fn playlist_follow_puts() {
     let server = MockServer::start();
@@ -151,11 +446,107 @@ fn playlist_add_posts() {
             .json_body(serde_json::json!({ "snapshot_id": "snap" }));
     });
     let client = client_with_token(&server);
-    client
+    let snapshot = client
         .playlists()
         .add_tracks("abc", &[String::from("spotify:track:1")])
         .unwrap();
     mock.assert();
+    assert_eq!(snapshot.as_deref(), Some("snap"));
+    teardown_env();
+}
+
+#[test]
+fn playlist_remove_deletes() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/playlists/abc/tracks")
+            .json_body(serde_json::json!({ "tracks": [ { "uri": "spotify:track:1" } ] }));
+        then.status(200)
+            .json_body(serde_json::json!({ "snapshot_id": "snap" }));
+    });
+    let client = client_with_token(&server);
+    let snapshot = client
+        .playlists()
+        .remove_tracks("abc", &[String::from("spotify:track:1")])
+        .unwrap();
+    mock.assert();
+    assert_eq!(snapshot.as_deref(), Some("snap"));
+    teardown_env();
+}
+
+#[test]
+fn playlist_reorder_puts_range() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/playlists/abc/tracks")
+            .json_body(serde_json::json!({ "range_start": 4, "insert_before": 1 }));
+        then.status(200)
+            .json_body(serde_json::json!({ "snapshot_id": "snap" }));
+    });
+    let client = client_with_token(&server);
+    let snapshot = client.playlists().reorder("abc", 4, 1).unwrap();
+    mock.assert();
+    assert_eq!(snapshot.as_deref(), Some("snap"));
+    teardown_env();
+}
+
+#[test]
+fn playlist_set_visibility_puts_flags() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/playlists/abc")
+            .json_body(serde_json::json!({ "public": false, "collaborative": true }));
+        then.status(200);
+    });
+    let client = client_with_token(&server);
+    client
+        .playlists()
+        .set_visibility("abc", Some(false), true)
+        .unwrap();
+    mock.assert();
+    teardown_env();
+}
+
+#[test]
+fn playlists_user_page_reads_owner_and_track_totals() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/users/alice/playlists")
+            .query_param("limit", "20")
+            .query_param("offset", "0");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "id": "pl1",
+                    "name": "Road Trip",
+                    "owner": { "id": "alice", "display_name": "Alice" },
+                    "collaborative": false,
+                    "public": true,
+                    "tracks": { "total": 42 }
+                },
+                {
+                    "id": "pl2",
+                    "name": "Followed",
+                    "owner": { "id": "bob", "display_name": "Bob" },
+                    "public": true
+                }
+            ],
+            "next": null,
+            "total": 2
+        }));
+    });
+    let client = client_with_token(&server);
+    let page = client.playlists().user_page("alice", 20, 0).unwrap();
+    mock.assert();
+    assert_eq!(page.total, 2);
+    assert_eq!(page.items[0].owner_id.as_deref(), Some("alice"));
+    assert_eq!(page.items[0].tracks_total, Some(42));
+    assert_eq!(page.items[1].playlist.owner.as_deref(), Some("Bob"));
+    assert_eq!(page.items[1].tracks_total, None);
     teardown_env();
 }
 
@@ -176,6 +567,27 @@ fn devices_list_parses_items() {
     teardown_env();
 }
 
+#[test]
+fn devices_active_reads_volume_support() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/me/player/devices");
+        then.status(200).json_body(serde_json::json!({
+            "devices": [
+                { "id": "1", "name": "Office", "volume_percent": 50, "is_active": false, "supports_volume": true },
+                { "id": "2", "name": "Receiver", "volume_percent": null, "is_active": true, "supports_volume": false }
+            ]
+        }));
+    });
+
+    let client = client_with_token(&server);
+    let device = client.devices().active().unwrap().unwrap();
+    mock.assert();
+    assert_eq!(device.name, "Receiver");
+    assert_eq!(device.supports_volume, Some(false));
+    teardown_env();
+}
+
 #[test]
 fn devices_set_active_puts() {
     let server = MockServer::start();
@@ -219,96 +631,962 @@ fn playback_status_parses_track() {
 }
 
 #[test]
-fn playback_control_puts() {
+fn playback_context_name_fetches_playlist_name() {
     let server = MockServer::start();
     let mock = server.mock(|when, then| {
-        when.method(PUT).path("/me/player/pause");
-        then.status(204);
+        when.method(GET)
+            .path("/playlists/p1")
+            .query_param("fields", "name");
+        then.status(200)
+            .json_body(serde_json::json!({ "name": "Focus" }));
     });
+
     let client = client_with_token(&server);
-    client.playback().pause().unwrap();
+    let name = client.playback().context_name("playlist", "p1").unwrap();
     mock.assert();
+    assert_eq!(name, "Focus");
     teardown_env();
 }
 
 #[test]
-fn albums_get_parses_tracks() {
+fn playback_current_episode_reads_show() {
     let server = MockServer::start();
-    let album_mock = server.mock(|when, then| {
-        when.method(GET).path("/albums/abc");
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/player")
+            .query_param("additional_types", "episode");
         then.status(200).json_body(serde_json::json!({
-            "id": "abc",
-            "name": "Album",
-            "uri": "uri",
-            "release_date": "2024-01-01",
-            "total_tracks": 1,
-            "artists": [ { "name": "Artist" } ]
+            "currently_playing_type": "episode",
+            "item": {
+                "id": "e1",
+                "uri": "spotify:episode:e1",
+                "show": { "id": "s1" }
+            }
         }));
     });
-    let tracks_mock = server.mock(|when, then| {
+
+    let client = client_with_token(&server);
+    let episode = client.playback().current_episode().unwrap().unwrap();
+    mock.assert();
+    assert_eq!(episode.id, "e1");
+    assert_eq!(episode.show_id.as_deref(), Some("s1"));
+    teardown_env();
+}
+
+#[test]
+fn playback_current_item_tells_chapters_from_episodes() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
         when.method(GET)
-            .path("/albums/abc/tracks")
-            .query_param("limit", "50");
+            .path("/me/player")
+            .query_param("additional_types", "episode");
         then.status(200).json_body(serde_json::json!({
-            "items": [ { "name": "Track", "duration_ms": 1000, "track_number": 1 } ],
-            "next": null
+            "currently_playing_type": "episode",
+            "item": { "id": "c1", "uri": "spotify:chapter:c1" },
+            "context": { "type": "audiobook", "uri": "spotify:audiobook:b1" }
         }));
     });
 
     let client = client_with_token(&server);
-    let album = client.albums().get("abc").unwrap();
-    album_mock.assert();
-    tracks_mock.assert();
-    assert_eq!(album.tracks.len(), 1);
+    let item = client.playback().current_item().unwrap();
+    mock.assert();
+    assert!(matches!(
+        item,
+        Some(PlayingItem::Chapter { audiobook_id }) if audiobook_id == "b1"
+    ));
     teardown_env();
 }
 
 #[test]
-fn artists_get_parses_artist() {
+fn playback_queue_parses_episodes() {
     let server = MockServer::start();
     let mock = server.mock(|when, then| {
-        when.method(GET).path("/artists/abc");
+        when.method(GET).path("/me/player/queue");
         then.status(200).json_body(serde_json::json!({
-            "id": "abc",
-            "name": "Artist",
-            "uri": "uri",
-            "genres": ["alt"],
-            "followers": { "total": 5 }
+            "currently_playing": {
+                "type": "episode",
+                "id": "e1",
+                "uri": "spotify:episode:e1",
+                "name": "Pilot",
+                "duration_ms": 60000,
+                "show": { "id": "s1", "name": "Talk" }
+            },
+            "queue": [
+                {
+                    "type": "track",
+                    "id": "t1",
+                    "uri": "spotify:track:t1",
+                    "name": "Song",
+                    "duration_ms": 1000,
+                    "album": { "id": "a1", "name": "Album" },
+                    "artists": [{ "id": "ar1", "name": "Artist" }]
+                },
+                {
+                    "type": "episode",
+                    "id": "e2",
+                    "uri": "spotify:episode:e2",
+                    "name": "Second",
+                    "duration_ms": 2000,
+                    "show": { "id": "s1", "name": "Talk" }
+                }
+            ]
         }));
     });
+
     let client = client_with_token(&server);
-    let artist = client.artists().get("abc").unwrap();
+    let state = client.playback().queue(20).unwrap();
     mock.assert();
-    assert_eq!(artist.followers, Some(5));
+    let now = state.now_playing.unwrap();
+    assert_eq!(now.kind, QueueItemKind::Episode);
+    assert_eq!(now.uri, "spotify:episode:e1");
+    assert_eq!(now.track.artists, vec!["Talk"]);
+    let uris: Vec<&str> = state.queue.iter().map(|item| item.uri.as_str()).collect();
+    assert_eq!(uris, vec!["spotify:track:t1", "spotify:episode:e2"]);
+    assert_eq!(state.queue[0].kind, QueueItemKind::Track);
+    assert_eq!(state.queue[1].kind, QueueItemKind::Episode);
     teardown_env();
 }
 
 #[test]
-fn track_like_puts() {
+fn shows_save_puts_ids() {
     let server = MockServer::start();
     let mock = server.mock(|when, then| {
         when.method(PUT)
-            .path("/me/tracks")
-            .query_param("ids", "abc");
+            .path("/me/shows")
+            .query_param("ids", "s1,s2");
         then.status(200);
     });
     let client = client_with_token(&server);
-    client.track().like("abc").unwrap();
+    client
+        .shows()
+        .save(&[String::from("s1"), String::from("s2")])
+        .unwrap();
     mock.assert();
     teardown_env();
 }
 
 #[test]
-fn track_unlike_deletes() {
+fn auth_profile_reads_product_and_country() {
     let server = MockServer::start();
     let mock = server.mock(|when, then| {
-        when.method(DELETE)
-            .path("/me/tracks")
-            .query_param("ids", "abc");
-        then.status(200);
+        when.method(GET).path("/me");
+        then.status(200).json_body(serde_json::json!({
+            "id": "u1", "display_name": "User", "product": "premium", "country": "DE"
+        }));
+    });
+
+    let _client = client_with_token(&server);
+    let auth = AuthService::new(MetadataStore::new(
+        temp_dir("profile").join("metadata.json"),
+    ));
+    auth.login(AuthToken {
+        access_token: "token".to_string(),
+        refresh_token: None,
+        expires_at: None,
+        scopes: None,
+    })
+    .unwrap();
+    let profile = auth.profile().unwrap();
+    mock.assert();
+    assert_eq!(profile.id, "u1");
+    assert_eq!(profile.product.as_deref(), Some("premium"));
+    assert_eq!(profile.country.as_deref(), Some("DE"));
+    teardown_env();
+}
+
+#[test]
+fn shows_and_audiobooks_get_parse_details() {
+    let server = MockServer::start();
+    let show = server.mock(|when, then| {
+        when.method(GET).path("/shows/s1");
+        then.status(200).json_body(serde_json::json!({
+            "id": "s1", "name": "Show", "uri": "spotify:show:s1",
+            "publisher": "Publisher", "total_episodes": 120
+        }));
+    });
+    let book = server.mock(|when, then| {
+        when.method(GET).path("/audiobooks/b1");
+        then.status(200).json_body(serde_json::json!({
+            "id": "b1", "name": "Book", "uri": "spotify:audiobook:b1",
+            "authors": [ { "name": "Author" } ],
+            "narrators": [ { "name": "Narrator" } ],
+            "total_chapters": 30
+        }));
     });
+
     let client = client_with_token(&server);
-    client.track().unlike("abc").unwrap();
+    let info = client.shows().get("s1").unwrap();
+    let audiobook = client.audiobooks().get("b1").unwrap();
+    show.assert();
+    book.assert();
+    assert_eq!(info.total_episodes, Some(120));
+    assert_eq!(audiobook.narrators, vec!["Narrator".to_string()]);
+    teardown_env();
+}
+
+#[test]
+fn playback_play_context_from_passes_position_offset() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/me/player/play")
+            .json_body(serde_json::json!({
+                "context_uri": "spotify:album:a1",
+                "offset": { "position": 4 }
+            }));
+        then.status(204);
+    });
+    let client = client_with_token(&server);
+    client
+        .playback()
+        .play_context_from("spotify:album:a1", 4)
+        .unwrap();
+    mock.assert();
+    teardown_env();
+}
+
+#[test]
+fn playback_control_puts() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(PUT).path("/me/player/pause");
+        then.status(204);
+    });
+    let client = client_with_token(&server);
+    client.playback().pause().unwrap();
+    mock.assert();
+    teardown_env();
+}
+
+#[test]
+fn playback_seek_passes_position() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/me/player/seek")
+            .query_param("position_ms", "90000");
+        then.status(204);
+    });
+    let client = client_with_token(&server);
+    client.playback().seek(90_000).unwrap();
+    mock.assert();
+    teardown_env();
+}
+
+#[test]
+fn albums_get_parses_tracks() {
+    let server = MockServer::start();
+    let album_mock = server.mock(|when, then| {
+        when.method(GET).path("/albums/abc");
+        then.status(200).json_body(serde_json::json!({
+            "id": "abc",
+            "name": "Album",
+            "uri": "uri",
+            "release_date": "2024-01-01",
+            "total_tracks": 1,
+            "artists": [ { "name": "Artist" } ]
+        }));
+    });
+    let tracks_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/albums/abc/tracks")
+            .query_param("limit", "50");
+        then.status(200).json_body(serde_json::json!({
+            "items": [ { "name": "Track", "duration_ms": 1000, "track_number": 1 } ],
+            "next": null
+        }));
+    });
+
+    let client = client_with_token(&server);
+    let album = client.albums().get("abc").unwrap();
+    album_mock.assert();
+    tracks_mock.assert();
+    assert_eq!(album.tracks.len(), 1);
+    teardown_env();
+}
+
+#[test]
+fn artists_get_parses_artist() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/artists/abc");
+        then.status(200).json_body(serde_json::json!({
+            "id": "abc",
+            "name": "Artist",
+            "uri": "uri",
+            "genres": ["alt"],
+            "followers": { "total": 5 }
+        }));
+    });
+    let client = client_with_token(&server);
+    let artist = client.artists().get("abc").unwrap();
+    mock.assert();
+    assert_eq!(artist.followers, Some(5));
+    teardown_env();
+}
+
+#[test]
+fn artists_several_batches_ids_and_skips_unknown() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/artists").query_param("ids", "a,zz");
+        then.status(200).json_body(serde_json::json!({
+            "artists": [
+                { "id": "a", "name": "A", "uri": "spotify:artist:a", "genres": ["idm"] },
+                null
+            ]
+        }));
+    });
+    let client = client_with_token(&server);
+    let artists = client
+        .artists()
+        .several(&["a".to_string(), "zz".to_string()])
+        .unwrap();
+    mock.assert();
+    assert_eq!(artists.len(), 1);
+    assert_eq!(artists[0].genres, vec!["idm".to_string()]);
+    teardown_env();
+}
+
+#[test]
+fn artists_unfollow_deletes_ids() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/me/following")
+            .query_param("type", "artist")
+            .query_param("ids", "a,b");
+        then.status(204);
+    });
+    let client = client_with_token(&server);
+    client
+        .artists()
+        .unfollow(&["a".to_string(), "b".to_string()])
+        .unwrap();
+    mock.assert();
+    teardown_env();
+}
+
+#[test]
+fn artists_albums_all_passes_groups_and_follows_next() {
+    let server = MockServer::start();
+    let next = format!("{}/artists/abc/albums/page2", server.base_url());
+    let first = server.mock(|when, then| {
+        when.method(GET)
+            .path("/artists/abc/albums")
+            .query_param("include_groups", "album,appears_on");
+        then.status(200).json_body(serde_json::json!({
+            "items": [ {
+                "id": "a1", "name": "A1", "uri": "spotify:album:a1",
+                "album_type": "album", "album_group": "album", "release_date": "2020"
+            } ],
+            "next": next
+        }));
+    });
+    let second = server.mock(|when, then| {
+        when.method(GET).path("/artists/abc/albums/page2");
+        then.status(200).json_body(serde_json::json!({
+            "items": [ {
+                "id": "a2", "name": "A2", "uri": "spotify:album:a2",
+                "album_type": "album", "album_group": "appears_on"
+            } ],
+            "next": null
+        }));
+    });
+    let client = client_with_token(&server);
+    let releases = client
+        .artists()
+        .albums_all("abc", &["album", "appears_on"])
+        .unwrap();
+    first.assert();
+    second.assert();
+    assert_eq!(releases.len(), 2);
+    assert_eq!(releases[1].album_group.as_deref(), Some("appears_on"));
+    teardown_env();
+}
+
+#[test]
+fn artists_albums_requests_albums_and_singles_separately() {
+    let server = MockServer::start();
+    let albums = server.mock(|when, then| {
+        when.method(GET)
+            .path("/artists/abc/albums")
+            .query_param("include_groups", "album")
+            .query_param("limit", "1");
+        then.status(200).json_body(serde_json::json!({
+            "items": [ {
+                "id": "a1", "name": "A1", "uri": "spotify:album:a1",
+                "album_type": "album", "album_group": "album", "release_date": "2020"
+            } ],
+            "next": null
+        }));
+    });
+    let singles = server.mock(|when, then| {
+        when.method(GET)
+            .path("/artists/abc/albums")
+            .query_param("include_groups", "single")
+            .query_param("limit", "1");
+        then.status(200).json_body(serde_json::json!({
+            "items": [ {
+                "id": "s1", "name": "S1", "uri": "spotify:album:s1",
+                "album_type": "single", "album_group": "single", "release_date": "2024-05-01"
+            } ],
+            "next": null
+        }));
+    });
+    let client = client_with_token(&server);
+    let releases = client.artists().albums("abc", 1).unwrap();
+    albums.assert();
+    singles.assert();
+    let ids: Vec<&str> = releases.iter().map(|release| release.id.as_str()).collect();
+    assert_eq!(ids, vec!["a1", "s1"]);
+    teardown_env();
+}
+
+#[test]
+fn artists_followed_follows_next_page() {
+    let server = MockServer::start();
+    let next = format!("{}/me/following/page2", server.base_url());
+    let first = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/following")
+            .query_param("type", "artist");
+        then.status(200).json_body(serde_json::json!({
+            "artists": {
+                "items": [ { "id": "a", "name": "A", "uri": "spotify:artist:a" } ],
+                "next": next
+            }
+        }));
+    });
+    let second = server.mock(|when, then| {
+        when.method(GET).path("/me/following/page2");
+        then.status(200).json_body(serde_json::json!({
+            "artists": {
+                "items": [ { "id": "b", "name": "B", "uri": "spotify:artist:b" } ],
+                "next": null
+            }
+        }));
+    });
+    let client = client_with_token(&server);
+    let artists = client.artists().followed().unwrap();
+    first.assert();
+    second.assert();
+    assert_eq!(artists.len(), 2);
+    teardown_env();
+}
+
+#[test]
+fn artists_followed_page_passes_and_returns_cursor() {
+    let server = MockServer::start();
+    let next = format!(
+        "{}/me/following?type=artist&after=b&limit=2",
+        server.base_url()
+    );
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/following")
+            .query_param("limit", "2")
+            .query_param("after", "a0");
+        then.status(200).json_body(serde_json::json!({
+            "artists": {
+                "items": [
+                    { "id": "a", "name": "A", "uri": "spotify:artist:a" },
+                    { "id": "b", "name": "B", "uri": "spotify:artist:b" }
+                ],
+                "next": next,
+                "total": 7,
+                "cursors": { "after": "b" }
+            }
+        }));
+    });
+    let client = client_with_token(&server);
+    let page = client.artists().followed_page(2, Some("a0")).unwrap();
+    mock.assert();
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.total, Some(7));
+    assert_eq!(page.after.as_deref(), Some("b"));
+    teardown_env();
+}
+
+#[test]
+fn albums_saved_maps_added_at() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/me/albums");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "added_at": "2024-01-01T00:00:00Z",
+                    "album": {
+                        "id": "al1",
+                        "name": "Album",
+                        "uri": "spotify:album:al1",
+                        "release_date": "2002-02-18",
+                        "total_tracks": 10,
+                        "artists": [ { "name": "Artist" } ]
+                    }
+                }
+            ],
+            "next": null
+        }));
+    });
+    let client = client_with_token(&server);
+    let albums = client.albums().saved().unwrap();
+    mock.assert();
+    assert_eq!(albums.len(), 1);
+    assert_eq!(albums[0].added_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+    teardown_env();
+}
+
+#[test]
+fn track_saved_skips_unavailable_tracks() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/me/tracks");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "added_at": "2024-01-01T00:00:00Z",
+                    "track": {
+                        "id": "t1",
+                        "name": "Track",
+                        "duration_ms": 1000,
+                        "album": { "id": "al1", "name": "Album" },
+                        "artists": [ { "id": "ar1", "name": "Artist" } ]
+                    }
+                },
+                {
+                    "added_at": "2024-01-02T00:00:00Z",
+                    "track": { "id": null, "name": "Local" }
+                }
+            ],
+            "next": null
+        }));
+    });
+    let client = client_with_token(&server);
+    let tracks = client.track().saved().unwrap();
+    mock.assert();
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(tracks[0].track.album.as_deref(), Some("Album"));
+    teardown_env();
+}
+
+#[test]
+fn search_lookup_preserves_order_and_gaps() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/artists")
+            .query_param("ids", "a2,missing,a1");
+        then.status(200).json_body(serde_json::json!({
+            "artists": [
+                { "id": "a2", "name": "Second", "uri": "spotify:artist:a2" },
+                null,
+                { "id": "a1", "name": "First", "uri": "spotify:artist:a1" }
+            ]
+        }));
+    });
+    let client = client_with_token(&server);
+    let ids = vec!["a2".to_string(), "missing".to_string(), "a1".to_string()];
+    let items = client
+        .search()
+        .lookup(spotify_cli::domain::search::SearchType::Artist, &ids)
+        .unwrap();
+    mock.assert();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].as_ref().map(|item| item.id.as_str()), Some("a2"));
+    assert!(items[1].is_none());
+    assert_eq!(items[2].as_ref().map(|item| item.id.as_str()), Some("a1"));
+    teardown_env();
+}
+
+#[test]
+fn track_contains_batches_ids_in_order() {
+    let server = MockServer::start();
+    let first: Vec<String> = (0..50).map(|i| format!("t{i}")).collect();
+    let first_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/tracks/contains")
+            .query_param("ids", first.join(","));
+        then.status(200).json_body(serde_json::json!(
+            (0..50).map(|i| i % 2 == 0).collect::<Vec<_>>()
+        ));
+    });
+    let second_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/tracks/contains")
+            .query_param("ids", "t50");
+        then.status(200).json_body(serde_json::json!([true]));
+    });
+    let client = client_with_token(&server);
+    let ids: Vec<String> = (0..51).map(|i| format!("t{i}")).collect();
+    let saved = client.track().contains(&ids).unwrap();
+    first_mock.assert();
+    second_mock.assert();
+    assert_eq!(saved.len(), 51);
+    assert!(saved[0] && !saved[1] && saved[50]);
+    teardown_env();
+}
+
+#[test]
+fn search_recommendations_seeds_by_artist() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/recommendations")
+            .query_param("seed_artists", "a1")
+            .query_param("limit", "2");
+        then.status(200).json_body(serde_json::json!({
+            "tracks": [
+                { "id": "t1", "name": "One", "uri": "spotify:track:t1", "artists": [] },
+                { "id": "t2", "name": "Two", "uri": "spotify:track:t2", "artists": [] }
+            ]
+        }));
+    });
+    let client = client_with_token(&server);
+    let items = client
+        .search()
+        .recommendations(spotify_cli::domain::search::SearchType::Artist, "a1", 2)
+        .unwrap();
+    mock.assert();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].uri, "spotify:track:t2");
+    teardown_env();
+}
+
+#[test]
+fn search_played_after_keeps_play_times() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/player/recently-played")
+            .query_param("limit", "50")
+            .query_param("after", "1700000000000");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "track": { "id": "t1", "name": "One", "uri": "spotify:track:t1", "artists": [] },
+                    "played_at": "2023-11-14T22:30:00.000Z"
+                },
+                {
+                    "track": { "id": "t2", "name": "Two", "uri": "spotify:track:t2", "artists": [] }
+                }
+            ]
+        }));
+    });
+    let client = client_with_token(&server);
+    let plays = client.search().played_after(1_700_000_000_000, 50).unwrap();
+    mock.assert();
+    assert_eq!(plays.len(), 1);
+    assert_eq!(plays[0].item.id, "t1");
+    assert_eq!(plays[0].played_at, "2023-11-14T22:30:00.000Z");
+    teardown_env();
+}
+
+#[test]
+fn search_played_before_returns_the_next_cursor() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/player/recently-played")
+            .query_param("limit", "20")
+            .query_param("before", "1700000000000");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "track": { "id": "t1", "name": "One", "uri": "spotify:track:t1", "artists": [] },
+                    "played_at": "2023-11-14T22:00:00.000Z"
+                }
+            ],
+            "cursors": { "after": "1699999200000", "before": "1699999200000" }
+        }));
+    });
+    let client = client_with_token(&server);
+    let page = client
+        .search()
+        .played_before(Some(1_700_000_000_000), 20)
+        .unwrap();
+    mock.assert();
+    assert_eq!(page.plays.len(), 1);
+    assert_eq!(page.before, Some(1_699_999_200_000));
+    teardown_env();
+}
+
+#[test]
+fn search_top_tracks_passes_range_and_offset() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/top/tracks")
+            .query_param("time_range", "short_term")
+            .query_param("limit", "50")
+            .query_param("offset", "50");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "id": "t1",
+                    "name": "Track",
+                    "uri": "spotify:track:t1",
+                    "artists": [ { "name": "Artist" } ],
+                    "album": { "name": "Album" },
+                    "duration_ms": 1000
+                }
+            ]
+        }));
+    });
+    let client = client_with_token(&server);
+    let items = client
+        .search()
+        .top(
+            spotify_cli::domain::search::SearchType::Track,
+            "short_term",
+            50,
+            50,
+        )
+        .unwrap();
+    mock.assert();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].uri, "spotify:track:t1");
+    teardown_env();
+}
+
+#[test]
+fn episodes_for_show_follows_next_page() {
+    let server = MockServer::start();
+    let next = format!("{}/shows/s1/episodes/page2", server.base_url());
+    let first = server.mock(|when, then| {
+        when.method(GET).path("/shows/s1/episodes");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "id": "e2",
+                    "name": "Newest",
+                    "uri": "spotify:episode:e2",
+                    "duration_ms": 1000,
+                    "resume_point": { "fully_played": false, "resume_position_ms": 500 }
+                }
+            ],
+            "next": next
+        }));
+    });
+    let second = server.mock(|when, then| {
+        when.method(GET).path("/shows/s1/episodes/page2");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                { "id": "e1", "name": "Oldest", "uri": "spotify:episode:e1", "duration_ms": 1000 }
+            ],
+            "next": null
+        }));
+    });
+    let client = client_with_token(&server);
+    let episodes = client.episodes().for_show("s1").unwrap();
+    first.assert();
+    second.assert();
+    assert_eq!(episodes.len(), 2);
+    assert_eq!(episodes[0].resume_position_ms, Some(500));
+    teardown_env();
+}
+
+#[test]
+fn episodes_search_requests_episode_type() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/search")
+            .query_param("q", "daily news")
+            .query_param("type", "episode")
+            .query_param("limit", "5");
+        then.status(200).json_body(serde_json::json!({
+            "episodes": {
+                "items": [
+                    { "id": "e1", "name": "Monday", "uri": "spotify:episode:e1", "duration_ms": 1000 },
+                    null
+                ]
+            }
+        }));
+    });
+    let client = client_with_token(&server);
+    let episodes = client.episodes().search("daily news", 5).unwrap();
+    mock.assert();
+    assert_eq!(episodes.len(), 1);
+    assert_eq!(episodes[0].uri, "spotify:episode:e1");
+    teardown_env();
+}
+
+#[test]
+fn shows_saved_follows_next_page_and_keeps_added_at() {
+    let server = MockServer::start();
+    let next = format!("{}/me/shows/page2", server.base_url());
+    let first = server.mock(|when, then| {
+        when.method(GET).path("/me/shows").query_param("limit", "50");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "added_at": "2024-02-01T00:00:00Z",
+                    "show": { "id": "s2", "name": "Newer", "uri": "spotify:show:s2", "publisher": "P" }
+                }
+            ],
+            "next": next
+        }));
+    });
+    let second = server.mock(|when, then| {
+        when.method(GET).path("/me/shows/page2");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "added_at": "2023-05-01T00:00:00Z",
+                    "show": { "id": "s1", "name": "Older", "uri": "spotify:show:s1" }
+                }
+            ],
+            "next": null
+        }));
+    });
+    let client = client_with_token(&server);
+    let shows = client.shows().saved(None).unwrap();
+    first.assert();
+    second.assert();
+    assert_eq!(shows.len(), 2);
+    assert_eq!(shows[0].show.publisher.as_deref(), Some("P"));
+    assert_eq!(shows[1].added_at.as_deref(), Some("2023-05-01T00:00:00Z"));
+    teardown_env();
+}
+
+#[test]
+fn episodes_saved_stops_at_limit() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/me/episodes")
+            .query_param("limit", "1");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "added_at": "2024-02-01T00:00:00Z",
+                    "episode": {
+                        "id": "e1",
+                        "name": "Monday",
+                        "uri": "spotify:episode:e1",
+                        "duration_ms": 1000,
+                        "show": { "name": "Daily" }
+                    }
+                }
+            ],
+            "next": format!("{}/me/episodes/page2", server.base_url())
+        }));
+    });
+    let client = client_with_token(&server);
+    let episodes = client.episodes().saved(Some(1)).unwrap();
+    mock.assert_hits(1);
+    assert_eq!(episodes.len(), 1);
+    assert_eq!(episodes[0].episode.show.as_deref(), Some("Daily"));
+    assert_eq!(
+        episodes[0].added_at.as_deref(),
+        Some("2024-02-01T00:00:00Z")
+    );
+    teardown_env();
+}
+
+#[test]
+fn audiobooks_saved_reads_bare_items() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/me/audiobooks");
+        then.status(200).json_body(serde_json::json!({
+            "items": [
+                {
+                    "id": "b1",
+                    "name": "Book",
+                    "uri": "spotify:audiobook:b1",
+                    "authors": [{ "name": "Author" }]
+                }
+            ],
+            "next": null
+        }));
+    });
+    let client = client_with_token(&server);
+    let audiobooks = client.audiobooks().saved(None).unwrap();
+    mock.assert();
+    assert_eq!(audiobooks.len(), 1);
+    assert_eq!(audiobooks[0].audiobook.authors, vec!["Author".to_string()]);
+    assert_eq!(audiobooks[0].added_at, None);
+    teardown_env();
+}
+
+#[test]
+fn track_like_puts() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/me/tracks")
+            .query_param("ids", "abc");
+        then.status(200);
+    });
+    let client = client_with_token(&server);
+    client.track().like("abc").unwrap();
+    mock.assert();
+    teardown_env();
+}
+
+#[test]
+fn track_unlike_deletes() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/me/tracks")
+            .query_param("ids", "abc");
+        then.status(200);
+    });
+    let client = client_with_token(&server);
+    client.track().unlike("abc").unwrap();
+    mock.assert();
+    teardown_env();
+}
+
+#[test]
+fn traced_requests_write_redacted_har() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/me/player");
+        then.status(429).header("Retry-After", "2");
+    });
+    let har = temp_dir("har").join("trace.har");
+    spotify_cli::spotify::trace::set_trace(spotify_cli::spotify::trace::TraceOptions {
+        log: false,
+        har: Some(har.clone()),
+        file: None,
+    });
+
+    let client = client_with_token(&server);
+    assert!(client.playback().status().is_err());
+    spotify_cli::spotify::trace::write_har().unwrap();
+    mock.assert();
+
+    let contents = fs::read_to_string(&har).unwrap();
+    let har: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    // Login also traces a profile request; the playback call is the last entry.
+    let entry = har["log"]["entries"].as_array().unwrap().last().unwrap();
+    assert_eq!(entry["request"]["method"], "GET");
+    assert_eq!(entry["response"]["status"], 429);
+    assert!(!contents.contains("Bearer token"));
+    assert!(contents.contains("[redacted]"));
+    teardown_env();
+}
+
+#[test]
+fn markets_availability_omits_market_param() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/tracks/t1");
+        then.status(200).json_body(serde_json::json!({
+            "name": "Song",
+            "available_markets": ["SE", "US"]
+        }));
+    });
+
+    let client = client_with_token(&server);
+    let (name, markets) = client.markets().availability("track", "t1").unwrap();
     mock.assert();
+    assert_eq!(name, "Song");
+    assert_eq!(markets, vec!["SE", "US"]);
     teardown_env();
 }